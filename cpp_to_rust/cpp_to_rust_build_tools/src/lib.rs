@@ -12,7 +12,7 @@
 pub extern crate cpp_to_rust_common as common;
 use common::errors::{fancy_unwrap, ChainErr, Result};
 use common::cpp_build_config::{CppBuildConfig, CppBuildPaths, CppLibraryType};
-use common::BuildScriptData;
+use common::{BuildScriptData, CppFeature};
 use common::file_utils::{PathBufWithAdded, load_json, create_file, file_to_string, path_to_str};
 use common::cpp_lib_builder::{CppLibBuilder, CMakeVar, BuildType};
 use common::target::current_target;
@@ -115,8 +115,19 @@ impl Config {
     cmake_vars.push(CMakeVar::new_list("C2R_LINKED_LIBS", cpp_build_config_data.linked_libs())?);
     cmake_vars.push(CMakeVar::new_list("C2R_LINKED_FRAMEWORKS",
                                        cpp_build_config_data.linked_frameworks())?);
-    cmake_vars.push(CMakeVar::new("C2R_COMPILER_FLAGS",
-                                  cpp_build_config_data.compiler_flags().join(" ")));
+    let mut compiler_flags = cpp_build_config_data.compiler_flags().to_vec();
+    if let Some(cpp_stdlib) = cpp_build_config_data.cpp_stdlib() {
+      compiler_flags.push(format!("-stdlib={}", cpp_stdlib));
+    }
+    for feature in &self.build_script_data.cpp_features {
+      if std::env::var(CppFeature::cargo_env_var_name(&feature.name)).is_ok() {
+        compiler_flags.push(format!("-D{}", CppFeature::guard_macro_name(&feature.name)));
+        for define in &feature.defines {
+          compiler_flags.push(format!("-D{}", define));
+        }
+      }
+    }
+    cmake_vars.push(CMakeVar::new("C2R_COMPILER_FLAGS", compiler_flags.join(" ")));
     let out_dir = out_dir()?;
     let c_lib_install_dir = out_dir.with_added("c_lib_install");
     let manifest_dir = manifest_dir()?;
@@ -166,8 +177,10 @@ impl Config {
       println!("cargo:rustc-link-lib={}", name);
     }
     if ::common::target::current_env() != ::common::target::Env::Msvc {
-      // TODO: make it configurable
-      println!("cargo:rustc-link-lib=stdc++");
+      let cpp_stdlib = cpp_build_config_data
+        .cpp_stdlib()
+        .map_or("stdc++", |name| name.trim_left_matches("lib"));
+      println!("cargo:rustc-link-lib={}", cpp_stdlib);
     }
     for name in cpp_build_config_data.linked_frameworks() {
       println!("cargo:rustc-link-lib=framework={}", name);
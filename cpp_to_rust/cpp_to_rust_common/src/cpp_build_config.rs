@@ -16,6 +16,9 @@ use errors::Result;
 /// During evaluation, each configuration item
 /// will only be used if the associated condition is currently true.
 /// All properties from all matching configuration are combined.
+/// Duplicate libraries, frameworks and compiler flags (together with
+/// their separate arguments) are only added once,
+/// and different `-std=` flags are reported as a conflict.
 ///
 /// If this conditional evaluation is not enough, a custom build script
 /// can modify this config during build script execution using
@@ -52,6 +55,7 @@ pub struct CppBuildConfigData {
   linked_frameworks: Vec<String>,
   compiler_flags: Vec<String>,
   library_type: Option<CppLibraryType>,
+  cpp_stdlib: Option<String>,
 }
 
 impl CppBuildConfigData {
@@ -91,6 +95,14 @@ impl CppBuildConfigData {
     self.library_type = Some(t);
   }
 
+  /// Sets the C++ standard library used by the C++ wrapper library,
+  /// e.g. `"libc++"` or `"libstdc++"`. Used as `-stdlib=` option to the compiler,
+  /// and the library is linked instead of the default `libstdc++`.
+  /// Not used with MSVC.
+  pub fn set_cpp_stdlib<P: Into<String>>(&mut self, name: P) {
+    self.cpp_stdlib = Some(name.into());
+  }
+
   /// Returns names of linked libraries.
   pub fn linked_libs(&self) -> &[String] {
     &self.linked_libs
@@ -111,14 +123,28 @@ impl CppBuildConfigData {
     self.library_type
   }
 
+  /// Returns the C++ standard library set by `CppBuildConfigData::set_cpp_stdlib`.
+  pub fn cpp_stdlib(&self) -> Option<&String> {
+    self.cpp_stdlib.as_ref()
+  }
+
   fn add_from(&mut self, other: &CppBuildConfigData) -> Result<()> {
-    self.linked_libs.append(&mut other.linked_libs.clone());
-    self
-      .linked_frameworks
-      .append(&mut other.linked_frameworks.clone());
-    self
-      .compiler_flags
-      .append(&mut other.compiler_flags.clone());
+    {
+      let mut std_flags = self
+        .compiler_flags
+        .iter()
+        .chain(other.compiler_flags.iter())
+        .filter(|x| x.starts_with("-std="));
+      if let Some(first_flag) = std_flags.next() {
+        if let Some(flag) = std_flags.find(|x| x != &first_flag) {
+          return Err(format!("conflicting C++ standards specified: {}, {}", first_flag, flag)
+                       .into());
+        }
+      }
+    }
+    append_new_items(&mut self.linked_libs, &other.linked_libs);
+    append_new_items(&mut self.linked_frameworks, &other.linked_frameworks);
+    append_new_flags(&mut self.compiler_flags, &other.compiler_flags);
     if self.library_type.is_some() {
       if other.library_type.is_some() && other.library_type != self.library_type {
         return Err("conflicting library types specified".into());
@@ -126,10 +152,64 @@ impl CppBuildConfigData {
     } else {
       self.library_type = other.library_type;
     }
+    if self.cpp_stdlib.is_some() {
+      if other.cpp_stdlib.is_some() && other.cpp_stdlib != self.cpp_stdlib {
+        return Err("conflicting C++ standard libraries specified".into());
+      }
+    } else {
+      self.cpp_stdlib = other.cpp_stdlib.clone();
+    }
     Ok(())
   }
 }
 
+/// Returns true if the compiler flag `flag` is followed by
+/// a separate argument, as in `-include file.h`.
+fn flag_takes_value(flag: &str) -> bool {
+  ["-include", "-imacros", "-isystem", "-iquote", "-idirafter", "-isysroot", "-I", "-D",
+   "-U", "-L", "-l", "-F", "-framework", "-Xlinker", "-Xclang", "-Xpreprocessor",
+   "-Xassembler", "-x", "-arch", "-target", "-o", "-MF", "-MT", "-MQ"]
+      .contains(&flag)
+}
+
+/// Splits compiler flags into groups that must stay together:
+/// a flag followed by its separate value (as in `-include file.h`)
+/// or a single flag.
+fn group_flags(flags: &[String]) -> Vec<&[String]> {
+  let mut groups = Vec::new();
+  let mut start = 0;
+  while start < flags.len() {
+    let mut end = start + 1;
+    if flag_takes_value(&flags[start]) && end < flags.len() {
+      end += 1;
+    }
+    groups.push(&flags[start..end]);
+    start = end;
+  }
+  groups
+}
+
+/// Appends items that are not already present in `target`.
+fn append_new_items(target: &mut Vec<String>, items: &[String]) {
+  for item in items {
+    if !target.contains(item) {
+      target.push(item.clone());
+    }
+  }
+}
+
+/// Appends compiler flags that are not already present in `target`.
+/// A flag with a separate value is only skipped if the same flag
+/// with the same value is present.
+fn append_new_flags(target: &mut Vec<String>, flags: &[String]) {
+  for group in group_flags(flags) {
+    let is_present = group_flags(target).contains(&group);
+    if !is_present {
+      target.extend_from_slice(group);
+    }
+  }
+}
+
 impl CppBuildConfig {
   /// Create an empty configuration
   pub fn new() -> CppBuildConfig {
@@ -155,6 +235,12 @@ impl CppBuildConfig {
     }
     Ok(data)
   }
+  /// Parses the target triple `triple` (e.g. `"aarch64-unknown-linux-gnu"`)
+  /// and evaluates the configuration for this target. This allows to
+  /// preview the configuration used when cross-compiling.
+  pub fn eval_for_triple(&self, triple: &str) -> Result<CppBuildConfigData> {
+    self.eval(&::target::Target::from_triple(triple)?)
+  }
 }
 
 use std::path::PathBuf;
@@ -193,6 +193,15 @@ pub fn save_json<P: AsRef<Path>, T: ::serde::Serialize>(path: P, value: &T) -> R
   })
 }
 
+/// Serialize `value` into JSON file `path` using human-readable formatting.
+pub fn save_json_pretty<P: AsRef<Path>, T: ::serde::Serialize>(path: P, value: &T) -> Result<()> {
+  let file = create_file(path.as_ref())?;
+  ::serde_json::to_writer_pretty(&mut file.into_file(), value).chain_err(|| {
+    format!("failed to serialize to JSON file: {}",
+            path.as_ref().display())
+  })
+}
+
 /// Deserialize value from binary file `path`.
 pub fn load_bincode<P: AsRef<Path>, T: ::serde::Deserialize>(path: P) -> Result<T> {
   let mut file = open_file(path.as_ref())?.into_file();
@@ -43,6 +43,35 @@ pub struct BuildScriptData {
   pub cpp_lib_version: Option<String>,
   /// Name of C++ wrapper library
   pub cpp_wrapper_lib_name: String,
+  /// Optional features of the C++ library
+  #[serde(default)]
+  pub cpp_features: Vec<CppFeature>,
+}
+
+/// Optional feature of the C++ library enabled by defining
+/// preprocessor macros. Each feature corresponds to a feature
+/// of the generated crate with the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct CppFeature {
+  /// Name of the feature of the generated crate
+  pub name: String,
+  /// Preprocessor macros defined when the feature is enabled
+  pub defines: Vec<String>,
+}
+
+impl CppFeature {
+  /// Returns name of the preprocessor macro that guards
+  /// the C++ wrapper functions available only with the feature `name`.
+  pub fn guard_macro_name(name: &str) -> String {
+    format!("C2R_FEATURE_{}", name.to_uppercase().replace('-', "_"))
+  }
+
+  /// Returns name of the environment variable set by Cargo
+  /// for the build script if the feature `name` is enabled.
+  pub fn cargo_env_var_name(name: &str) -> String {
+    format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"))
+  }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 //! Types for expressing properties of different target platforms and platform-based conditions
 
+use errors::Result;
+
 /// CPU architecture, as reported by `target_arch`.
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -265,6 +267,76 @@ pub fn current_target() -> Target {
   }
 }
 
+impl Target {
+  /// Parses a target triple (e.g. `"aarch64-unknown-linux-gnu"`)
+  /// and returns properties of the described target.
+  pub fn from_triple(triple: &str) -> Result<Target> {
+    let parts: Vec<&str> = triple.split('-').collect();
+    let (arch, pointer_width, endian) = match parts[0] {
+      "x86_64" => (Arch::X86_64, PointerWidth::P64, Endian::Little),
+      "i386" | "i586" | "i686" => (Arch::X86, PointerWidth::P32, Endian::Little),
+      "aarch64" => (Arch::AArch64, PointerWidth::P64, Endian::Little),
+      "mips" => (Arch::Mips, PointerWidth::P32, Endian::Big),
+      "mipsel" => (Arch::Mips, PointerWidth::P32, Endian::Little),
+      "powerpc" => (Arch::PowerPC, PointerWidth::P32, Endian::Big),
+      "powerpc64" => (Arch::PowerPC64, PointerWidth::P64, Endian::Big),
+      "powerpc64le" => (Arch::PowerPC64, PointerWidth::P64, Endian::Little),
+      arch if arch.starts_with("arm") || arch.starts_with("thumb") => {
+        (Arch::Arm, PointerWidth::P32, Endian::Little)
+      }
+      arch => return Err(format!("unsupported architecture in target triple: {}", arch).into()),
+    };
+    if parts.len() < 2 {
+      return Err(format!("invalid target triple: {}", triple).into());
+    }
+    let os_parts = &parts[1..];
+    let has_part = |name: &str| os_parts.iter().any(|part| part.starts_with(name));
+    let os = if has_part("windows") {
+      OS::Windows
+    } else if has_part("darwin") || has_part("macos") {
+      OS::MacOS
+    } else if has_part("ios") {
+      OS::IOS
+    } else if has_part("android") {
+      OS::Android
+    } else if has_part("linux") {
+      OS::Linux
+    } else if has_part("freebsd") {
+      OS::FreeBSD
+    } else if has_part("dragonfly") {
+      OS::DragonFly
+    } else if has_part("bitrig") {
+      OS::Bitrig
+    } else if has_part("openbsd") {
+      OS::OpenBSD
+    } else if has_part("netbsd") {
+      OS::NetBSD
+    } else {
+      return Err(format!("unsupported operating system in target triple: {}", triple).into());
+    };
+    let env = if has_part("gnu") {
+      Env::Gnu
+    } else if has_part("msvc") {
+      Env::Msvc
+    } else if has_part("musl") {
+      Env::Musl
+    } else {
+      Env::None
+    };
+    Ok(Target {
+         arch: arch,
+         os: os,
+         family: if os == OS::Windows {
+           Family::Windows
+         } else {
+           Family::Unix
+         },
+         env: env,
+         pointer_width: pointer_width,
+         endian: endian,
+       })
+  }
+}
 
 impl Condition {
   /// Evaluate the condition for `target`. Returns true if
@@ -0,0 +1,155 @@
+use cpp_build_config::{CppBuildConfig, CppBuildConfigData, CppLibraryType};
+use target::{Arch, Condition, Env, Target, OS};
+
+#[test]
+fn eval_for_triple() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_linked_lib("common");
+    config.add(Condition::True, data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_linked_lib("arm64_only");
+    config.add(Condition::Arch(Arch::AArch64), data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_linked_lib("msvc_only");
+    config.add(Condition::Env(Env::Msvc), data);
+  }
+  let data = config.eval_for_triple("aarch64-unknown-linux-gnu").unwrap();
+  assert_eq!(data.linked_libs(),
+             &["common".to_string(), "arm64_only".to_string()]);
+
+  let data = config.eval_for_triple("x86_64-pc-windows-msvc").unwrap();
+  assert_eq!(data.linked_libs(),
+             &["common".to_string(), "msvc_only".to_string()]);
+
+  assert!(config.eval_for_triple("unknown").is_err());
+  assert!(config.eval_for_triple("x86_64-unknown-plan9").is_err());
+}
+
+#[test]
+fn target_from_triple() {
+  let target = Target::from_triple("armv7-linux-androideabi").unwrap();
+  assert_eq!(target.arch, Arch::Arm);
+  assert_eq!(target.os, OS::Android);
+  assert_eq!(target.env, Env::None);
+  let target = Target::from_triple("x86_64-apple-darwin").unwrap();
+  assert_eq!(target.os, OS::MacOS);
+}
+
+#[test]
+fn library_type_conflict() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.set_library_type(CppLibraryType::Static);
+    config.add(Condition::True, data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.set_library_type(CppLibraryType::Shared);
+    config.add(Condition::OS(OS::Windows), data);
+  }
+  let data = config.eval_for_triple("x86_64-unknown-linux-gnu").unwrap();
+  assert_eq!(data.library_type(), Some(CppLibraryType::Static));
+  assert!(config.eval_for_triple("x86_64-pc-windows-msvc").is_err());
+}
+
+#[test]
+fn cpp_stdlib_conflict() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.set_cpp_stdlib("libc++");
+    config.add(Condition::True, data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.set_cpp_stdlib("libc++");
+    config.add(Condition::OS(OS::MacOS), data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.set_cpp_stdlib("libstdc++");
+    config.add(Condition::OS(OS::Linux), data);
+  }
+  let data = config.eval_for_triple("x86_64-apple-darwin").unwrap();
+  assert_eq!(data.cpp_stdlib(), Some(&"libc++".to_string()));
+  assert!(config.eval_for_triple("x86_64-unknown-linux-gnu").is_err());
+}
+
+#[test]
+fn duplicate_items() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flags(vec!["-std=c++14", "-fPIC"]);
+    data.add_linked_lib("common");
+    data.add_linked_framework("Foundation");
+    config.add(Condition::True, data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flags(vec!["-fPIC", "-std=c++14", "-Wall"]);
+    data.add_linked_lib("common");
+    data.add_linked_lib("extra");
+    data.add_linked_framework("Foundation");
+    config.add(Condition::OS(OS::MacOS), data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flag("-std=c++11");
+    config.add(Condition::OS(OS::Linux), data);
+  }
+  let data = config.eval_for_triple("x86_64-apple-darwin").unwrap();
+  assert_eq!(data.compiler_flags(),
+             &["-std=c++14".to_string(), "-fPIC".to_string(), "-Wall".to_string()]);
+  assert_eq!(data.linked_libs(),
+             &["common".to_string(), "extra".to_string()]);
+  assert_eq!(data.linked_frameworks(), &["Foundation".to_string()]);
+  assert!(config.eval_for_triple("x86_64-unknown-linux-gnu").is_err());
+}
+
+#[test]
+fn duplicate_flags_with_values() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flags(vec!["-include", "a.h", "-fPIC"]);
+    data.add_linked_lib("common");
+    data.add_linked_lib("common");
+    config.add(Condition::True, data);
+  }
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flags(vec!["-include", "b.h", "-include", "a.h", "-fPIC", "-isystem",
+                                 "/usr/include/x", "-isystem", "/usr/include/x"]);
+    data.add_linked_lib("common");
+    config.add(Condition::True, data);
+  }
+  let data = config.eval_for_triple("x86_64-unknown-linux-gnu").unwrap();
+  assert_eq!(data.compiler_flags(),
+             &["-include".to_string(),
+               "a.h".to_string(),
+               "-fPIC".to_string(),
+               "-include".to_string(),
+               "b.h".to_string(),
+               "-isystem".to_string(),
+               "/usr/include/x".to_string()]);
+  assert_eq!(data.linked_libs(), &["common".to_string()]);
+}
+
+#[test]
+fn std_flag_conflict_in_one_item() {
+  let mut config = CppBuildConfig::new();
+  {
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flags(vec!["-std=c++11", "-std=c++14"]);
+    config.add(Condition::True, data);
+  }
+  assert!(config.eval_for_triple("x86_64-unknown-linux-gnu").is_err());
+}
@@ -1,2 +1,2 @@
-
 mod utils;
+mod cpp_build_config;
@@ -5,9 +5,15 @@ use common::errors::Result;
 use cpp_method::CppMethod;
 use cpp_data::ParserCppData;
 pub use cpp_data::CppTypeAllocationPlace;
+pub use cpp_ffi_data::{CppFfiFailureDump, CppComputedTypeConstant,
+                       CppTemplateMethodInstantiation, CppFunctorTemplateInstantiation,
+                       CppVariadicSliceFunction, CppErrnoMethod, CppFallibleConstructor};
+pub use rust_type::{RustName, RustToCTypeConversion};
 use common::cpp_build_config::CppBuildConfig;
+use common::CppFeature;
 use std::collections::HashMap;
 use common;
+use regex::Regex;
 
 /// Function type used in `Config::add_cpp_ffi_generator_filter`.
 pub type CppFfiGeneratorFilterFn = Fn(&CppMethod) -> Result<bool>;
@@ -224,6 +230,93 @@ impl Default for DebugLoggingConfig {
   }
 }
 
+/// Value of this enum determines how enums selected with
+/// `Config::add_serde_enum` are represented by their `serde` implementations.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SerdeEnumRepresentation {
+  /// Enum values are serialized as strings containing Rust variant names.
+  Name,
+  /// Enum values are serialized as integers containing C++ values.
+  Value,
+}
+
+impl Default for SerdeEnumRepresentation {
+  fn default() -> SerdeEnumRepresentation {
+    SerdeEnumRepresentation::Name
+  }
+}
+
+/// Mapping between integer values of a C++ enum in an older version
+/// of the library and in the current version,
+/// added with `Config::add_enum_value_migration`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EnumValueMigration {
+  /// Old version number used in the name of the generated function
+  pub version: u32,
+  /// Values of the enum in the old version
+  pub old_values: Vec<i64>,
+  /// Corresponding values of the enum in the current version
+  pub new_values: Vec<i64>,
+}
+
+/// Value of this enum determines which generated Rust methods
+/// are marked as `unsafe`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SafetyPolicy {
+  /// Methods accepting raw pointers are `unsafe`.
+  /// Other methods (including those returning raw pointers) are safe.
+  RawPointerArguments,
+  /// Methods accepting or returning raw pointers are `unsafe`.
+  Strict,
+  /// All generated methods are `unsafe`. Trait implementations
+  /// (e.g. `Drop` and casts) are not affected.
+  AllUnsafe,
+}
+
+impl Default for SafetyPolicy {
+  fn default() -> SafetyPolicy {
+    SafetyPolicy::RawPointerArguments
+  }
+}
+
+/// Value of this enum determines how the generated C++ wrapper code
+/// is split into translation units.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FfiTuStrategy {
+  /// One source file is generated for each header of the C++ library.
+  PerHeader,
+  /// Generated code is bundled into the specified number of source files.
+  /// This reduces total build time for large libraries at the cost of
+  /// coarser incremental rebuilds.
+  Bundled(usize),
+}
+
+impl Default for FfiTuStrategy {
+  fn default() -> FfiTuStrategy {
+    FfiTuStrategy::PerHeader
+  }
+}
+
+/// Value of this enum determines how methods using pointers
+/// to `volatile` types are processed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VolatilePolicy {
+  /// Methods with `volatile` types are skipped.
+  Reject,
+  /// Pointers to `volatile` types are preserved in the C++ wrapper
+  /// and exposed as raw pointers in Rust. Access to the pointee
+  /// must be performed with `std::ptr::read_volatile` and
+  /// `std::ptr::write_volatile`. References and values of `volatile`
+  /// types are not supported.
+  Preserve,
+}
+
+impl Default for VolatilePolicy {
+  fn default() -> VolatilePolicy {
+    VolatilePolicy::Reject
+  }
+}
+
 /// The starting point of `cpp_to_rust` API.
 /// Create a `Config` object, set its properties,
 /// add custom functions if necessary, and start
@@ -242,10 +335,12 @@ pub struct Config {
   target_include_paths: Vec<PathBuf>,
   include_directives: Vec<PathBuf>,
   cpp_parser_arguments: Vec<String>,
+  cpp_features: Vec<CppFeature>,
   cpp_parser_blocked_names: Vec<String>,
   cpp_ffi_generator_filters: Vec<CppFfiGeneratorFilter>,
   cpp_data_filters: Vec<CppDataFilter>,
   cpp_filtered_namespaces: Vec<String>,
+  namespace_mappings: HashMap<String, Vec<String>>,
   cpp_build_config: CppBuildConfig, // TODO: add CppBuildPaths when needed
   write_dependencies_local_paths: bool,
   type_allocation_places: HashMap<String, CppTypeAllocationPlace>,
@@ -253,6 +348,40 @@ pub struct Config {
   quiet_mode: bool,
   write_cache: bool,
   cpp_lib_version: Option<String>,
+  failure_dump_dir: Option<PathBuf>,
+  symbol_version: Option<String>,
+  metatypes: Vec<String>,
+  computed_type_constants: Vec<CppComputedTypeConstant>,
+  template_method_instantiations: Vec<CppTemplateMethodInstantiation>,
+  slicing_conversions: Vec<(String, String)>,
+  functor_template_instantiations: Vec<CppFunctorTemplateInstantiation>,
+  owned_void_ptr_returns: HashMap<String, String>,
+  owned_ptr_returns: Vec<String>,
+  fallible_constructors: Vec<CppFallibleConstructor>,
+  consuming_methods: Vec<String>,
+  exposed_protected_methods: Vec<String>,
+  method_blocklist: Vec<Regex>,
+  allocation_benchmarks: Vec<String>,
+  batch_operations: Vec<String>,
+  variadic_slice_functions: Vec<CppVariadicSliceFunction>,
+  errno_methods: Vec<CppErrnoMethod>,
+  string_buffer_outs: HashMap<String, (String, String)>,
+  array_out_params: HashMap<String, String>,
+  return_lifetimes: HashMap<String, usize>,
+  ownership_transfer_args: HashMap<String, Vec<usize>>,
+  type_mappings: HashMap<String, (RustName, RustToCTypeConversion)>,
+  virtual_clones: HashMap<String, String>,
+  serde_enums: HashMap<String, Option<String>>,
+  serde_enum_representation: SerdeEnumRepresentation,
+  enum_value_migrations: HashMap<String, Vec<EnumValueMigration>>,
+  flaggable_scoped_enums: Vec<String>,
+  enums_non_exhaustive: bool,
+  track_caller: bool,
+  tracing_spans: bool,
+  safety_policy: SafetyPolicy,
+  ffi_tu_strategy: FfiTuStrategy,
+  volatile_policy: VolatilePolicy,
+  force_cpp_code_regeneration: bool,
 }
 
 impl Config {
@@ -276,10 +405,12 @@ impl Config {
       target_include_paths: Default::default(),
       include_directives: Default::default(),
       cpp_parser_arguments: Default::default(),
+      cpp_features: Default::default(),
       cpp_parser_blocked_names: Default::default(),
       cpp_ffi_generator_filters: Default::default(),
       cpp_data_filters: Default::default(),
       cpp_filtered_namespaces: Default::default(),
+      namespace_mappings: Default::default(),
       cpp_build_config: Default::default(),
       type_allocation_places: Default::default(),
       write_dependencies_local_paths: true,
@@ -288,6 +419,40 @@ impl Config {
       quiet_mode: false,
       write_cache: true,
       cpp_lib_version: None,
+      failure_dump_dir: None,
+      symbol_version: None,
+      metatypes: Default::default(),
+      computed_type_constants: Default::default(),
+      template_method_instantiations: Default::default(),
+      slicing_conversions: Default::default(),
+      functor_template_instantiations: Default::default(),
+      owned_void_ptr_returns: Default::default(),
+      owned_ptr_returns: Default::default(),
+      fallible_constructors: Default::default(),
+      consuming_methods: Default::default(),
+      exposed_protected_methods: Default::default(),
+      method_blocklist: Default::default(),
+      allocation_benchmarks: Default::default(),
+      batch_operations: Default::default(),
+      variadic_slice_functions: Default::default(),
+      errno_methods: Default::default(),
+      string_buffer_outs: Default::default(),
+      array_out_params: Default::default(),
+      return_lifetimes: Default::default(),
+      ownership_transfer_args: Default::default(),
+      type_mappings: Default::default(),
+      virtual_clones: Default::default(),
+      serde_enums: Default::default(),
+      serde_enum_representation: SerdeEnumRepresentation::default(),
+      enum_value_migrations: Default::default(),
+      flaggable_scoped_enums: Default::default(),
+      enums_non_exhaustive: false,
+      track_caller: false,
+      tracing_spans: false,
+      safety_policy: SafetyPolicy::default(),
+      ffi_tu_strategy: FfiTuStrategy::default(),
+      volatile_policy: VolatilePolicy::default(),
+      force_cpp_code_regeneration: false,
     }
   }
 
@@ -386,6 +551,27 @@ impl Config {
     }
   }
 
+  /// Declares an optional feature of the C++ library that is enabled
+  /// by defining preprocessor macros `defines` (e.g. `#ifdef FEATURE_X`).
+  ///
+  /// The C++ parser is run once more for each feature with these macros
+  /// defined. Methods that are only found when the feature is enabled
+  /// are wrapped under `#[cfg(feature = "{name}")]`, and the feature
+  /// is added to `Cargo.toml` of the generated crate. When the crate's
+  /// feature is enabled, the build script passes `defines` to the compiler
+  /// when building the wrapper library, so the C++ library must also
+  /// be built with this feature. Types and methods removed by defining
+  /// the macros are not affected, and types only declared with the feature
+  /// enabled are not supported.
+  pub fn add_cpp_feature<S: Into<String>>(&mut self, name: S, defines: Vec<String>) {
+    self
+      .cpp_features
+      .push(CppFeature {
+              name: name.into(),
+              defines: defines,
+            });
+  }
+
 
   /// Adds path to an include directory.
   /// It's supplied to the C++ parser via `-I` option.
@@ -472,6 +658,23 @@ impl Config {
     }
   }
 
+  /// Places types and free functions declared in the C++ namespace
+  /// `cpp_namespace` (e.g. `"mylib::v2::detail"`) into the Rust module
+  /// `rust_module_path` instead of creating a module for each level of
+  /// the namespace. The path is relative to the module generated for
+  /// the header, and nested namespaces get their own modules inside it.
+  /// An empty path places the entities directly in the header's module.
+  ///
+  /// If the mapping makes two C++ types or free functions
+  /// have the same Rust name, the generator reports an error.
+  pub fn add_namespace_mapping<S: Into<String>>(&mut self,
+                                                cpp_namespace: S,
+                                                rust_module_path: Vec<String>) {
+    self
+      .namespace_mappings
+      .insert(cpp_namespace.into(), rust_module_path);
+  }
+
 
   /// Overrides automatic selection of type allocation place for `type_name` and uses `place`
   /// instead. See `CppTypeAllocationPlace` for more information.
@@ -495,6 +698,400 @@ impl Config {
     }
   }
 
+  /// Requests a benchmark comparing construction of the class `class_name`
+  /// as a Rust struct (`CppTypeAllocationPlace::Stack`) and in a `CppBox`
+  /// (`CppTypeAllocationPlace::Heap`). The class must be allocated on stack
+  /// and have a public default constructor and destructor.
+  ///
+  /// The benchmark is saved to `benches/allocation_places.rs` in the
+  /// generated crate and can be run with `cargo bench`. Its results can
+  /// help to choose the allocation place with `set_type_allocation_place`.
+  pub fn add_allocation_benchmark<S: Into<String>>(&mut self, class_name: S) {
+    self.allocation_benchmarks.push(class_name.into());
+  }
+
+  /// Declares that the method `method_name` returns a `void*` pointer
+  /// owned by the caller that must be released with `free_function`.
+  /// `method_name` is the fully qualified C++ name of the method
+  /// (e.g. `"ns::Class1::allocate"`). `free_function` is a path
+  /// to a Rust function accepting the pointer (e.g. `"::libc::free"`).
+  ///
+  /// The return value of such method will be wrapped in
+  /// `cpp_utils::OwnedVoidPtr` that calls `free_function` when dropped.
+  pub fn add_owned_void_ptr_return<S1: Into<String>, S2: Into<String>>(&mut self,
+                                                                       method_name: S1,
+                                                                       free_function: S2) {
+    self
+      .owned_void_ptr_returns
+      .insert(method_name.into(), free_function.into());
+  }
+
+  /// Declares that the method `method_name` returns a pointer to an object
+  /// owned by the caller (e.g. a static factory method of a class
+  /// without public constructors). `method_name` is the fully qualified
+  /// C++ name of the method.
+  ///
+  /// The return value of such method will be wrapped in `cpp_utils::CppBox`
+  /// that deletes the object when dropped. The returned class must have
+  /// a public destructor.
+  pub fn add_owned_ptr_return<S: Into<String>>(&mut self, method_name: S) {
+    self.owned_ptr_returns.push(method_name.into());
+  }
+
+  /// Declares that the constructor `constructor_name` may fail.
+  /// `constructor_name` is the fully qualified C++ name of the constructor
+  /// (e.g. `"ns::File::File"`), and `argument_types` contain C++ code of
+  /// its argument types (e.g. `["const QString&", "int"]`). Only the overload
+  /// with these argument types is affected.
+  /// `validity_check` is a C++ expression of `bool` type that is evaluated
+  /// after construction, with `object` being a pointer to the constructed object
+  /// (e.g. `"object->isValid()"`). If it's false, the object is deleted
+  /// immediately.
+  ///
+  /// The Rust wrapper of such constructor returns
+  /// `Result<CppBox<T>, cpp_utils::ConstructionError>` instead of `CppBox<T>`.
+  /// The class must be allocated on heap.
+  pub fn add_fallible_constructor<S1, S2, Item, Iter>(&mut self,
+                                                      constructor_name: S1,
+                                                      argument_types: Iter,
+                                                      validity_check: S2)
+    where S1: Into<String>,
+          S2: Into<String>,
+          Item: Into<String>,
+          Iter: IntoIterator<Item = Item>
+  {
+    self
+      .fallible_constructors
+      .push(CppFallibleConstructor {
+              constructor_name: constructor_name.into(),
+              argument_types: argument_types.into_iter().map(|x| x.into()).collect(),
+              validity_check: validity_check.into(),
+            });
+  }
+
+  /// Declares that the Rust wrapper of the method `method_name` should take
+  /// `self` by value and return it after the call, allowing chained calls
+  /// on owned values (e.g. `let x = x.with_foo().with_bar()`).
+  /// `method_name` is the fully qualified C++ name of the method.
+  ///
+  /// The method must return `void` or a reference (or a pointer) to
+  /// the object itself, as builder-style methods usually do. The class must
+  /// be allocated on stack (see `Config::set_type_allocation_place`),
+  /// so that its objects can be moved in Rust.
+  pub fn add_consuming_method<S: Into<String>>(&mut self, method_name: S) {
+    self.consuming_methods.push(method_name.into());
+  }
+
+  /// Requests wrapping of protected methods matching `pattern`.
+  /// Protected methods are skipped by default. `pattern` is either
+  /// a fully qualified method name (e.g. `"QWidget::paintEvent"`) or
+  /// `"Class::*"` to expose all protected methods of the class.
+  ///
+  /// The C++ wrapper calls these methods through a helper subclass
+  /// that makes them public, so the class must not be `final`.
+  /// Protected constructors and destructors are never exposed.
+  pub fn add_exposed_protected_method<S: Into<String>>(&mut self, pattern: S) {
+    self.exposed_protected_methods.push(pattern.into());
+  }
+
+  /// Prevents wrapping of methods matching the regular expression `pattern`.
+  /// The pattern is matched against both the fully qualified name
+  /// (e.g. `"QWidget::setVisible"`) and the short text representation
+  /// of the method that includes its arguments (see `CppMethod::short_text`),
+  /// so overloads can be distinguished by their argument types.
+  /// Returns an error if `pattern` is not a valid regular expression.
+  pub fn add_method_blocklist(&mut self, pattern: &str) -> Result<()> {
+    self.method_blocklist.push(Regex::new(pattern)?);
+    Ok(())
+  }
+
+  /// Requests a batched version of the method `method_name`.
+  /// `method_name` is the fully qualified C++ name of a non-static
+  /// method with one argument passed by value or by const reference
+  /// (e.g. `"QVector::append"`).
+  ///
+  /// The generated `{method}_batch` Rust method receives a slice and
+  /// calls the C++ method for each element within a single FFI call,
+  /// which is much faster than calling the method for each element from Rust.
+  pub fn add_batch_operation<S: Into<String>>(&mut self, method_name: S) {
+    self.batch_operations.push(method_name.into());
+  }
+
+  /// Requests a wrapper of the variadic function `function_name`
+  /// (e.g. a function taking a null-terminated list of strings after `...`).
+  /// All variadic arguments must have the C++ type `element_type`
+  /// (e.g. `"const char*"`). Pointers and numeric types not affected by
+  /// default argument promotions (e.g. `int` or `double`, but not `float`
+  /// or `short`) are supported.
+  ///
+  /// The generated Rust function receives the variadic arguments as a slice
+  /// after the fixed arguments. Up to `max_count` items can be passed;
+  /// the function panics if the slice is longer. Sentinel values,
+  /// if the C++ function requires them, must be included in the slice.
+  pub fn add_variadic_slice_function<S1, S2>(&mut self,
+                                             function_name: S1,
+                                             element_type: S2,
+                                             max_count: usize)
+    where S1: Into<String>,
+          S2: Into<String>
+  {
+    self
+      .variadic_slice_functions
+      .push(CppVariadicSliceFunction {
+              function_name: function_name.into(),
+              element_type: element_type.into(),
+              max_count: max_count,
+            });
+  }
+
+  /// Declares that the method `method_name` reports errors errno-style:
+  /// it returns `error_sentinel` on failure, and the error code can be
+  /// obtained by evaluating `error_getter` (e.g. `"-1"` and `"errno"`).
+  /// `method_name` is the fully qualified C++ name of the method, and
+  /// both expressions are C++ code. The error code is read in the C++
+  /// wrapper immediately after the call, so it can't be overwritten
+  /// by other calls.
+  ///
+  /// The method must return a numeric type or a pointer. The Rust method
+  /// will return `Result<T, cpp_utils::CppError>` instead of `T`.
+  pub fn add_errno_method<S1, S2, S3>(&mut self,
+                                      method_name: S1,
+                                      error_sentinel: S2,
+                                      error_getter: S3)
+    where S1: Into<String>,
+          S2: Into<String>,
+          S3: Into<String>
+  {
+    self
+      .errno_methods
+      .push(CppErrnoMethod {
+              method_name: method_name.into(),
+              error_sentinel: error_sentinel.into(),
+              error_getter: error_getter.into(),
+            });
+  }
+
+  /// Declares that the method `method_name` fills a caller-provided
+  /// `char*` buffer passed as the argument `buffer_arg`, receives
+  /// the buffer's size as the argument `length_arg`, and returns
+  /// the length of the string without the terminating null character.
+  /// `method_name` is the fully qualified C++ name of the method.
+  ///
+  /// The Rust method will not have these arguments and will return
+  /// a `String` instead. If the returned length doesn't fit in the buffer,
+  /// it's treated as the required size, and the method is called again
+  /// with a larger buffer (like `snprintf` does).
+  pub fn add_string_buffer_out<S1, S2, S3>(&mut self,
+                                           method_name: S1,
+                                           buffer_arg: S2,
+                                           length_arg: S3)
+    where S1: Into<String>,
+          S2: Into<String>,
+          S3: Into<String>
+  {
+    self
+      .string_buffer_outs
+      .insert(method_name.into(), (buffer_arg.into(), length_arg.into()));
+  }
+
+  /// Declares that the method `method_name` fills a caller-provided
+  /// fixed-size array passed by reference as the argument `array_arg`
+  /// (e.g. `void getMatrix(float (&out)[16])`).
+  /// `method_name` is the fully qualified C++ name of the method,
+  /// and it must return `void`.
+  ///
+  /// The Rust method will not have this argument and will return
+  /// the filled array (e.g. `[f32; 16]`) instead. Without this option,
+  /// the method takes a mutable reference to the array.
+  pub fn add_array_out_param<S1: Into<String>, S2: Into<String>>(&mut self,
+                                                                 method_name: S1,
+                                                                 array_arg: S2) {
+    self
+      .array_out_params
+      .insert(method_name.into(), array_arg.into());
+  }
+
+  /// Declares that the reference returned by the method `method_name`
+  /// points into its argument with index `arg_index` (counting from 0,
+  /// not including `this`) rather than into `self`.
+  /// `method_name` is the fully qualified C++ name of the method.
+  ///
+  /// By default, a returned reference is tied to the lifetime of `self`
+  /// or of the first reference argument of a free function. With this option,
+  /// the returned reference will be tied to the lifetime of the specified
+  /// argument instead, so it can't outlive the object it borrows from.
+  pub fn add_return_lifetime<S: Into<String>>(&mut self, method_name: S, arg_index: usize) {
+    self.return_lifetimes.insert(method_name.into(), arg_index);
+  }
+
+  /// Declares that the method `method_name` takes ownership of the object
+  /// passed as a pointer in its argument with index `arg_index` (counting from 0,
+  /// not including `this`) and deletes it later (e.g. `QLayout::addWidget`).
+  /// `method_name` is the fully qualified C++ name of the method.
+  ///
+  /// The Rust method will consume `cpp_utils::CppBox<T>` passed by value
+  /// and release it without deleting the object, so the object is only
+  /// deleted by the C++ library. The argument must be a non-const pointer
+  /// to a class with a public destructor. Call this method once per argument.
+  pub fn add_ownership_transfer_arg<S: Into<String>>(&mut self,
+                                                     method_name: S,
+                                                     arg_index: usize) {
+    self
+      .ownership_transfer_args
+      .entry(method_name.into())
+      .or_insert_with(Vec::new)
+      .push(arg_index);
+  }
+
+  /// Maps the C++ class `cpp_name` to an existing Rust type `rust_name`
+  /// (e.g. a hand-written wrapper from another crate) instead of generating
+  /// a new opaque type for it. Methods of the class are not generated.
+  ///
+  /// Wherever the class is used in the API, the FFI functions use
+  /// a pointer to `rust_name`, and `conversion` determines the Rust API type:
+  /// `RustToCTypeConversion::RefToPtr` gives a reference,
+  /// `RustToCTypeConversion::ValueToPtr` gives the value itself,
+  /// `RustToCTypeConversion::CppBoxToPtr` gives `cpp_utils::CppBox<T>`,
+  /// and `RustToCTypeConversion::None` keeps the raw pointer.
+  /// The crate containing `rust_name` should be added as a dependency
+  /// with `CrateProperties::add_dependency`.
+  pub fn add_type_mapping<S: Into<String>>(&mut self,
+                                           cpp_name: S,
+                                           rust_name: RustName,
+                                           conversion: RustToCTypeConversion) {
+    self
+      .type_mappings
+      .insert(cpp_name.into(), (rust_name, conversion));
+  }
+
+  /// Declares that the C++ class `cpp_class` can be copied by calling
+  /// its virtual method `clone_method` that returns a pointer to a new object
+  /// owned by the caller (e.g. `virtual Base* clone() const`).
+  ///
+  /// `cpp_utils::CppVirtualClone` will be implemented for the Rust type,
+  /// making `CppBox` of this type implement `Clone`. If the method returns
+  /// a pointer to a base class, the pointer is converted to `cpp_class` using
+  /// `static_cast`. The class must be heap allocated and have a public destructor.
+  pub fn add_virtual_clone<S1: Into<String>, S2: Into<String>>(&mut self,
+                                                               cpp_class: S1,
+                                                               clone_method: S2) {
+    self
+      .virtual_clones
+      .insert(cpp_class.into(), clone_method.into());
+  }
+
+  /// Enables generation of `serde::Serialize` and `serde::Deserialize`
+  /// implementations for the C++ enum `cpp_name` (e.g. `"ns::Class1::Enum1"`).
+  /// The generated crate will depend on `serde` if any enum is added.
+  /// Deserialization of an unknown value will produce an error
+  /// unless a fallback is set with `Config::set_serde_enum_fallback`.
+  pub fn add_serde_enum<S: Into<String>>(&mut self, cpp_name: S) {
+    self.serde_enums.entry(cpp_name.into()).or_insert(None);
+  }
+
+  /// Makes deserialization of the C++ enum `cpp_name` produce
+  /// the Rust variant `variant_name` instead of an error
+  /// if an unknown value is encountered. Also enables `serde`
+  /// implementations for this enum (see `Config::add_serde_enum`).
+  pub fn set_serde_enum_fallback<S1: Into<String>, S2: Into<String>>(&mut self,
+                                                                     cpp_name: S1,
+                                                                     variant_name: S2) {
+    self
+      .serde_enums
+      .insert(cpp_name.into(), Some(variant_name.into()));
+  }
+
+  /// Sets representation used by all generated `serde` implementations.
+  /// Default is `SerdeEnumRepresentation::Name`.
+  pub fn set_serde_enum_representation(&mut self, value: SerdeEnumRepresentation) {
+    self.serde_enum_representation = value;
+  }
+
+  /// Generates `migrate_from_v{version}(old: i64) -> Option<Self>` function
+  /// for the C++ enum `cpp_name`. The function converts integer values
+  /// of the enum used in the version `version` of the C++ library
+  /// (e.g. values persisted by an older release) to current variants.
+  /// `old_values[i]` is mapped to `new_values[i]`, and values
+  /// not present in `old_values` are considered unchanged.
+  /// Call this method once per old version of the library.
+  pub fn add_enum_value_migration<S: Into<String>>(&mut self,
+                                                   cpp_name: S,
+                                                   version: u32,
+                                                   old_values: &[i64],
+                                                   new_values: &[i64]) {
+    self
+      .enum_value_migrations
+      .entry(cpp_name.into())
+      .or_insert_with(Vec::new)
+      .push(EnumValueMigration {
+              version: version,
+              old_values: old_values.to_vec(),
+              new_values: new_values.to_vec(),
+            });
+  }
+
+  /// Allows implementation of `FlaggableEnum` for the scoped C++ enum
+  /// (`enum class`) `cpp_name` if `QFlags<T>` with this enum is used in API.
+  /// Scoped enums can't be implicitly converted to integers in C++,
+  /// so they are not considered flaggable by default.
+  pub fn add_flaggable_scoped_enum<S: Into<String>>(&mut self, cpp_name: S) {
+    self.flaggable_scoped_enums.push(cpp_name.into());
+  }
+
+  /// Enables emission of `#[non_exhaustive]` on all generated enums,
+  /// so that code outside of the generated crate can't match them exhaustively
+  /// and won't break if a future version of the C++ library adds more values.
+  /// The generated crate will require Rust 1.40 or newer.
+  /// Disabled by default.
+  pub fn set_enums_non_exhaustive(&mut self, value: bool) {
+    self.enums_non_exhaustive = value;
+  }
+
+  /// Enables emission of `#[track_caller]` on generated methods that may panic
+  /// (e.g. methods converting a returned null pointer to a reference),
+  /// so that panic messages point to the caller's code instead of the generated crate.
+  /// This slightly increases the code size.
+  /// The generated crate will require Rust 1.46 or newer.
+  /// Disabled by default.
+  pub fn set_track_caller(&mut self, value: bool) {
+    self.track_caller = value;
+  }
+
+  /// Enables opening a `tracing` span in each generated method calling a C++ function.
+  /// The span has `TRACE` level and is named after the C++ method
+  /// (e.g. `QObject::setObjectName`).
+  /// The generated crate will have an optional dependency on `tracing`,
+  /// and the spans are only compiled if its `tracing` feature is enabled.
+  /// Disabled by default.
+  pub fn set_tracing_spans(&mut self, value: bool) {
+    self.tracing_spans = value;
+  }
+
+  /// Sets the policy determining which generated methods are `unsafe`.
+  /// Default is `SafetyPolicy::RawPointerArguments`.
+  pub fn set_safety_policy(&mut self, value: SafetyPolicy) {
+    self.safety_policy = value;
+  }
+
+  /// Sets how the generated C++ wrapper code is split into source files.
+  /// Default is `FfiTuStrategy::PerHeader`.
+  pub fn set_ffi_tu_strategy(&mut self, value: FfiTuStrategy) {
+    self.ffi_tu_strategy = value;
+  }
+
+  /// Sets how methods using `volatile` types are processed.
+  /// Default is `VolatilePolicy::Reject`.
+  pub fn set_volatile_policy(&mut self, value: VolatilePolicy) {
+    self.volatile_policy = value;
+  }
+
+  /// If enabled, all headers of the C++ wrapper library are regenerated.
+  /// Otherwise, code generated in the previous run is reused for headers
+  /// whose input data didn't change. Disabled by default.
+  pub fn set_force_cpp_code_regeneration(&mut self, value: bool) {
+    self.force_cpp_code_regeneration = value;
+  }
+
   /// Changes how debug logs are handled. See `DebugLoggingConfig` for more information.
   pub fn set_debug_logging_config(&mut self, config: DebugLoggingConfig) {
     self.debug_logging_config = config;
@@ -539,6 +1136,132 @@ impl Config {
     self.cpp_lib_version.as_ref().map(|x| x.as_str())
   }
 
+  /// Sets the directory for failure dumps. If set, each C++ method
+  /// the FFI generator fails to process will be saved to this directory
+  /// as a JSON file containing the method, the error message and
+  /// declarations of all involved types. The dump can be loaded as
+  /// `CppFfiFailureDump` to reproduce the failure in a unit test.
+  pub fn set_failure_dump_dir<P: Into<PathBuf>>(&mut self, path: P) {
+    self.failure_dump_dir = Some(path.into());
+  }
+
+  /// Sets a version tag appended to names of all generated FFI functions.
+  /// Wrappers generated for different versions of the library
+  /// with different tags can be linked into the same binary without
+  /// symbol collisions. Characters not allowed in C identifiers
+  /// are replaced with underscores.
+  pub fn set_symbol_version<S: Into<String>>(&mut self, tag: S) {
+    self.symbol_version = Some(tag.into());
+  }
+
+  /// Enables `QVariant` conversions for the C++ class `cpp_name`
+  /// (e.g. `"ns::Class1"`). The class must be registered with
+  /// `Q_DECLARE_METATYPE` in the library's headers.
+  ///
+  /// The generated Rust type will have `to_variant`, `from_variant`,
+  /// `can_convert_from_variant` and `try_from_variant` methods,
+  /// and `Variant` will implement `From` a reference to the type.
+  pub fn add_metatype<S: Into<String>>(&mut self, cpp_name: S) {
+    self.metatypes.push(cpp_name.into());
+  }
+
+  /// Adds an instantiation of the template method `method_name`
+  /// of a non-template class (e.g. `"QObject::findChild"`).
+  /// `template_arguments` contain C++ code of the template arguments
+  /// (e.g. `["QWidget*"]`). Class and enum types, built-in numeric types
+  /// and pointers and references to them are supported.
+  ///
+  /// The instantiation will be available as a separate Rust method
+  /// with the template arguments appended to its name (e.g. `find_child_widget`).
+  /// Call this method multiple times to add several instantiations
+  /// of the same method.
+  pub fn add_template_method_instantiation<S, Item, Iter>(&mut self,
+                                                          method_name: S,
+                                                          template_arguments: Iter)
+    where S: Into<String>,
+          Item: Into<String>,
+          Iter: IntoIterator<Item = Item>
+  {
+    self
+      .template_method_instantiations
+      .push(CppTemplateMethodInstantiation {
+              method_name: method_name.into(),
+              template_arguments: template_arguments.into_iter().map(|x| x.into()).collect(),
+            });
+  }
+
+  /// Adds an instantiation of the template method `method_name`
+  /// (e.g. `"IntList::sort"`) that takes a function object as its only
+  /// template parameter (e.g. `template<class LessThan> void sort(LessThan f)`).
+  /// `return_type` and `argument_types` contain C++ code of the signature
+  /// of the function object (e.g. `"bool"` and `["int", "int"]`).
+  /// Only built-in numeric types are currently supported in the signature.
+  ///
+  /// The method will be instantiated with a generated C++ class calling
+  /// a callback function. On Rust side, the class is wrapped
+  /// in a struct that can be constructed from a Rust closure.
+  pub fn add_functor_template_instantiation<S1, S2, Item, Iter>(&mut self,
+                                                                method_name: S1,
+                                                                return_type: S2,
+                                                                argument_types: Iter)
+    where S1: Into<String>,
+          S2: Into<String>,
+          Item: Into<String>,
+          Iter: IntoIterator<Item = Item>
+  {
+    self
+      .functor_template_instantiations
+      .push(CppFunctorTemplateInstantiation {
+              method_name: method_name.into(),
+              return_type: return_type.into(),
+              argument_types: argument_types.into_iter().map(|x| x.into()).collect(),
+            });
+  }
+
+  /// Enables conversion from a reference to the C++ class `derived_name`
+  /// to a value of its base class `base_name` (e.g. `"ns::Derived"` and `"ns::Base"`).
+  ///
+  /// The conversion copies only the base part of the object (object slicing),
+  /// so data of the derived class is lost and virtual methods of the result
+  /// behave as in the base class. This is usually a bug, so such conversions
+  /// are not generated by default. Use reference upcasts (`AsRef`, `AsMut`,
+  /// `StaticCast`) to access a derived object as its base without copying.
+  ///
+  /// If the conversion is enabled, the base class will implement
+  /// `From<&Derived>` using its copy constructor.
+  pub fn add_slicing_conversion<S1, S2>(&mut self, derived_name: S1, base_name: S2)
+    where S1: Into<String>,
+          S2: Into<String>
+  {
+    self
+      .slicing_conversions
+      .push((derived_name.into(), base_name.into()));
+  }
+
+  /// Adds a constant `const_name` to the C++ class `cpp_name`.
+  /// The value of the constant is computed by the C++ expression
+  /// `ffi_initializer` of `int` type (e.g. `"qRegisterMetaType<QPoint>()"`).
+  ///
+  /// The generated Rust type will have a function returning the value.
+  /// The expression is evaluated on the first call of the function,
+  /// and its value is reused by subsequent calls.
+  pub fn add_computed_type_constant<S1, S2, S3>(&mut self,
+                                                cpp_name: S1,
+                                                const_name: S2,
+                                                ffi_initializer: S3)
+    where S1: Into<String>,
+          S2: Into<String>,
+          S3: Into<String>
+  {
+    self
+      .computed_type_constants
+      .push(CppComputedTypeConstant {
+              cpp_name: cpp_name.into(),
+              const_name: const_name.into(),
+              ffi_initializer: ffi_initializer.into(),
+            });
+  }
+
   /// Starts execution of the generator.
   /// This function will print the necessary build script output to stdout.
   /// It also displays some debugging output that can be made visible by
@@ -592,6 +1315,10 @@ impl Config {
   pub fn cpp_parser_arguments(&self) -> &[String] {
     &self.cpp_parser_arguments
   }
+  /// Returns values added by `Config::add_cpp_feature`.
+  pub fn cpp_features(&self) -> &[CppFeature] {
+    &self.cpp_features
+  }
 
 
   /// Returns values added by `Config::add_include_path`.
@@ -637,6 +1364,13 @@ impl Config {
     &self.cpp_filtered_namespaces
   }
 
+  /// Returns values added by `Config::add_namespace_mapping`.
+  /// Keys of the hash map are names of C++ namespaces, values are
+  /// paths of the corresponding Rust modules.
+  pub fn namespace_mappings(&self) -> &HashMap<String, Vec<String>> {
+    &self.namespace_mappings
+  }
+
   /// Returns current `CppBuildConfig` value.
   pub fn cpp_build_config(&self) -> &CppBuildConfig {
     &self.cpp_build_config
@@ -670,6 +1404,156 @@ impl Config {
   pub fn write_cache(&self) -> bool {
     self.write_cache
   }
+  /// Returns values added by `Config::add_owned_void_ptr_return`.
+  /// Keys of the hash map are names of C++ methods, values are
+  /// Rust paths to free functions.
+  pub fn owned_void_ptr_returns(&self) -> &HashMap<String, String> {
+    &self.owned_void_ptr_returns
+  }
+  /// Returns values added by `Config::add_owned_ptr_return`.
+  pub fn owned_ptr_returns(&self) -> &[String] {
+    &self.owned_ptr_returns
+  }
+  /// Returns values added by `Config::add_fallible_constructor`.
+  pub fn fallible_constructors(&self) -> &[CppFallibleConstructor] {
+    &self.fallible_constructors
+  }
+  /// Returns values added by `Config::add_consuming_method`.
+  pub fn consuming_methods(&self) -> &[String] {
+    &self.consuming_methods
+  }
+  /// Returns values added by `Config::add_exposed_protected_method`.
+  pub fn exposed_protected_methods(&self) -> &[String] {
+    &self.exposed_protected_methods
+  }
+  /// Returns patterns added by `Config::add_method_blocklist`.
+  pub fn method_blocklist(&self) -> &[Regex] {
+    &self.method_blocklist
+  }
+  /// Returns values added by `Config::add_allocation_benchmark`.
+  pub fn allocation_benchmarks(&self) -> &[String] {
+    &self.allocation_benchmarks
+  }
+  /// Returns values added by `Config::add_batch_operation`.
+  pub fn batch_operations(&self) -> &[String] {
+    &self.batch_operations
+  }
+  /// Returns values added by `Config::add_variadic_slice_function`.
+  pub fn variadic_slice_functions(&self) -> &[CppVariadicSliceFunction] {
+    &self.variadic_slice_functions
+  }
+  /// Returns values added by `Config::add_errno_method`.
+  pub fn errno_methods(&self) -> &[CppErrnoMethod] {
+    &self.errno_methods
+  }
+  /// Returns values added by `Config::add_string_buffer_out`.
+  /// Keys of the hash map are names of C++ methods, values are
+  /// names of the buffer and length arguments.
+  pub fn string_buffer_outs(&self) -> &HashMap<String, (String, String)> {
+    &self.string_buffer_outs
+  }
+  /// Returns values added by `Config::add_array_out_param`.
+  /// Keys of the hash map are names of C++ methods, values are
+  /// names of the array arguments.
+  pub fn array_out_params(&self) -> &HashMap<String, String> {
+    &self.array_out_params
+  }
+  /// Returns values added by `Config::add_return_lifetime`.
+  /// Keys of the hash map are names of C++ methods, values are
+  /// indexes of the arguments.
+  pub fn return_lifetimes(&self) -> &HashMap<String, usize> {
+    &self.return_lifetimes
+  }
+  /// Returns values added by `Config::add_ownership_transfer_arg`.
+  pub fn ownership_transfer_args(&self) -> &HashMap<String, Vec<usize>> {
+    &self.ownership_transfer_args
+  }
+  /// Returns values added by `Config::add_type_mapping`.
+  pub fn type_mappings(&self) -> &HashMap<String, (RustName, RustToCTypeConversion)> {
+    &self.type_mappings
+  }
+  /// Returns values added by `Config::add_virtual_clone`.
+  /// Keys of the hash map are names of C++ classes, values are
+  /// names of their clone methods.
+  pub fn virtual_clones(&self) -> &HashMap<String, String> {
+    &self.virtual_clones
+  }
+  /// Returns values added by `Config::add_serde_enum` and
+  /// `Config::set_serde_enum_fallback`. Keys of the hash map are names of C++ enums,
+  /// values are fallback Rust variant names.
+  pub fn serde_enums(&self) -> &HashMap<String, Option<String>> {
+    &self.serde_enums
+  }
+  /// Returns values added by `Config::add_enum_value_migration`.
+  /// Keys of the hash map are names of C++ enums.
+  pub fn enum_value_migrations(&self) -> &HashMap<String, Vec<EnumValueMigration>> {
+    &self.enum_value_migrations
+  }
+  /// Returns values added by `Config::add_flaggable_scoped_enum`.
+  pub fn flaggable_scoped_enums(&self) -> &[String] {
+    &self.flaggable_scoped_enums
+  }
+  /// Returns value set by `Config::set_serde_enum_representation`.
+  pub fn serde_enum_representation(&self) -> &SerdeEnumRepresentation {
+    &self.serde_enum_representation
+  }
+  /// Returns value set by `Config::set_enums_non_exhaustive`.
+  pub fn enums_non_exhaustive(&self) -> bool {
+    self.enums_non_exhaustive
+  }
+  /// Returns value set by `Config::set_track_caller`.
+  pub fn track_caller(&self) -> bool {
+    self.track_caller
+  }
+  /// Returns value set by `Config::set_tracing_spans`.
+  pub fn tracing_spans(&self) -> bool {
+    self.tracing_spans
+  }
+  /// Returns value set by `Config::set_safety_policy`.
+  pub fn safety_policy(&self) -> &SafetyPolicy {
+    &self.safety_policy
+  }
+  /// Returns value set by `Config::set_ffi_tu_strategy`.
+  pub fn ffi_tu_strategy(&self) -> &FfiTuStrategy {
+    &self.ffi_tu_strategy
+  }
+  /// Returns value set by `Config::set_volatile_policy`.
+  pub fn volatile_policy(&self) -> &VolatilePolicy {
+    &self.volatile_policy
+  }
+  /// Returns value set by `Config::set_force_cpp_code_regeneration`.
+  pub fn force_cpp_code_regeneration(&self) -> bool {
+    self.force_cpp_code_regeneration
+  }
+  /// Returns value set by `Config::set_failure_dump_dir`.
+  pub fn failure_dump_dir(&self) -> Option<&PathBuf> {
+    self.failure_dump_dir.as_ref()
+  }
+  /// Returns value set by `Config::set_symbol_version`.
+  pub fn symbol_version(&self) -> Option<&String> {
+    self.symbol_version.as_ref()
+  }
+  /// Returns values added by `Config::add_metatype`.
+  pub fn metatypes(&self) -> &[String] {
+    &self.metatypes
+  }
+  /// Returns values added by `Config::add_computed_type_constant`.
+  pub fn computed_type_constants(&self) -> &[CppComputedTypeConstant] {
+    &self.computed_type_constants
+  }
+  /// Returns values added by `Config::add_template_method_instantiation`.
+  pub fn template_method_instantiations(&self) -> &[CppTemplateMethodInstantiation] {
+    &self.template_method_instantiations
+  }
+  /// Returns values added by `Config::add_slicing_conversion`.
+  /// Each item contains names of the derived and the base class.
+  pub fn slicing_conversions(&self) -> &[(String, String)] {
+    &self.slicing_conversions
+  }
+  /// Returns values added by `Config::add_functor_template_instantiation`.
+  pub fn functor_template_instantiations(&self) -> &[CppFunctorTemplateInstantiation] {
+    &self.functor_template_instantiations
+  }
 }
 
 pub use launcher::{is_completed, completed_marker_path, exec};
@@ -1,13 +1,22 @@
 use cpp_ffi_data::{QtSlotWrapper, CppIndirectionChange, CppAndFfiMethod, CppFfiArgumentMeaning,
-                   CppFfiHeaderData, CppFfiType, CppFieldAccessorType, CppFfiMethodKind};
-use cpp_method::ReturnValueAllocationPlace;
-use cpp_type::{CppTypeIndirection, CppTypeBase, CppType};
+                   CppFfiHeaderData, CppFfiType, CppFieldAccessorType, CppFfiMethodKind,
+                   CppVariantConversion, QtEventFilterWrapper, CppFunctorWrapper,
+                   CppFallibleConstructor};
+use cpp_method::{ReturnValueAllocationPlace, CppVisibility, CppRefQualifier};
+use cpp_type::{CppTypeIndirection, CppTypeBase, CppType, CppTypeClassBase};
 use common::errors::{Result, ChainErr, unexpected};
-use common::file_utils::{PathBufWithAdded, create_dir_all, create_file, path_to_str};
+use common::file_utils::{PathBufWithAdded, create_dir_all, create_file, path_to_str,
+                         file_to_string, load_bincode, save_bincode};
+use common::log;
 use common::string_utils::JoinWithSeparator;
 use common::utils::MapIfOk;
 use common::utils::get_command_output;
+use common::CppFeature;
+use config::FfiTuStrategy;
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::iter::once;
 use std::process::Command;
@@ -20,17 +29,76 @@ pub struct CppCodeGenerator {
   lib_name_upper: String,
   /// Path to the directory where the library is generated
   lib_path: ::std::path::PathBuf,
+  /// Determines how the generated code is split into source files
+  tu_strategy: FfiTuStrategy,
+  /// Path to the file containing code generated in the previous run
+  code_cache_path: Option<PathBuf>,
+  /// If true, the code cache is not used to skip generation of headers
+  force_regeneration: bool,
+  /// Validity checks of fallible constructors, as in `Config`
+  fallible_constructors: Vec<CppFallibleConstructor>,
+}
+
+/// Generated code of a header of the C++ wrapper library.
+/// It's saved to the code cache and reused in the next run
+/// if the header's input data doesn't change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CppHeaderCode {
+  /// Hash of the header's input data
+  hash: u64,
+  /// Content of the FFI header file
+  header: String,
+  /// Code implementing the header's functions
+  source: String,
 }
 
 impl CppCodeGenerator {
   /// Creates a generator for a library.
   /// lib_name: library name
   /// lib_path: path to the directory where the library is generated
-  pub fn new(lib_name: String, lib_path: ::std::path::PathBuf) -> Self {
+  /// tu_strategy: determines how the generated code is split into source files
+  pub fn new(lib_name: String,
+             lib_path: ::std::path::PathBuf,
+             tu_strategy: FfiTuStrategy)
+             -> Self {
     CppCodeGenerator {
       lib_name: lib_name.clone(),
       lib_name_upper: lib_name.to_uppercase(),
       lib_path: lib_path,
+      tu_strategy: tu_strategy,
+      code_cache_path: None,
+      force_regeneration: false,
+      fallible_constructors: Vec::new(),
+    }
+  }
+
+  /// Enables the code cache stored at `path`. Headers whose input data
+  /// didn't change since the previous run are restored from the cache
+  /// instead of being generated again. If `force_regeneration` is true,
+  /// all headers are generated, but the cache is still updated.
+  pub fn set_code_cache(&mut self, path: PathBuf, force_regeneration: bool) {
+    self.code_cache_path = Some(path);
+    self.force_regeneration = force_regeneration;
+  }
+
+  /// Sets fallible constructors and their validity checks
+  /// (see `Config::add_fallible_constructor`).
+  pub fn set_fallible_constructors(&mut self, value: Vec<CppFallibleConstructor>) {
+    self.fallible_constructors = value;
+  }
+
+  /// Returns the validity check of `method` if it's a heap allocating
+  /// constructor added with `Config::add_fallible_constructor`.
+  fn fallible_constructor_check(&self, method: &CppAndFfiMethod) -> Option<&String> {
+    if method.cpp_method.is_constructor() &&
+       method.allocation_place == ReturnValueAllocationPlace::Heap {
+      self
+        .fallible_constructors
+        .iter()
+        .find(|c| c.matches(&method.cpp_method))
+        .map(|c| &c.validity_check)
+    } else {
+      None
     }
   }
 
@@ -43,7 +111,7 @@ impl CppCodeGenerator {
     }
     let name_with_args = format!("{}({})", method.c_name, arg_texts.join(", "));
     let return_type = &method.c_signature.return_type.ffi_type;
-    let r = if let CppTypeBase::FunctionPointer(..) = return_type.base {
+    let r = if return_type.base.is_function_pointer() || return_type.base.is_array() {
       return_type.to_cpp_code(Some(&name_with_args))?
     } else {
       format!("{} {}", return_type.to_cpp_code(None)?, name_with_args)
@@ -65,6 +133,7 @@ impl CppCodeGenerator {
       indirection: CppTypeIndirection::None,
       is_const: false,
       is_const2: false,
+      is_volatile: false,
     };
     let method_args = wrapper
       .arguments
@@ -92,6 +161,64 @@ impl CppCodeGenerator {
 
   }
 
+  /// Generates definition of the event filter wrapper class.
+  fn qt_event_filter_wrapper(&self, wrapper: &QtEventFilterWrapper) -> Result<String> {
+    let func_type = CppType {
+      base: CppTypeBase::FunctionPointer(wrapper.function_type.clone()),
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+    };
+    Ok(format!(include_str!("../templates/c_lib/qt_event_filter_wrapper.h"),
+               class_name = &wrapper.class_name,
+               func_arg = func_type.to_cpp_code(Some("func"))?,
+               func_field = func_type.to_cpp_code(Some("m_func"))?))
+  }
+
+  /// Generates definition of a function object wrapper class.
+  fn functor_wrapper(&self, wrapper: &CppFunctorWrapper) -> Result<String> {
+    let func_type = CppType {
+      base: CppTypeBase::FunctionPointer(wrapper.function_type.clone()),
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+    };
+    let method_args = wrapper
+      .arguments
+      .iter()
+      .enumerate()
+      .map_if_ok(|(num, t)| -> Result<_> {
+                   Ok(format!("{} arg{}", t.original_type.to_cpp_code(None)?, num))
+                 })?
+      .join(", ");
+    let func_args = once("m_data".to_string())
+      .chain(wrapper
+               .arguments
+               .iter()
+               .enumerate()
+               .map_if_ok(|(num, t)| self.convert_type_to_ffi(t, format!("arg{}", num)))?)
+      .join(", ");
+    if let Some(ref base_class) = wrapper.base_class {
+      return Ok(format!(include_str!("../templates/c_lib/member_function_trampoline.h"),
+                        class_name = &wrapper.class_name,
+                        base_class = base_class.to_cpp_code()?,
+                        return_type = wrapper.return_type.original_type.to_cpp_code(None)?,
+                        func_arg = func_type.to_cpp_code(Some("func"))?,
+                        func_field = func_type.to_cpp_code(Some("m_func"))?,
+                        method_args = method_args,
+                        func_args = func_args));
+    }
+    Ok(format!(include_str!("../templates/c_lib/functor_wrapper.h"),
+               class_name = &wrapper.class_name,
+               return_type = wrapper.return_type.original_type.to_cpp_code(None)?,
+               func_arg = func_type.to_cpp_code(Some("func"))?,
+               func_field = func_type.to_cpp_code(Some("m_func"))?,
+               method_args = method_args,
+               func_args = func_args))
+  }
+
   /// Generates code that wraps `expression` of type `type1.original_type` and
   /// converts it to type `type1.ffi_type`
   fn convert_type_to_ffi(&self, type1: &CppFfiType, expression: String) -> Result<String> {
@@ -104,6 +231,23 @@ impl CppCodeGenerator {
          }
          CppIndirectionChange::ReferenceToPointer => format!("&{}", expression),
          CppIndirectionChange::QFlagsToUInt => format!("uint({})", expression),
+         CppIndirectionChange::QCharToUShort => format!("{}.unicode()", expression),
+         CppIndirectionChange::RValueReferenceToPointer => {
+           return Err(unexpected("rvalue references can't be converted to FFI").into());
+         }
+         CppIndirectionChange::StringViewToStrRef => {
+           return Err(unexpected("std::string_view can't be converted to FFI").into());
+         }
+         CppIndirectionChange::SpanToSliceRef => {
+           return Err(unexpected("std::span can't be converted to FFI").into());
+         }
+         CppIndirectionChange::UniquePtrToPtr => format!("{}.release()", expression),
+         CppIndirectionChange::InitializerListToVecOut => {
+           return Err(unexpected("std::initializer_list can't be converted to FFI").into());
+         }
+         CppIndirectionChange::StdFunctionToClosure => {
+           return Err(unexpected("std::function can't be converted to FFI").into());
+         }
        })
   }
 
@@ -139,6 +283,40 @@ impl CppCodeGenerator {
       CppIndirectionChange::QFlagsToUInt => {
         result = format!("uint({})", result);
       }
+      CppIndirectionChange::QCharToUShort => {
+        return Err(unexpected("QChar is not converted in return types").into());
+      }
+      CppIndirectionChange::RValueReferenceToPointer => {
+        return Err(unexpected("rvalue references are not supported as return types").into());
+      }
+      CppIndirectionChange::StringViewToStrRef => {
+        return Err(unexpected("std::string_view is not supported as return type").into());
+      }
+      CppIndirectionChange::SpanToSliceRef => {
+        return Err(unexpected("std::span is not supported as return type").into());
+      }
+      CppIndirectionChange::UniquePtrToPtr => {
+        result = format!("{}.release()", result);
+      }
+      CppIndirectionChange::InitializerListToVecOut => {
+        return Err(unexpected("std::initializer_list must be returned \
+                               through an output argument")
+                       .into());
+      }
+      CppIndirectionChange::StdFunctionToClosure => {
+        return Err(unexpected("std::function is not supported as return type").into());
+      }
+    }
+
+    if let Some(arg) = method
+         .c_signature
+         .arguments
+         .iter()
+         .find(|x| {
+                 x.meaning == CppFfiArgumentMeaning::ReturnValue &&
+                 x.argument_type.conversion == CppIndirectionChange::InitializerListToVecOut
+               }) {
+      return Ok(format!("cpp_utils_copy_to_vec_out({}, {})", arg.name, result));
     }
 
     if method.allocation_place == ReturnValueAllocationPlace::Stack &&
@@ -158,9 +336,25 @@ impl CppCodeGenerator {
   }
 
   /// Generates code for values passed to the original C++ method.
-  fn arguments_values(&self, method: &CppAndFfiMethod) -> Result<String> {
+  /// `variadic_items` are appended to the values in place of the `items`
+  /// and `count` arguments of a `VariadicSlice` function. The output arguments
+  /// of an `ErrnoMethod` function are not passed to the original method.
+  /// A `MemberFunctionTrampoline` function passes its trampoline object
+  /// and a pointer to the trampoline's `call` method.
+  fn arguments_values(&self,
+                      method: &CppAndFfiMethod,
+                      variadic_items: &[String])
+                      -> Result<String> {
     let mut filled_arguments = vec![];
-    for (i, cpp_argument) in method.cpp_method.arguments.iter().enumerate() {
+    let arguments = match method.kind {
+      CppFfiMethodKind::VariadicSlice { .. } |
+      CppFfiMethodKind::ErrnoMethod { .. } => {
+        let fixed_count = method.cpp_method.arguments.len() - 2;
+        &method.cpp_method.arguments[..fixed_count]
+      }
+      _ => &method.cpp_method.arguments[..],
+    };
+    for (i, cpp_argument) in arguments.iter().enumerate() {
       if let Some(c_argument) = method
            .c_signature
            .arguments
@@ -170,6 +364,40 @@ impl CppCodeGenerator {
         match c_argument.argument_type.conversion {
           CppIndirectionChange::ValueToPointer |
           CppIndirectionChange::ReferenceToPointer => result = format!("*{}", result),
+          CppIndirectionChange::RValueReferenceToPointer => {
+            result = format!("std::move(*{})", result)
+          }
+          CppIndirectionChange::StringViewToStrRef => {
+            result = format!("std::string_view({0}.data, {0}.size)", result)
+          }
+          CppIndirectionChange::SpanToSliceRef => {
+            if let CppTypeBase::Span { ref element } = cpp_argument.argument_type.base {
+              result = format!("std::span<{0}>(static_cast<{0}*>({1}.data), {1}.size)",
+                               element.to_cpp_code(None)?,
+                               result);
+            } else {
+              return Err(unexpected("std::span argument expected").into());
+            }
+          }
+          CppIndirectionChange::UniquePtrToPtr => {
+            return Err(unexpected("std::unique_ptr arguments are not supported").into());
+          }
+          CppIndirectionChange::InitializerListToVecOut => {
+            return Err(unexpected("std::initializer_list arguments are not supported").into());
+          }
+          CppIndirectionChange::StdFunctionToClosure => {
+            let function = match cpp_argument.argument_type.base {
+              CppTypeBase::Class(ref class_type) => class_type.std_function_type(),
+              _ => None,
+            };
+            if let Some(function) = function {
+              result = format!("std::function< {0} >(cpp_utils_closure_function< {0} >({1}))",
+                               function.to_cpp_function_type_code()?,
+                               result);
+            } else {
+              return Err(unexpected("std::function argument expected").into());
+            }
+          }
           CppIndirectionChange::NoChange => {}
           CppIndirectionChange::QFlagsToUInt => {
             let type_text = if cpp_argument.argument_type.indirection == CppTypeIndirection::Ref &&
@@ -183,18 +411,52 @@ impl CppCodeGenerator {
             };
             result = format!("{}({})", type_text, result);
           }
+          CppIndirectionChange::QCharToUShort => result = format!("QChar({})", result),
+        }
+        if let CppFfiMethodKind::MemberFunctionTrampoline {
+                 ref trampoline_class,
+                 ref object_argument_index,
+                 ..
+               } = method.kind {
+          if i == *object_argument_index {
+            // the trampoline is passed by const reference to allow using it
+            // from Rust through a shared reference
+            result = format!("const_cast<{}*>({})", trampoline_class, c_argument.name);
+          }
         }
         filled_arguments.push(result);
       } else {
         panic!("Error: no positional argument found\n{:?}", method);
       }
     }
+    if let CppFfiMethodKind::MemberFunctionTrampoline {
+             ref trampoline_class,
+             ref member_pointer_index,
+             ref member_pointer_type,
+             ..
+           } = method.kind {
+      let member_pointer_type = CppType {
+        base: CppTypeBase::FunctionPointer(member_pointer_type.clone()),
+        indirection: CppTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      };
+      filled_arguments.insert(*member_pointer_index,
+                              format!("static_cast<{}>(&{}::call)",
+                                      member_pointer_type.to_cpp_code(Some(""))?,
+                                      trampoline_class));
+    }
+    filled_arguments.extend(variadic_items.iter().cloned());
     Ok(filled_arguments.into_iter().join(", "))
   }
 
   /// Generates code for the value returned by the FFI method.
   #[cfg_attr(feature="clippy", allow(collapsible_if))]
-  fn returned_expression(&self, method: &CppAndFfiMethod) -> Result<String> {
+  fn returned_expression(&self,
+                         method: &CppAndFfiMethod,
+                         variadic_items: &[String])
+                         -> Result<String> {
     let result = if method.cpp_method.is_destructor() {
       if let Some(arg) = method
            .c_signature
@@ -206,7 +468,7 @@ impl CppCodeGenerator {
         return Err(unexpected("no this arg in destructor").into());
       }
     } else {
-      let mut is_field_accessor = false;
+      let mut is_complete_expression = false;
       let result_without_args = if let Some(info) = method.cpp_method.class_info_if_constructor() {
         let class_type = &info.class_type;
         match method.allocation_place {
@@ -216,7 +478,9 @@ impl CppCodeGenerator {
                  .arguments
                  .iter()
                  .find(|x| x.meaning == CppFfiArgumentMeaning::ReturnValue) {
-              format!("new({}) {}", arg.name, class_type.to_cpp_code()?)
+              // global placement new is used explicitly because
+              // class-specific `operator new` would hide it
+              format!("::new({}) {}", arg.name, class_type.to_cpp_code()?)
             } else {
               return Err(unexpected(format!("return value argument not found\n{:?}", method))
                            .into());
@@ -238,7 +502,11 @@ impl CppCodeGenerator {
                  .arguments
                  .iter()
                  .find(|x| x.meaning == CppFfiArgumentMeaning::This) {
-              format!("{}->", arg.name)
+              if class_membership.ref_qualifier == Some(CppRefQualifier::RValue) {
+                format!("std::move(*{}).", arg.name)
+              } else {
+                format!("{}->", arg.name)
+              }
             } else {
               return Err(unexpected("no this arg in non-static method").into());
             }
@@ -260,15 +528,33 @@ impl CppCodeGenerator {
                  ref accessor_type,
                  ref field_name,
                } = method.kind {
-          is_field_accessor = true;
+          is_complete_expression = true;
           if accessor_type == &CppFieldAccessorType::Setter {
             format!("{}{} = {}",
                     scope_specifier,
                     field_name,
-                    self.arguments_values(method)?)
+                    self.arguments_values(method, &[])?)
           } else {
             format!("{}{}", scope_specifier, field_name)
           }
+        } else if let CppFfiMethodKind::VariantConversion(ref conversion) = method.kind {
+          is_complete_expression = true;
+          self.variant_conversion_expression(method, conversion)?
+        } else if let CppFfiMethodKind::ComputedTypeConstant { ref ffi_initializer } =
+          method.kind {
+          is_complete_expression = true;
+          format!("({})", ffi_initializer)
+        } else if let CppFfiMethodKind::StdVariantGetIf { index } = method.kind {
+          is_complete_expression = true;
+          let this_arg = method
+            .c_signature
+            .arguments
+            .iter()
+            .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+            .chain_err(|| unexpected("no this arg in std::get_if accessor"))?;
+          format!("std::get_if<{}>({})", index, this_arg.name)
+        } else if let Some(callee) = self.protected_method_callee(method, &template_args)? {
+          callee
         } else {
           format!("{}{}{}",
                   scope_specifier,
@@ -276,17 +562,164 @@ impl CppCodeGenerator {
                   template_args)
         }
       };
-      if is_field_accessor {
+      if is_complete_expression {
         result_without_args
       } else {
         format!("{}({})",
                 result_without_args,
-                self.arguments_values(method)?)
+                self.arguments_values(method, variadic_items)?)
       }
     };
     self.convert_return_type(method, result)
   }
 
+  /// Returns name of the helper class that makes protected methods
+  /// of `class_type` accessible from the wrapper functions.
+  fn protected_access_class_name(&self, class_type: &CppTypeClassBase) -> Result<String> {
+    Ok(format!("{}_{}_protected_access", self.lib_name, class_type.caption()?))
+  }
+
+  /// Generates the called expression for a protected method
+  /// exposed with `Config::add_exposed_protected_method`.
+  /// Returns `None` if the method is not protected.
+  fn protected_method_callee(&self,
+                             method: &CppAndFfiMethod,
+                             template_args: &str)
+                             -> Result<Option<String>> {
+    let membership = match method.cpp_method.class_membership {
+      Some(ref membership) if membership.visibility == CppVisibility::Protected => membership,
+      _ => return Ok(None),
+    };
+    let access_class = self.protected_access_class_name(&membership.class_type)?;
+    if membership.is_static {
+      return Ok(Some(format!("{}::{}{}", access_class, method.cpp_method.name, template_args)));
+    }
+    let this_arg = method
+      .c_signature
+      .arguments
+      .iter()
+      .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+      .chain_err(|| "no this arg in non-static method")?;
+    // Taking the address through the helper class yields a pointer to member
+    // of the original class, so it can be applied to the original object.
+    // The explicit type selects the right overload. Variants with omitted
+    // arguments are not generated for protected methods, so the pointer
+    // has the same arguments as the call.
+    let argument_types = method
+      .cpp_method
+      .arguments
+      .iter()
+      .map_if_ok(|arg| arg.argument_type.to_cpp_code(None))?
+      .join(", ");
+    Ok(Some(format!("({}->*static_cast<{} ({}::*)({}){}>(&{}::{}{}))",
+                    this_arg.name,
+                    method.cpp_method.return_type.to_cpp_code(None)?,
+                    membership.class_type.to_cpp_code()?,
+                    argument_types,
+                    if membership.is_const { " const" } else { "" },
+                    access_class,
+                    method.cpp_method.name,
+                    template_args)))
+  }
+
+  /// Generates helper classes for protected methods of `methods`
+  /// exposed with `Config::add_exposed_protected_method`.
+  fn protected_access_classes(&self, methods: &[CppAndFfiMethod]) -> Result<String> {
+    let mut classes = BTreeMap::new();
+    for method in methods {
+      if let Some(ref membership) = method.cpp_method.class_membership {
+        if membership.visibility == CppVisibility::Protected {
+          classes
+            .entry(self.protected_access_class_name(&membership.class_type)?)
+            .or_insert_with(|| (membership.class_type.clone(), BTreeSet::new()))
+            .1
+            .insert(method.cpp_method.name.clone());
+        }
+      }
+    }
+    let mut code = String::new();
+    for (class_name, (class_type, method_names)) in classes {
+      let base_class = class_type.to_cpp_code()?;
+      let using_declarations = method_names
+        .iter()
+        .map(|name| format!("  using {}::{};", base_class, name))
+        .join("\n");
+      code.push_str(&format!(include_str!("../templates/c_lib/protected_access.h"),
+                             class_name = class_name,
+                             base_class = base_class,
+                             using_declarations = using_declarations));
+    }
+    Ok(code)
+  }
+
+  /// Generates code of a `QVariant` conversion for a type
+  /// added with `Config::add_metatype`.
+  fn variant_conversion_expression(&self,
+                                   method: &CppAndFfiMethod,
+                                   conversion: &CppVariantConversion)
+                                   -> Result<String> {
+    let class_type = match method.cpp_method.class_membership {
+      Some(ref info) => info.class_type.to_cpp_code()?,
+      None => return Err(unexpected("variant conversion must be a class member").into()),
+    };
+    Ok(match *conversion {
+         CppVariantConversion::ToVariant => {
+           let this_arg = method
+             .c_signature
+             .arguments
+             .iter()
+             .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+             .chain_err(|| unexpected("no this arg in variant conversion"))?;
+           format!("QVariant::fromValue(*{})", this_arg.name)
+         }
+         CppVariantConversion::FromVariant => {
+           format!("({}).value< {} >()",
+                   self.arguments_values(method, &[])?,
+                   class_type)
+         }
+         CppVariantConversion::CanConvertFromVariant => {
+           format!("({}).canConvert< {} >()",
+                   self.arguments_values(method, &[])?,
+                   class_type)
+         }
+       })
+  }
+
+  /// Generates body of a `std::map` or `std::unordered_map` accessor,
+  /// or returns `None` if `method` is not such an accessor.
+  fn std_map_accessor_body(&self, method: &CppAndFfiMethod) -> Result<Option<String>> {
+    match method.kind {
+      CppFfiMethodKind::StdMapGet |
+      CppFfiMethodKind::StdMapInsert |
+      CppFfiMethodKind::StdMapNextEntry => {}
+      _ => return Ok(None),
+    }
+    let this_arg = method
+      .c_signature
+      .arguments
+      .iter()
+      .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+      .chain_err(|| unexpected("no this arg in map accessor"))?;
+    let map = &this_arg.name;
+    Ok(Some(match method.kind {
+              CppFfiMethodKind::StdMapGet => {
+                format!("auto it = {0}->find(key);\n  \
+                         return it == {0}->end() ? 0 : &it->second;\n",
+                        map)
+              }
+              CppFfiMethodKind::StdMapInsert => format!("(*{})[key] = value;\n", map),
+              _ => {
+                format!("auto it = previous_key ? {0}->find(*previous_key) : {0}->begin();\n  \
+                         if (previous_key && it != {0}->end()) {{\n    ++it;\n  }}\n  \
+                         if (it == {0}->end()) {{\n    return false;\n  }}\n  \
+                         *key = &it->first;\n  \
+                         *value = &it->second;\n  \
+                         return true;\n",
+                        map)
+              }
+            }))
+  }
+
   /// Generates body of the FFI method implementation.
   fn source_body(&self, method: &CppAndFfiMethod) -> Result<String> {
     if method.cpp_method.is_destructor() &&
@@ -300,6 +733,71 @@ impl CppCodeGenerator {
       } else {
         panic!("Error: no this argument found\n{:?}", method);
       }
+    } else if let CppFfiMethodKind::BatchOperation { ref method_name } = method.kind {
+      let this_arg = method
+        .c_signature
+        .arguments
+        .iter()
+        .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+        .chain_err(|| "no this arg in batch operation")?;
+      Ok(format!("for (size_t i = 0; i < count; i++) {{\n    {}->{}(items[i]);\n  }}\n",
+                 this_arg.name,
+                 method_name))
+    } else if let CppFfiMethodKind::VariadicSlice { max_count } = method.kind {
+      let is_void = method.c_signature.return_type.ffi_type.is_void();
+      let mut code = "switch (count) {\n".to_string();
+      for count in 0..max_count + 1 {
+        let items: Vec<_> = (0..count).map(|i| format!("items[{}]", i)).collect();
+        let call = self.returned_expression(&method, &items)?;
+        if is_void {
+          code.push_str(&format!("  case {}:\n    {};\n    break;\n", count, call));
+        } else {
+          code.push_str(&format!("  case {}:\n    return {};\n", count, call));
+        }
+      }
+      // the Rust wrapper doesn't allow more than `max_count` items
+      code.push_str("  default:\n    std::abort();\n  }\n");
+      Ok(code)
+    } else if let Some(code) = self.std_map_accessor_body(method)? {
+      Ok(code)
+    } else if method.kind == CppFfiMethodKind::StreamOutput {
+      let this_arg = method
+        .c_signature
+        .arguments
+        .iter()
+        .find(|x| x.meaning == CppFfiArgumentMeaning::This)
+        .chain_err(|| unexpected("no this arg in stream output method"))?;
+      Ok(format!("std::ostringstream stream;\n  \
+                  stream << *{};\n  \
+                  std::string text = stream.str();\n  \
+                  if (length > 0 && text.size() < static_cast<size_t>(length)) {{\n    \
+                  std::memcpy(buffer, text.data(), text.size());\n  }}\n  \
+                  return static_cast<int>(text.size());\n",
+                 this_arg.name))
+    } else if let CppFfiMethodKind::ErrnoMethod {
+                    ref error_sentinel,
+                    ref error_getter,
+                  } = method.kind {
+      // the error code must be read before anything else can change it
+      Ok(format!("{} result = {};\n  \
+                  *error_flag = result == ({});\n  \
+                  *error_code = *error_flag ? ({}) : 0;\n  \
+                  return result;\n",
+                 method.c_signature.return_type.ffi_type.to_cpp_code(None)?,
+                 self.returned_expression(&method, &[])?,
+                 error_sentinel,
+                 error_getter))
+    } else if let Some(validity_check) = self.fallible_constructor_check(method) {
+      let info = method
+        .cpp_method
+        .class_info_if_constructor()
+        .chain_err(|| "class info expected for a constructor")?;
+      Ok(format!("{}* object = {};\n  \
+                  if (!({})) {{\n    delete object;\n    return 0;\n  }}\n  \
+                  return object;\n",
+                 info.class_type.to_cpp_code()?,
+                 self.returned_expression(&method, &[])?,
+                 validity_check))
     } else {
       Ok(format!("{}{};\n",
                  if method.c_signature.return_type.ffi_type.is_void() {
@@ -307,7 +805,7 @@ impl CppCodeGenerator {
                  } else {
                    "return "
                  },
-                 self.returned_expression(&method)?))
+                 self.returned_expression(&method, &[])?))
     }
   }
 
@@ -353,14 +851,133 @@ impl CppCodeGenerator {
     Ok(())
   }
 
-  /// Generates all regular files of the C++ wrapper library
-  pub fn generate_files(&self, data: &[CppFfiHeaderData]) -> Result<()> {
+  /// Generates all regular files of the C++ wrapper library.
+  /// Returns names of the headers that were generated
+  /// rather than restored from the code cache.
+  pub fn generate_files(&self, data: &[CppFfiHeaderData]) -> Result<Vec<String>> {
+    // the list of headers may have changed, so this file is always generated
     self
       .generate_all_headers_file(data.iter().map(|x| &x.include_file_base_name))?;
+    let old_cache = self.load_code_cache();
+    let mut new_cache = HashMap::new();
+    let mut generated_headers = Vec::new();
+    let mut sources = Vec::new();
     for item in data {
-      self
-        .generate_one(item)
-        .chain_err(|| "C++ code generator failed")?;
+      let hash = self.header_data_hash(item);
+      let code = match old_cache.get(&item.include_file_base_name) {
+        Some(code) if code.hash == hash => {
+          create_file(self.header_path(item))?
+            .write(&code.header)?;
+          code.clone()
+        }
+        _ => {
+          let source = self
+            .generate_one(item)
+            .chain_err(|| "C++ code generator failed")?;
+          generated_headers.push(item.include_file_base_name.clone());
+          CppHeaderCode {
+            hash: hash,
+            header: file_to_string(self.header_path(item))?,
+            source: source,
+          }
+        }
+      };
+      sources.push(code.source.clone());
+      new_cache.insert(item.include_file_base_name.clone(), code);
+    }
+    if let Some(ref path) = self.code_cache_path {
+      save_bincode(path, &new_cache)?;
+    }
+    match self.tu_strategy {
+      FfiTuStrategy::PerHeader => {
+        for (item, source) in data.iter().zip(sources) {
+          self
+            .write_source_file(&item.include_file_base_name, once(item), once(source))?;
+        }
+      }
+      FfiTuStrategy::Bundled(count) => {
+        if count == 0 {
+          return Err("FfiTuStrategy::Bundled requires at least one translation unit".into());
+        }
+        // headers are split into contiguous chunks to keep the original include order
+        let chunk_size = (data.len() + count - 1) / count;
+        if chunk_size > 0 {
+          for (index, (items, chunk_sources)) in
+            data
+              .chunks(chunk_size)
+              .zip(sources.chunks(chunk_size))
+              .enumerate() {
+            self
+              .write_source_file(&format!("bundle{}", index + 1),
+                                 items.iter(),
+                                 chunk_sources.iter().cloned())?;
+          }
+        }
+      }
+    }
+    Ok(generated_headers)
+  }
+
+  /// Loads code generated in the previous run. Returns an empty cache
+  /// if the code cache is disabled or can't be loaded.
+  fn load_code_cache(&self) -> HashMap<String, CppHeaderCode> {
+    if self.force_regeneration {
+      return HashMap::new();
+    }
+    match self.code_cache_path {
+      Some(ref path) if path.exists() => {
+        match load_bincode(path) {
+          Ok(cache) => cache,
+          Err(err) => {
+            log::llog(log::DebugGeneral,
+                      || format!("failed to load C++ code cache: {}", err));
+            HashMap::new()
+          }
+        }
+      }
+      _ => HashMap::new(),
+    }
+  }
+
+  /// Returns hash of the input data of a header. FFI data types don't
+  /// implement `Hash`, so their `Debug` representation is hashed.
+  fn header_data_hash(&self, data: &CppFfiHeaderData) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    self.lib_name.hash(&mut hasher);
+    format!("{:?}", data).hash(&mut hasher);
+    for method in &data.methods {
+      self.fallible_constructor_check(method).hash(&mut hasher);
+    }
+    hasher.finish()
+  }
+
+  /// Returns path to the FFI header file corresponding to `data`.
+  fn header_path(&self, data: &CppFfiHeaderData) -> PathBuf {
+    self
+      .lib_path
+      .with_added("include")
+      .with_added(format!("{}_{}.h", &self.lib_name, data.include_file_base_name))
+  }
+
+  /// Writes a source file named after `name` that includes FFI headers
+  /// corresponding to `items` and contains generated code from `sources`.
+  fn write_source_file<'a, I1, I2>(&self, name: &str, items: I1, sources: I2) -> Result<()>
+    where I1: Iterator<Item = &'a CppFfiHeaderData>,
+          I2: Iterator<Item = String>
+  {
+    let cpp_path = self
+      .lib_path
+      .with_added("src")
+      .with_added(format!("{}_{}.cpp", &self.lib_name, name));
+    let mut cpp_file = create_file(&cpp_path)?;
+    for item in items {
+      cpp_file
+        .write(format!("#include \"{}_{}.h\"\n", &self.lib_name, item.include_file_base_name))?;
+    }
+    cpp_file.write("\n")?;
+    for source in sources {
+      cpp_file.write(source)?;
     }
     Ok(())
   }
@@ -382,27 +999,17 @@ impl CppCodeGenerator {
     Ok(())
   }
 
-  /// Generates a header file and a source file for a portion of data
-  /// corresponding to a header file of original C++ library.
-  fn generate_one(&self, data: &CppFfiHeaderData) -> Result<()> {
+  /// Generates a header file for a portion of data corresponding to
+  /// a header file of original C++ library. Returns the source code
+  /// implementing the header's functions.
+  fn generate_one(&self, data: &CppFfiHeaderData) -> Result<String> {
     let ffi_include_file = format!("{}_{}.h", &self.lib_name, data.include_file_base_name);
+    let h_path = self.header_path(data);
 
-    let cpp_path = self
-      .lib_path
-      .with_added("src")
-      .with_added(format!("{}_{}.cpp", &self.lib_name, data.include_file_base_name));
-
-    let h_path = self
-      .lib_path
-      .with_added("include")
-      .with_added(&ffi_include_file);
-
-    let mut cpp_file = create_file(&cpp_path)?;
+    let mut cpp_code = String::new();
     {
       let mut h_file = create_file(&h_path)?;
 
-      cpp_file
-        .write(format!("#include \"{}\"\n\n", ffi_include_file))?;
       let include_guard_name = ffi_include_file.replace(".", "_").to_uppercase();
       h_file
         .write(format!("#ifndef {}\n#define {}\n\n",
@@ -414,10 +1021,32 @@ impl CppCodeGenerator {
       for wrapper in &data.qt_slot_wrappers {
         h_file.write(self.qt_slot_wrapper(wrapper)?)?;
       }
+      if let Some(ref wrapper) = data.qt_event_filter_wrapper {
+        h_file.write(self.qt_event_filter_wrapper(wrapper)?)?;
+      }
+      for wrapper in &data.functor_wrappers {
+        h_file.write(self.functor_wrapper(wrapper)?)?;
+      }
+      h_file.write(self.protected_access_classes(&data.methods)?)?;
       h_file.write("extern \"C\" {\n\n")?;
       for method in &data.methods {
-        h_file.write(self.function_declaration(method)?)?;
-        cpp_file.write(self.function_implementation(method)?)?;
+        if method.is_direct_extern_c() {
+          // the original function is called directly
+          continue;
+        }
+        if let Some(ref feature) = method.cpp_method.feature {
+          // the function is only available if the crate's feature is enabled
+          let guard = format!("#ifdef {}\n", CppFeature::guard_macro_name(feature));
+          h_file.write(&guard)?;
+          h_file.write(self.function_declaration(method)?)?;
+          h_file.write("#endif\n")?;
+          cpp_code.push_str(&guard);
+          cpp_code.push_str(&self.function_implementation(method)?);
+          cpp_code.push_str("#endif\n\n");
+        } else {
+          h_file.write(self.function_declaration(method)?)?;
+          cpp_code.push_str(&self.function_implementation(method)?);
+        }
       }
 
       h_file.write("\n} // extern \"C\"\n\n")?;
@@ -427,11 +1056,10 @@ impl CppCodeGenerator {
     }
     if !data.qt_slot_wrappers.is_empty() {
       let moc_output = get_command_output(Command::new("moc").arg("-i").arg(&h_path))?;
-      cpp_file
-        .write(format!("// start of MOC generated code\n{}\n// end of MOC generated code\n",
-                       moc_output))?;
+      cpp_code.push_str(&format!("// start of MOC generated code\n{}\n// end of MOC generated code\n",
+                                 moc_output));
     }
-    Ok(())
+    Ok(cpp_code)
   }
 }
 
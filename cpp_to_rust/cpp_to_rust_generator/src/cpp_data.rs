@@ -73,6 +73,13 @@ pub enum CppTypeKind {
   Enum {
     /// List of items
     values: Vec<CppEnumValue>,
+    /// True if this is a C++11 scoped enum (`enum class` or `enum struct`)
+    #[serde(default)]
+    is_scoped: bool,
+    /// True if the enum has no name in C++. Only anonymous enums nested
+    /// in classes are supported, and their names are synthesized by the parser.
+    #[serde(default)]
+    is_anonymous: bool,
   },
   /// Class declaration
   Class {
@@ -84,6 +91,9 @@ pub enum CppTypeKind {
     template_arguments: Option<TemplateArgumentsDeclaration>,
     /// List of using directives, like "using BaseClass::method1;"
     using_directives: Vec<CppClassUsingDirective>,
+    /// True if the class is declared `final` and can't be derived from
+    #[serde(default)]
+    is_final: bool,
   },
 }
 
@@ -158,6 +168,13 @@ pub struct TemplateArgumentsDeclaration {
   /// Names of template arguments. Names themselves are
   /// not particularly important, but their count is.
   pub names: Vec<String>,
+  /// Default values of template arguments in the same order as `names`
+  /// (`None` for arguments without a default value). A default value
+  /// may refer to preceding template arguments, like `Alloc` in
+  /// `template<class T, class Alloc = std::allocator<T>>`.
+  /// Can be shorter than `names` if trailing arguments have no default values.
+  #[serde(default)]
+  pub defaults: Vec<Option<CppType>>,
 }
 
 /// Information about a C++ template class
@@ -203,6 +220,10 @@ pub struct ParserCppData {
   pub types: Vec<CppTypeData>,
   /// List of found methods
   pub methods: Vec<CppMethod>,
+  /// Full names of found inline namespaces (like `ns::v1`).
+  /// These namespaces are omitted from Rust paths.
+  #[serde(default)]
+  pub inline_namespaces: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
@@ -255,6 +276,27 @@ pub struct CppDataWithDeps<'a> {
 
 
 
+impl CppBaseSpecifier {
+  /// Returns true if this is a CRTP base of `class_name`, i.e. an instantiation
+  /// of a template class with `class_name` as one of its template arguments
+  /// (like `Base<Derived>`).
+  pub fn is_crtp_base_of(&self, class_name: &str) -> bool {
+    if let CppTypeBase::Class(CppTypeClassBase { template_arguments: Some(ref args), .. }) =
+      self.base_type.base {
+      args
+        .iter()
+        .any(|arg| {
+               arg.indirection == CppTypeIndirection::None &&
+               match arg.base {
+                 CppTypeBase::Class(ref arg_base) => arg_base.name == class_name,
+                 _ => false,
+               }
+             })
+    } else {
+      false
+    }
+  }
+}
 
 impl CppTypeData {
   /// Checks if the type is a class type.
@@ -304,6 +346,7 @@ impl CppTypeData {
               CppType {
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
                 indirection: CppTypeIndirection::None,
                 base: CppTypeBase::TemplateParameter {
                   nested_level: arguments.nested_level,
@@ -336,6 +379,25 @@ impl CppTypeData {
 }
 
 impl ParserCppData {
+  /// Adds methods found by the C++ parser with preprocessor macros
+  /// of `feature` defined (see `Config::add_cpp_feature`).
+  /// Methods of `feature_data` that are not present in `self` are added
+  /// and marked as only available with `feature`. Types of `feature_data`
+  /// are not added, so methods using types declared only with `feature`
+  /// enabled will be skipped by the generator.
+  pub fn add_feature_methods(&mut self, feature_data: ParserCppData, feature: &str) {
+    let known_methods: HashSet<_> = self.methods.iter().map(|m| m.short_text()).collect();
+    let mut count = 0;
+    for mut method in feature_data.methods {
+      if !known_methods.contains(&method.short_text()) {
+        method.feature = Some(feature.to_string());
+        self.methods.push(method);
+        count += 1;
+      }
+    }
+    log::status(format!("Methods added by feature {}: {}", feature, count));
+  }
+
   /// Checks if specified class is a template class.
   #[allow(dead_code)]
   pub fn is_template_class(&self, name: &str) -> bool {
@@ -505,6 +567,29 @@ impl ParserCppData {
     Ok(())
   }
 
+  /// Returns true if a class derived from `class_type` can be default-constructed,
+  /// i.e. the class has a public or protected default constructor
+  /// or doesn't declare any constructors.
+  pub fn has_accessible_default_constructor(&self, class_type: &CppTypeClassBase) -> bool {
+    let mut constructors = self
+      .methods
+      .iter()
+      .filter(|method| if let Some(ref info) = method.class_membership {
+                info.kind == CppMethodKind::Constructor && &info.class_type == class_type
+              } else {
+                false
+              })
+      .peekable();
+    if constructors.peek().is_none() {
+      return true;
+    }
+    constructors.any(|method| {
+                       let info = method.class_membership.as_ref().expect("filtered above");
+                       info.visibility != CppVisibility::Private &&
+                       method.arguments.iter().all(|arg| arg.has_default_value)
+                     })
+  }
+
   /// Checks if specified class has explicitly declared protected or private destructor.
   pub fn has_non_public_destructor(&self, class_type: &CppTypeClassBase) -> bool {
     for method in &self.methods {
@@ -524,6 +609,33 @@ impl TemplateArgumentsDeclaration {
   pub fn count(&self) -> usize {
     self.names.len()
   }
+
+  /// Returns `arguments` completed with default values
+  /// of the omitted trailing template arguments.
+  pub fn with_defaults(&self, arguments: &[CppType]) -> Result<Vec<CppType>> {
+    if arguments.len() > self.names.len() {
+      return Err(format!("too many template arguments: {} expected, {} found",
+                         self.names.len(),
+                         arguments.len())
+                     .into());
+    }
+    let mut result = arguments.to_vec();
+    for index in arguments.len()..self.names.len() {
+      let value = {
+        let default = self
+          .defaults
+          .get(index)
+          .and_then(|x| x.as_ref())
+          .chain_err(|| {
+                       format!("template argument {} is omitted but has no default value",
+                               self.names[index])
+                     })?;
+        default.instantiate(self.nested_level, &result)?
+      };
+      result.push(value);
+    }
+    Ok(result)
+  }
 }
 
 
@@ -568,6 +680,76 @@ impl<'a> CppDataWithDeps<'a> {
 
 
 
+  /// Returns `Err` if a method of the current library refers to a class
+  /// or enum type that is declared neither in the current library
+  /// nor in any of the loaded dependencies. This usually means that
+  /// a dependency is missing in `Config`.
+  pub fn check_dependency_types(&self) -> Result<()> {
+    for method in &self.current.parser.methods {
+      for cpp_type in method.all_involved_types() {
+        if let Some(name) = self.find_unresolved_type(&cpp_type) {
+          return Err(format!("type {} used in {} is not declared in this library \
+                              or its dependencies (is a dependency missing?)",
+                             name,
+                             method.short_text())
+                         .into());
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns name of a class or enum type used in `cpp_type`
+  /// that is not declared in this library or its dependencies.
+  fn find_unresolved_type(&self, cpp_type: &CppType) -> Option<String> {
+    match cpp_type.base {
+      CppTypeBase::Enum { ref name } => {
+        if self.find_type_info(|t| &t.name == name).is_none() {
+          return Some(name.clone());
+        }
+      }
+      CppTypeBase::Class(ref class_type) if class_type.std_function_type().is_some() => {
+        // `std::function` is not a part of any library
+        let function = class_type.std_function_type().expect("checked above");
+        return once(&*function.return_type)
+                 .chain(function.arguments.iter())
+                 .filter_map(|t| self.find_unresolved_type(t))
+                 .next();
+      }
+      CppTypeBase::Class(CppTypeClassBase {
+                           ref name,
+                           template_arguments: None,
+                         }) if name == "std::ostream" => {
+        // `std::ostream` is not a part of any library either
+      }
+      CppTypeBase::Class(CppTypeClassBase {
+                           ref name,
+                           ref template_arguments,
+                         }) => {
+        if self.find_type_info(|t| &t.name == name).is_none() {
+          return Some(name.clone());
+        }
+        if let Some(ref args) = *template_arguments {
+          return args.iter().filter_map(|t| self.find_unresolved_type(t)).next();
+        }
+      }
+      CppTypeBase::Array { ref element, .. } |
+      CppTypeBase::Span { ref element } |
+      CppTypeBase::InitializerList { ref element } |
+      CppTypeBase::UniquePtr { pointee: ref element } => {
+        return self.find_unresolved_type(element);
+      }
+      CppTypeBase::FunctionPointer(ref function) => {
+        return once(&*function.return_type)
+                 .chain(function.arguments.iter())
+                 .filter_map(|t| self.find_unresolved_type(t))
+                 .next();
+      }
+      _ => {}
+    }
+    None
+  }
+
   /// Returns selected type allocation place for type `class_name`.
   pub fn type_allocation_place(&self, class_name: &str) -> Result<CppTypeAllocationPlace> {
     if let Some(r) = self
@@ -595,6 +777,58 @@ impl<'a> CppDataWithDeps<'a> {
       .find(f)
   }
 
+  /// Returns declarations of all class and enum types used in `types`
+  /// and all types they depend on (base classes, field types and
+  /// template arguments). Types without a known declaration are skipped.
+  pub fn involved_type_infos(&self, types: &[CppType]) -> Vec<CppTypeData> {
+    let mut result = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue: Vec<CppType> = types.iter().cloned().collect();
+    while let Some(cpp_type) = queue.pop() {
+      let name = match cpp_type.base {
+        CppTypeBase::Enum { ref name } => name.clone(),
+        CppTypeBase::Class(CppTypeClassBase {
+                             ref name,
+                             ref template_arguments,
+                           }) => {
+          if let Some(ref args) = *template_arguments {
+            queue.extend(args.iter().cloned());
+          }
+          name.clone()
+        }
+        CppTypeBase::FunctionPointer(ref function) => {
+          queue.push((*function.return_type).clone());
+          queue.extend(function.arguments.iter().cloned());
+          continue;
+        }
+        CppTypeBase::Array { ref element, .. } |
+        CppTypeBase::Span { ref element } |
+        CppTypeBase::InitializerList { ref element } |
+        CppTypeBase::UniquePtr { pointee: ref element } => {
+          queue.push((**element).clone());
+          continue;
+        }
+        _ => continue,
+      };
+      if visited.contains(&name) {
+        continue;
+      }
+      visited.insert(name.clone());
+      if let Some(type_info) = self.find_type_info(|x| &x.name == &name) {
+        if let CppTypeKind::Class {
+                 ref bases,
+                 ref fields,
+                 ..
+               } = type_info.kind {
+          queue.extend(bases.iter().map(|x| x.base_type.clone()));
+          queue.extend(fields.iter().map(|x| x.field_type.clone()));
+        }
+        result.push(type_info.clone());
+      }
+    }
+    result
+  }
+
 
 
 
@@ -910,6 +1144,22 @@ impl<'a> CppDataWithDeps<'a> {
     false
   }
 
+  /// Checks if specified class declares its own meta-object
+  /// (i.e. uses `Q_OBJECT` macro), so it can be a target of `qobject_cast`.
+  pub fn has_meta_object(&self, class_name: &str) -> bool {
+    self
+      .current
+      .parser
+      .methods
+      .iter()
+      .any(|method| if let Some(ref info) = method.class_membership {
+             &info.class_type.name == class_name && &method.name == "metaObject" &&
+             !info.is_static
+           } else {
+             false
+           })
+  }
+
   /// Checks if specified class has any virtual methods (own or inherited).
   pub fn has_pure_virtual_methods(&self, class_name: &str) -> bool {
     for method in self
@@ -948,6 +1198,14 @@ impl<'a> CppDataWithDeps<'a> {
       .collect()
   }
 
+  /// Returns true if `name` is a full name of an inline namespace
+  /// declared in this library or its dependencies.
+  pub fn is_inline_namespace(&self, name: &str) -> bool {
+    once(&self.current.parser.inline_namespaces)
+      .chain(self.dependencies.iter().map(|x| &x.parser.inline_namespaces))
+      .any(|names| names.iter().any(|x| x == name))
+  }
+
   /// Returns all include files found within this `CppData`
   /// (excluding dependencies).
   pub fn all_include_files(&self) -> Result<HashSet<String>> {
@@ -1,7 +1,8 @@
 use caption_strategy::{ArgumentCaptionStrategy, MethodCaptionStrategy, TypeCaptionStrategy};
 use cpp_method::{CppMethod, ReturnValueAllocationPlace, CppMethodArgument};
+use cpp_data::CppTypeData;
 use cpp_operator::CppOperator;
-use cpp_type::{CppType, CppTypeBase, CppFunctionPointerType};
+use cpp_type::{CppType, CppFunctionPointerType, CppTypeClassBase};
 use common::errors::Result;
 use common::utils::MapIfOk;
 
@@ -19,6 +20,101 @@ pub enum CppFieldAccessorType {
   Setter,
 }
 
+/// Variation of a `QVariant` conversion method
+/// generated for a type added with `Config::add_metatype`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CppVariantConversion {
+  /// Wraps the value in a `QVariant` using `QVariant::fromValue`
+  ToVariant,
+  /// Extracts the value from a `QVariant` using `QVariant::value`
+  FromVariant,
+  /// Checks the stored type using `QVariant::canConvert`
+  CanConvertFromVariant,
+}
+
+/// Per-type constant added with `Config::add_computed_type_constant`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppComputedTypeConstant {
+  /// Name of the C++ class
+  pub cpp_name: String,
+  /// Name of the constant
+  pub const_name: String,
+  /// C++ expression of `int` type that computes value of the constant
+  pub ffi_initializer: String,
+}
+
+/// Instantiation of a template method added with
+/// `Config::add_template_method_instantiation`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppTemplateMethodInstantiation {
+  /// Fully qualified name of the method
+  pub method_name: String,
+  /// C++ code of template arguments
+  pub template_arguments: Vec<String>,
+}
+
+/// Variadic function wrapped with a slice argument,
+/// added with `Config::add_variadic_slice_function`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppVariadicSliceFunction {
+  /// Fully qualified name of the function
+  pub function_name: String,
+  /// C++ code of the type of all variadic arguments
+  pub element_type: String,
+  /// Maximal number of variadic arguments
+  pub max_count: usize,
+}
+
+/// Method reporting errors errno-style,
+/// added with `Config::add_errno_method`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppErrnoMethod {
+  /// Fully qualified name of the method
+  pub method_name: String,
+  /// C++ expression for the return value indicating an error
+  pub error_sentinel: String,
+  /// C++ expression for the error code (e.g. `errno`)
+  pub error_getter: String,
+}
+
+/// Constructor that may fail, added with `Config::add_fallible_constructor`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppFallibleConstructor {
+  /// Fully qualified name of the constructor
+  pub constructor_name: String,
+  /// C++ code of the argument types of the constructor
+  pub argument_types: Vec<String>,
+  /// C++ expression of `bool` type checking the constructed `object`
+  pub validity_check: String,
+}
+
+impl CppFallibleConstructor {
+  /// Returns true if `method` is the constructor overload
+  /// described by this value. Whitespace in argument types is ignored.
+  pub fn matches(&self, method: &CppMethod) -> bool {
+    let strip = |code: &str| code.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    method.is_constructor() && method.full_name() == self.constructor_name &&
+    method.arguments.len() == self.argument_types.len() &&
+    method
+      .arguments
+      .iter()
+      .zip(self.argument_types.iter())
+      .all(|(arg, code)| strip(&arg.argument_type.to_cpp_pseudo_code()) == strip(code))
+  }
+}
+
+/// Instantiation of a template method taking a function object,
+/// added with `Config::add_functor_template_instantiation`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CppFunctorTemplateInstantiation {
+  /// Fully qualified name of the method
+  pub method_name: String,
+  /// C++ code of the return type of the function object
+  pub return_type: String,
+  /// C++ code of the argument types of the function object
+  pub argument_types: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CppCast {
   Static {
@@ -27,9 +123,12 @@ pub enum CppCast {
     /// If true, this is a wrapper of `static_cast` between a class and its
     /// direct base.
     is_direct: bool,
+    /// If true, this is a wrapper of `static_cast` between a class and its
+    /// primary base, i.e. its only public base or, in case of multiple
+    /// inheritance, its first public non-virtual base.
+    is_primary: bool,
   },
   Dynamic,
-  #[allow(unused)]
   QObject,
 }
 
@@ -55,6 +154,12 @@ impl CppCast {
     }
 
   }
+  pub fn is_primary_static_cast(&self) -> bool {
+    match *self {
+      CppCast::Static { ref is_primary, .. } => *is_primary,
+      _ => false,
+    }
+  }
 }
 
 /// Information about real nature of a C++ FFI method.
@@ -78,6 +183,88 @@ pub enum CppFfiMethodKind {
   /// This is an instance of `static_cast`, `dynamic_cast` or
   /// `qobject_cast` function call.
   Cast(CppCast),
+  /// This is a non-existing method converting between
+  /// a metatype and `QVariant`.
+  VariantConversion(CppVariantConversion),
+  /// This is a non-existing static method computing a constant
+  /// added with `Config::add_computed_type_constant`.
+  ComputedTypeConstant {
+    /// C++ expression computing the value
+    ffi_initializer: String,
+  },
+  /// This is a non-existing constructor of a base class that copies
+  /// the base part of a derived class object (object slicing).
+  /// Such constructors are only added with `Config::add_slicing_conversion`.
+  SlicingConversion,
+  /// This is a non-existing method calling a method added with
+  /// `Config::add_batch_operation` for each element of an array.
+  /// The method has `items` and `count` arguments.
+  BatchOperation {
+    /// Name of the C++ method called for each element
+    method_name: String,
+  },
+  /// This is a non-existing function calling a variadic function
+  /// added with `Config::add_variadic_slice_function`. The function has
+  /// `items` and `count` arguments after the fixed arguments of the variadic
+  /// function, and the items are passed as its variadic arguments.
+  VariadicSlice {
+    /// Maximal supported value of `count`
+    max_count: usize,
+  },
+  /// This is a non-existing method of a `std::map` or `std::unordered_map`
+  /// instantiation returning a pointer to the value for `key`,
+  /// or null if the map doesn't contain the key.
+  StdMapGet,
+  /// This is a non-existing method of a `std::map` or `std::unordered_map`
+  /// instantiation setting the value for `key` to `value`.
+  StdMapInsert,
+  /// This is a non-existing method of a `std::map` or `std::unordered_map`
+  /// instantiation writing pointers to the key and the value of the entry
+  /// following the entry with `previous_key` (or the first entry
+  /// if `previous_key` is null) to `key` and `value`. Returns false
+  /// if there is no such entry.
+  StdMapNextEntry,
+  /// This is a non-existing function calling a method added with
+  /// `Config::add_errno_method`. The function has `error_flag` and
+  /// `error_code` output arguments after the arguments of the method,
+  /// and they are filled immediately after the call.
+  ErrnoMethod {
+    /// C++ expression for the return value indicating an error
+    error_sentinel: String,
+    /// C++ expression for the error code
+    error_getter: String,
+  },
+  /// This is a non-existing method of a `std::variant` instantiation
+  /// returning a pointer to the alternative with index `index`
+  /// obtained with `std::get_if`, or null if another alternative is active.
+  StdVariantGetIf {
+    /// Index of the alternative
+    index: usize,
+  },
+  /// This is a non-existing const method writing the object
+  /// to a `std::ostringstream` with a free `operator<<` and copying the text
+  /// to `buffer` if it's shorter than `length`. Returns the text's length.
+  StreamOutput,
+  /// This is a non-existing function calling a method that takes
+  /// a pointer to a member function and an object to call it on.
+  /// The function takes a trampoline object in place of the object
+  /// and has no argument for the member function pointer: a pointer to
+  /// the trampoline's `call` method is passed instead.
+  MemberFunctionTrampoline {
+    /// Name of the trampoline class
+    trampoline_class: String,
+    /// Index of the object argument
+    object_argument_index: usize,
+    /// Index of the member function pointer argument in the original method
+    member_pointer_index: usize,
+    /// Type of the member function pointer argument
+    member_pointer_type: CppFunctionPointerType,
+  },
+  /// This is a default constructor or a destructor of a class added with
+  /// `Config::add_allocation_benchmark`, processed with heap allocation
+  /// although the class is allocated on stack. It's only used by
+  /// the generated benchmark and not exposed in the Rust API.
+  AllocationBenchmark,
 }
 
 /// Relation between original C++ method's argument value
@@ -96,6 +283,33 @@ pub enum CppIndirectionChange {
   /// C++ argument is QFlags<T>
   /// and FFI argument is uint
   QFlagsToUInt,
+  /// C++ argument is QChar (value or const reference)
+  /// and FFI argument is ushort (only for arguments)
+  QCharToUShort,
+  /// C++ argument is an rvalue reference (like QPoint&&)
+  /// and FFI argument is a pointer (like QPoint*).
+  /// Only used for the argument of a move assignment operator.
+  RValueReferenceToPointer,
+  /// C++ argument is `std::string_view` (or a const reference to it)
+  /// and FFI argument is `cpp_utils_str_ref` struct
+  /// containing a pointer to the data and its size.
+  StringViewToStrRef,
+  /// C++ argument is `std::span<T>` (or a const reference to it)
+  /// and FFI argument is `cpp_utils_slice_ref` struct
+  /// containing a pointer to the elements and their number.
+  SpanToSliceRef,
+  /// C++ return type is `std::unique_ptr<T>`
+  /// and FFI return type is a pointer (like T*)
+  /// released from it and owned by the caller.
+  UniquePtrToPtr,
+  /// C++ return type is `std::initializer_list<T>`
+  /// and FFI function receives a `cpp_utils_vec_out` output argument
+  /// used to copy the elements to a Rust vector.
+  InitializerListToVecOut,
+  /// C++ argument is `std::function<R(Args...)>` (or a const reference to it)
+  /// and FFI argument is `cpp_utils_closure` struct containing a Rust closure,
+  /// a callback that calls it and a function that frees it.
+  StdFunctionToClosure,
 }
 
 /// Information that indicates how an FFI function argument
@@ -163,7 +377,8 @@ impl CppFfiMethodArgument {
   /// Generates C++ code for the part of FFI function signature
   /// corresponding to this argument
   pub fn to_cpp_code(&self) -> Result<String> {
-    if let CppTypeBase::FunctionPointer(..) = self.argument_type.ffi_type.base {
+    let ffi_base = &self.argument_type.ffi_type.base;
+    if ffi_base.is_function_pointer() || ffi_base.is_array() {
       Ok(self
            .argument_type
            .ffi_type
@@ -197,6 +412,20 @@ impl CppFfiMethodSignature {
       .any(|arg| arg.meaning == CppFfiArgumentMeaning::This && arg.argument_type.ffi_type.is_const)
   }
 
+  /// Returns true if this signature is identical to the signature
+  /// of the original C++ function, i.e. there is no this pointer,
+  /// no return value buffer and no type conversions.
+  pub fn is_direct(&self) -> bool {
+    fn is_same(t: &CppFfiType) -> bool {
+      t.conversion == CppIndirectionChange::NoChange && t.ffi_type == t.original_type
+    }
+    is_same(&self.return_type) &&
+    self
+      .arguments
+      .iter()
+      .all(|arg| arg.meaning.is_argument() && is_same(&arg.argument_type))
+  }
+
   /// Generates arguments caption string for FFI method.
   /// Used to generate FFI methods with different names
   /// for overloaded functions.
@@ -348,6 +577,23 @@ pub fn c_base_name(cpp_method: &CppMethod,
   Ok(scope_prefix + &method_name + &template_args_text)
 }
 
+/// Returns true if `cpp_method` is an `extern "C"` function that is bound
+/// directly by its own name, so no C++ wrapper is generated for it.
+fn is_direct_extern_c(cpp_method: &CppMethod,
+                      kind: &CppFfiMethodKind,
+                      c_signature: &CppFfiMethodSignature)
+                      -> bool {
+  cpp_method.is_extern_c && kind == &CppFfiMethodKind::Real && c_signature.is_direct()
+}
+
+impl CppMethodWithFfiSignature {
+  /// Returns true if this is an `extern "C"` function that is bound
+  /// directly by its own name, so no C++ wrapper is generated for it.
+  pub fn is_direct_extern_c(&self) -> bool {
+    is_direct_extern_c(&self.cpp_method, &self.kind, &self.c_signature)
+  }
+}
+
 impl CppAndFfiMethod {
   /// Adds FFI method name to a CppMethodWithFfiSignature object.
   pub fn new(data: CppMethodWithFfiSignature, c_name: String) -> CppAndFfiMethod {
@@ -360,6 +606,12 @@ impl CppAndFfiMethod {
     }
   }
 
+  /// Returns true if this is an `extern "C"` function that is bound
+  /// directly by its own name, so no C++ wrapper is generated for it.
+  pub fn is_direct_extern_c(&self) -> bool {
+    is_direct_extern_c(&self.cpp_method, &self.kind, &self.c_signature)
+  }
+
   /// Convenience function to call `CppMethod::short_text`.
   pub fn short_text(&self) -> String {
     self.cpp_method.short_text()
@@ -381,6 +633,37 @@ pub struct QtSlotWrapper {
   pub receiver_id: String,
 }
 
+/// Information about a generated `QObject` subclass that forwards
+/// `eventFilter` calls to a callback function
+#[derive(Debug, Clone)]
+pub struct QtEventFilterWrapper {
+  /// Generated name of the wrapper class
+  pub class_name: String,
+  /// The function pointer type accepted by this wrapper
+  pub function_type: CppFunctionPointerType,
+}
+
+/// Information about a generated function object class that forwards
+/// its calls to a callback function
+#[derive(Debug, Clone)]
+pub struct CppFunctorWrapper {
+  /// Generated name of the wrapper class
+  pub class_name: String,
+  /// Alphanumeric description of the signature of the function object
+  pub caption: String,
+  /// Argument types of the function object
+  pub arguments: Vec<CppFfiType>,
+  /// Return type of the function object
+  pub return_type: CppFfiType,
+  /// The function pointer type accepted by this wrapper
+  pub function_type: CppFunctionPointerType,
+  /// If Some, this is a trampoline class derived from this class.
+  /// It forwards calls of its `call` method to the callback function,
+  /// so a pointer to `call` can be used as a pointer to a member function
+  /// of the base class.
+  pub base_class: Option<CppTypeClassBase>,
+}
+
 /// Information about a header of the generated C++ wrapper library
 #[derive(Debug, Clone)]
 pub struct CppFfiHeaderData {
@@ -390,4 +673,24 @@ pub struct CppFfiHeaderData {
   pub methods: Vec<CppAndFfiMethod>,
   /// Generated Qt slot wrappers
   pub qt_slot_wrappers: Vec<QtSlotWrapper>,
+  /// Generated Qt event filter wrapper
+  pub qt_event_filter_wrapper: Option<QtEventFilterWrapper>,
+  /// Generated function object wrappers
+  pub functor_wrappers: Vec<CppFunctorWrapper>,
+}
+
+/// Information about a method the FFI generator failed to process.
+/// These objects are saved to the directory set with
+/// `Config::set_failure_dump_dir` and can be loaded in a unit test
+/// to reproduce the failure.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct CppFfiFailureDump {
+  /// The method as it was passed to the FFI generator
+  pub method: CppMethod,
+  /// Error message produced by the FFI generator
+  pub error: String,
+  /// Declarations of all types used in the method
+  /// and types they depend on
+  pub types: Vec<CppTypeData>,
 }
@@ -1,18 +1,26 @@
 use caption_strategy::{TypeCaptionStrategy, MethodCaptionStrategy};
 use cpp_data::{CppVisibility, CppTypeAllocationPlace, CppDataWithDeps, CppTypeKind,
-               CppTemplateInstantiation, CppOperator};
+               CppTypeData, CppTemplateInstantiation, CppOperator};
 use cpp_type::{CppTypeRole, CppType, CppTypeBase, CppTypeIndirection, CppTypeClassBase,
-               CppFunctionPointerType};
+               CppFunctionPointerType, CppBuiltInNumericType, CppSpecificNumericType,
+               CppSpecificNumericTypeKind};
 use cpp_ffi_data::{CppAndFfiMethod, c_base_name, CppFfiHeaderData, QtSlotWrapper,
-                   CppFfiMethodKind, CppFieldAccessorType, CppMethodWithFfiSignature, CppCast};
+                   CppFfiMethodKind, CppFieldAccessorType, CppMethodWithFfiSignature, CppCast,
+                   CppFfiFailureDump, CppVariantConversion, CppComputedTypeConstant,
+                   CppTemplateMethodInstantiation, QtEventFilterWrapper,
+                   CppFunctorTemplateInstantiation, CppFunctorWrapper, CppVariadicSliceFunction,
+                   CppErrnoMethod};
 use cpp_method::{CppMethod, CppMethodKind, CppMethodArgument, CppMethodClassMembership,
                  ReturnValueAllocationPlace};
-use common::errors::{Result, ChainErr, unexpected};
+use common::errors::{Result, Error, ChainErr, unexpected};
+use common::file_utils::{PathBufWithAdded, create_dir_all, save_json};
 use common::log;
 use common::utils::{MapIfOk, add_to_multihash};
-use config::CppFfiGeneratorFilterFn;
+use config::{CppFfiGeneratorFilterFn, VolatilePolicy};
 use std::collections::{HashSet, HashMap};
 use std::iter::once;
+use std::path::PathBuf;
+use regex::Regex;
 
 /// This object generates the C++ wrapper library
 struct CppFfiGenerator<'a> {
@@ -22,6 +30,16 @@ struct CppFfiGenerator<'a> {
   cpp_ffi_lib_name: String,
   /// FFI filters passed to `Config`
   filters: Vec<&'a Box<CppFfiGeneratorFilterFn>>,
+  /// Directory for failure dumps passed to `Config`
+  failure_dump_dir: Option<&'a PathBuf>,
+  /// Version tag for FFI function names passed to `Config`
+  symbol_version: Option<String>,
+  /// Handling of `volatile` types passed to `Config`
+  volatile_policy: VolatilePolicy,
+  /// Protected methods that should be wrapped, passed to `Config`
+  exposed_protected_methods: &'a [String],
+  /// Patterns of methods that should not be wrapped, passed to `Config`
+  method_blocklist: &'a [Regex],
 }
 
 #[derive(Debug, Clone)]
@@ -44,15 +62,73 @@ impl CppMethodWithKind {
   }
 }
 
+/// Options of the FFI generator passed to `Config`.
+/// The default value doesn't request any additional wrappers.
+#[derive(Default)]
+pub struct CppFfiGeneratorOptions<'a> {
+  /// FFI filters
+  pub filters: Vec<&'a Box<CppFfiGeneratorFilterFn>>,
+  /// Directory for failure dumps
+  pub failure_dump_dir: Option<&'a PathBuf>,
+  /// Version tag for FFI function names
+  pub symbol_version: Option<&'a String>,
+  /// Types convertible to and from `QVariant`
+  pub metatypes: &'a [String],
+  /// Constants computed by the C++ compiler
+  pub computed_type_constants: &'a [CppComputedTypeConstant],
+  /// Instantiations of template methods
+  pub template_method_instantiations: &'a [CppTemplateMethodInstantiation],
+  /// Pairs of derived and base classes for slicing conversions
+  pub slicing_conversions: &'a [(String, String)],
+  /// Instantiations of functor templates
+  pub functor_template_instantiations: &'a [CppFunctorTemplateInstantiation],
+  /// Protected methods that should be wrapped
+  pub exposed_protected_methods: &'a [String],
+  /// Methods that should get batch wrappers
+  pub batch_operations: &'a [String],
+  /// Variadic functions that should accept slices
+  pub variadic_slice_functions: &'a [CppVariadicSliceFunction],
+  /// Methods reporting errors through `errno`
+  pub errno_methods: &'a [CppErrnoMethod],
+  /// Patterns of methods that should not be wrapped
+  pub method_blocklist: &'a [Regex],
+  /// Classes compared by the allocation benchmark
+  pub allocation_benchmarks: &'a [String],
+  /// Handling of `volatile` types
+  pub volatile_policy: VolatilePolicy,
+}
+
 /// Runs the FFI generator
 pub fn run(cpp_data: &CppDataWithDeps,
            cpp_ffi_lib_name: String,
-           filters: Vec<&Box<CppFfiGeneratorFilterFn>>)
+           options: CppFfiGeneratorOptions)
            -> Result<Vec<CppFfiHeaderData>> {
+  let CppFfiGeneratorOptions {
+    filters,
+    failure_dump_dir,
+    symbol_version,
+    metatypes,
+    computed_type_constants,
+    template_method_instantiations,
+    slicing_conversions,
+    functor_template_instantiations,
+    exposed_protected_methods,
+    batch_operations,
+    variadic_slice_functions,
+    errno_methods,
+    method_blocklist,
+    allocation_benchmarks,
+    volatile_policy,
+  } = options;
   let generator = CppFfiGenerator {
     cpp_data: cpp_data,
     cpp_ffi_lib_name: cpp_ffi_lib_name,
     filters: filters,
+    failure_dump_dir: failure_dump_dir,
+    symbol_version: symbol_version.map(|tag| sanitize_symbol_version(tag)),
+    volatile_policy: volatile_policy,
+    exposed_protected_methods: exposed_protected_methods,
+    method_blocklist: method_blocklist,
   };
 
   let mut c_headers = Vec::new();
@@ -65,8 +141,33 @@ pub fn run(cpp_data: &CppDataWithDeps,
 
   let mut extra_methods = Vec::new();
   extra_methods.append(&mut instantiate_templates(&generator.cpp_data)?);
+  extra_methods.append(&mut instantiate_template_methods(&generator.cpp_data,
+                                                         template_method_instantiations)?);
   extra_methods.append(&mut generate_field_accessors(&generator.cpp_data)?);
   extra_methods.append(&mut generate_casts(&generator.cpp_data)?);
+  extra_methods.append(&mut crtp_inherited_methods(&generator.cpp_data));
+  extra_methods.append(&mut generate_slicing_conversions(&generator.cpp_data,
+                                                         slicing_conversions)?);
+  let (mut functor_wrappers, mut functor_methods) =
+    generate_functor_wrappers(&generator.cpp_data,
+                              &generator.cpp_ffi_lib_name,
+                              functor_template_instantiations)?;
+  extra_methods.append(&mut functor_methods);
+  let (mut trampolines, mut trampoline_methods) =
+    generate_member_function_trampolines(&generator.cpp_data, &generator.cpp_ffi_lib_name)?;
+  functor_wrappers.append(&mut trampolines);
+  extra_methods.append(&mut trampoline_methods);
+  extra_methods.append(&mut generate_std_function_call_methods(&generator.cpp_data)?);
+  extra_methods.append(&mut generate_std_variant_methods(&generator.cpp_data)?);
+  extra_methods.append(&mut generate_std_map_methods(&generator.cpp_data)?);
+  extra_methods.append(&mut generate_variant_conversions(&generator.cpp_data, metatypes)?);
+  extra_methods.append(&mut generate_computed_type_constants(&generator.cpp_data,
+                                                             computed_type_constants)?);
+  extra_methods.append(&mut generate_batch_operations(&generator.cpp_data, batch_operations)?);
+  extra_methods.append(&mut generate_variadic_slice_functions(&generator.cpp_data,
+                                                              variadic_slice_functions)?);
+  extra_methods.append(&mut generate_errno_methods(&generator.cpp_data, errno_methods)?);
+  extra_methods.append(&mut generate_stream_output_methods(&generator.cpp_data)?);
 
   for include_file in &include_name_list {
     let mut include_file_base_name = include_file.clone();
@@ -74,13 +175,20 @@ pub fn run(cpp_data: &CppDataWithDeps,
     if let Some(index) = include_file_base_name.find('.') {
       include_file_base_name = include_file_base_name[0..index].to_string();
     }
-    let methods = generator
+    let mut methods = generator
       .process_methods(&include_file_base_name,
                        None,
                        generator
                          .cpp_data
                          .current
                          .methods_and_implicit_destructors()
+                         // errno methods are only available through
+                         // their generated wrappers
+                         .filter(|m| {
+                                   !errno_methods
+                                     .iter()
+                                     .any(|e| e.method_name == m.full_name())
+                                 })
                          .map(|m| {
                                 CppMethodRefWithKind {
                                   method: m,
@@ -89,6 +197,29 @@ pub fn run(cpp_data: &CppDataWithDeps,
                               })
                          .chain(extra_methods.iter().map(|i| i.as_ref()))
                          .filter(|x| &x.method.include_file == include_file))?;
+    let mut header_functor_wrappers = Vec::new();
+    for functor_wrapper in &functor_wrappers {
+      if &functor_wrapper.include_file == include_file {
+        // function object classes are not known to the C++ parser,
+        // so they can only be allocated on heap
+        methods.append(&mut generator
+                              .process_methods(&include_file_base_name,
+                                               Some(CppTypeAllocationPlace::Heap),
+                                               functor_wrapper
+                                                 .methods
+                                                 .iter()
+                                                 .map(|i| i.as_ref()))?);
+        header_functor_wrappers.push(functor_wrapper.wrapper.clone());
+      }
+    }
+    let benchmark_methods =
+      allocation_benchmark_methods(&generator.cpp_data, allocation_benchmarks, include_file)?;
+    if !benchmark_methods.is_empty() {
+      methods.append(&mut generator
+                            .process_methods(&include_file_base_name,
+                                             Some(CppTypeAllocationPlace::Heap),
+                                             benchmark_methods.into_iter())?);
+    }
     if methods.is_empty() {
       log::llog(log::DebugFfiSkips,
                 || format!("Skipping empty include file {}", include_file));
@@ -97,18 +228,91 @@ pub fn run(cpp_data: &CppDataWithDeps,
                        include_file_base_name: include_file_base_name,
                        methods: methods,
                        qt_slot_wrappers: Vec::new(),
+                       qt_event_filter_wrapper: None,
+                       functor_wrappers: header_functor_wrappers,
                      });
     }
   }
   if let Some(header) = generator.generate_slot_wrappers()? {
     c_headers.push(header);
   }
+  if let Some(header) = generator.generate_event_filter_wrapper()? {
+    c_headers.push(header);
+  }
   if c_headers.is_empty() {
     return Err("No FFI headers generated".into());
   }
   Ok(c_headers)
 }
 
+/// Returns the default constructor and the destructor of each class
+/// added with `Config::add_allocation_benchmark` and declared in `include_file`.
+/// They are processed again with heap allocation, so that the benchmark
+/// can compare both allocation places.
+fn allocation_benchmark_methods<'a>(cpp_data: &'a CppDataWithDeps,
+                                    class_names: &[String],
+                                    include_file: &str)
+                                    -> Result<Vec<CppMethodRefWithKind<'a>>> {
+  let mut result = Vec::new();
+  for class_name in class_names {
+    let type_info = cpp_data
+      .current
+      .parser
+      .types
+      .iter()
+      .find(|t| &t.name == class_name && t.is_class())
+      .chain_err(|| format!("allocation benchmark class not found: {}", class_name))?;
+    if &type_info.include_file != include_file {
+      continue;
+    }
+    if cpp_data.current.processed.type_allocation_places.get(class_name) !=
+       Some(&CppTypeAllocationPlace::Stack) {
+      return Err(format!("allocation benchmark class is not allocated on stack: {}",
+                         class_name)
+                     .into());
+    }
+    let methods: Vec<_> = cpp_data
+      .current
+      .methods_and_implicit_destructors()
+      .filter(|m| if let Some(ref info) = m.class_membership {
+                &info.class_type.name == class_name &&
+                info.class_type.template_arguments.is_none() &&
+                info.visibility == CppVisibility::Public &&
+                ((m.is_constructor() && m.arguments.is_empty()) || m.is_destructor())
+              } else {
+                false
+              })
+      .collect();
+    if !methods.iter().any(|m| m.is_constructor()) || !methods.iter().any(|m| m.is_destructor()) {
+      return Err(format!("allocation benchmark class must have a public default constructor \
+                          and destructor: {}",
+                         class_name)
+                     .into());
+    }
+    result.extend(methods
+                    .into_iter()
+                    .map(|m| {
+                           CppMethodRefWithKind {
+                             method: m,
+                             kind: CppFfiMethodKind::AllocationBenchmark,
+                           }
+                         }));
+  }
+  Ok(result)
+}
+
+/// Replaces characters not allowed in C identifiers in
+/// a symbol version tag with underscores.
+fn sanitize_symbol_version(tag: &str) -> String {
+  tag
+    .chars()
+    .map(|c| match c {
+           'a'...'z' | 'A'...'Z' | '0'...'9' => c,
+           _ => '_',
+         })
+    .collect()
+}
+
 /// Tries to apply each of `template_instantiations` to `method`.
 /// Only types at the specified `nested_level` are replaced.
 /// Returns `Err` if any of `template_instantiations` is incompatible
@@ -121,60 +325,10 @@ fn apply_instantiations_to_method(method: &CppMethod,
   for ins in template_instantiations {
     log::llog(log::DebugTemplateInstantiation,
               || format!("instantiation: {:?}", ins.template_arguments));
-    let mut new_method = method.clone();
-    if let Some(ref args) = method.template_arguments {
-      if args.nested_level == nested_level {
-        if args.count() != ins.template_arguments.len() {
-          return Err("template arguments count mismatch".into());
-        }
-        new_method.template_arguments = None;
-        new_method.template_arguments_values = Some(ins.template_arguments.clone());
-      }
-    }
-    new_method.arguments.clear();
-    for arg in &method.arguments {
-      new_method
-        .arguments
-        .push(CppMethodArgument {
-                name: arg.name.clone(),
-                has_default_value: arg.has_default_value,
-                argument_type: arg
-                  .argument_type
-                  .instantiate(nested_level, &ins.template_arguments)?,
-              });
-    }
-    new_method.return_type = method
-      .return_type
-      .instantiate(nested_level, &ins.template_arguments)?;
-    if let Some(ref mut info) = new_method.class_membership {
-      info.class_type = info
-        .class_type
-        .instantiate_class(nested_level, &ins.template_arguments)?;
-    }
-    let mut conversion_type = None;
-    if let Some(ref mut operator) = new_method.operator {
-      if let CppOperator::Conversion(ref mut cpp_type) = *operator {
-        let r = cpp_type
-          .instantiate(nested_level, &ins.template_arguments)?;
-        *cpp_type = r.clone();
-        conversion_type = Some(r);
-      }
-    }
-    if new_method
-         .all_involved_types()
-         .iter()
-         .any(|t| t.base.is_or_contains_template_parameter()) {
-      return Err(format!("extra template parameters left: {}",
-                         new_method.short_text())
-                     .into());
-    } else {
-      if let Some(conversion_type) = conversion_type {
-        new_method.name = format!("operator {}", conversion_type.to_cpp_code(None)?);
-      }
-      log::llog(log::DebugTemplateInstantiation,
-                || format!("success: {}", new_method.short_text()));
-      new_methods.push(new_method);
-    }
+    let new_method = method.instantiate(nested_level, &ins.template_arguments)?;
+    log::llog(log::DebugTemplateInstantiation,
+              || format!("success: {}", new_method.short_text()));
+    new_methods.push(new_method);
   }
   Ok(new_methods)
 }
@@ -262,6 +416,1039 @@ fn instantiate_templates(data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind
 }
 
 
+/// Converts C++ code of a template argument added with
+/// `Config::add_template_method_instantiation` to `CppType`.
+fn parse_template_argument(data: &CppDataWithDeps, code: &str) -> Result<CppType> {
+  let mut name = code.trim();
+  let mut indirection = CppTypeIndirection::None;
+  if name.ends_with('*') {
+    indirection = CppTypeIndirection::Ptr;
+    name = name[..name.len() - 1].trim();
+  } else if name.ends_with('&') {
+    indirection = CppTypeIndirection::Ref;
+    name = name[..name.len() - 1].trim();
+  }
+  let is_const = name.starts_with("const ");
+  if is_const {
+    name = name["const ".len()..].trim();
+  }
+  let base = if let Some(numeric) = CppBuiltInNumericType::all()
+       .iter()
+       .find(|t| t.to_cpp_code() == name) {
+    CppTypeBase::BuiltInNumeric(numeric.clone())
+  } else {
+    match data.find_type_info(|t| t.name == name) {
+      Some(type_info) => {
+        match type_info.kind {
+          CppTypeKind::Class { .. } => {
+            CppTypeBase::Class(CppTypeClassBase {
+                                 name: name.to_string(),
+                                 template_arguments: None,
+                               })
+          }
+          CppTypeKind::Enum { .. } => CppTypeBase::Enum { name: name.to_string() },
+        }
+      }
+      None => return Err(format!("unsupported template argument: {}", code).into()),
+    }
+  };
+  Ok(CppType {
+       base: base,
+       indirection: indirection,
+       is_const: is_const,
+       is_const2: false,
+       is_volatile: false,
+     })
+}
+
+/// Generates instantiations of template methods of non-template classes
+/// added with `Config::add_template_method_instantiation`.
+fn instantiate_template_methods(data: &CppDataWithDeps,
+                                instantiations: &[CppTemplateMethodInstantiation])
+                                -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for instantiation in instantiations {
+    let template_arguments = instantiation
+      .template_arguments
+      .iter()
+      .map_if_ok(|arg| parse_template_argument(data, arg))?;
+    let methods: Vec<_> = data
+      .current
+      .parser
+      .methods
+      .iter()
+      .filter(|m| m.template_arguments.is_some() && m.full_name() == instantiation.method_name)
+      .collect();
+    if methods.is_empty() {
+      return Err(format!("template method not found: {}", instantiation.method_name).into());
+    }
+    for method in methods {
+      let nested_level = match method.template_arguments {
+        Some(ref args) => args.nested_level,
+        None => unreachable!(),
+      };
+      match apply_instantiations_to_method(method,
+                                           nested_level,
+                                           &[CppTemplateInstantiation {
+                                               template_arguments: template_arguments.clone(),
+                                             }]) {
+        Ok(methods) => {
+          for mut method in methods {
+            method.is_ffi_whitelisted = true;
+            new_methods.push(CppMethodWithKind {
+                               method: method,
+                               kind: CppFfiMethodKind::Real,
+                             });
+          }
+        }
+        Err(msg) => {
+          log::llog(log::DebugTemplateInstantiation, || {
+            format!("failed to instantiate {}: {}", method.short_text(), msg)
+          });
+        }
+      }
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Function object class generated for an instantiation added with
+/// `Config::add_functor_template_instantiation`
+struct GeneratedFunctorWrapper {
+  /// Include file containing the instantiated method
+  include_file: String,
+  /// Information about the class
+  wrapper: CppFunctorWrapper,
+  /// Constructor, destructor and `set` method of the class
+  methods: Vec<CppMethodWithKind>,
+}
+
+/// Generates function object classes for instantiations added with
+/// `Config::add_functor_template_instantiation` and instantiates
+/// the template methods with these classes. Returns generated classes
+/// and instantiated methods.
+fn generate_functor_wrappers(data: &CppDataWithDeps,
+                             cpp_ffi_lib_name: &str,
+                             instantiations: &[CppFunctorTemplateInstantiation])
+                             -> Result<(Vec<GeneratedFunctorWrapper>, Vec<CppMethodWithKind>)> {
+  let mut wrappers: Vec<GeneratedFunctorWrapper> = Vec::new();
+  let mut new_methods = Vec::new();
+  for instantiation in instantiations {
+    let parse_type = |code: &str| -> Result<CppType> {
+      let cpp_type = if code.trim() == "void" {
+        CppType::void()
+      } else {
+        parse_template_argument(data, code)?
+      };
+      match cpp_type.base {
+        CppTypeBase::Void |
+        CppTypeBase::BuiltInNumeric(..) if cpp_type.indirection == CppTypeIndirection::None => {}
+        _ => {
+          return Err(format!("unsupported type in function object signature: {}", code).into())
+        }
+      }
+      Ok(cpp_type)
+    };
+    let return_type = parse_type(&instantiation.return_type)?;
+    let argument_types = instantiation
+      .argument_types
+      .iter()
+      .map_if_ok(|arg| parse_type(arg.as_str()))?;
+    if argument_types.iter().any(|t| t.base == CppTypeBase::Void) {
+      return Err(format!("void argument in function object signature for {}",
+                         instantiation.method_name)
+                     .into());
+    }
+    let methods: Vec<_> = data
+      .current
+      .parser
+      .methods
+      .iter()
+      .filter(|m| m.full_name() == instantiation.method_name)
+      .filter(|m| m.template_arguments.as_ref().map_or(false, |args| args.count() == 1))
+      .collect();
+    if methods.is_empty() {
+      return Err(format!("template method with one template parameter not found: {}",
+                         instantiation.method_name)
+                     .into());
+    }
+    for method in methods {
+      let include_file_base_name = match method.include_file.find('.') {
+        Some(index) => method.include_file[0..index].to_string(),
+        None => method.include_file.clone(),
+      };
+      let caption = once(&return_type)
+        .chain(argument_types.iter())
+        .map_if_ok(|t| t.caption(TypeCaptionStrategy::Full))?
+        .join("_");
+      let class_name = format!("{}_{}_Functor_{}",
+                               cpp_ffi_lib_name,
+                               include_file_base_name,
+                               caption);
+      let nested_level = match method.template_arguments {
+        Some(ref args) => args.nested_level,
+        None => unreachable!(),
+      };
+      let class_type = CppType {
+        base: CppTypeBase::Class(CppTypeClassBase {
+                                   name: class_name.clone(),
+                                   template_arguments: None,
+                                 }),
+        indirection: CppTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      };
+      match apply_instantiations_to_method(method,
+                                           nested_level,
+                                           &[CppTemplateInstantiation {
+                                               template_arguments: vec![class_type],
+                                             }]) {
+        Ok(methods) => {
+          for mut method in methods {
+            method.is_ffi_whitelisted = true;
+            new_methods.push(CppMethodWithKind {
+                               method: method,
+                               kind: CppFfiMethodKind::Real,
+                             });
+          }
+        }
+        Err(msg) => {
+          log::llog(log::DebugTemplateInstantiation, || {
+            format!("failed to instantiate {}: {}", method.short_text(), msg)
+          });
+          continue;
+        }
+      }
+      if wrappers.iter().any(|w| w.wrapper.class_name == class_name) {
+        continue;
+      }
+      let void_ptr = CppType {
+        base: CppTypeBase::Void,
+        indirection: CppTypeIndirection::Ptr,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      };
+      let function_type = CppFunctionPointerType {
+        return_type: Box::new(return_type.clone()),
+        arguments: once(void_ptr.clone())
+          .chain(argument_types.iter().cloned())
+          .collect(),
+        allows_variadic_arguments: false,
+        class_type: None,
+      };
+      wrappers.push(GeneratedFunctorWrapper {
+                      include_file: method.include_file.clone(),
+                      wrapper: CppFunctorWrapper {
+                        class_name: class_name.clone(),
+                        caption: caption,
+                        arguments: argument_types
+                          .iter()
+                          .map_if_ok(|t| t.to_cpp_ffi_type(CppTypeRole::NotReturnType))?,
+                        return_type: return_type.to_cpp_ffi_type(CppTypeRole::ReturnType)?,
+                        function_type: function_type.clone(),
+                        base_class: None,
+                      },
+                      methods: functor_wrapper_methods(&class_name,
+                                                       &method.include_file,
+                                                       &function_type),
+                    });
+    }
+  }
+  Ok((wrappers, new_methods))
+}
+
+/// Returns constructor, destructor and `set` method of a generated
+/// function object class accepting a function pointer of `function_type`.
+fn functor_wrapper_methods(class_name: &str,
+                           include_file: &str,
+                           function_type: &CppFunctionPointerType)
+                           -> Vec<CppMethodWithKind> {
+  let void_ptr = CppType {
+    base: CppTypeBase::Void,
+    indirection: CppTypeIndirection::Ptr,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let create_function = |kind: CppMethodKind,
+                         name: String,
+                         arguments: Vec<CppMethodArgument>|
+   -> CppMethodWithKind {
+    CppMethodWithKind {
+      method: CppMethod {
+        name: name,
+        class_membership: Some(CppMethodClassMembership {
+                                 class_type: CppTypeClassBase {
+                                   name: class_name.to_string(),
+                                   template_arguments: None,
+                                 },
+                                 is_virtual: false,
+                                 is_pure_virtual: false,
+                                 is_const: false,
+                                 is_static: false,
+                                 visibility: CppVisibility::Public,
+                                 is_signal: false,
+                                 is_slot: false,
+                                 is_move_assignment: false,
+                                 ref_qualifier: None,
+                                 kind: kind,
+                               }),
+        operator: None,
+        return_type: CppType::void(),
+        arguments: arguments,
+        allows_variadic_arguments: false,
+        include_file: include_file.to_string(),
+        origin_location: None,
+        template_arguments: None,
+        template_arguments_values: None,
+        declaration_code: None,
+        doc: None,
+        inheritance_chain: Vec::new(),
+        is_ffi_whitelisted: false,
+        is_extern_c: false,
+        feature: None,
+      },
+      kind: CppFfiMethodKind::Real,
+    }
+  };
+  let mut methods = Vec::new();
+  methods.push(create_function(CppMethodKind::Constructor, class_name.to_string(), vec![]));
+  methods.push(create_function(CppMethodKind::Destructor, format!("~{}", class_name), vec![]));
+  methods.push(create_function(CppMethodKind::Regular,
+                               "set".to_string(),
+                               vec![CppMethodArgument {
+                                      name: "func".to_string(),
+                                      argument_type: CppType {
+                                        base: CppTypeBase::FunctionPointer(function_type.clone()),
+                                        indirection: CppTypeIndirection::None,
+                                        is_const: false,
+                                        is_const2: false,
+                                        is_volatile: false,
+                                      },
+                                      has_default_value: false,
+                                      default_value: None,
+                                    },
+                                    CppMethodArgument {
+                                      name: "data".to_string(),
+                                      argument_type: void_ptr.clone(),
+                                      has_default_value: false,
+                                      default_value: None,
+                                    }]));
+  methods
+}
+
+/// Generates trampoline classes for methods taking a pointer to a member
+/// function of class `T` (e.g. `void (T::*)()`) and a `T*` object
+/// to call it on. A trampoline is derived from `T` and forwards calls
+/// of its `call` method to a callback function. Returns generated classes
+/// and methods taking a trampoline in place of the object and the member
+/// function pointer.
+fn generate_member_function_trampolines
+  (data: &CppDataWithDeps,
+   cpp_ffi_lib_name: &str)
+   -> Result<(Vec<GeneratedFunctorWrapper>, Vec<CppMethodWithKind>)> {
+  let mut wrappers: Vec<GeneratedFunctorWrapper> = Vec::new();
+  let mut new_methods = Vec::new();
+  for method in &data.current.parser.methods {
+    let member_pointers: Vec<_> = method
+      .arguments
+      .iter()
+      .enumerate()
+      .filter_map(|(index, arg)| match arg.argument_type.base {
+                    CppTypeBase::FunctionPointer(ref function) if function.class_type.is_some() => {
+                      Some((index, function))
+                    }
+                    _ => None,
+                  })
+      .collect();
+    if member_pointers.is_empty() || method.template_arguments.is_some() {
+      continue;
+    }
+    let skip = |reason: &str| {
+      log::error(format!("Warning: method {} is skipped because {}",
+                         method.short_text(),
+                         reason));
+    };
+    if member_pointers.len() > 1 {
+      skip("it takes multiple member function pointers");
+      continue;
+    }
+    let (member_pointer_index, function) = member_pointers[0];
+    let class_type = function.class_type.as_ref().expect("filtered above");
+    let is_final = match data.find_type_info(|t| t.name == class_type.name && t.is_class()) {
+      Some(&CppTypeData { kind: CppTypeKind::Class { is_final, .. }, .. }) => is_final,
+      _ => {
+        skip(&format!("{} is not a generated type", class_type.name));
+        continue;
+      }
+    };
+    // the trampoline is derived from the class and default-constructed
+    if class_type.template_arguments.is_some() || is_final ||
+       data.has_pure_virtual_methods(&class_type.name) ||
+       !data.current.parser.has_accessible_default_constructor(class_type) ||
+       data.current.parser.has_non_public_destructor(class_type) {
+      skip(&format!("a trampoline class can't be derived from {}", class_type.name));
+      continue;
+    }
+    let object_argument_index = match method
+            .arguments
+            .iter()
+            .position(|arg| {
+                        arg.argument_type.indirection == CppTypeIndirection::Ptr &&
+                        !arg.argument_type.is_const &&
+                        arg.argument_type.base == CppTypeBase::Class(class_type.clone())
+                      }) {
+      Some(index) => index,
+      None => {
+        skip(&format!("it doesn't take a {}* object to call the member function on",
+                      class_type.name));
+        continue;
+      }
+    };
+    let is_supported = |t: &CppType| match t.base {
+      CppTypeBase::Void |
+      CppTypeBase::BuiltInNumeric(..) => t.indirection == CppTypeIndirection::None,
+      _ => false,
+    };
+    if !is_supported(&function.return_type) ||
+       function.arguments.iter().any(|t| t.base == CppTypeBase::Void || !is_supported(t)) ||
+       function.allows_variadic_arguments {
+      skip("the member function's signature is not supported");
+      continue;
+    }
+    let include_file_base_name = match method.include_file.find('.') {
+      Some(index) => method.include_file[0..index].to_string(),
+      None => method.include_file.clone(),
+    };
+    let mut caption_parts = vec![class_type.caption()?];
+    for t in once(&*function.return_type).chain(function.arguments.iter()) {
+      caption_parts.push(t.caption(TypeCaptionStrategy::Full)?);
+    }
+    let caption = caption_parts.join("_");
+    let class_name = format!("{}_{}_MemberCallback_{}",
+                             cpp_ffi_lib_name,
+                             include_file_base_name,
+                             caption);
+    let mut new_method = method.clone();
+    new_method.arguments.remove(member_pointer_index);
+    let object_argument_index = if object_argument_index > member_pointer_index {
+      object_argument_index - 1
+    } else {
+      object_argument_index
+    };
+    new_method.arguments[object_argument_index].argument_type = CppType {
+      base: CppTypeBase::Class(CppTypeClassBase {
+                                 name: class_name.clone(),
+                                 template_arguments: None,
+                               }),
+      indirection: CppTypeIndirection::Ref,
+      is_const: true,
+      is_const2: false,
+      is_volatile: false,
+    };
+    new_methods.push(CppMethodWithKind {
+                       method: new_method,
+                       kind: CppFfiMethodKind::MemberFunctionTrampoline {
+                         trampoline_class: class_name.clone(),
+                         object_argument_index: object_argument_index,
+                         member_pointer_index: member_pointer_index,
+                         member_pointer_type: function.clone(),
+                       },
+                     });
+    if wrappers.iter().any(|w| w.wrapper.class_name == class_name) {
+      continue;
+    }
+    let void_ptr = CppType {
+      base: CppTypeBase::Void,
+      indirection: CppTypeIndirection::Ptr,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+    };
+    let function_type = CppFunctionPointerType {
+      return_type: function.return_type.clone(),
+      arguments: once(void_ptr)
+        .chain(function.arguments.iter().cloned())
+        .collect(),
+      allows_variadic_arguments: false,
+      class_type: None,
+    };
+    wrappers.push(GeneratedFunctorWrapper {
+                    include_file: method.include_file.clone(),
+                    wrapper: CppFunctorWrapper {
+                      class_name: class_name.clone(),
+                      caption: caption,
+                      arguments: function
+                        .arguments
+                        .iter()
+                        .map_if_ok(|t| t.to_cpp_ffi_type(CppTypeRole::NotReturnType))?,
+                      return_type: function.return_type.to_cpp_ffi_type(CppTypeRole::ReturnType)?,
+                      function_type: function_type.clone(),
+                      base_class: Some(class_type.clone()),
+                    },
+                    methods: functor_wrapper_methods(&class_name,
+                                                     &method.include_file,
+                                                     &function_type),
+                  });
+  }
+  Ok((wrappers, new_methods))
+}
+
+/// Adds `operator()` methods to instantiations of `std::function`
+/// that are used in the processed library. The template class itself
+/// is not parsed, so these methods are created manually based on
+/// the function type in the template argument.
+fn generate_std_function_call_methods(cpp_data: &CppDataWithDeps)
+                                      -> Result<Vec<CppMethodWithKind>> {
+  let type_info = match cpp_data
+          .current
+          .parser
+          .types
+          .iter()
+          .find(|t| &t.name == "std::function") {
+    Some(type_info) => type_info,
+    None => return Ok(Vec::new()),
+  };
+  log::status("Adding call methods for std::function instantiations");
+  let mut new_methods = Vec::new();
+  for template_instantiations in &cpp_data.current.processed.template_instantiations {
+    if &template_instantiations.class_name != "std::function" {
+      continue;
+    }
+    for ins in &template_instantiations.instantiations {
+      let class_type = CppTypeClassBase {
+        name: template_instantiations.class_name.clone(),
+        template_arguments: Some(ins.template_arguments.clone()),
+      };
+      let function = match class_type.std_function_type() {
+        Some(function) => function.clone(),
+        None => {
+          log::llog(log::DebugFfiSkips, || {
+            format!("Unsupported std::function instantiation: {:?}",
+                    ins.template_arguments)
+          });
+          continue;
+        }
+      };
+      new_methods.push(CppMethodWithKind {
+                         method: CppMethod {
+                           name: "operator()".to_string(),
+                           class_membership: Some(CppMethodClassMembership {
+                                                    class_type: class_type,
+                                                    kind: CppMethodKind::Regular,
+                                                    is_virtual: false,
+                                                    is_pure_virtual: false,
+                                                    is_const: true,
+                                                    is_static: false,
+                                                    visibility: CppVisibility::Public,
+                                                    is_signal: false,
+                                                    is_slot: false,
+                                                    is_move_assignment: false,
+                                                    ref_qualifier: None,
+                                                  }),
+                           operator: Some(CppOperator::FunctionCall),
+                           return_type: function.return_type.as_ref().clone(),
+                           arguments: function
+                             .arguments
+                             .iter()
+                             .enumerate()
+                             .map(|(index, arg)| {
+                                    CppMethodArgument {
+                                      name: format!("arg{}", index + 1),
+                                      argument_type: arg.clone(),
+                                      has_default_value: false,
+                                      default_value: None,
+                                    }
+                                  })
+                             .collect(),
+                           allows_variadic_arguments: false,
+                           include_file: type_info.include_file.clone(),
+                           origin_location: None,
+                           template_arguments: None,
+                           template_arguments_values: None,
+                           declaration_code: None,
+                           doc: None,
+                           inheritance_chain: Vec::new(),
+                           is_ffi_whitelisted: false,
+                           is_extern_c: false,
+                           feature: None,
+                         },
+                         kind: CppFfiMethodKind::Real,
+                       });
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Adds constructors, destructors and `std::get_if` accessors to instantiations
+/// of `std::variant` that are used in the processed library. As with `std::function`,
+/// the template class itself is not parsed, so these methods are created
+/// based on the alternatives in the template arguments.
+fn generate_std_variant_methods(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind>> {
+  let type_info = match cpp_data
+          .current
+          .parser
+          .types
+          .iter()
+          .find(|t| &t.name == "std::variant") {
+    Some(type_info) => type_info,
+    None => return Ok(Vec::new()),
+  };
+  log::status("Adding methods for std::variant instantiations");
+  let mut new_methods = Vec::new();
+  for template_instantiations in &cpp_data.current.processed.template_instantiations {
+    if &template_instantiations.class_name != "std::variant" {
+      continue;
+    }
+    for ins in &template_instantiations.instantiations {
+      let alternatives = &ins.template_arguments;
+      if alternatives
+           .iter()
+           .enumerate()
+           .any(|(index, t)| {
+                  t.is_void() || t.indirection != CppTypeIndirection::None ||
+                  alternatives[..index].contains(t)
+                }) {
+        // only distinct value types are supported, so that constructors
+        // are not ambiguous
+        log::llog(log::DebugFfiSkips,
+                  || format!("Unsupported std::variant instantiation: {:?}", alternatives));
+        continue;
+      }
+      let class_type = CppTypeClassBase {
+        name: template_instantiations.class_name.clone(),
+        template_arguments: Some(alternatives.clone()),
+      };
+      let create_method = |name: String,
+                           kind: CppMethodKind,
+                           is_const: bool,
+                           return_type: CppType,
+                           arguments: Vec<CppMethodArgument>| {
+        CppMethod {
+          name: name,
+          class_membership: Some(CppMethodClassMembership {
+                                   class_type: class_type.clone(),
+                                   kind: kind,
+                                   is_virtual: false,
+                                   is_pure_virtual: false,
+                                   is_const: is_const,
+                                   is_static: false,
+                                   visibility: CppVisibility::Public,
+                                   is_signal: false,
+                                   is_slot: false,
+                                   is_move_assignment: false,
+                                   ref_qualifier: None,
+                                 }),
+          operator: None,
+          return_type: return_type,
+          arguments: arguments,
+          allows_variadic_arguments: false,
+          include_file: type_info.include_file.clone(),
+          origin_location: None,
+          template_arguments: None,
+          template_arguments_values: None,
+          declaration_code: None,
+          doc: None,
+          inheritance_chain: Vec::new(),
+          is_ffi_whitelisted: false,
+          is_extern_c: false,
+          feature: None,
+        }
+      };
+      new_methods.push(CppMethodWithKind {
+                         method: create_method("~variant".to_string(),
+                                               CppMethodKind::Destructor,
+                                               false,
+                                               CppType::void(),
+                                               Vec::new()),
+                         kind: CppFfiMethodKind::Real,
+                       });
+      for (index, alternative) in alternatives.iter().enumerate() {
+        let argument = CppMethodArgument {
+          name: "value".to_string(),
+          argument_type: alternative.clone(),
+          has_default_value: false,
+          default_value: None,
+        };
+        new_methods.push(CppMethodWithKind {
+                           method: create_method("variant".to_string(),
+                                                 CppMethodKind::Constructor,
+                                                 false,
+                                                 CppType::void(),
+                                                 vec![argument]),
+                           kind: CppFfiMethodKind::Real,
+                         });
+        let pointer_type = CppType {
+          indirection: CppTypeIndirection::Ptr,
+          is_const: true,
+          ..alternative.clone()
+        };
+        new_methods.push(CppMethodWithKind {
+                           method: create_method(format!("get_if_{}", index),
+                                                 CppMethodKind::Regular,
+                                                 true,
+                                                 pointer_type,
+                                                 Vec::new()),
+                           kind: CppFfiMethodKind::StdVariantGetIf { index: index },
+                         });
+      }
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Adds constructors, destructors and accessors to instantiations of `std::map`
+/// and `std::unordered_map` that are used in the processed library. As with
+/// `std::variant`, the template classes themselves are not parsed.
+/// Only maps with integer keys and numeric values are supported.
+fn generate_std_map_methods(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind>> {
+  fn is_numeric(cpp_type: &CppType, allow_floating_point: bool) -> bool {
+    cpp_type.indirection == CppTypeIndirection::None && !cpp_type.is_const &&
+    match cpp_type.base {
+      CppTypeBase::BuiltInNumeric(ref numeric) => {
+        numeric.is_signed_integer() || numeric.is_unsigned_integer() ||
+        (allow_floating_point && numeric.is_float())
+      }
+      CppTypeBase::SpecificNumeric(CppSpecificNumericType { ref kind, .. }) => {
+        allow_floating_point || kind != &CppSpecificNumericTypeKind::FloatingPoint
+      }
+      CppTypeBase::PointerSizedInteger { .. } => true,
+      _ => false,
+    }
+  }
+  let mut new_methods = Vec::new();
+  for template_instantiations in &cpp_data.current.processed.template_instantiations {
+    let class_name = &template_instantiations.class_name;
+    let short_name = match class_name.as_str() {
+      "std::map" => "map",
+      "std::unordered_map" => "unordered_map",
+      _ => continue,
+    };
+    let type_info = cpp_data
+      .current
+      .parser
+      .types
+      .iter()
+      .find(|t| &t.name == class_name)
+      .chain_err(|| format!("type info not found for {}", class_name))?;
+    log::status(format!("Adding methods for {} instantiations", class_name));
+    for ins in &template_instantiations.instantiations {
+      let args = &ins.template_arguments;
+      if args.len() != 2 || !is_numeric(&args[0], false) || !is_numeric(&args[1], true) {
+        log::llog(log::DebugFfiSkips,
+                  || format!("Unsupported {} instantiation: {:?}", class_name, args));
+        continue;
+      }
+      let (key_type, value_type) = (&args[0], &args[1]);
+      let class_type = CppTypeClassBase {
+        name: class_name.clone(),
+        template_arguments: Some(args.clone()),
+      };
+      let create_method = |name: &str,
+                           kind: CppMethodKind,
+                           is_const: bool,
+                           return_type: CppType,
+                           arguments: Vec<(&str, CppType)>| {
+        CppMethod {
+          name: name.to_string(),
+          class_membership: Some(CppMethodClassMembership {
+                                   class_type: class_type.clone(),
+                                   kind: kind,
+                                   is_virtual: false,
+                                   is_pure_virtual: false,
+                                   is_const: is_const,
+                                   is_static: false,
+                                   visibility: CppVisibility::Public,
+                                   is_signal: false,
+                                   is_slot: false,
+                                   is_move_assignment: false,
+                                   ref_qualifier: None,
+                                 }),
+          operator: None,
+          return_type: return_type,
+          arguments: arguments
+            .into_iter()
+            .map(|(name, argument_type)| {
+                   CppMethodArgument {
+                     name: name.to_string(),
+                     argument_type: argument_type,
+                     has_default_value: false,
+                     default_value: None,
+                   }
+                 })
+            .collect(),
+          allows_variadic_arguments: false,
+          include_file: type_info.include_file.clone(),
+          origin_location: None,
+          template_arguments: None,
+          template_arguments_values: None,
+          declaration_code: None,
+          doc: None,
+          inheritance_chain: Vec::new(),
+          is_ffi_whitelisted: false,
+          is_extern_c: false,
+          feature: None,
+        }
+      };
+      let const_pointer = |cpp_type: &CppType, indirection: CppTypeIndirection| {
+        CppType {
+          indirection: indirection,
+          is_const: true,
+          ..cpp_type.clone()
+        }
+      };
+      let plain_type = |base: CppTypeBase| {
+        CppType {
+          base: base,
+          indirection: CppTypeIndirection::None,
+          is_const: false,
+          is_const2: false,
+          is_volatile: false,
+        }
+      };
+      let size_type = plain_type(CppTypeBase::PointerSizedInteger {
+                                   name: "size_t".to_string(),
+                                   is_signed: false,
+                                 });
+      let bool_type = plain_type(CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool));
+      let methods = vec![(create_method(&format!("~{}", short_name),
+                                        CppMethodKind::Destructor,
+                                        false,
+                                        CppType::void(),
+                                        Vec::new()),
+                          CppFfiMethodKind::Real),
+                         (create_method(short_name,
+                                        CppMethodKind::Constructor,
+                                        false,
+                                        CppType::void(),
+                                        Vec::new()),
+                          CppFfiMethodKind::Real),
+                         (create_method("size",
+                                        CppMethodKind::Regular,
+                                        true,
+                                        size_type,
+                                        Vec::new()),
+                          CppFfiMethodKind::Real),
+                         (create_method("get",
+                                        CppMethodKind::Regular,
+                                        true,
+                                        const_pointer(value_type, CppTypeIndirection::Ptr),
+                                        vec![("key", key_type.clone())]),
+                          CppFfiMethodKind::StdMapGet),
+                         (create_method("insert",
+                                        CppMethodKind::Regular,
+                                        false,
+                                        CppType::void(),
+                                        vec![("key", key_type.clone()),
+                                             ("value", value_type.clone())]),
+                          CppFfiMethodKind::StdMapInsert),
+                         (create_method("next_entry",
+                                        CppMethodKind::Regular,
+                                        true,
+                                        bool_type,
+                                        vec![("previous_key",
+                                              const_pointer(key_type, CppTypeIndirection::Ptr)),
+                                             ("key",
+                                              const_pointer(key_type,
+                                                            CppTypeIndirection::PtrPtr)),
+                                             ("value",
+                                              const_pointer(value_type,
+                                                            CppTypeIndirection::PtrPtr))]),
+                          CppFfiMethodKind::StdMapNextEntry)];
+      for (method, kind) in methods {
+        new_methods.push(CppMethodWithKind {
+                           method: method,
+                           kind: kind,
+                         });
+      }
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Generates methods converting values of types added with
+/// `Config::add_metatype` to and from `QVariant`.
+fn generate_variant_conversions(cpp_data: &CppDataWithDeps,
+                                metatypes: &[String])
+                                -> Result<Vec<CppMethodWithKind>> {
+  if metatypes.is_empty() {
+    return Ok(Vec::new());
+  }
+  if cpp_data
+       .find_type_info(|x| &x.name == "QVariant")
+       .is_none() {
+    log::llog(log::DebugFfiSkips,
+              || "Skipping metatype conversions because QVariant type is not found");
+    return Ok(Vec::new());
+  }
+  log::status("Adding QVariant conversions");
+  let variant_ref_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "QVariant".to_string(),
+                               template_arguments: None,
+                             }),
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let bool_type = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let mut new_methods = Vec::new();
+  for name in metatypes {
+    let type_info = match cpp_data
+            .current
+            .parser
+            .types
+            .iter()
+            .find(|t| &t.name == name) {
+      Some(type_info) => type_info,
+      None => {
+        log::llog(log::DebugFfiSkips,
+                  || format!("Skipping QVariant conversions: metatype not found: {}", name));
+        continue;
+      }
+    };
+    let class_type = type_info.default_class_type()?;
+    let create_method = |method_name: &str,
+                         conversion: CppVariantConversion,
+                         is_static: bool,
+                         return_type: CppType,
+                         arguments: Vec<CppMethodArgument>| {
+      CppMethodWithKind {
+        method: CppMethod {
+          name: method_name.to_string(),
+          class_membership: Some(CppMethodClassMembership {
+                                   class_type: class_type.clone(),
+                                   kind: CppMethodKind::Regular,
+                                   is_virtual: false,
+                                   is_pure_virtual: false,
+                                   is_const: !is_static,
+                                   is_static: is_static,
+                                   visibility: CppVisibility::Public,
+                                   is_signal: false,
+                                   is_slot: false,
+                                   is_move_assignment: false,
+                                   ref_qualifier: None,
+                                 }),
+          operator: None,
+          return_type: return_type,
+          arguments: arguments,
+          allows_variadic_arguments: false,
+          include_file: type_info.include_file.clone(),
+          origin_location: None,
+          template_arguments: None,
+          template_arguments_values: None,
+          declaration_code: None,
+          doc: None,
+          inheritance_chain: Vec::new(),
+          is_ffi_whitelisted: false,
+          is_extern_c: false,
+          feature: None,
+        },
+        kind: CppFfiMethodKind::VariantConversion(conversion),
+      }
+    };
+    let variant_arg = CppMethodArgument {
+      name: "variant".to_string(),
+      argument_type: variant_ref_type.clone(),
+      has_default_value: false,
+      default_value: None,
+    };
+    new_methods.push(create_method("toVariant",
+                                   CppVariantConversion::ToVariant,
+                                   false,
+                                   CppType {
+                                     indirection: CppTypeIndirection::None,
+                                     is_const: false,
+                                     ..variant_ref_type.clone()
+                                   },
+                                   Vec::new()));
+    new_methods.push(create_method("fromVariant",
+                                   CppVariantConversion::FromVariant,
+                                   true,
+                                   CppType {
+                                     base: CppTypeBase::Class(class_type.clone()),
+                                     indirection: CppTypeIndirection::None,
+                                     is_const: false,
+                                     is_const2: false,
+                                     is_volatile: false,
+                                   },
+                                   vec![variant_arg.clone()]));
+    new_methods.push(create_method("canConvertFromVariant",
+                                   CppVariantConversion::CanConvertFromVariant,
+                                   true,
+                                   bool_type.clone(),
+                                   vec![variant_arg]));
+  }
+  Ok(new_methods)
+}
+
+/// Generates static methods computing constants added with
+/// `Config::add_computed_type_constant`.
+fn generate_computed_type_constants(cpp_data: &CppDataWithDeps,
+                                    constants: &[CppComputedTypeConstant])
+                                    -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for constant in constants {
+    let type_info = cpp_data
+      .current
+      .parser
+      .types
+      .iter()
+      .find(|t| t.name == constant.cpp_name)
+      .chain_err(|| format!("type of computed constant not found: {}", constant.cpp_name))?;
+    new_methods.push(CppMethodWithKind {
+                       method: CppMethod {
+                         name: constant.const_name.clone(),
+                         class_membership: Some(CppMethodClassMembership {
+                                                  class_type: type_info.default_class_type()?,
+                                                  kind: CppMethodKind::Regular,
+                                                  is_virtual: false,
+                                                  is_pure_virtual: false,
+                                                  is_const: false,
+                                                  is_static: true,
+                                                  visibility: CppVisibility::Public,
+                                                  is_signal: false,
+                                                  is_slot: false,
+                                                  is_move_assignment: false,
+                                                  ref_qualifier: None,
+                                                }),
+                         operator: None,
+                         return_type: CppType {
+                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                           indirection: CppTypeIndirection::None,
+                           is_const: false,
+                           is_const2: false,
+                           is_volatile: false,
+                         },
+                         arguments: Vec::new(),
+                         allows_variadic_arguments: false,
+                         include_file: type_info.include_file.clone(),
+                         origin_location: None,
+                         template_arguments: None,
+                         template_arguments_values: None,
+                         declaration_code: None,
+                         doc: None,
+                         inheritance_chain: Vec::new(),
+                         is_ffi_whitelisted: false,
+                         is_extern_c: false,
+                         feature: None,
+                       },
+                       kind: CppFfiMethodKind::ComputedTypeConstant {
+                         ffi_initializer: constant.ffi_initializer.clone(),
+                       },
+                     });
+  }
+  Ok(new_methods)
+}
 
 /// Adds fictional getter and setter methods for each known public field of each class.
 fn generate_field_accessors(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind>> {
@@ -291,6 +1478,8 @@ fn generate_field_accessors(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodW
                                             visibility: CppVisibility::Public,
                                             is_signal: false,
                                             is_slot: false,
+                                            is_move_assignment: false,
+                                            ref_qualifier: None,
                                           }),
                    operator: None,
                    return_type: return_type,
@@ -305,6 +1494,8 @@ fn generate_field_accessors(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodW
                    inheritance_chain: Vec::new(),
                    //is_fake_inherited_method: false,
                    is_ffi_whitelisted: false,
+                   is_extern_c: false,
+                   feature: None,
                  },
                  kind: CppFfiMethodKind::FieldAccessor {
                    accessor_type: accessor_type,
@@ -340,6 +1531,7 @@ fn generate_field_accessors(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodW
             argument_type: field.field_type.clone(),
             name: "value".to_string(),
             has_default_value: false,
+            default_value: None,
           };
           new_methods.push(create_method(format!("set_{}", field.name),
                                          CppFieldAccessorType::Setter,
@@ -353,7 +1545,7 @@ fn generate_field_accessors(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodW
 }
 
 /// Convenience function to create `CppMethod` object for
-/// `static_cast` or `dynamic_cast` from type `from` to type `to`.
+/// `static_cast`, `dynamic_cast` or `qobject_cast` from type `from` to type `to`.
 /// See `CppMethod`'s documentation for more information
 /// about `is_unsafe_static_cast` and `is_direct_static_cast`.
 fn create_cast_method(cast: CppCast,
@@ -371,6 +1563,7 @@ fn create_cast_method(cast: CppCast,
                         name: "ptr".to_string(),
                         argument_type: from.clone(),
                         has_default_value: false,
+                        default_value: None,
                       }],
       allows_variadic_arguments: false,
       include_file: include_file.to_string(),
@@ -381,6 +1574,8 @@ fn create_cast_method(cast: CppCast,
       doc: None,
       inheritance_chain: Vec::new(),
       is_ffi_whitelisted: true,
+      is_extern_c: false,
+      feature: None,
     },
     kind: CppFfiMethodKind::Cast(cast),
   }
@@ -395,7 +1590,8 @@ fn create_cast_method(cast: CppCast,
 fn generate_casts_one(cpp_data: &CppDataWithDeps,
                       target_type: &CppTypeClassBase,
                       base_type: &CppType,
-                      is_direct: bool)
+                      is_direct: bool,
+                      is_primary: bool)
                       -> Result<Vec<CppMethodWithKind>> {
   let type_info = cpp_data
     .find_type_info(|x| x.name == target_type.name)
@@ -405,17 +1601,20 @@ fn generate_casts_one(cpp_data: &CppDataWithDeps,
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
   };
   let base_ptr_type = CppType {
     base: base_type.base.clone(),
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
   };
   let mut new_methods = Vec::new();
   new_methods.push(create_cast_method(CppCast::Static {
                                         is_unsafe: true,
                                         is_direct: is_direct,
+                                        is_primary: is_primary,
                                       },
                                       &base_ptr_type,
                                       &target_ptr_type,
@@ -423,6 +1622,7 @@ fn generate_casts_one(cpp_data: &CppDataWithDeps,
   new_methods.push(create_cast_method(CppCast::Static {
                                         is_unsafe: false,
                                         is_direct: is_direct,
+                                        is_primary: is_primary,
                                       },
                                       &target_ptr_type,
                                       &base_ptr_type,
@@ -434,6 +1634,12 @@ fn generate_casts_one(cpp_data: &CppDataWithDeps,
                                           &target_ptr_type,
                                           &type_info.include_file));
     }
+    if &base.name == "QObject" && cpp_data.has_meta_object(&target_type.name) {
+      new_methods.push(create_cast_method(CppCast::QObject,
+                                          &base_ptr_type,
+                                          &target_ptr_type,
+                                          &type_info.include_file));
+    }
   }
 
   if let CppTypeBase::Class(ref base) = base_type.base {
@@ -443,6 +1649,7 @@ fn generate_casts_one(cpp_data: &CppDataWithDeps,
           new_methods.append(&mut generate_casts_one(cpp_data,
                                                      target_type,
                                                      &base.base_type,
+                                                     false,
                                                      false)?);
         }
       }
@@ -451,23 +1658,562 @@ fn generate_casts_one(cpp_data: &CppDataWithDeps,
   Ok(new_methods)
 }
 
-/// Adds `static_cast` and `dynamic_cast` functions for all appropriate pairs of types
-/// in this `CppData`.
+/// Adds `static_cast`, `dynamic_cast` and `qobject_cast` functions
+/// for all appropriate pairs of types in this `CppData`.
 fn generate_casts(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind>> {
   log::status("Adding cast functions");
   let mut new_methods = Vec::new();
   for type_info in &cpp_data.current.parser.types {
     if let CppTypeKind::Class { ref bases, .. } = type_info.kind {
       let t = type_info.default_class_type()?;
-      let single_base = bases.len() == 1;
-      for base in bases {
-        new_methods.append(&mut generate_casts_one(cpp_data, &t, &base.base_type, single_base)?);
+      let primary_base_index = if bases.len() == 1 {
+        if bases[0].visibility == CppVisibility::Public {
+          Some(0)
+        } else {
+          None
+        }
+      } else {
+        bases
+          .iter()
+          .position(|base| base.visibility == CppVisibility::Public && !base.is_virtual)
+      };
+      for (index, base) in bases.iter().enumerate() {
+        new_methods.append(&mut generate_casts_one(cpp_data,
+                                                   &t,
+                                                   &base.base_type,
+                                                   true,
+                                                   primary_base_index == Some(index))?);
       }
     }
   }
   Ok(new_methods)
 }
 
+/// Returns methods inherited from CRTP bases. Unlike methods of other bases,
+/// they are not accessible through a wrapper of the base class
+/// (which is a template instantiation specific to the derived class),
+/// so they are added to the derived class.
+fn crtp_inherited_methods(cpp_data: &CppDataWithDeps) -> Vec<CppMethodWithKind> {
+  cpp_data
+    .current
+    .processed
+    .inherited_methods
+    .iter()
+    .filter(|method| match (method.class_membership.as_ref(), method.inheritance_chain.first()) {
+              (Some(info), Some(base)) => base.is_crtp_base_of(&info.class_type.name),
+              _ => false,
+            })
+    .map(|method| {
+           CppMethodWithKind {
+             method: method.clone(),
+             kind: CppFfiMethodKind::Real,
+           }
+         })
+    .collect()
+}
+
+/// Generates base class constructors for conversions added
+/// with `Config::add_slicing_conversion`.
+fn generate_slicing_conversions(cpp_data: &CppDataWithDeps,
+                                conversions: &[(String, String)])
+                                -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for &(ref derived_name, ref base_name) in conversions {
+    if !cpp_data.inherits(derived_name, base_name) {
+      return Err(format!("slicing conversion is configured for {} and {}, \
+                          but the class doesn't inherit the base",
+                         derived_name,
+                         base_name)
+                     .into());
+    }
+    let base_info = cpp_data
+      .current
+      .parser
+      .types
+      .iter()
+      .find(|t| &t.name == base_name)
+      .chain_err(|| format!("base class of slicing conversion not found: {}", base_name))?;
+    let derived_type = cpp_data
+      .find_type_info(|t| &t.name == derived_name)
+      .chain_err(|| format!("derived class of slicing conversion not found: {}", derived_name))?
+      .default_class_type()?;
+    new_methods.push(CppMethodWithKind {
+                       method: CppMethod {
+                         name: base_name.split("::").last().unwrap_or(base_name).to_string(),
+                         class_membership: Some(CppMethodClassMembership {
+                                                  class_type: base_info.default_class_type()?,
+                                                  kind: CppMethodKind::Constructor,
+                                                  is_virtual: false,
+                                                  is_pure_virtual: false,
+                                                  is_const: false,
+                                                  is_static: false,
+                                                  visibility: CppVisibility::Public,
+                                                  is_signal: false,
+                                                  is_slot: false,
+                                                  is_move_assignment: false,
+                                                  ref_qualifier: None,
+                                                }),
+                         operator: None,
+                         return_type: CppType::void(),
+                         arguments: vec![CppMethodArgument {
+                                           name: "value".to_string(),
+                                           argument_type: CppType {
+                                             base: CppTypeBase::Class(derived_type),
+                                             indirection: CppTypeIndirection::Ref,
+                                             is_const: true,
+                                             is_const2: false,
+                                             is_volatile: false,
+                                           },
+                                           has_default_value: false,
+                                           default_value: None,
+                                         }],
+                         allows_variadic_arguments: false,
+                         include_file: base_info.include_file.clone(),
+                         origin_location: None,
+                         template_arguments: None,
+                         template_arguments_values: None,
+                         declaration_code: None,
+                         doc: None,
+                         inheritance_chain: Vec::new(),
+                         is_ffi_whitelisted: false,
+                         is_extern_c: false,
+                         feature: None,
+                       },
+                       kind: CppFfiMethodKind::SlicingConversion,
+                     });
+  }
+  Ok(new_methods)
+}
+
+/// Generates methods calling methods added with `Config::add_batch_operation`
+/// for each element of an array, so that the whole array is processed
+/// in one FFI call.
+fn generate_batch_operations(cpp_data: &CppDataWithDeps,
+                             method_names: &[String])
+                             -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for method_name in method_names {
+    let candidates: Vec<_> = cpp_data
+      .current
+      .parser
+      .methods
+      .iter()
+      .filter(|m| &m.full_name() == method_name && m.arguments.len() == 1)
+      .collect();
+    if candidates.len() != 1 {
+      return Err(format!("batch operation is configured for {}, but the class doesn't have \
+                          exactly one such method with one argument",
+                         method_name)
+                     .into());
+    }
+    let method = candidates[0];
+    match method.class_membership {
+      Some(ref info) if info.kind == CppMethodKind::Regular && !info.is_static => {}
+      _ => {
+        return Err(format!("batch operation {} must be a non-static class method",
+                           method_name)
+                       .into())
+      }
+    }
+    let mut item_type = method.arguments[0].argument_type.clone();
+    if item_type.indirection != CppTypeIndirection::None &&
+       !(item_type.indirection == CppTypeIndirection::Ref && item_type.is_const) {
+      return Err(format!("argument of batch operation {} must be passed by value \
+                          or by const reference",
+                         method_name)
+                     .into());
+    }
+    item_type.indirection = CppTypeIndirection::Ptr;
+    item_type.is_const = true;
+    let mut batch_method = method.clone();
+    batch_method.name = format!("{}_batch", method.name);
+    if let Some(ref mut info) = batch_method.class_membership {
+      info.is_virtual = false;
+      info.is_pure_virtual = false;
+    }
+    batch_method.return_type = CppType::void();
+    batch_method.arguments = vec![CppMethodArgument {
+                                    name: "items".to_string(),
+                                    argument_type: item_type,
+                                    has_default_value: false,
+                                    default_value: None,
+                                  },
+                                  CppMethodArgument {
+                                    name: "count".to_string(),
+                                    argument_type: CppType {
+                                      base: CppTypeBase::PointerSizedInteger {
+                                        name: "size_t".to_string(),
+                                        is_signed: false,
+                                      },
+                                      indirection: CppTypeIndirection::None,
+                                      is_const: false,
+                                      is_const2: false,
+                                      is_volatile: false,
+                                    },
+                                    has_default_value: false,
+                                    default_value: None,
+                                  }];
+    batch_method.origin_location = None;
+    batch_method.declaration_code = None;
+    batch_method.doc = None;
+    new_methods.push(CppMethodWithKind {
+                       method: batch_method,
+                       kind: CppFfiMethodKind::BatchOperation { method_name: method.name.clone() },
+                     });
+  }
+  Ok(new_methods)
+}
+
+/// Returns true if values of `cpp_type` are passed to variadic arguments
+/// without default argument promotions, so they can be read as `cpp_type`.
+fn is_variadic_compatible(cpp_type: &CppType) -> bool {
+  use cpp_type::CppBuiltInNumericType::*;
+  match cpp_type.indirection {
+    CppTypeIndirection::Ptr => true,
+    CppTypeIndirection::None => {
+      match cpp_type.base {
+        CppTypeBase::BuiltInNumeric(ref numeric) => {
+          match *numeric {
+            Bool | Char | SChar | UChar | WChar | Char16 | Char32 | Short | UShort | Float => false,
+            _ => true,
+          }
+        }
+        CppTypeBase::SpecificNumeric(CppSpecificNumericType { bits, .. }) => bits >= 32,
+        CppTypeBase::PointerSizedInteger { .. } => true,
+        _ => false,
+      }
+    }
+    _ => false,
+  }
+}
+
+/// Generates functions calling variadic functions added with
+/// `Config::add_variadic_slice_function`. The items are passed
+/// in `items` and `count` arguments instead of variadic arguments.
+fn generate_variadic_slice_functions(cpp_data: &CppDataWithDeps,
+                                     functions: &[CppVariadicSliceFunction])
+                                     -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for function in functions {
+    let element_type = parse_template_argument(cpp_data, &function.element_type)?;
+    if !is_variadic_compatible(&element_type) {
+      log::error(format!("Warning: variadic function {} is skipped because \
+                          {} can't be passed as a variadic argument without promotion",
+                         function.function_name,
+                         function.element_type));
+      continue;
+    }
+    let methods: Vec<_> = cpp_data
+      .current
+      .parser
+      .methods
+      .iter()
+      .filter(|m| m.allows_variadic_arguments && m.full_name() == function.function_name)
+      .collect();
+    if methods.is_empty() {
+      return Err(format!("variadic function not found: {}", function.function_name).into());
+    }
+    let items_type = CppType {
+      indirection: if element_type.indirection == CppTypeIndirection::Ptr {
+        CppTypeIndirection::PtrPtr
+      } else {
+        CppTypeIndirection::Ptr
+      },
+      is_const: element_type.is_const || element_type.indirection == CppTypeIndirection::None,
+      is_const2: element_type.indirection == CppTypeIndirection::Ptr,
+      ..element_type.clone()
+    };
+    for method in methods {
+      let mut slice_method = method.clone();
+      slice_method.allows_variadic_arguments = false;
+      slice_method
+        .arguments
+        .push(CppMethodArgument {
+                name: "items".to_string(),
+                argument_type: items_type.clone(),
+                has_default_value: false,
+                default_value: None,
+              });
+      slice_method
+        .arguments
+        .push(CppMethodArgument {
+                name: "count".to_string(),
+                argument_type: CppType {
+                  base: CppTypeBase::PointerSizedInteger {
+                    name: "size_t".to_string(),
+                    is_signed: false,
+                  },
+                  indirection: CppTypeIndirection::None,
+                  is_const: false,
+                  is_const2: false,
+                  is_volatile: false,
+                },
+                has_default_value: false,
+                default_value: None,
+              });
+      slice_method.declaration_code = None;
+      new_methods.push(CppMethodWithKind {
+                         method: slice_method,
+                         kind: CppFfiMethodKind::VariadicSlice { max_count: function.max_count },
+                       });
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Generates functions calling methods added with `Config::add_errno_method`.
+/// The functions have additional `error_flag` and `error_code` output arguments.
+fn generate_errno_methods(cpp_data: &CppDataWithDeps,
+                          errno_methods: &[CppErrnoMethod])
+                          -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for errno_method in errno_methods {
+    let methods: Vec<_> = cpp_data
+      .current
+      .parser
+      .methods
+      .iter()
+      .filter(|m| m.full_name() == errno_method.method_name)
+      .collect();
+    if methods.is_empty() {
+      return Err(format!("errno method not found: {}", errno_method.method_name).into());
+    }
+    for method in methods {
+      let is_supported_return_type = match method.return_type.indirection {
+        CppTypeIndirection::Ptr => true,
+        CppTypeIndirection::None => {
+          match method.return_type.base {
+            CppTypeBase::BuiltInNumeric(ref numeric) => numeric != &CppBuiltInNumericType::Void,
+            CppTypeBase::SpecificNumeric(..) |
+            CppTypeBase::PointerSizedInteger { .. } => true,
+            _ => false,
+          }
+        }
+        _ => false,
+      };
+      if !is_supported_return_type {
+        return Err(format!("errno method {} must return a numeric type or a pointer",
+                           errno_method.method_name)
+                       .into());
+      }
+      if method
+           .arguments
+           .iter()
+           .any(|arg| arg.name == "error_flag" || arg.name == "error_code") {
+        return Err(format!("errno method {} has an argument conflicting with \
+                            the generated error arguments",
+                           errno_method.method_name)
+                       .into());
+      }
+      let output_arg = |name: &str, base: CppBuiltInNumericType| {
+        CppMethodArgument {
+          name: name.to_string(),
+          argument_type: CppType {
+            base: CppTypeBase::BuiltInNumeric(base),
+            indirection: CppTypeIndirection::Ptr,
+            is_const: false,
+            is_const2: false,
+            is_volatile: false,
+          },
+          has_default_value: false,
+          default_value: None,
+        }
+      };
+      let mut wrapper = method.clone();
+      wrapper
+        .arguments
+        .push(output_arg("error_flag", CppBuiltInNumericType::Bool));
+      wrapper
+        .arguments
+        .push(output_arg("error_code", CppBuiltInNumericType::Int));
+      wrapper.declaration_code = None;
+      new_methods.push(CppMethodWithKind {
+                         method: wrapper,
+                         kind: CppFfiMethodKind::ErrnoMethod {
+                           error_sentinel: errno_method.error_sentinel.clone(),
+                           error_getter: errno_method.error_getter.clone(),
+                         },
+                       });
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Generates methods writing objects to a string buffer using free
+/// `operator<<(std::ostream&, const T&)` operators. A template operator
+/// is used for each known instantiation of its class template.
+fn generate_stream_output_methods(cpp_data: &CppDataWithDeps) -> Result<Vec<CppMethodWithKind>> {
+  let mut new_methods = Vec::new();
+  for method in &cpp_data.current.parser.methods {
+    if method.operator != Some(CppOperator::BitwiseLeftShift) ||
+       method.class_membership.is_some() || method.arguments.len() != 2 {
+      continue;
+    }
+    let stream_type = &method.arguments[0].argument_type;
+    let is_ostream = stream_type.indirection == CppTypeIndirection::Ref &&
+                     !stream_type.is_const &&
+                     stream_type.base ==
+                     CppTypeBase::Class(CppTypeClassBase {
+                                          name: "std::ostream".to_string(),
+                                          template_arguments: None,
+                                        });
+    if !is_ostream {
+      continue;
+    }
+    let value_type = &method.arguments[1].argument_type;
+    let is_value_or_const_ref = value_type.indirection == CppTypeIndirection::None ||
+                                value_type.indirection == CppTypeIndirection::Ref &&
+                                value_type.is_const;
+    let class_type = match value_type.base {
+      CppTypeBase::Class(ref class_type) if is_value_or_const_ref => class_type,
+      _ => continue,
+    };
+    let class_types = if let Some(ref template_arguments) = method.template_arguments {
+      // the operator must accept any instantiation of the class template
+      let is_generic = class_type.template_arguments.as_ref().map_or(false, |args| {
+        args.len() == template_arguments.count() &&
+        args
+          .iter()
+          .enumerate()
+          .all(|(index, arg)| {
+                 arg.base ==
+                 CppTypeBase::TemplateParameter {
+                   nested_level: template_arguments.nested_level,
+                   index: index,
+                 } && arg.indirection == CppTypeIndirection::None
+               })
+      });
+      if !is_generic {
+        log::llog(log::DebugFfiSkips, || {
+          format!("Unsupported template stream operator: {}", method.short_text())
+        });
+        continue;
+      }
+      cpp_data
+        .current
+        .processed
+        .template_instantiations
+        .iter()
+        .filter(|i| i.class_name == class_type.name)
+        .flat_map(|i| i.instantiations.iter())
+        .map(|ins| {
+               CppTypeClassBase {
+                 name: class_type.name.clone(),
+                 template_arguments: Some(ins.template_arguments.clone()),
+               }
+             })
+        .collect()
+    } else {
+      vec![class_type.clone()]
+    };
+    let numeric_type = |base: CppBuiltInNumericType, indirection: CppTypeIndirection| {
+      CppType {
+        base: CppTypeBase::BuiltInNumeric(base),
+        indirection: indirection,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      }
+    };
+    for class_type in class_types {
+      new_methods.push(CppMethodWithKind {
+                         method: CppMethod {
+                           name: "display".to_string(),
+                           class_membership: Some(CppMethodClassMembership {
+                                                    class_type: class_type,
+                                                    is_virtual: false,
+                                                    is_pure_virtual: false,
+                                                    is_const: true,
+                                                    is_static: false,
+                                                    visibility: CppVisibility::Public,
+                                                    is_signal: false,
+                                                    is_slot: false,
+                                                    is_move_assignment: false,
+                                                    ref_qualifier: None,
+                                                    kind: CppMethodKind::Regular,
+                                                  }),
+                           operator: None,
+                           return_type: numeric_type(CppBuiltInNumericType::Int,
+                                                     CppTypeIndirection::None),
+                           arguments: vec![CppMethodArgument {
+                                             name: "buffer".to_string(),
+                                             argument_type:
+                                               numeric_type(CppBuiltInNumericType::Char,
+                                                            CppTypeIndirection::Ptr),
+                                             has_default_value: false,
+                                             default_value: None,
+                                           },
+                                           CppMethodArgument {
+                                             name: "length".to_string(),
+                                             argument_type:
+                                               numeric_type(CppBuiltInNumericType::Int,
+                                                            CppTypeIndirection::None),
+                                             has_default_value: false,
+                                             default_value: None,
+                                           }],
+                           allows_variadic_arguments: false,
+                           include_file: method.include_file.clone(),
+                           origin_location: None,
+                           template_arguments: None,
+                           template_arguments_values: None,
+                           declaration_code: None,
+                           doc: None,
+                           inheritance_chain: Vec::new(),
+                           is_ffi_whitelisted: false,
+                           is_extern_c: false,
+                           feature: method.feature.clone(),
+                         },
+                         kind: CppFfiMethodKind::StreamOutput,
+                       });
+    }
+  }
+  Ok(new_methods)
+}
+
+/// Removes methods produced from identical declarations of the same C++ method,
+/// keeping the declaration with the most complete documentation and location.
+/// Such duplicates would otherwise make all caption strategies fail.
+fn remove_duplicate_methods(methods: Vec<CppMethodWithFfiSignature>)
+                            -> Vec<CppMethodWithFfiSignature> {
+  fn richness(method: &CppMethod) -> (bool, bool) {
+    (method.doc.is_some(), method.origin_location.is_some())
+  }
+  let mut result: Vec<CppMethodWithFfiSignature> = Vec::new();
+  for method in methods {
+    let duplicate_index = result
+      .iter()
+      .position(|m| {
+                  m.kind == method.kind && m.allocation_place == method.allocation_place &&
+                  m.cpp_method.is_same_declaration(&method.cpp_method)
+                });
+    if let Some(index) = duplicate_index {
+      log::llog(log::DebugFfiSkips, || {
+        format!("Merging identical declarations of method: {}",
+                method.cpp_method.short_text())
+      });
+      if richness(&method.cpp_method) > richness(&result[index].cpp_method) {
+        result[index] = method;
+      }
+    } else {
+      result.push(method);
+    }
+  }
+  result
+}
+
+/// Returns key used to order FFI methods that have the same base name
+/// before their final names are chosen, so that the names don't depend
+/// on the order of input methods. Methods are ordered by their declaration
+/// location, and signatures are used to order methods with the same location
+/// (e.g. methods with omitted arguments or generated methods).
+fn ffi_method_sort_key(method: &CppMethodWithFfiSignature) -> (Option<(String, u32, u32)>, String) {
+  let location = method
+    .cpp_method
+    .origin_location
+    .as_ref()
+    .map(|x| (x.include_file_path.clone(), x.line, x.column));
+  (location, format!("{:?} {:?}", method.kind, method.c_signature))
+}
 
 /// Generates the FFI function signature for this method.
 fn method_to_ffi_signature<'a>(method: CppMethodRefWithKind<'a>,
@@ -499,6 +2245,9 @@ fn method_to_ffi_signature<'a>(method: CppMethodRefWithKind<'a>,
               .needs_allocation_place_variants() {
     if let CppTypeBase::Class(CppTypeClassBase { ref name, .. }) = method.method.return_type.base {
       get_place(name)?
+    } else if method.method.return_type.base.is_std_array() {
+      // arrays are returned into a Rust-owned buffer
+      ReturnValueAllocationPlace::Stack
     } else {
       return Err(unexpected("class type expected here").into());
     }
@@ -520,6 +2269,39 @@ fn method_to_ffi_signature<'a>(method: CppMethodRefWithKind<'a>,
 impl<'a> CppFfiGenerator<'a> {
   /// Returns false if the method is excluded from processing
   /// for some reason
+  /// Returns true if `method` is a protected method selected by
+  /// `Config::add_exposed_protected_method`. Constructors and destructors
+  /// can't be called through a helper subclass and are never exposed.
+  fn is_exposed_protected(&self, method: &CppMethod) -> bool {
+    let membership = match method.class_membership {
+      Some(ref membership) => membership,
+      None => return false,
+    };
+    if membership.kind != CppMethodKind::Regular {
+      return false;
+    }
+    let full_name = method.full_name();
+    let class_wildcard = format!("{}::*", membership.class_type.name);
+    self
+      .exposed_protected_methods
+      .iter()
+      .any(|pattern| pattern == &full_name || pattern == &class_wildcard)
+  }
+
+  /// Returns true if `method` matches any of the patterns
+  /// added with `Config::add_method_blocklist`.
+  fn is_blocked(&self, method: &CppMethod) -> bool {
+    if self.method_blocklist.is_empty() {
+      return false;
+    }
+    let full_name = method.full_name();
+    let short_text = method.short_text();
+    self
+      .method_blocklist
+      .iter()
+      .any(|pattern| pattern.is_match(&full_name) || pattern.is_match(&short_text))
+  }
+
   fn should_process_method(&self, method: &CppMethod) -> Result<bool> {
     //    if method.is_fake_inherited_method {
     //      return Ok(false);
@@ -537,6 +2319,13 @@ impl<'a> CppFfiGenerator<'a> {
     if class_name == "QFlags" {
       return Ok(false);
     }
+    if self.volatile_policy == VolatilePolicy::Reject && method.has_volatile_types() {
+      log::llog(log::DebugFfiSkips, || {
+        format!("Skipping method with volatile types (see Config::set_volatile_policy):\n{}\n",
+                method.short_text())
+      });
+      return Ok(false);
+    }
     if let Some(ref membership) = method.class_membership {
       if membership.kind == CppMethodKind::Constructor &&
          self.cpp_data.has_pure_virtual_methods(&class_name) {
@@ -547,19 +2336,30 @@ impl<'a> CppFfiGenerator<'a> {
       if membership.visibility == CppVisibility::Private {
         return Ok(false);
       }
-      if membership.visibility == CppVisibility::Protected {
+      if membership.visibility == CppVisibility::Protected && !self.is_exposed_protected(method) {
         return Ok(false);
       }
       if membership.is_signal {
         return Ok(false);
       }
     }
+    if method
+         .operator
+         .as_ref()
+         .map_or(false, |op| op.is_allocation_operator()) {
+      // allocation operators are used implicitly by `new` and `delete`
+      // expressions in constructor and destructor wrappers
+      log::llog(log::DebugFfiSkips,
+                || format!("Skipping allocation operator: {}", method.short_text()));
+      return Ok(false);
+    }
     if method.template_arguments.is_some() {
       return Ok(false);
     }
     if method.template_arguments_values.is_some() && !method.is_ffi_whitelisted {
       // TODO: re-enable after template test compilation (#24) is implemented
-      // TODO: QObject::findChild and QObject::findChildren should be allowed
+      // (instantiations added with `Config::add_template_method_instantiation`
+      // are whitelisted)
       return Ok(false);
     }
     if method
@@ -594,6 +2394,7 @@ impl<'a> CppFfiGenerator<'a> {
                       method.method.short_text(),
                       msg)
             });
+            self.dump_failure(method.method, &msg);
           }
           Ok(result) => {
             match c_base_name(&result.cpp_method,
@@ -605,12 +2406,22 @@ impl<'a> CppFfiGenerator<'a> {
                           method.method.short_text(),
                           msg)
                 });
+                self.dump_failure(method.method, &msg);
               }
               Ok(name) => {
-
-                add_to_multihash(&mut hash_name_to_methods,
-                                 format!("{}_{}", &self.cpp_ffi_lib_name, name),
-                                 result);
+                let name = if result.is_direct_extern_c() {
+                  // the function is bound directly, without a C++ wrapper;
+                  // C linkage names don't include namespaces
+                  let full_name = &result.cpp_method.name;
+                  full_name.split("::").last().unwrap_or(full_name).to_string()
+                } else {
+                  let name = match self.symbol_version {
+                    Some(ref tag) => format!("{}_{}", name, tag),
+                    None => name,
+                  };
+                  format!("{}_{}", &self.cpp_ffi_lib_name, name)
+                };
+                add_to_multihash(&mut hash_name_to_methods, name, result);
               }
             }
           }
@@ -618,13 +2429,32 @@ impl<'a> CppFfiGenerator<'a> {
       };
 
       for method in methods {
+        if self.is_blocked(&method.method) {
+          log::llog(log::DebugFfiSkips, || {
+            format!("Skipping method matching a blocklist pattern:\n{}\n",
+                    method.method.short_text())
+          });
+          continue;
+        }
         if !self.should_process_method(&method.method)? {
           continue;
         }
         process_one(method.clone());
         // generate methods with omitted arguments
         if let Some(last_arg) = method.method.arguments.last() {
-          if last_arg.has_default_value {
+          let is_protected = method
+            .method
+            .class_membership
+            .as_ref()
+            .map_or(false, |info| info.visibility == CppVisibility::Protected);
+          if last_arg.has_default_value && is_protected {
+            // protected methods are called through a pointer to member,
+            // which can't omit arguments with default values
+            log::llog(log::DebugFfiSkips, || {
+              format!("Skipping variants with omitted arguments of protected method:\n{}\n",
+                      method.method.short_text())
+            });
+          } else if last_arg.has_default_value {
             let mut method_copy = method.method.clone();
             while let Some(arg) = method_copy.arguments.pop() {
               if !arg.has_default_value {
@@ -656,8 +2486,14 @@ impl<'a> CppFfiGenerator<'a> {
 
 
 
+    // iteration order of `HashMap` is random, so names and methods
+    // are sorted to produce identical output on every run
+    let mut hash_name_to_methods: Vec<_> = hash_name_to_methods.into_iter().collect();
+    hash_name_to_methods.sort_by(|a, b| a.0.cmp(&b.0));
     let mut processed_methods = Vec::new();
     for (key, mut values) in hash_name_to_methods {
+      values.sort_by_key(ffi_method_sort_key);
+      let mut values = remove_duplicate_methods(values);
       if values.len() == 1 {
         processed_methods.push(CppAndFfiMethod::new(values.remove(0), key.clone()));
         continue;
@@ -689,6 +2525,22 @@ impl<'a> CppFfiGenerator<'a> {
           };
           processed_methods.push(CppAndFfiMethod::new(x, final_name));
         }
+      } else if values.iter().all(|x| x.cpp_method.is_constructor()) {
+        // constructors can't be renamed, so they are numbered
+        // in declaration order instead
+        log::llog(log::DebugFfiSkips, || {
+          format!("All type caption strategies have failed for constructors: {}. \
+                   Using numbered names.",
+                  key)
+        });
+        for (index, x) in values.into_iter().enumerate() {
+          let final_name = if index == 0 {
+            key.clone()
+          } else {
+            format!("{}_{}", key, index + 1)
+          };
+          processed_methods.push(CppAndFfiMethod::new(x, final_name));
+        }
       } else {
         log::error(format!("values dump: {:?}\n", values));
         log::error("All type caption strategies have failed! Involved functions:");
@@ -702,6 +2554,38 @@ impl<'a> CppFfiGenerator<'a> {
     Ok(processed_methods)
   }
 
+  /// Saves `method` and declarations of involved types to
+  /// the failure dump directory if it was set in `Config`.
+  /// Failing to save the dump is logged but doesn't stop the processing.
+  fn dump_failure(&self, method: &CppMethod, error: &Error) {
+    if let Some(dir) = self.failure_dump_dir {
+      let dump = CppFfiFailureDump {
+        method: method.clone(),
+        error: error.to_string(),
+        types: self
+          .cpp_data
+          .involved_type_infos(&method.all_involved_types()),
+      };
+      let base_name: String = method
+        .full_name()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+      let mut index = 0;
+      let mut path = dir.with_added(format!("{}.json", base_name));
+      while path.exists() {
+        index += 1;
+        path = dir.with_added(format!("{}_{}.json", base_name, index));
+      }
+      let result = create_dir_all(dir).and_then(|_| save_json(&path, &dump));
+      if let Err(err) = result {
+        log::error(format!("Failed to save failure dump for method: {}: {}",
+                           method.short_text(),
+                           err));
+      }
+    }
+  }
+
   /// Generates slot wrappers for all encountered argument types
   /// (excluding types already handled in the dependencies).
   fn generate_slot_wrappers(&'a self) -> Result<Option<CppFfiHeaderData>> {
@@ -731,6 +2615,7 @@ impl<'a> CppFfiGenerator<'a> {
         indirection: CppTypeIndirection::Ptr,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
       };
       let func_arguments = once(void_ptr.clone())
         .chain(ffi_types.iter().map(|t| t.ffi_type.clone()))
@@ -740,6 +2625,7 @@ impl<'a> CppFfiGenerator<'a> {
         return_type: Box::new(CppType::void()),
         arguments: func_arguments,
         allows_variadic_arguments: false,
+        class_type: None,
       };
       let create_function = |kind: CppMethodKind,
                              name: String,
@@ -761,6 +2647,8 @@ impl<'a> CppFfiGenerator<'a> {
                                      visibility: CppVisibility::Public,
                                      is_signal: false,
                                      is_slot: is_slot,
+                                     is_move_assignment: false,
+                                     ref_qualifier: None,
                                      kind: kind,
                                    }),
             operator: None,
@@ -775,6 +2663,8 @@ impl<'a> CppFfiGenerator<'a> {
             doc: None,
             inheritance_chain: Vec::new(),
             is_ffi_whitelisted: false,
+            is_extern_c: false,
+            feature: None,
           //is_fake_inherited_method: false,
           },
           kind: CppFfiMethodKind::Real,
@@ -795,13 +2685,16 @@ impl<'a> CppFfiGenerator<'a> {
                                      indirection: CppTypeIndirection::None,
                                      is_const: false,
                                      is_const2: false,
+                                     is_volatile: false,
                                    },
                                    has_default_value: false,
+                                   default_value: None,
                                  },
                                  CppMethodArgument {
                                    name: "data".to_string(),
                                    argument_type: void_ptr.clone(),
                                    has_default_value: false,
+                                   default_value: None,
                                  }];
       methods.push(create_function(CppMethodKind::Regular,
                                    "set".to_string(),
@@ -819,6 +2712,7 @@ impl<'a> CppFfiGenerator<'a> {
                                                           name: format!("arg{}", num),
                                                           argument_type: t.clone(),
                                                           has_default_value: false,
+                                                          default_value: None,
                                                         }
                                                       })
                                                  .collect());
@@ -838,6 +2732,7 @@ impl<'a> CppFfiGenerator<'a> {
         indirection: CppTypeIndirection::Ptr,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
       };
       let cast_to = CppType {
         base: CppTypeBase::Class(CppTypeClassBase {
@@ -847,10 +2742,12 @@ impl<'a> CppFfiGenerator<'a> {
         indirection: CppTypeIndirection::Ptr,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
       };
       methods.push(create_cast_method(CppCast::Static {
                                         is_unsafe: false,
                                         is_direct: true,
+                                        is_primary: true,
                                       },
                                       &cast_from,
                                       &cast_to,
@@ -863,6 +2760,138 @@ impl<'a> CppFfiGenerator<'a> {
                                  Some(CppTypeAllocationPlace::Heap),
                                  methods.iter().map(|i| i.as_ref()))?,
               qt_slot_wrappers: qt_slot_wrappers,
+              qt_event_filter_wrapper: None,
+              functor_wrappers: Vec::new(),
+            }))
+  }
+
+  /// Generates a `QObject` subclass that forwards its `eventFilter` calls
+  /// to a callback function. The wrapper is only generated for the crate
+  /// that contains `QObject` and `QEvent` (i.e. `qt_core`).
+  fn generate_event_filter_wrapper(&'a self) -> Result<Option<CppFfiHeaderData>> {
+    let include_file_name = "event_filter";
+    if !["QObject", "QEvent"]
+          .iter()
+          .all(|name| self.cpp_data.current.parser.types.iter().any(|t| &t.name == name)) {
+      return Ok(None);
+    }
+    let class_name = format!("{}_EventFilterWrapper", self.cpp_ffi_lib_name);
+    let class_ptr = |name: &str| {
+      CppType {
+        base: CppTypeBase::Class(CppTypeClassBase {
+                                   name: name.to_string(),
+                                   template_arguments: None,
+                                 }),
+        indirection: CppTypeIndirection::Ptr,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      }
+    };
+    let void_ptr = CppType {
+      base: CppTypeBase::Void,
+      indirection: CppTypeIndirection::Ptr,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+    };
+    let function_type = CppFunctionPointerType {
+      return_type: Box::new(CppType {
+                              base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
+                              indirection: CppTypeIndirection::None,
+                              is_const: false,
+                              is_const2: false,
+                              is_volatile: false,
+                            }),
+      arguments: vec![void_ptr.clone(), class_ptr("QObject"), class_ptr("QEvent")],
+      allows_variadic_arguments: false,
+      class_type: None,
+    };
+    let create_function = |kind: CppMethodKind,
+                           name: String,
+                           arguments: Vec<CppMethodArgument>|
+     -> CppMethodWithKind {
+      CppMethodWithKind {
+        method: CppMethod {
+          name: name,
+          class_membership: Some(CppMethodClassMembership {
+                                   class_type: CppTypeClassBase {
+                                     name: class_name.clone(),
+                                     template_arguments: None,
+                                   },
+                                   is_virtual: true,
+                                   is_pure_virtual: false,
+                                   is_const: false,
+                                   is_static: false,
+                                   visibility: CppVisibility::Public,
+                                   is_signal: false,
+                                   is_slot: false,
+                                   is_move_assignment: false,
+                                   ref_qualifier: None,
+                                   kind: kind,
+                                 }),
+          operator: None,
+          return_type: CppType::void(),
+          arguments: arguments,
+          allows_variadic_arguments: false,
+          include_file: include_file_name.to_string(),
+          origin_location: None,
+          template_arguments: None,
+          template_arguments_values: None,
+          declaration_code: None,
+          doc: None,
+          inheritance_chain: Vec::new(),
+          is_ffi_whitelisted: false,
+          is_extern_c: false,
+          feature: None,
+        },
+        kind: CppFfiMethodKind::Real,
+      }
+    };
+    let mut methods = Vec::new();
+    methods.push(create_function(CppMethodKind::Constructor, class_name.clone(), vec![]));
+    methods.push(create_function(CppMethodKind::Destructor, format!("~{}", class_name), vec![]));
+    methods.push(create_function(CppMethodKind::Regular,
+                                 "set".to_string(),
+                                 vec![CppMethodArgument {
+                                        name: "func".to_string(),
+                                        argument_type: CppType {
+                                          base: CppTypeBase::FunctionPointer(function_type
+                                                                               .clone()),
+                                          indirection: CppTypeIndirection::None,
+                                          is_const: false,
+                                          is_const2: false,
+                                          is_volatile: false,
+                                        },
+                                        has_default_value: false,
+                                        default_value: None,
+                                      },
+                                      CppMethodArgument {
+                                        name: "data".to_string(),
+                                        argument_type: void_ptr.clone(),
+                                        has_default_value: false,
+                                        default_value: None,
+                                      }]));
+    methods.push(create_cast_method(CppCast::Static {
+                                      is_unsafe: false,
+                                      is_direct: true,
+                                      is_primary: true,
+                                    },
+                                    &class_ptr(&class_name),
+                                    &class_ptr("QObject"),
+                                    include_file_name));
+    Ok(Some(CppFfiHeaderData {
+              include_file_base_name: include_file_name.to_string(),
+              methods: self
+                .process_methods(include_file_name,
+                                 Some(CppTypeAllocationPlace::Heap),
+                                 methods.iter().map(|i| i.as_ref()))?,
+              qt_slot_wrappers: Vec::new(),
+              qt_event_filter_wrapper: Some(QtEventFilterWrapper {
+                                              class_name: class_name.clone(),
+                                              function_type: function_type,
+                                            }),
+              functor_wrappers: Vec::new(),
             }))
   }
 }
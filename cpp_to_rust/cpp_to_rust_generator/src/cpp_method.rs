@@ -2,7 +2,8 @@
 
 
 use cpp_data::{CppVisibility, CppOriginLocation, TemplateArgumentsDeclaration, CppBaseSpecifier};
-use cpp_ffi_data::{CppFfiType, CppFfiMethodSignature, CppFfiMethodArgument, CppFfiArgumentMeaning};
+use cpp_ffi_data::{CppFfiType, CppFfiMethodSignature, CppFfiMethodArgument, CppFfiArgumentMeaning,
+                   CppIndirectionChange};
 use cpp_type::{CppType, CppTypeIndirection, CppTypeRole, CppTypeBase, CppTypeClassBase};
 use common::errors::{Result, unexpected};
 use common::string_utils::JoinWithSeparator;
@@ -23,6 +24,10 @@ pub struct CppMethodArgument {
   /// Flag indicating that the argument has default value and
   /// therefore can be omitted when calling the method
   pub has_default_value: bool,
+  /// C++ expression of the default value as written in the declaration
+  /// (tokens separated by spaces), if it's available
+  #[serde(default)]
+  pub default_value: Option<String>,
 }
 
 /// Enumerator indicating special cases of C++ methods.
@@ -38,6 +43,15 @@ pub enum CppMethodKind {
 }
 
 
+/// Ref-qualifier of a C++ member function (`&` or `&&` after the arguments).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Serialize, Deserialize)]
+pub enum CppRefQualifier {
+  /// The method can only be called on lvalues (`void f() &`)
+  LValue,
+  /// The method can only be called on rvalues (`void f() &&`)
+  RValue,
+}
 
 /// Information about an automatically generated method
 //#[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -82,6 +96,12 @@ pub struct CppMethodClassMembership {
   pub is_signal: bool,
   /// True if the method is a Qt slot
   pub is_slot: bool,
+  /// True if the method is a move assignment operator (`operator=(T&&)`)
+  #[serde(default)]
+  pub is_move_assignment: bool,
+  /// Ref-qualifier of the method, if any
+  #[serde(default)]
+  pub ref_qualifier: Option<CppRefQualifier>,
 
   // / If this method is a generated field accessor, this field contains
   // / information about it. Field accessors do not have real C++ methods corresponding to them.
@@ -161,6 +181,15 @@ pub struct CppMethod {
   pub doc: Option<CppMethodDoc>,
   /// If true, FFI generator skips some checks
   pub is_ffi_whitelisted: bool,
+  /// True if this is a free function declared with C language linkage
+  /// (`extern "C"`). Such functions can be called from Rust directly,
+  /// without a C++ wrapper.
+  #[serde(default)]
+  pub is_extern_c: bool,
+  /// Name of the feature added with `Config::add_cpp_feature`
+  /// if this method is only available when the feature is enabled
+  #[serde(default)]
+  pub feature: Option<String>,
 }
 
 
@@ -223,10 +252,47 @@ impl CppMethod {
     true
   }
 
+  /// Returns true if `other` is a structurally identical declaration of
+  /// the same method (e.g. the same inline method declared in multiple
+  /// headers). Argument names, documentation and location are ignored.
+  /// Methods from different include files are never considered identical
+  /// because their bodies could differ.
+  pub fn is_same_declaration(&self, other: &CppMethod) -> bool {
+    self.name == other.name && self.class_membership == other.class_membership &&
+    self.operator == other.operator && self.return_type == other.return_type &&
+    self.argument_types_equal(other) && self.include_file == other.include_file &&
+    self.template_arguments == other.template_arguments &&
+    self.template_arguments_values == other.template_arguments_values &&
+    self.is_extern_c == other.is_extern_c && self.feature == other.feature
+  }
+
+  /// Returns true if this method receives multiple non-const references
+  /// of the same type. Such arguments may refer to the same object,
+  /// which is not allowed for `&mut` references in Rust.
+  pub fn has_aliasing_mutable_references(&self) -> bool {
+    let mutable_refs: Vec<_> = self
+      .arguments
+      .iter()
+      .map(|arg| &arg.argument_type)
+      .filter(|t| t.indirection == CppTypeIndirection::Ref && !t.is_const)
+      .collect();
+    mutable_refs
+      .iter()
+      .enumerate()
+      .any(|(index, t1)| mutable_refs[index + 1..].iter().any(|t2| t1.base == t2.base))
+  }
+
+  /// Returns true if return type or any argument type
+  /// of this method has `volatile` qualifier.
+  pub fn has_volatile_types(&self) -> bool {
+    self.return_type.is_volatile || self.arguments.iter().any(|arg| arg.argument_type.is_volatile)
+  }
+
   /// Creates FFI method signature for this method:
   /// - converts all types to FFI types;
   /// - adds "this" argument explicitly if present;
-  /// - adds "output" argument for return value if `allocation_place` is `Stack`.
+  /// - adds "output" argument for return value if `allocation_place` is `Stack`
+  ///   or if the return value is a `std::initializer_list`.
   pub fn c_signature(&self,
                      allocation_place: ReturnValueAllocationPlace)
                      -> Result<CppFfiMethodSignature> {
@@ -246,6 +312,7 @@ impl CppMethod {
                       base: CppTypeBase::Class(info.class_type.clone()),
                       is_const: info.is_const,
                       is_const2: false,
+                      is_volatile: false,
                       indirection: CppTypeIndirection::Ptr,
                     }
                     .to_cpp_ffi_type(CppTypeRole::NotReturnType)?,
@@ -268,6 +335,7 @@ impl CppMethod {
       CppType {
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         indirection: CppTypeIndirection::None,
         base: CppTypeBase::Class(info.class_type.clone()),
       }
@@ -276,7 +344,14 @@ impl CppMethod {
     };
     let c_type = real_return_type
       .to_cpp_ffi_type(CppTypeRole::ReturnType)?;
-    if real_return_type.needs_allocation_place_variants() {
+    if c_type.conversion == CppIndirectionChange::InitializerListToVecOut {
+      r.arguments
+        .push(CppFfiMethodArgument {
+                name: "output".to_string(),
+                argument_type: c_type,
+                meaning: CppFfiArgumentMeaning::ReturnValue,
+              });
+    } else if real_return_type.needs_allocation_place_variants() {
       match allocation_place {
         ReturnValueAllocationPlace::Stack => {
           r.arguments
@@ -391,6 +466,11 @@ impl CppMethod {
       if info.is_const {
         s = format!("{} const", s);
       }
+      match info.ref_qualifier {
+        Some(CppRefQualifier::LValue) => s = format!("{} &", s),
+        Some(CppRefQualifier::RValue) => s = format!("{} &&", s),
+        None => {}
+      }
     }
     s.trim().to_string()
   }
@@ -494,6 +574,7 @@ impl CppMethod {
                     base: CppTypeBase::Class(class_membership.class_type.clone()),
                     is_const: class_membership.is_const,
                     is_const2: false,
+                    is_volatile: false,
                     indirection: CppTypeIndirection::Ptr,
                   });
     }
@@ -508,4 +589,65 @@ impl CppMethod {
     }
     result
   }
+
+  /// Returns a copy of this method with template parameters at
+  /// `nested_level` replaced with `template_arguments`.
+  /// Returns `Err` if `template_arguments` are incompatible with the method
+  /// or if any template parameters are left after the replacement.
+  pub fn instantiate(&self,
+                     nested_level: usize,
+                     template_arguments: &[CppType])
+                     -> Result<CppMethod> {
+    let mut new_method = self.clone();
+    if let Some(ref args) = self.template_arguments {
+      if args.nested_level == nested_level {
+        if args.count() != template_arguments.len() {
+          return Err("template arguments count mismatch".into());
+        }
+        new_method.template_arguments = None;
+        new_method.template_arguments_values = Some(template_arguments.to_vec());
+      }
+    }
+    new_method.arguments.clear();
+    for arg in &self.arguments {
+      new_method
+        .arguments
+        .push(CppMethodArgument {
+                name: arg.name.clone(),
+                has_default_value: arg.has_default_value,
+                default_value: arg.default_value.clone(),
+                argument_type: arg
+                  .argument_type
+                  .instantiate(nested_level, template_arguments)?,
+              });
+    }
+    new_method.return_type = self
+      .return_type
+      .instantiate(nested_level, template_arguments)?;
+    if let Some(ref mut info) = new_method.class_membership {
+      info.class_type = info
+        .class_type
+        .instantiate_class(nested_level, template_arguments)?;
+    }
+    let mut conversion_type = None;
+    if let Some(ref mut operator) = new_method.operator {
+      if let CppOperator::Conversion(ref mut cpp_type) = *operator {
+        let r = cpp_type.instantiate(nested_level, template_arguments)?;
+        *cpp_type = r.clone();
+        conversion_type = Some(r);
+      }
+    }
+    if new_method
+         .all_involved_types()
+         .iter()
+         .any(|t| t.base.is_or_contains_template_parameter()) {
+      return Err(format!("extra template parameters left: {}",
+                         new_method.short_text())
+                     .into());
+    }
+    if let Some(conversion_type) = conversion_type {
+      new_method.name = format!("operator {}", conversion_type.to_cpp_code(None)?);
+    }
+    Ok(new_method)
+  }
 }
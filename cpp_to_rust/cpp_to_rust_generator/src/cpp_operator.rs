@@ -124,7 +124,8 @@ pub struct CppOperatorInfo {
   /// but total number of arguments is the same in both cases.
   pub arguments_count: usize,
   /// True if this kind of operator can have variadic arguments.
-  /// Only the function call operator has this property.
+  /// Only the function call operator and allocation operators
+  /// (which may have extra placement arguments) have this property.
   pub allows_variadic_arguments: bool,
 }
 
@@ -139,6 +140,13 @@ impl CppOperator {
         allows_variadic_arguments: false,
       }
     }
+    fn allocation_oi(suffix: &'static str) -> CppOperatorInfo {
+      CppOperatorInfo {
+        function_name_suffix: Some(suffix),
+        arguments_count: 1,
+        allows_variadic_arguments: true,
+      }
+    }
 
     match *self {
       Conversion(..) => {
@@ -198,10 +206,21 @@ impl CppOperator {
         }
       }
       Comma => oi(",", 2),
-      New => oi("new", 2),
-      NewArray => oi("new[]", 2),
-      Delete => oi("delete", 2),
-      DeleteArray => oi("delete[]", 2),
+      New => allocation_oi("new"),
+      NewArray => allocation_oi("new[]"),
+      Delete => allocation_oi("delete"),
+      DeleteArray => allocation_oi("delete[]"),
+    }
+  }
+
+  /// Returns true if this is a class-specific or global allocation
+  /// or deallocation function (`operator new`, `operator delete`
+  /// or their array forms).
+  pub fn is_allocation_operator(&self) -> bool {
+    use self::CppOperator::*;
+    match *self {
+      New | NewArray | Delete | DeleteArray => true,
+      _ => false,
     }
   }
 
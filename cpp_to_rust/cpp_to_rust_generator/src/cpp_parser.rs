@@ -1,7 +1,8 @@
 use cpp_data::{ParserCppData, CppData, CppTypeData, CppTypeKind, CppClassField, CppEnumValue,
                CppOriginLocation, CppVisibility, CppClassUsingDirective, CppBaseSpecifier,
                TemplateArgumentsDeclaration};
-use cpp_method::{CppMethod, CppMethodArgument, CppMethodKind, CppMethodClassMembership};
+use cpp_method::{CppMethod, CppMethodArgument, CppMethodKind, CppMethodClassMembership,
+                 CppRefQualifier};
 use cpp_operator::CppOperator;
 use cpp_type::{CppType, CppTypeBase, CppBuiltInNumericType, CppTypeIndirection,
                CppSpecificNumericTypeKind, CppTypeClassBase, CppSpecificNumericType,
@@ -52,6 +53,8 @@ struct CppParser<'a> {
   config: CppParserConfig,
   /// C++ types found by the parser
   types: Vec<CppTypeData>,
+  /// Full names of inline namespaces found by the parser
+  inline_namespaces: Vec<String>,
   /// Processed C++ data of the dependencies
   dependencies_data: &'a [&'a CppData],
 }
@@ -107,10 +110,143 @@ fn get_template_arguments(entity: Entity) -> Option<TemplateArgumentsDeclaration
     Some(TemplateArgumentsDeclaration {
            nested_level: nested_level,
            names: names,
+           defaults: Vec::new(),
          })
   }
 }
 
+/// Returns true if free function `entity` is declared inside
+/// a C language linkage specification (`extern "C"`).
+/// Depending on libclang version, linkage specifications are reported
+/// as `LinkageSpec` or `UnexposedDecl`, so the tokens are checked as well.
+fn is_extern_c(entity: Entity) -> bool {
+  let mut current_entity = entity;
+  while let Some(parent) = current_entity.get_lexical_parent() {
+    match parent.get_kind() {
+      EntityKind::LinkageSpec |
+      EntityKind::UnexposedDecl => {
+        if let Some(range) = parent.get_range() {
+          let tokens: Vec<_> = range
+            .tokenize()
+            .into_iter()
+            .take(2)
+            .map(|t| t.get_spelling())
+            .collect();
+          if tokens.len() == 2 && &tokens[0] == "extern" && &tokens[1] == "\"C\"" {
+            return true;
+          }
+        }
+      }
+      EntityKind::TranslationUnit => return false,
+      _ => {}
+    }
+    current_entity = parent;
+  }
+  false
+}
+
+/// Returns true if `entity` is an inline namespace (`inline namespace v1 { ... }`).
+/// libclang doesn't report this property directly, so the tokens are checked.
+fn is_inline_namespace(entity: Entity) -> bool {
+  if entity.get_kind() != EntityKind::Namespace {
+    return false;
+  }
+  if let Some(range) = entity.get_range() {
+    let tokens: Vec<_> = range
+      .tokenize()
+      .into_iter()
+      .take(2)
+      .map(|t| t.get_spelling())
+      .collect();
+    tokens.len() == 2 && &tokens[0] == "inline" && &tokens[1] == "namespace"
+  } else {
+    false
+  }
+}
+
+/// Returns true if `entity` is a scoped enum (`enum class` or `enum struct`).
+/// libclang doesn't report this property directly, so the tokens are checked.
+fn is_scoped_enum(entity: Entity) -> bool {
+  if entity.get_kind() != EntityKind::EnumDecl {
+    return false;
+  }
+  if let Some(range) = entity.get_range() {
+    let tokens: Vec<_> = range
+      .tokenize()
+      .into_iter()
+      .take(2)
+      .map(|t| t.get_spelling())
+      .collect();
+    tokens.len() == 2 && &tokens[0] == "enum" && (&tokens[1] == "class" || &tokens[1] == "struct")
+  } else {
+    false
+  }
+}
+
+/// Returns true if class `entity` is declared `final`.
+/// libclang doesn't report this property directly, so the tokens
+/// before the class body are checked.
+fn is_final_class(entity: Entity) -> bool {
+  if let Some(range) = entity.get_range() {
+    range
+      .tokenize()
+      .into_iter()
+      .map(|t| t.get_spelling())
+      .take_while(|t| t != "{")
+      .any(|t| t == "final")
+  } else {
+    false
+  }
+}
+
+/// Returns true if `entity` is an enum without a name. Depending on
+/// the version, libclang reports no name or a placeholder
+/// (like `(anonymous enum at file.h:1:1)`) for such enums.
+fn is_anonymous_enum(entity: Entity) -> bool {
+  entity.get_kind() == EntityKind::EnumDecl &&
+  entity
+    .get_name()
+    .map_or(true, |name| name.is_empty() || name.starts_with('('))
+}
+
+/// Returns true if `entity` is declared inside a class or a struct.
+fn is_class_member(entity: Entity) -> bool {
+  entity
+    .get_semantic_parent()
+    .map_or(false, |parent| match parent.get_kind() {
+      EntityKind::ClassDecl |
+      EntityKind::StructDecl => true,
+      _ => false,
+    })
+}
+
+/// Returns synthesized name of an anonymous enum `entity` nested in a class.
+/// The name is derived from the name of the class and the first enumerator
+/// (e.g. `Class1::AnonymousEnum_MaxSize`), so it's stable between runs.
+fn anonymous_enum_name(entity: Entity, values: &[CppEnumValue]) -> Result<String> {
+  if !is_class_member(entity) {
+    return Err("anonymous enums are only supported inside classes".into());
+  }
+  let parent = entity
+    .get_semantic_parent()
+    .chain_err(|| "anonymous enum has no parent")?;
+  let first_value = values
+    .first()
+    .chain_err(|| "anonymous enum has no values")?;
+  Ok(format!("{}::AnonymousEnum_{}", get_full_name(parent)?, first_value.name))
+}
+
+/// Returns true if `name` is the name of `std::{template_name}` template,
+/// possibly inside an inline namespace of the standard library (like `std::__1`).
+fn is_std_template_name(name: &str, template_name: &str) -> bool {
+  let parts: Vec<_> = name.split("::").collect();
+  match parts.len() {
+    2 => parts[0] == "std" && parts[1] == template_name,
+    3 => parts[0] == "std" && parts[1].starts_with("__") && parts[2] == template_name,
+    _ => false,
+  }
+}
+
 /// Returns fully qualified name of `entity`.
 fn get_full_name(entity: Entity) -> Result<String> {
   let mut current_entity = entity;
@@ -269,6 +405,7 @@ pub fn run(config: CppParserConfig, dependencies_data: &[&CppData]) -> Result<Pa
   let (mut parser, methods) = run_clang(&config, None, |translation_unit| {
     let mut parser = CppParser {
       types: Vec::new(),
+      inline_namespaces: Vec::new(),
       config: config.clone(),
       dependencies_data: dependencies_data,
     };
@@ -285,6 +422,7 @@ pub fn run(config: CppParserConfig, dependencies_data: &[&CppData]) -> Result<Pa
   Ok(ParserCppData {
        types: parser.types,
        methods: good_methods,
+       inline_namespaces: parser.inline_namespaces,
      })
 }
 
@@ -356,13 +494,16 @@ impl<'a> CppParser<'a> {
             } else {
               return Err(unexpected("invalid matches count in regexp").into());
             }
+            let class_name = get_full_name(declaration)?;
+            let arg_types = self.complete_template_arguments(&class_name, arg_types)?;
             return Ok(CppType {
                         base: CppTypeBase::Class(CppTypeClassBase {
-                                                   name: get_full_name(declaration)?,
+                                                   name: class_name,
                                                    template_arguments: Some(arg_types),
                                                  }),
                         is_const: is_const,
                         is_const2: false,
+                        is_volatile: false,
                         indirection: CppTypeIndirection::None,
                       });
           } else {
@@ -400,6 +541,7 @@ impl<'a> CppParser<'a> {
                   },
                   is_const: is_const,
                   is_const2: false,
+                  is_volatile: false,
                   indirection: CppTypeIndirection::None,
                 });
     }
@@ -413,6 +555,7 @@ impl<'a> CppParser<'a> {
                       },
                       is_const: is_const,
                       is_const2: false,
+                      is_volatile: false,
                       indirection: CppTypeIndirection::None,
                     });
         }
@@ -428,6 +571,7 @@ impl<'a> CppParser<'a> {
                       },
                       is_const: is_const,
                       is_const2: false,
+                      is_volatile: false,
                       indirection: CppTypeIndirection::None,
                     });
         }
@@ -437,6 +581,7 @@ impl<'a> CppParser<'a> {
     let mut result_type = CppType {
       is_const: is_const,
       is_const2: false,
+      is_volatile: false,
       indirection: CppTypeIndirection::None,
       base: CppTypeBase::Void,
     };
@@ -484,6 +629,7 @@ impl<'a> CppParser<'a> {
                     base: subtype.base,
                     is_const: subtype.is_const,
                     is_const2: new_is_const2,
+                    is_volatile: false,
                     indirection: new_indirection,
                   });
       }
@@ -526,6 +672,7 @@ impl<'a> CppParser<'a> {
             }
           }
         }
+        let arg_types = self.complete_template_arguments(class_name, arg_types)?;
         result_type.base = CppTypeBase::Class(CppTypeClassBase {
                                                 name: class_name.to_string(),
                                                 template_arguments: Some(arg_types),
@@ -541,6 +688,66 @@ impl<'a> CppParser<'a> {
     Err(format!("Unrecognized unexposed type: {}", name).into())
   }
 
+  /// Parses default values of template arguments of a class template `entity`.
+  /// Trailing arguments without default values are omitted from the result.
+  /// Default values that can't be parsed are logged and ignored.
+  fn parse_template_argument_defaults(&self, entity: Entity) -> Vec<Option<CppType>> {
+    let mut result: Vec<_> = entity
+      .get_children()
+      .into_iter()
+      .filter(|c| c.get_kind() == EntityKind::TemplateTypeParameter)
+      .map(|c| {
+        let tokens: Vec<_> = match c.get_range() {
+          Some(range) => range.tokenize().into_iter().map(|t| t.get_spelling()).collect(),
+          None => return None,
+        };
+        let value_tokens = match tokens.iter().position(|t| t == "=") {
+          Some(index) => &tokens[index + 1..],
+          None => return None,
+        };
+        // restore spaces only between words, e.g. in "unsigned int"
+        let mut value = String::new();
+        for token in value_tokens {
+          let is_word = token.chars().all(|c| c.is_alphanumeric() || c == '_');
+          if is_word && value.chars().last().map_or(false, |c| c.is_alphanumeric() || c == '_') {
+            value.push(' ');
+          }
+          value.push_str(token);
+        }
+        match self.parse_unexposed_type(None, Some(value.clone()), Some(entity), None) {
+          Ok(r) => Some(r),
+          Err(msg) => {
+            log::llog(log::DebugParser, || {
+              format!("Failed to parse default template argument: {}: {}", value, msg)
+            });
+            None
+          }
+        }
+      })
+      .collect();
+    while result.last().map_or(false, |x| x.is_none()) {
+      result.pop();
+    }
+    result
+  }
+
+  /// Completes `arguments` of the template class `class_name`
+  /// with default values of omitted template arguments.
+  fn complete_template_arguments(&self,
+                                 class_name: &str,
+                                 arguments: Vec<CppType>)
+                                 -> Result<Vec<CppType>> {
+    if let Some(type_data) = self.find_type(|x| &x.name == class_name) {
+      if let CppTypeKind::Class { template_arguments: Some(ref declaration), .. } =
+        type_data.kind {
+        if arguments.len() < declaration.names.len() {
+          return declaration.with_defaults(&arguments);
+        }
+      }
+    }
+    Ok(arguments)
+  }
+
   /// Parses type `type1`.
   /// Surrounding class and/or
   /// method may be specified in `context_class` and `context_method`.
@@ -549,9 +756,6 @@ impl<'a> CppParser<'a> {
                 context_class: Option<Entity>,
                 context_method: Option<Entity>)
                 -> Result<CppType> {
-    if type1.is_volatile_qualified() {
-      return Err("Volatile type".into());
-    }
     let display_name = type1.get_display_name();
     if &display_name == "std::list<T>" {
       return Err(format!("Type blacklisted because it causes crash on Windows: {}",
@@ -559,6 +763,7 @@ impl<'a> CppParser<'a> {
                      .into());
     }
     let is_const = type1.is_const_qualified();
+    let is_volatile = type1.is_volatile_qualified();
     match type1.get_kind() {
       TypeKind::Typedef => {
         let parsed = self
@@ -575,6 +780,7 @@ impl<'a> CppParser<'a> {
                           indirection: parsed.indirection,
                           is_const: parsed.is_const,
                           is_const2: parsed.is_const2,
+                          is_volatile: parsed.is_volatile,
                         });
             }
           }
@@ -587,6 +793,7 @@ impl<'a> CppParser<'a> {
              base: CppTypeBase::Void,
              is_const: is_const,
              is_const2: false,
+             is_volatile: is_volatile,
              indirection: CppTypeIndirection::None,
            })
       }
@@ -615,6 +822,7 @@ impl<'a> CppParser<'a> {
              base: CppTypeBase::BuiltInNumeric(convert_type_kind(type1.get_kind())),
              is_const: is_const,
              is_const2: false,
+             is_volatile: is_volatile,
              indirection: CppTypeIndirection::None,
            })
       }
@@ -624,6 +832,7 @@ impl<'a> CppParser<'a> {
                base: CppTypeBase::Enum { name: get_full_name(declaration)? },
                is_const: is_const,
                is_const2: false,
+               is_volatile: is_volatile,
                indirection: CppTypeIndirection::None,
              })
         } else {
@@ -640,6 +849,44 @@ impl<'a> CppParser<'a> {
                            .into());
           }
           let declaration_name = get_full_name(declaration)?;
+          if is_std_template_name(&declaration_name, "array") {
+            return self
+                     .parse_std_array(type1, context_class, context_method, is_const, is_volatile)
+                     .chain_err(|| "failed to parse std::array type");
+          }
+          if is_std_template_name(&declaration_name, "basic_string_view") {
+            return self
+                     .parse_std_string_view(type1, context_class, context_method, is_const)
+                     .chain_err(|| "failed to parse std::basic_string_view type");
+          }
+          if is_std_template_name(&declaration_name, "span") {
+            return self
+                     .parse_std_span(type1, context_class, context_method, is_const)
+                     .chain_err(|| "failed to parse std::span type");
+          }
+          if is_std_template_name(&declaration_name, "unique_ptr") {
+            return self
+                     .parse_std_unique_ptr(type1, context_class, context_method, is_const)
+                     .chain_err(|| "failed to parse std::unique_ptr type");
+          }
+          if is_std_template_name(&declaration_name, "initializer_list") {
+            return self
+                     .parse_std_initializer_list(type1, context_class, context_method, is_const)
+                     .chain_err(|| "failed to parse std::initializer_list type");
+          }
+          if is_std_template_name(&declaration_name, "basic_ostream") {
+            return self
+                     .parse_std_ostream(type1, context_class, context_method, is_const)
+                     .chain_err(|| "failed to parse std::basic_ostream type");
+          }
+          for &map_name in &["map", "unordered_map"] {
+            if is_std_template_name(&declaration_name, map_name) {
+              let name = format!("std::{}", map_name);
+              return self
+                       .parse_std_map(type1, &name, context_class, context_method, is_const)
+                       .chain_err(|| format!("failed to parse {} type", name));
+            }
+          }
           let template_arguments = match type1.get_template_argument_types() {
             None => None,
             Some(arg_types) => {
@@ -672,6 +919,7 @@ impl<'a> CppParser<'a> {
                                         }),
                is_const: is_const,
                is_const2: false,
+               is_volatile: is_volatile,
                indirection: CppTypeIndirection::None,
              })
         } else {
@@ -717,9 +965,72 @@ impl<'a> CppParser<'a> {
                                                   return_type: return_type,
                                                   arguments: arguments,
                                                   allows_variadic_arguments: type1.is_variadic(),
+                                                  class_type: None,
                                                 }),
              is_const: is_const,
              is_const2: false,
+             is_volatile: is_volatile,
+             indirection: CppTypeIndirection::None,
+           })
+      }
+      TypeKind::MemberPointer => {
+        if display_name.ends_with(") const") {
+          return Err("pointers to const member functions are not supported".into());
+        }
+        let class_type = type1
+          .get_class_type()
+          .chain_err(|| "can't get class type of member pointer")?;
+        let class_type = match self
+                .parse_type(class_type, context_class, context_method)
+                .chain_err(|| "failed to parse class type of member pointer")? {
+          CppType {
+            base: CppTypeBase::Class(class_type),
+            indirection: CppTypeIndirection::None,
+            ..
+          } => class_type,
+          t => return Err(format!("unexpected member pointer class type: {:?}", t).into()),
+        };
+        let pointee = type1
+          .get_pointee_type()
+          .chain_err(|| "can't get pointee type of member pointer")?;
+        let function = match self
+                .parse_type(pointee, context_class, context_method)
+                .chain_err(|| "failed to parse pointee type of member pointer")? {
+          CppType {
+            base: CppTypeBase::FunctionPointer(function),
+            indirection: CppTypeIndirection::None,
+            ..
+          } => function,
+          _ => return Err("pointers to data members are not supported".into()),
+        };
+        Ok(CppType {
+             base: CppTypeBase::FunctionPointer(CppFunctionPointerType {
+                                                  class_type: Some(class_type),
+                                                  ..function
+                                                }),
+             is_const: is_const,
+             is_const2: false,
+             is_volatile: is_volatile,
+             indirection: CppTypeIndirection::None,
+           })
+      }
+      TypeKind::ConstantArray => {
+        let element_type = type1
+          .get_element_type()
+          .chain_err(|| "can't get array element type")?;
+        let size = type1.get_size().chain_err(|| "can't get array size")?;
+        let element = self
+          .parse_type(element_type, context_class, context_method)
+          .chain_err(|| "failed to parse array element type")?;
+        Ok(CppType {
+             base: CppTypeBase::Array {
+               element: Box::new(element),
+               size: size,
+               is_std_array: false,
+             },
+             is_const: false,
+             is_const2: false,
+             is_volatile: false,
              indirection: CppTypeIndirection::None,
            })
       }
@@ -755,6 +1066,7 @@ impl<'a> CppParser<'a> {
                      base: subtype.base,
                      is_const: subtype.is_const,
                      is_const2: new_is_const2,
+                     is_volatile: subtype.is_volatile,
                    })
               }
               Err(msg) => Err(msg),
@@ -763,6 +1075,14 @@ impl<'a> CppParser<'a> {
           None => Err("can't get pointee type".into()),
         }
       }
+      TypeKind::Auto => {
+        // deduced type (e.g. `auto` return type without trailing return type)
+        let canonical = type1.get_canonical_type();
+        if canonical.get_kind() == TypeKind::Auto {
+          return Err(format!("Undeduced auto type: {}", display_name).into());
+        }
+        self.parse_type(canonical, context_class, context_method)
+      }
       TypeKind::Unexposed => {
         let canonical = type1.get_canonical_type();
         if canonical.get_kind() != TypeKind::Unexposed {
@@ -802,6 +1122,288 @@ impl<'a> CppParser<'a> {
     }
   }
 
+  /// Parses an instantiation of `std::array`. libclang doesn't report
+  /// non-type template arguments, so the size is taken from the
+  /// canonical display name of the type.
+  fn parse_std_array(&self,
+                     type1: Type,
+                     context_class: Option<Entity>,
+                     context_method: Option<Entity>,
+                     is_const: bool,
+                     is_volatile: bool)
+                     -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    let element_type = arg_types
+      .get(0)
+      .and_then(|x| *x)
+      .chain_err(|| "failed to get element type")?;
+    let element = self
+      .parse_type(element_type, context_class, context_method)
+      .chain_err(|| "failed to parse element type")?;
+    if element.base.is_template_parameter() {
+      return Err("std::array of template parameter is not supported".into());
+    }
+    let canonical_name = type1.get_canonical_type().get_display_name();
+    let size_regex = Regex::new(r",\s*(\d+)[uUlL]*\s*>$")?;
+    let size = match size_regex.captures(canonical_name.as_ref()) {
+      Some(matches) => {
+        matches
+          .at(1)
+          .chain_err(|| "invalid matches count in regexp")?
+          .parse()
+          .chain_err(|| "failed to parse std::array size")?
+      }
+      None => return Err(format!("can't get std::array size: {}", canonical_name).into()),
+    };
+    Ok(CppType {
+         base: CppTypeBase::Array {
+           element: Box::new(element),
+           size: size,
+           is_std_array: true,
+         },
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: is_volatile,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses an instantiation of `std::basic_string_view`.
+  /// Only `std::string_view` (with `char` elements) is supported.
+  fn parse_std_string_view(&self,
+                           type1: Type,
+                           context_class: Option<Entity>,
+                           context_method: Option<Entity>,
+                           is_const: bool)
+                           -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    let char_type = arg_types
+      .get(0)
+      .and_then(|x| *x)
+      .chain_err(|| "failed to get character type")?;
+    let char_type = self
+      .parse_type(char_type, context_class, context_method)
+      .chain_err(|| "failed to parse character type")?;
+    if char_type.indirection != CppTypeIndirection::None ||
+       char_type.base != CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char) {
+      return Err("only std::basic_string_view<char> is supported".into());
+    }
+    Ok(CppType {
+         base: CppTypeBase::StringView,
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses an instantiation of `std::span`. Only spans with dynamic extent
+  /// are supported. As with `std::array`, the extent is taken from the
+  /// canonical display name of the type.
+  fn parse_std_span(&self,
+                    type1: Type,
+                    context_class: Option<Entity>,
+                    context_method: Option<Entity>,
+                    is_const: bool)
+                    -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    let element_type = arg_types
+      .get(0)
+      .and_then(|x| *x)
+      .chain_err(|| "failed to get element type")?;
+    let element = self
+      .parse_type(element_type, context_class, context_method)
+      .chain_err(|| "failed to parse element type")?;
+    if element.base.is_template_parameter() {
+      return Err("std::span of template parameter is not supported".into());
+    }
+    let canonical_name = type1.get_canonical_type().get_display_name();
+    let extent_regex = Regex::new(r",\s*(\d+)[uUlL]*\s*>$")?;
+    if let Some(matches) = extent_regex.captures(canonical_name.as_ref()) {
+      // `std::dynamic_extent` is the maximal value of `size_t`
+      let extent = matches
+        .at(1)
+        .chain_err(|| "invalid matches count in regexp")?;
+      if extent != ::std::u64::MAX.to_string() && extent != ::std::u32::MAX.to_string() {
+        return Err(format!("std::span with static extent is not supported: {}",
+                           canonical_name)
+                       .into());
+      }
+    }
+    Ok(CppType {
+         base: CppTypeBase::Span { element: Box::new(element) },
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses an instantiation of `std::initializer_list`.
+  fn parse_std_initializer_list(&self,
+                                type1: Type,
+                                context_class: Option<Entity>,
+                                context_method: Option<Entity>,
+                                is_const: bool)
+                                -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    let element_type = arg_types
+      .get(0)
+      .and_then(|x| *x)
+      .chain_err(|| "failed to get element type")?;
+    let element = self
+      .parse_type(element_type, context_class, context_method)
+      .chain_err(|| "failed to parse element type")?;
+    if element.base.is_template_parameter() {
+      return Err("std::initializer_list of template parameter is not supported".into());
+    }
+    Ok(CppType {
+         base: CppTypeBase::InitializerList { element: Box::new(element) },
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses an instantiation of `std::unique_ptr`. Only pointers to single
+  /// objects with the default deleter are supported.
+  fn parse_std_unique_ptr(&self,
+                          type1: Type,
+                          context_class: Option<Entity>,
+                          context_method: Option<Entity>,
+                          is_const: bool)
+                          -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    let pointee_type = arg_types
+      .get(0)
+      .and_then(|x| *x)
+      .chain_err(|| "failed to get pointee type")?;
+    if pointee_type.get_kind() == TypeKind::IncompleteArray {
+      return Err("std::unique_ptr of arrays is not supported".into());
+    }
+    if let Some(deleter_type) = arg_types.get(1).and_then(|x| *x) {
+      let is_default_deleter = match deleter_type.get_declaration() {
+        Some(declaration) => {
+          is_std_template_name(&get_full_name(declaration)?, "default_delete")
+        }
+        None => false,
+      };
+      if !is_default_deleter {
+        // the deleter can't be called from the Rust side,
+        // so functions using this type are skipped
+        log::error(format!("Warning: {} is skipped because custom deleters of \
+                            std::unique_ptr are not supported",
+                           type1.get_display_name()));
+        return Err("std::unique_ptr with a custom deleter is not supported".into());
+      }
+    }
+    let pointee = self
+      .parse_type(pointee_type, context_class, context_method)
+      .chain_err(|| "failed to parse pointee type")?;
+    if pointee.base.is_template_parameter() {
+      return Err("std::unique_ptr of template parameter is not supported".into());
+    }
+    Ok(CppType {
+         base: CppTypeBase::UniquePtr { pointee: Box::new(pointee) },
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses an instantiation of `std::map` or `std::unordered_map` (`name`).
+  /// Only the key and value types are kept in the template arguments,
+  /// so instantiations with custom comparators, hash functions, key equality
+  /// predicates or allocators are not supported.
+  fn parse_std_map(&self,
+                   type1: Type,
+                   name: &str,
+                   context_class: Option<Entity>,
+                   context_method: Option<Entity>,
+                   is_const: bool)
+                   -> Result<CppType> {
+    let arg_types = type1
+      .get_template_argument_types()
+      .chain_err(|| "failed to get template arguments")?;
+    if arg_types.len() < 2 {
+      return Err(format!("not enough template arguments for {}", name).into());
+    }
+    let mut template_arguments = Vec::new();
+    for arg_type in &arg_types[..2] {
+      let arg_type = (*arg_type).chain_err(|| "failed to get template argument")?;
+      template_arguments.push(self
+                                .parse_type(arg_type, context_class, context_method)
+                                .chain_err(|| "failed to parse template argument")?);
+    }
+    let default_helpers: &[&str] = if name == "std::map" {
+      &["less", "allocator"]
+    } else {
+      &["hash", "equal_to", "allocator"]
+    };
+    for (arg_type, helper) in arg_types[2..].iter().zip(default_helpers) {
+      let is_default = match (*arg_type).and_then(|t| t.get_declaration()) {
+        Some(declaration) => is_std_template_name(&get_full_name(declaration)?, helper),
+        None => false,
+      };
+      if !is_default {
+        return Err(format!("{} with a custom {} is not supported", name, helper).into());
+      }
+    }
+    Ok(CppType {
+         base: CppTypeBase::Class(CppTypeClassBase {
+                                    name: name.to_string(),
+                                    template_arguments: Some(template_arguments),
+                                  }),
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
+  /// Parses `std::basic_ostream<char>` as `std::ostream`. Streams of other
+  /// character types are not supported.
+  fn parse_std_ostream(&self,
+                       type1: Type,
+                       context_class: Option<Entity>,
+                       context_method: Option<Entity>,
+                       is_const: bool)
+                       -> Result<CppType> {
+    let char_type = type1
+      .get_template_argument_types()
+      .and_then(|args| args.get(0).cloned())
+      .and_then(|arg| arg)
+      .chain_err(|| "failed to get character type")?;
+    let char_type = self
+      .parse_type(char_type, context_class, context_method)
+      .chain_err(|| "failed to parse character type")?;
+    if char_type.base != CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char) {
+      return Err("only streams of char are supported".into());
+    }
+    Ok(CppType {
+         base: CppTypeBase::Class(CppTypeClassBase {
+                                    name: "std::ostream".to_string(),
+                                    template_arguments: None,
+                                  }),
+         is_const: is_const,
+         is_const2: false,
+         is_volatile: false,
+         indirection: CppTypeIndirection::None,
+       })
+  }
+
   /// Checks if the typedef `name` has a special meaning for the parser.
   fn parse_special_typedef(&self, name: &str) -> Option<CppTypeBase> {
     match name {
@@ -879,13 +1481,22 @@ impl<'a> CppParser<'a> {
       }
       "qintptr" |
       "qptrdiff" |
-      "QList::difference_type" => {
+      "QList::difference_type" |
+      "ptrdiff_t" |
+      "std::ptrdiff_t" |
+      "ssize_t" |
+      "intptr_t" |
+      "std::intptr_t" => {
         Some(CppTypeBase::PointerSizedInteger {
                name: name.to_string(),
                is_signed: true,
              })
       }
-      "quintptr" => {
+      "quintptr" |
+      "size_t" |
+      "std::size_t" |
+      "uintptr_t" |
+      "std::uintptr_t" => {
         Some(CppTypeBase::PointerSizedInteger {
                name: name.to_string(),
                is_signed: false,
@@ -991,6 +1602,9 @@ impl<'a> CppParser<'a> {
                              clang_type.get_display_name())
                    })?;
       let mut has_default_value = false;
+      let mut default_value_tokens = Vec::new();
+      // nesting level of brackets within the default value expression
+      let mut level = 0;
       for token in argument_entity
             .get_range()
             .chain_err(|| {
@@ -999,11 +1613,20 @@ impl<'a> CppParser<'a> {
                        })?
             .tokenize() {
         let spelling = token.get_spelling();
-        if spelling == "=" {
+        if has_default_value {
+          if level == 0 && (spelling == "," || spelling == ")") {
+            // the range may include the token following the argument
+            break;
+          }
+          if ["(", "<", "[", "{"].contains(&spelling.as_str()) {
+            level += 1;
+          } else if [")", ">", "]", "}"].contains(&spelling.as_str()) {
+            level -= 1;
+          }
+          default_value_tokens.push(spelling);
+        } else if spelling == "=" {
           has_default_value = true;
-          break;
-        }
-        if spelling == "{" {
+        } else if spelling == "{" {
           // clang sometimes reports incorrect range for arguments
           break;
         }
@@ -1012,6 +1635,11 @@ impl<'a> CppParser<'a> {
                        name: name,
                        argument_type: argument_type,
                        has_default_value: has_default_value,
+                       default_value: if default_value_tokens.is_empty() {
+                         None
+                       } else {
+                         Some(default_value_tokens.join(" "))
+                       },
                      });
     }
     let mut name = entity
@@ -1143,6 +1771,21 @@ impl<'a> CppParser<'a> {
       }
       Some(token_strings.join(" "))
     };
+    let extern_c = class_name.is_none() && is_extern_c(entity);
+    // `T& T::operator=(T&&)`, as opposed to copy assignment taking `const T&`
+    let is_move_assignment = method_operator == Some(CppOperator::Assignment) &&
+                             arguments.len() == 1 &&
+                             {
+                               let arg_type = &arguments[0].argument_type;
+                               arg_type.indirection == CppTypeIndirection::RValueRef &&
+                               !arg_type.is_const &&
+                               match arg_type.base {
+                                 CppTypeBase::Class(CppTypeClassBase { ref name, .. }) => {
+                                   Some(name) == class_name.as_ref()
+                                 }
+                                 _ => false,
+                               }
+                             };
     Ok(CppMethod {
          name: name_with_namespace,
          operator: method_operator,
@@ -1168,6 +1811,12 @@ impl<'a> CppParser<'a> {
              // not all signals are detected here! see CppData::detect_signals_and_slots
              is_signal: is_signal,
              is_slot: false,
+             is_move_assignment: is_move_assignment,
+             ref_qualifier: match entity.get_type().and_then(|t| t.get_ref_qualifier()) {
+               Some(RefQualifier::LValue) => Some(CppRefQualifier::LValue),
+               Some(RefQualifier::RValue) => Some(CppRefQualifier::RValue),
+               None => None,
+             },
              class_type: match self.find_type(|x| &x.name == &class_name) {
                Some(info) => info.default_class_type()?,
                None => return Err(format!("Unknown class type: {}", class_name).into()),
@@ -1188,6 +1837,8 @@ impl<'a> CppParser<'a> {
          inheritance_chain: Vec::new(),
          //is_fake_inherited_method: false,
          is_ffi_whitelisted: false,
+         is_extern_c: extern_c,
+         feature: None,
        })
   }
 
@@ -1215,11 +1866,21 @@ impl<'a> CppParser<'a> {
                     });
       }
     }
+    let is_anonymous = is_anonymous_enum(entity);
+    let name = if is_anonymous {
+      anonymous_enum_name(entity, &values)?
+    } else {
+      get_full_name(entity)?
+    };
     Ok(CppTypeData {
-         name: get_full_name(entity)?,
+         name: name,
          include_file: include_file,
          origin_location: get_origin_location(entity)?,
-         kind: CppTypeKind::Enum { values: values },
+         kind: CppTypeKind::Enum {
+           values: values,
+           is_scoped: is_scoped_enum(entity),
+           is_anonymous: is_anonymous,
+         },
          doc: None,
        })
   }
@@ -1327,7 +1988,10 @@ impl<'a> CppParser<'a> {
         return Err("Non-type template parameter is not supported".into());
       }
     }
-    let template_arguments = get_template_arguments(entity);
+    let mut template_arguments = get_template_arguments(entity);
+    if let Some(ref mut template_arguments) = template_arguments {
+      template_arguments.defaults = self.parse_template_argument_defaults(entity);
+    }
     if entity.get_kind() == EntityKind::ClassTemplate {
       if template_arguments.is_none() {
         return Err(unexpected("missing template arguments").into());
@@ -1356,6 +2020,7 @@ impl<'a> CppParser<'a> {
            fields: fields,
            using_directives: using_directives,
            template_arguments: template_arguments,
+           is_final: is_final_class(entity),
          },
          doc: None,
        })
@@ -1424,11 +2089,39 @@ impl<'a> CppParser<'a> {
       return;
     }
     match entity.get_kind() {
+      EntityKind::Namespace => {
+        if is_inline_namespace(entity) {
+          match get_full_name(entity) {
+            Ok(name) => {
+              if !self.inline_namespaces.contains(&name) {
+                self.inline_namespaces.push(name);
+              }
+            }
+            Err(msg) => {
+              log::llog(log::DebugParserSkips,
+                        || format!("Failed to get inline namespace name: {:?}: {}", entity, msg));
+            }
+          }
+        }
+      }
       EntityKind::EnumDecl => {
         if entity.get_accessibility() == Some(Accessibility::Private) {
           return; // skipping private stuff
         }
-        if entity.get_name().is_some() && entity.is_definition() {
+        if is_anonymous_enum(entity) && !is_class_member(entity) {
+          // there is no type to expose the values as associated constants of
+          log::llog(log::DebugParserSkips, || {
+            format!("Skipping anonymous enum outside of a class: {:?}\nvalues: {}",
+                    entity,
+                    entity
+                      .get_children()
+                      .into_iter()
+                      .filter_map(|child| child.get_name())
+                      .join(", "))
+          });
+          return;
+        }
+        if entity.is_definition() {
           match self.parse_enum(entity) {
             Ok(r) => {
               if let Some(info) = self.find_type(|x| x.name == r.name).cloned() {
@@ -1494,6 +2187,7 @@ impl<'a> CppParser<'a> {
       EntityKind::StructDecl |
       EntityKind::ClassDecl |
       EntityKind::UnexposedDecl |
+      EntityKind::LinkageSpec |
       EntityKind::ClassTemplate => {
         for c in entity.get_children() {
           self.parse_types(c);
@@ -1561,6 +2255,7 @@ impl<'a> CppParser<'a> {
       EntityKind::StructDecl |
       EntityKind::ClassDecl |
       EntityKind::UnexposedDecl |
+      EntityKind::LinkageSpec |
       EntityKind::ClassTemplate => {
         for c in entity.get_children() {
           methods.append(&mut self.parse_methods(c));
@@ -1580,12 +2275,40 @@ impl<'a> CppParser<'a> {
       CppTypeBase::BuiltInNumeric(..) |
       CppTypeBase::SpecificNumeric { .. } |
       CppTypeBase::PointerSizedInteger { .. } |
+      CppTypeBase::StringView |
       CppTypeBase::TemplateParameter { .. } => {}
       CppTypeBase::Enum { ref name } => {
         if self.find_type(|x| &x.name == name).is_none() {
           return Err(format!("unknown type: {}", name).into());
         }
       }
+      CppTypeBase::Class(ref class_type) if class_type.std_function_type().is_some() => {
+        // `std::function` is not a part of the library, but
+        // its instantiations can be wrapped if the function type is known
+        let function = class_type.std_function_type().expect("checked above");
+        self.check_type_integrity(&function.return_type)?;
+        for arg in &function.arguments {
+          self.check_type_integrity(arg)?;
+        }
+      }
+      CppTypeBase::Class(ref class_type) if class_type.std_variant_alternatives().is_some() => {
+        // `std::variant` is not a part of the library either
+        for arg in class_type.std_variant_alternatives().expect("checked above") {
+          self.check_type_integrity(arg)?;
+        }
+      }
+      CppTypeBase::Class(CppTypeClassBase {
+                           ref name,
+                           template_arguments: None,
+                         }) if name == "std::ostream" => {
+        // `std::ostream` is only supported as an argument of stream operators
+      }
+      CppTypeBase::Class(ref class_type) if class_type.std_map_types().is_some() => {
+        // neither are `std::map` and `std::unordered_map`
+        let (key_type, value_type) = class_type.std_map_types().expect("checked above");
+        self.check_type_integrity(key_type)?;
+        self.check_type_integrity(value_type)?;
+      }
       CppTypeBase::Class(CppTypeClassBase {
                            ref name,
                            ref template_arguments,
@@ -1601,6 +2324,12 @@ impl<'a> CppParser<'a> {
           }
         }
       }
+      CppTypeBase::Array { ref element, .. } |
+      CppTypeBase::Span { ref element } |
+      CppTypeBase::InitializerList { ref element } |
+      CppTypeBase::UniquePtr { pointee: ref element } => {
+        self.check_type_integrity(element)?;
+      }
       CppTypeBase::FunctionPointer(CppFunctionPointerType {
                                      ref return_type,
                                      ref arguments,
@@ -1619,6 +2348,70 @@ impl<'a> CppParser<'a> {
     Ok(())
   }
 
+  /// Creates a declaration of the standard library template class `name`
+  /// (`std::function`, `std::variant`, `std::map` or `std::unordered_map`)
+  /// if any of `methods` uses its instantiations and it's not declared
+  /// in the dependencies.
+  /// `is_instantiation` checks whether a class type is a supported instantiation.
+  /// The declaration is placed in the include file of the first method using it.
+  fn std_template_type_data<F>(&self,
+                               methods: &[CppMethod],
+                               name: &str,
+                               is_instantiation: F)
+                               -> Option<CppTypeData>
+    where F: Fn(&CppTypeClassBase) -> bool
+  {
+    if self.find_type(|x| &x.name == name).is_some() {
+      return None;
+    }
+    let method = match methods
+            .iter()
+            .find(|method| {
+      method
+        .all_involved_types()
+        .iter()
+        .any(|t| if let CppTypeBase::Class(ref class_type) = t.base {
+               is_instantiation(class_type)
+             } else {
+               false
+             })
+    }) {
+      Some(method) => method,
+      None => return None,
+    };
+    Some(CppTypeData {
+           name: name.to_string(),
+           include_file: method.include_file.clone(),
+           origin_location: method
+             .origin_location
+             .clone()
+             .unwrap_or_else(|| {
+                               CppOriginLocation {
+                                 include_file_path: method.include_file.clone(),
+                                 line: 0,
+                                 column: 0,
+                               }
+                             }),
+           kind: CppTypeKind::Class {
+             bases: Vec::new(),
+             fields: Vec::new(),
+             template_arguments: Some(vec![CppType {
+                                             base: CppTypeBase::TemplateParameter {
+                                               nested_level: 0,
+                                               index: 0,
+                                             },
+                                             indirection: CppTypeIndirection::None,
+                                             is_const: false,
+                                             is_const2: false,
+                                             is_volatile: false,
+                                           }]),
+             using_directives: Vec::new(),
+             is_final: false,
+           },
+           doc: None,
+         })
+  }
+
   /// Returns types and methods that don't refer to any unknown types.
   fn check_integrity(&self, methods: Vec<CppMethod>) -> (Vec<CppMethod>, Vec<CppTypeData>) {
     let good_methods = methods
@@ -1641,6 +2434,23 @@ impl<'a> CppParser<'a> {
       .collect();
 
     let mut good_types = Vec::new();
+    if let Some(t) = self.std_template_type_data(&good_methods, "std::function", |t| {
+      t.std_function_type().is_some()
+    }) {
+      good_types.push(t);
+    }
+    if let Some(t) = self.std_template_type_data(&good_methods, "std::variant", |t| {
+      t.std_variant_alternatives().is_some()
+    }) {
+      good_types.push(t);
+    }
+    for map_name in &["std::map", "std::unordered_map"] {
+      if let Some(t) = self.std_template_type_data(&good_methods, map_name, |t| {
+        &t.name == map_name && t.std_map_types().is_some()
+      }) {
+        good_types.push(t);
+      }
+    }
     for t in &self.types {
       let mut good_type = t.clone();
       if let CppTypeKind::Class {
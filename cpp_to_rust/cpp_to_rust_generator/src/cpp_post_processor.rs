@@ -1,7 +1,7 @@
 use cpp_data::{CppDataWithDeps, CppData, ParserCppData, ProcessedCppData, CppTypeAllocationPlace,
                CppTypeKind, CppVisibility, CppTemplateInstantiations, CppTemplateInstantiation,
                CppTypeData, CppBaseSpecifier};
-use cpp_method::{CppMethod, CppMethodKind, CppMethodClassMembership};
+use cpp_method::{CppMethod, CppMethodKind, CppMethodClassMembership, CppOperator};
 use cpp_type::{CppType, CppTypeClassBase, CppTypeBase, CppTypeIndirection};
 use common::log;
 use common::errors::{Result, unexpected};
@@ -82,6 +82,27 @@ impl<'a> CppPostProcessor<'a> {
     }
     false
   }
+  /// Checks if specified class declares (or inherits) class-specific
+  /// `operator new` or `operator delete`.
+  pub fn has_allocation_operators(&self,
+                                  class_name: &str,
+                                  inherited_methods: &[CppMethod])
+                                  -> bool {
+    self
+      .parser_data
+      .methods
+      .iter()
+      .chain(inherited_methods.iter())
+      .any(|method| if let Some(ref info) = method.class_membership {
+             &info.class_type.name == class_name &&
+             method
+               .operator
+               .as_ref()
+               .map_or(false, |op| op.is_allocation_operator())
+           } else {
+             false
+           })
+  }
 
 
   /// Adds destructors for every class that does not have explicitly
@@ -92,6 +113,12 @@ impl<'a> CppPostProcessor<'a> {
     for type1 in &self.parser_data.types {
       if let CppTypeKind::Class { .. } = type1.kind {
         let class_name = &type1.name;
+        if class_name == "std::variant" || class_name == "std::map" ||
+           class_name == "std::unordered_map" {
+          // the declarations don't match the templates, so destructors
+          // are generated for each instantiation by `cpp_ffi_generator`
+          continue;
+        }
         let found_destructor = self
           .parser_data
           .methods
@@ -110,6 +137,8 @@ impl<'a> CppPostProcessor<'a> {
                                                   visibility: CppVisibility::Public,
                                                   is_signal: false,
                                                   is_slot: false,
+                                                  is_move_assignment: false,
+                                                  ref_qualifier: None,
                                                   kind: CppMethodKind::Destructor,
                                                 }),
                          operator: None,
@@ -125,6 +154,8 @@ impl<'a> CppPostProcessor<'a> {
                          inheritance_chain: Vec::new(),
                          //is_fake_inherited_method: false,
                          is_ffi_whitelisted: false,
+                         is_extern_c: false,
+                         feature: None,
                        });
         }
       }
@@ -289,6 +320,7 @@ impl<'a> CppPostProcessor<'a> {
       } else {
         unreachable!()
       };
+      let crtp_base_methods = self.crtp_base_methods(class, bases);
       let bases_with_methods: Vec<(&CppBaseSpecifier, Vec<&CppMethod>)> = bases
         .iter()
         .filter(|base| {
@@ -302,6 +334,7 @@ impl<'a> CppPostProcessor<'a> {
               .chain(self.dependencies.iter().map(|d| &d.parser))
               .map(|p| &p.methods)
               .flat_map(|m| m)
+              .chain(crtp_base_methods.iter())
               .filter(|m| if let Some(ref info) = m.class_membership {
                         &info.class_type == base_class_base
                       } else {
@@ -379,6 +412,58 @@ impl<'a> CppPostProcessor<'a> {
     Ok(result)
   }
 
+  /// Returns methods of CRTP bases of `class` (see `CppBaseSpecifier::is_crtp_base_of`)
+  /// instantiated with the bases' template arguments. Constructors, destructors
+  /// and assignment operators are not included because they are not inherited.
+  fn crtp_base_methods(&self, class: &CppTypeData, bases: &[CppBaseSpecifier]) -> Vec<CppMethod> {
+    let mut result = Vec::new();
+    for base in bases {
+      if !base.is_crtp_base_of(&class.name) {
+        continue;
+      }
+      let (base_name, base_arguments) = match base.base_type.base {
+        CppTypeBase::Class(CppTypeClassBase {
+                             ref name,
+                             template_arguments: Some(ref args),
+                           }) => (name, args),
+        _ => continue,
+      };
+      for method in once(&self.parser_data)
+            .chain(self.dependencies.iter().map(|d| &d.parser))
+            .flat_map(|p| &p.methods) {
+        let info = match method.class_membership {
+          Some(ref info) if &info.class_type.name == base_name => info,
+          _ => continue,
+        };
+        if info.kind.is_constructor() || info.kind.is_destructor() ||
+           method.operator == Some(CppOperator::Assignment) {
+          continue;
+        }
+        let nested_level = match info.class_type.template_arguments {
+          Some(ref args) if args.iter().all(|x| x.base.is_template_parameter()) => {
+            if let CppTypeBase::TemplateParameter { nested_level, .. } = args[0].base {
+              nested_level
+            } else {
+              continue;
+            }
+          }
+          _ => continue,
+        };
+        match method.instantiate(nested_level, base_arguments) {
+          Ok(new_method) => result.push(new_method),
+          Err(msg) => {
+            log::llog(log::DebugInheritance, || {
+              format!("Failed to instantiate CRTP base method {}: {}",
+                      method.short_text(),
+                      msg)
+            });
+          }
+        }
+      }
+    }
+    result
+  }
+
 
   fn detect_signal_argument_types(&self) -> Result<Vec<Vec<CppType>>> {
     let mut all_types = HashSet::new();
@@ -452,6 +537,7 @@ impl<'a> CppPostProcessor<'a> {
     struct TypeStats {
       // has_derived_classes: bool,
       has_virtual_methods: bool,
+      has_allocation_operators: bool,
       pointers_count: usize,
       not_pointers_count: usize,
     };
@@ -486,6 +572,12 @@ impl<'a> CppPostProcessor<'a> {
         }
         data.get_mut(&type1.name).unwrap().has_virtual_methods = true;
       }
+      if self.has_allocation_operators(&type1.name, inherited_methods) {
+        if !data.contains_key(&type1.name) {
+          data.insert(type1.name.clone(), TypeStats::default());
+        }
+        data.get_mut(&type1.name).unwrap().has_allocation_operators = true;
+      }
     }
     for method in &self.parser_data.methods {
       check_type(&method.return_type, &mut data);
@@ -499,9 +591,10 @@ impl<'a> CppPostProcessor<'a> {
       if logger.is_on(log::DebugAllocationPlace) {
         for (name, stats) in &data {
           logger.log(log::DebugAllocationPlace,
-                     format!("{}\t{}\t{}\t{}",
+                     format!("{}\t{}\t{}\t{}\t{}",
                              name,
                              stats.has_virtual_methods,
+                             stats.has_allocation_operators,
                              stats.pointers_count,
                              stats.not_pointers_count));
         }
@@ -515,9 +608,17 @@ impl<'a> CppPostProcessor<'a> {
       let name = &type1.name;
       let result = if overrides.contains_key(name) {
         overrides[name].clone()
+      } else if name == "std::function" || name == "std::variant" || name == "std::map" ||
+                name == "std::unordered_map" {
+        // function objects, variants and maps are exposed as `CppBox` and freed on drop
+        CppTypeAllocationPlace::Heap
       } else if let Some(ref stats) = data.get(name) {
         if stats.has_virtual_methods {
           CppTypeAllocationPlace::Heap
+        } else if stats.has_allocation_operators {
+          // heap allocation glue uses `new` and `delete` expressions,
+          // so the class-specific operators will be called
+          CppTypeAllocationPlace::Heap
         } else if stats.pointers_count == 0 {
           CppTypeAllocationPlace::Stack
         } else {
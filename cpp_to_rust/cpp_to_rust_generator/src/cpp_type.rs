@@ -85,6 +85,10 @@ pub struct CppFunctionPointerType {
   pub arguments: Vec<CppType>,
   /// Whether arguments are terminated with "..."
   pub allows_variadic_arguments: bool,
+  /// If this is a pointer to a member function (e.g. `void (T::*)()`),
+  /// the class containing the function
+  #[serde(default)]
+  pub class_type: Option<CppTypeClassBase>,
 }
 
 /// Information about a numeric C++ type that is
@@ -141,8 +145,61 @@ pub enum CppTypeBase {
   },
   /// Function pointer type
   FunctionPointer(CppFunctionPointerType),
+  /// Fixed-size array type, like `int[4]` or `std::array<int, 4>`.
+  /// Constness of a built-in array is stored in `element`.
+  Array {
+    /// Type of the array's elements
+    element: Box<CppType>,
+    /// Number of elements
+    size: usize,
+    /// True if this is a `std::array` instantiation
+    /// rather than a built-in array
+    #[serde(default)]
+    is_std_array: bool,
+  },
+  /// `std::string_view` (i.e. `std::basic_string_view<char>`)
+  StringView,
+  /// `std::span<T>` with dynamic extent.
+  /// The span is mutable if `element` is not const.
+  Span {
+    /// Type of the span's elements
+    element: Box<CppType>,
+  },
+  /// `std::unique_ptr<T>` with the default deleter.
+  /// Only supported as a return type.
+  UniquePtr {
+    /// Type of the owned object
+    pointee: Box<CppType>,
+  },
+  /// `std::initializer_list<T>`. Only supported as a return type.
+  /// The backing array may not outlive the returned value, so
+  /// the elements are copied to a Rust `Vec<T>` by the wrapper.
+  InitializerList {
+    /// Type of the list's elements
+    element: Box<CppType>,
+  },
 }
 
+/// Name of the C struct used to pass `std::string_view` arguments
+/// through FFI. It's declared in the C++ wrapper library's global header
+/// and corresponds to `cpp_utils::StrRef`.
+pub const STR_REF_FFI_TYPE_NAME: &'static str = "cpp_utils_str_ref";
+
+/// Name of the C struct used to pass `std::span` arguments
+/// through FFI. It's declared in the C++ wrapper library's global header
+/// and corresponds to `cpp_utils::SliceRef`.
+pub const SLICE_REF_FFI_TYPE_NAME: &'static str = "cpp_utils_slice_ref";
+
+/// Name of the C struct used to return copies of `std::initializer_list`
+/// elements through FFI. It's declared in the C++ wrapper library's global header
+/// and corresponds to `cpp_utils::VecOut`.
+pub const VEC_OUT_FFI_TYPE_NAME: &'static str = "cpp_utils_vec_out";
+
+/// Name of the C struct used to pass Rust closures to `std::function` arguments
+/// through FFI. It's declared in the C++ wrapper library's global header
+/// and corresponds to `cpp_utils::Closure`.
+pub const CLOSURE_FFI_TYPE_NAME: &'static str = "cpp_utils_closure";
+
 /// Information about a C++ type
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[derive(Serialize, Deserialize)]
@@ -163,6 +220,9 @@ pub struct CppType {
   /// `is_const2` indicates constness of indirection that is applied second.
   /// In other cases it is `false`.
   pub is_const2: bool,
+  /// Same as `is_const`, but indicates `volatile` qualifier.
+  #[serde(default)]
+  pub is_volatile: bool,
 }
 
 impl CppTypeIndirection {
@@ -273,9 +333,83 @@ impl CppBuiltInNumericType {
 }
 
 
+impl CppFunctionPointerType {
+  /// Returns C++ code representing the function type itself
+  /// (e.g. `int (int, bool)`), as used in `std::function` template argument.
+  pub fn to_cpp_function_type_code(&self) -> Result<String> {
+    if self.allows_variadic_arguments {
+      return Err("function types with variadic arguments are not supported".into());
+    }
+    let mut arg_texts = Vec::new();
+    for arg in &self.arguments {
+      arg_texts.push(arg.to_cpp_code(None)?);
+    }
+    Ok(format!("{} ({})",
+               self.return_type.as_ref().to_cpp_code(None)?,
+               arg_texts.join(", ")))
+  }
+
+  /// Returns true if a Rust closure can be called through this function type,
+  /// i.e. if its arguments are numbers and its return type is a number or `void`.
+  pub fn is_closure_compatible(&self) -> bool {
+    let is_number = |t: &CppType| {
+      t.indirection == CppTypeIndirection::None &&
+      match t.base {
+        CppTypeBase::BuiltInNumeric(..) |
+        CppTypeBase::SpecificNumeric(..) |
+        CppTypeBase::PointerSizedInteger { .. } => true,
+        _ => false,
+      }
+    };
+    !self.allows_variadic_arguments && self.class_type.is_none() &&
+    self.arguments.iter().all(|arg| is_number(arg)) &&
+    (self.return_type.is_void() || is_number(&self.return_type))
+  }
+}
+
 impl CppTypeClassBase {
+  /// Returns function type of this class if it's an instantiation
+  /// of `std::function` template (e.g. `std::function<int(int)>`).
+  pub fn std_function_type(&self) -> Option<&CppFunctionPointerType> {
+    if &self.name != "std::function" {
+      return None;
+    }
+    if let Some(ref args) = self.template_arguments {
+      if args.len() == 1 && args[0].indirection == CppTypeIndirection::None {
+        if let CppTypeBase::FunctionPointer(ref function) = args[0].base {
+          return Some(function);
+        }
+      }
+    }
+    None
+  }
+
+  /// Returns types of alternatives of this class if it's an instantiation
+  /// of `std::variant` template (e.g. `std::variant<int, double>`).
+  pub fn std_variant_alternatives(&self) -> Option<&[CppType]> {
+    if &self.name != "std::variant" {
+      return None;
+    }
+    self.template_arguments.as_ref().map(|args| &args[..])
+  }
+
+  /// Returns key and value types of this class if it's an instantiation
+  /// of `std::map` or `std::unordered_map` template (e.g. `std::map<int, double>`).
+  pub fn std_map_types(&self) -> Option<(&CppType, &CppType)> {
+    if &self.name != "std::map" && &self.name != "std::unordered_map" {
+      return None;
+    }
+    match self.template_arguments {
+      Some(ref args) if args.len() == 2 => Some((&args[0], &args[1])),
+      _ => None,
+    }
+  }
+
   /// Returns C++ code representing this type.
   pub fn to_cpp_code(&self) -> Result<String> {
+    if let Some(function) = self.std_function_type() {
+      return Ok(format!("{}< {} >", self.name, function.to_cpp_function_type_code()?));
+    }
     match self.template_arguments {
       Some(ref args) => {
         let mut arg_texts = Vec::new();
@@ -371,6 +505,48 @@ impl CppTypeBase {
       _ => false,
     }
   }
+  /// Returns true if this is a fixed-size array.
+  pub fn is_array(&self) -> bool {
+    match *self {
+      CppTypeBase::Array { .. } => true,
+      _ => false,
+    }
+  }
+  /// Returns true if this is a `std::array` instantiation.
+  pub fn is_std_array(&self) -> bool {
+    match *self {
+      CppTypeBase::Array { is_std_array, .. } => is_std_array,
+      _ => false,
+    }
+  }
+  /// Returns true if this is `std::string_view`.
+  pub fn is_string_view(&self) -> bool {
+    match *self {
+      CppTypeBase::StringView => true,
+      _ => false,
+    }
+  }
+  /// Returns true if this is a `std::span` instantiation.
+  pub fn is_span(&self) -> bool {
+    match *self {
+      CppTypeBase::Span { .. } => true,
+      _ => false,
+    }
+  }
+  /// Returns true if this is a `std::unique_ptr` instantiation.
+  pub fn is_unique_ptr(&self) -> bool {
+    match *self {
+      CppTypeBase::UniquePtr { .. } => true,
+      _ => false,
+    }
+  }
+  /// Returns true if this is a `std::initializer_list` instantiation.
+  pub fn is_initializer_list(&self) -> bool {
+    match *self {
+      CppTypeBase::InitializerList { .. } => true,
+      _ => false,
+    }
+  }
   /// Returns true if this is a template parameter or a type that
   /// contains any template parameters.
   pub fn is_or_contains_template_parameter(&self) -> bool {
@@ -385,13 +561,19 @@ impl CppTypeBase {
           false
         }
       }
+      CppTypeBase::Array { ref element, .. } |
+      CppTypeBase::Span { ref element } |
+      CppTypeBase::InitializerList { ref element } => {
+        element.base.is_or_contains_template_parameter()
+      }
+      CppTypeBase::UniquePtr { ref pointee } => pointee.base.is_or_contains_template_parameter(),
       _ => false,
     }
   }
 
   /// Returns C++ code representing this type.
   pub fn to_cpp_code(&self, function_pointer_inner_text: Option<&str>) -> Result<String> {
-    if !self.is_function_pointer() && function_pointer_inner_text.is_some() {
+    if !self.is_function_pointer() && !self.is_array() && function_pointer_inner_text.is_some() {
       return Err("unexpected function_pointer_inner_text".into());
     }
     match *self {
@@ -410,6 +592,7 @@ impl CppTypeBase {
                                      ref return_type,
                                      ref arguments,
                                      ref allows_variadic_arguments,
+                                     ref class_type,
                                    }) => {
         if *allows_variadic_arguments {
           return Err("function pointers with variadic arguments are not supported".into());
@@ -418,15 +601,50 @@ impl CppTypeBase {
         for arg in arguments {
           arg_texts.push(arg.to_cpp_code(None)?);
         }
+        let class_prefix = match *class_type {
+          Some(ref class_type) => format!("{}::", class_type.to_cpp_code()?),
+          None => String::new(),
+        };
         if let Some(function_pointer_inner_text) = function_pointer_inner_text {
-          Ok(format!("{} (*{})({})",
+          Ok(format!("{} ({}*{})({})",
                      return_type.as_ref().to_cpp_code(None)?,
+                     class_prefix,
                      function_pointer_inner_text,
                      arg_texts.join(", ")))
         } else {
           return Err("function_pointer_inner_text argument is missing".into());
         }
       }
+      CppTypeBase::Array {
+        ref element,
+        ref size,
+        is_std_array: true,
+      } => {
+        if function_pointer_inner_text.is_some() {
+          return Err("unexpected function_pointer_inner_text".into());
+        }
+        Ok(format!("std::array<{}, {}>", element.to_cpp_code(None)?, size))
+      }
+      CppTypeBase::Array {
+        ref element,
+        ref size,
+        is_std_array: false,
+      } => {
+        Ok(format!("{} {}[{}]",
+                   element.to_cpp_code(None)?,
+                   function_pointer_inner_text.unwrap_or(""),
+                   size))
+      }
+      CppTypeBase::StringView => Ok("std::string_view".to_string()),
+      CppTypeBase::Span { ref element } => {
+        Ok(format!("std::span<{}>", element.to_cpp_code(None)?))
+      }
+      CppTypeBase::UniquePtr { ref pointee } => {
+        Ok(format!("std::unique_ptr<{}>", pointee.to_cpp_code(None)?))
+      }
+      CppTypeBase::InitializerList { ref element } => {
+        Ok(format!("std::initializer_list<{}>", element.to_cpp_code(None)?))
+      }
     }
   }
 
@@ -460,7 +678,42 @@ impl CppTypeBase {
              }
            }
          }
-
+         CppTypeBase::Array {
+           ref element,
+           ref size,
+           ref is_std_array,
+         } => {
+           let name = if *is_std_array { "std_array" } else { "array" };
+           match strategy {
+             TypeCaptionStrategy::Short => name.to_string(),
+             TypeCaptionStrategy::Full => {
+               format!("{}_{}_{}", element.caption(strategy.clone())?, name, size)
+             }
+           }
+         }
+         CppTypeBase::StringView => "string_view".to_string(),
+         CppTypeBase::Span { ref element } => {
+           match strategy {
+             TypeCaptionStrategy::Short => "span".to_string(),
+             TypeCaptionStrategy::Full => format!("{}_span", element.caption(strategy.clone())?),
+           }
+         }
+         CppTypeBase::UniquePtr { ref pointee } => {
+           match strategy {
+             TypeCaptionStrategy::Short => "unique_ptr".to_string(),
+             TypeCaptionStrategy::Full => {
+               format!("{}_unique_ptr", pointee.caption(strategy.clone())?)
+             }
+           }
+         }
+         CppTypeBase::InitializerList { ref element } => {
+           match strategy {
+             TypeCaptionStrategy::Short => "initializer_list".to_string(),
+             TypeCaptionStrategy::Full => {
+               format!("{}_initializer_list", element.caption(strategy.clone())?)
+             }
+           }
+         }
        })
   }
 
@@ -504,6 +757,7 @@ impl CppType {
     CppType {
       is_const: false,
       is_const2: false,
+      is_volatile: false,
       indirection: CppTypeIndirection::None,
       base: CppTypeBase::Void,
     }
@@ -517,8 +771,9 @@ impl CppType {
   /// Internal function to generate C++ code of the type
   /// after code for `self.base` was already generated.
   fn to_cpp_code_intermediate(&self, base_code: &str) -> String {
-    format!("{}{}{}",
+    format!("{}{}{}{}",
             if self.is_const { "const " } else { "" },
+            if self.is_volatile { "volatile " } else { "" },
             base_code,
             match self.indirection {
               CppTypeIndirection::None => "",
@@ -532,6 +787,25 @@ impl CppType {
 
   /// Returns C++ code representing this type.
   pub fn to_cpp_code(&self, function_pointer_inner_text: Option<&str>) -> Result<String> {
+    if let CppTypeBase::Array {
+             ref element,
+             ref size,
+             is_std_array: false,
+           } = self.base {
+      // indirection of an array type wraps the declared name,
+      // like in `const int (&name)[4]`
+      let indirection = match self.indirection {
+        CppTypeIndirection::None => return self.base.to_cpp_code(function_pointer_inner_text),
+        CppTypeIndirection::Ptr => "*",
+        CppTypeIndirection::Ref => "&",
+        _ => return Err(format!("unsupported indirection of array type: {:?}", self).into()),
+      };
+      return Ok(format!("{} ({}{})[{}]",
+                        element.to_cpp_code(None)?,
+                        indirection,
+                        function_pointer_inner_text.unwrap_or(""),
+                        size));
+    }
     let base_code = self.base.to_cpp_code(function_pointer_inner_text)?;
     Ok(self.to_cpp_code_intermediate(&base_code))
   }
@@ -556,11 +830,16 @@ impl CppType {
                                      ref return_type,
                                      ref arguments,
                                      ref allows_variadic_arguments,
+                                     ref class_type,
                                    }) => {
         if *allows_variadic_arguments {
           return Err(Error::from("function pointers with variadic arguments are not supported"))
                    .chain_err(&err);
         }
+        if class_type.is_some() {
+          return Err(Error::from("pointers to member functions are not supported"))
+                   .chain_err(&err);
+        }
         let mut all_types: Vec<&CppType> = arguments.iter().collect();
         all_types.push(return_type.as_ref());
         for arg in all_types {
@@ -601,8 +880,202 @@ impl CppType {
                     original_type: self.clone(),
                   });
       }
+      CppTypeBase::Array {
+        ref element,
+        ref is_std_array,
+        ..
+      } => {
+        match self.indirection {
+          CppTypeIndirection::Ptr |
+          CppTypeIndirection::Ref => {}
+          // `std::array` values are passed by pointer, like classes
+          CppTypeIndirection::None if *is_std_array => {}
+          _ => {
+            return Err(Error::from("only pointers and references to arrays are supported"))
+                     .chain_err(&err);
+          }
+        }
+        if element.indirection != CppTypeIndirection::None {
+          return Err(Error::from("arrays of pointers are not supported")).chain_err(&err);
+        }
+        match element.base {
+          CppTypeBase::BuiltInNumeric(..) |
+          CppTypeBase::SpecificNumeric(..) |
+          CppTypeBase::PointerSizedInteger { .. } |
+          CppTypeBase::Enum { .. } => {}
+          _ => {
+            return Err(Error::from("only arrays of numbers and enums are supported"))
+                     .chain_err(&err);
+          }
+        }
+      }
+      CppTypeBase::Class(ref class_type) if role != CppTypeRole::ReturnType &&
+                                             class_type.std_function_type().is_some() => {
+        // the closure is moved to a function object owned by the C++ side;
+        // other `std::function` arguments are passed as objects, like other classes
+        let function = class_type.std_function_type().expect("checked above");
+        if function.is_closure_compatible() &&
+           (self.indirection == CppTypeIndirection::None ||
+            (self.indirection == CppTypeIndirection::Ref && self.is_const)) {
+          return Ok(CppFfiType {
+                      ffi_type: CppType {
+                        base: CppTypeBase::Class(CppTypeClassBase {
+                                                   name: CLOSURE_FFI_TYPE_NAME.to_string(),
+                                                   template_arguments: None,
+                                                 }),
+                        indirection: CppTypeIndirection::None,
+                        is_const: false,
+                        is_const2: false,
+                        is_volatile: false,
+                      },
+                      conversion: CppIndirectionChange::StdFunctionToClosure,
+                      original_type: self.clone(),
+                    });
+        }
+      }
+      CppTypeBase::StringView => {
+        // `&str` is passed as a pointer and a length,
+        // so returning the data is not supported
+        if role == CppTypeRole::ReturnType {
+          return Err(Error::from("std::string_view return values are not supported"))
+                   .chain_err(&err);
+        }
+        if !(self.indirection == CppTypeIndirection::None ||
+             (self.indirection == CppTypeIndirection::Ref && self.is_const)) {
+          return Err(Error::from("std::string_view can only be passed by value \
+                                  or by const reference"))
+                     .chain_err(&err);
+        }
+        return Ok(CppFfiType {
+                    ffi_type: CppType {
+                      base: CppTypeBase::Class(CppTypeClassBase {
+                                                 name: STR_REF_FFI_TYPE_NAME.to_string(),
+                                                 template_arguments: None,
+                                               }),
+                      indirection: CppTypeIndirection::None,
+                      is_const: false,
+                      is_const2: false,
+                      is_volatile: false,
+                    },
+                    conversion: CppIndirectionChange::StringViewToStrRef,
+                    original_type: self.clone(),
+                  });
+      }
+      CppTypeBase::Span { ref element } => {
+        // the span is passed as a pointer and a length,
+        // and the elements must have the same layout in Rust
+        if role == CppTypeRole::ReturnType {
+          return Err(Error::from("std::span return values are not supported"))
+                   .chain_err(&err);
+        }
+        if !(self.indirection == CppTypeIndirection::None ||
+             (self.indirection == CppTypeIndirection::Ref && self.is_const)) {
+          return Err(Error::from("std::span can only be passed by value \
+                                  or by const reference"))
+                     .chain_err(&err);
+        }
+        if element.indirection != CppTypeIndirection::None {
+          return Err(Error::from("spans of pointers are not supported")).chain_err(&err);
+        }
+        match element.base {
+          CppTypeBase::BuiltInNumeric(..) |
+          CppTypeBase::SpecificNumeric(..) |
+          CppTypeBase::PointerSizedInteger { .. } |
+          CppTypeBase::Enum { .. } => {}
+          _ => {
+            return Err(Error::from("only spans of numbers and enums are supported"))
+                     .chain_err(&err);
+          }
+        }
+        return Ok(CppFfiType {
+                    ffi_type: CppType {
+                      base: CppTypeBase::Class(CppTypeClassBase {
+                                                 name: SLICE_REF_FFI_TYPE_NAME.to_string(),
+                                                 template_arguments: None,
+                                               }),
+                      indirection: CppTypeIndirection::None,
+                      is_const: false,
+                      is_const2: false,
+                      is_volatile: false,
+                    },
+                    conversion: CppIndirectionChange::SpanToSliceRef,
+                    original_type: self.clone(),
+                  });
+      }
+      CppTypeBase::UniquePtr { ref pointee } => {
+        // the owned object is released from the unique_ptr
+        // and returned as a pointer owned by the caller
+        if role != CppTypeRole::ReturnType {
+          return Err(Error::from("std::unique_ptr arguments are not supported"))
+                   .chain_err(&err);
+        }
+        if self.indirection != CppTypeIndirection::None {
+          return Err(Error::from("std::unique_ptr can only be returned by value"))
+                   .chain_err(&err);
+        }
+        if pointee.indirection != CppTypeIndirection::None || !pointee.base.is_class() {
+          return Err(Error::from("only std::unique_ptr of classes is supported"))
+                   .chain_err(&err);
+        }
+        if pointee.is_const {
+          return Err(Error::from("std::unique_ptr of const objects is not supported"))
+                   .chain_err(&err);
+        }
+        let mut ffi_type = (**pointee).clone();
+        ffi_type.indirection = CppTypeIndirection::Ptr;
+        return Ok(CppFfiType {
+                    ffi_type: ffi_type,
+                    conversion: CppIndirectionChange::UniquePtrToPtr,
+                    original_type: self.clone(),
+                  });
+      }
+      CppTypeBase::InitializerList { ref element } => {
+        // the elements are copied to a buffer provided by the caller
+        // through an output argument because the backing array
+        // may be destroyed together with the returned list
+        if role != CppTypeRole::ReturnType {
+          return Err(Error::from("std::initializer_list arguments are not supported"))
+                   .chain_err(&err);
+        }
+        if self.indirection != CppTypeIndirection::None {
+          return Err(Error::from("std::initializer_list can only be returned by value"))
+                   .chain_err(&err);
+        }
+        if element.indirection != CppTypeIndirection::None {
+          return Err(Error::from("initializer lists of pointers are not supported"))
+                   .chain_err(&err);
+        }
+        match element.base {
+          CppTypeBase::BuiltInNumeric(..) |
+          CppTypeBase::SpecificNumeric(..) |
+          CppTypeBase::PointerSizedInteger { .. } |
+          CppTypeBase::Enum { .. } => {}
+          _ => {
+            return Err(Error::from("only initializer lists of numbers and enums \
+                                    are supported"))
+                       .chain_err(&err);
+          }
+        }
+        return Ok(CppFfiType {
+                    ffi_type: CppType {
+                      base: CppTypeBase::Class(CppTypeClassBase {
+                                                 name: VEC_OUT_FFI_TYPE_NAME.to_string(),
+                                                 template_arguments: None,
+                                               }),
+                      indirection: CppTypeIndirection::None,
+                      is_const: false,
+                      is_const2: false,
+                      is_volatile: false,
+                    },
+                    conversion: CppIndirectionChange::InitializerListToVecOut,
+                    original_type: self.clone(),
+                  });
+      }
       _ => {}
     }
+    if self.is_volatile && self.indirection != CppTypeIndirection::Ptr {
+      return Err(Error::from("only pointers to volatile types are supported")).chain_err(&err);
+    }
     let mut result = self.clone();
     let mut conversion = CppIndirectionChange::NoChange;
     match self.indirection {
@@ -620,7 +1093,17 @@ impl CppType {
         conversion = CppIndirectionChange::ReferenceToPointer;
       }
       CppTypeIndirection::RValueRef => {
-        return Err(Error::from("rvalue references are not supported")).chain_err(&err);
+        // the object is moved from a pointer passed by the caller
+        if role == CppTypeRole::ReturnType {
+          return Err(Error::from("rvalue reference return types are not supported"))
+                   .chain_err(&err);
+        }
+        if !self.base.is_class() {
+          return Err(Error::from("rvalue references are only supported for class types"))
+                   .chain_err(&err);
+        }
+        result.indirection = CppTypeIndirection::Ptr;
+        conversion = CppIndirectionChange::RValueReferenceToPointer;
       }
     }
     if let CppTypeBase::Class(CppTypeClassBase { ref name, .. }) = self.base {
@@ -636,6 +1119,15 @@ impl CppType {
         result.base = CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::UInt);
         result.is_const = false;
         result.indirection = CppTypeIndirection::None;
+      } else if name == "QChar" && role == CppTypeRole::NotReturnType &&
+                (self.indirection == CppTypeIndirection::None ||
+                 (self.indirection == CppTypeIndirection::Ref && self.is_const)) {
+        // the character is passed as a UTF-16 code unit,
+        // so that a Rust `char` can be used in the API
+        conversion = CppIndirectionChange::QCharToUShort;
+        result.base = CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::UShort);
+        result.is_const = false;
+        result.indirection = CppTypeIndirection::None;
       } else {
         // structs can't be passed by value
         if self.indirection == CppTypeIndirection::None {
@@ -647,6 +1139,11 @@ impl CppType {
         }
       }
     }
+    if self.base.is_std_array() && self.indirection == CppTypeIndirection::None {
+      result.indirection = CppTypeIndirection::Ptr;
+      conversion = CppIndirectionChange::ValueToPointer;
+      result.is_const = role != CppTypeRole::ReturnType;
+    }
     Ok(CppFfiType {
          ffi_type: result,
          conversion: conversion,
@@ -675,6 +1172,9 @@ impl CppType {
         }
         CppTypeIndirection::RValueRef => r = format!("{}_rvalue_ref", r),
       }
+      if self.is_volatile {
+        r = format!("volatile_{}", r);
+      }
       if self.is_const {
         r = format!("const_{}", r);
       }
@@ -691,7 +1191,8 @@ impl CppType {
         return false; // converted to uint in FFI
       }
     }
-    self.indirection == CppTypeIndirection::None && self.base.is_class()
+    self.indirection == CppTypeIndirection::None &&
+    (self.base.is_class() || self.base.is_std_array())
   }
 
   /// Attempts to replace template types at `nested_level1`
@@ -752,6 +1253,7 @@ impl CppType {
     Ok(CppType {
          is_const: self.is_const,
          is_const2: self.is_const2,
+         is_volatile: self.is_volatile,
          indirection: self.indirection.clone(),
          base: match self.base {
            CppTypeBase::Class(ref data) => {
@@ -2,7 +2,7 @@
 //! of the output Rust crate.
 
 use rust_code_generator::rust_type_to_code;
-use rust_type::RustType;
+use rust_type::{RustName, RustType};
 use rust_info::{RustMethodSelfArgKind, RustMethodArgumentsVariant, RustTypeDeclaration,
                 RustTypeDeclarationKind, RustMethodScope, RustEnumValue, RustMethod,
                 RustMethodArguments, RustMethodDocItem, RustTypeWrapperKind,
@@ -11,6 +11,12 @@ use cpp_type::{CppType, CppTypeBase, CppTypeClassBase, CppTypeIndirection};
 use common::string_utils::JoinWithSeparator;
 use common::log;
 use common::errors::{Result, unexpected};
+use std::collections::HashMap;
+
+/// Rust names of generated types, indexed by URLs of their C++ documentation pages
+/// (without the fragment part). Used to convert links found in C++ documentation
+/// to intra-doc links.
+pub type DocLinks = HashMap<String, RustName>;
 
 /// Generates pseudo-code illustrating argument types for one variant of
 /// a Rust method with emulated overloading.
@@ -47,13 +53,128 @@ pub fn wrap_inline_cpp_code(code: &str) -> String {
   format!("<span style='color: green;'>```{}```</span>", code)
 }
 
-pub fn wrap_cpp_doc_block(html: &str) -> String {
-  format!("<div style='border: 1px solid #5CFF95; \
-                       background: #D6FFE4; padding: 16px;'>{}</div>",
-          html)
+/// Replaces HTML entities in `text` with corresponding characters.
+fn decode_html_entities(text: &str) -> String {
+  text
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&#39;", "'")
+    .replace("&nbsp;", " ")
+    .replace("&amp;", "&")
 }
 
-pub fn type_doc(type1: &RustTypeDeclaration) -> String {
+/// Returns value of attribute `name` of an HTML tag.
+/// `tag` is the content of the tag without angle brackets.
+fn html_tag_attribute(tag: &str, name: &str) -> Option<String> {
+  for quote in &["\"", "'"] {
+    let prefix = format!("{}={}", name, quote);
+    if let Some(index) = tag.find(&prefix) {
+      let value = &tag[index + prefix.len()..];
+      if let Some(end) = value.find(quote) {
+        return Some(decode_html_entities(&value[..end]));
+      }
+    }
+  }
+  None
+}
+
+/// Converts HTML content of C++ documentation to Markdown.
+/// Links to URLs present in `cross_references` are converted to
+/// intra-doc links if the target page belongs to a type in `links`.
+/// Other links are kept as external links.
+pub fn html_to_markdown(html: &str,
+                        cross_references: &[String],
+                        links: &DocLinks,
+                        crate_name: &str)
+                        -> String {
+  let mut result = String::new();
+  let mut in_pre = false;
+  let mut in_code = false;
+  // targets of currently open <a> tags (None for anchors without a link)
+  let mut open_links: Vec<Option<String>> = Vec::new();
+  let mut rest = html;
+  while let Some(start) = rest.find('<') {
+    let text = &rest[..start];
+    if in_pre || in_code {
+      result.push_str(&decode_html_entities(text));
+    } else {
+      result.push_str(text);
+    }
+    let end = match rest[start..].find('>') {
+      Some(index) => start + index,
+      None => {
+        rest = &rest[start..];
+        break;
+      }
+    };
+    let tag = &rest[start + 1..end];
+    rest = &rest[end + 1..];
+    let is_closing = tag.starts_with('/');
+    let tag_name = tag
+      .trim_left_matches('/')
+      .split(|c: char| c.is_whitespace() || c == '/')
+      .next()
+      .unwrap_or("")
+      .to_lowercase();
+    match tag_name.as_str() {
+      "pre" => {
+        in_pre = !is_closing;
+        result.push_str(if is_closing { "\n```\n\n" } else { "\n\n```cpp\n" });
+      }
+      "code" | "tt" if !in_pre => {
+        in_code = !is_closing;
+        result.push('`');
+      }
+      "br" => result.push('\n'),
+      "p" | "div" | "table" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+        result.push_str("\n\n")
+      }
+      "tr" if is_closing => result.push('\n'),
+      "li" if !is_closing => result.push_str("\n- "),
+      "a" if !in_pre && !in_code => {
+        if is_closing {
+          if let Some(Some(target)) = open_links.pop() {
+            result.push_str(&format!("]({})", target));
+          }
+        } else if let Some(url) = html_tag_attribute(tag, "href") {
+          let page_url = url.split('#').next().unwrap().to_string();
+          let target = match links.get(&page_url) {
+            Some(rust_name) if cross_references.iter().any(|x| x == &url) => {
+              rust_name.full_name(Some(crate_name))
+            }
+            _ => url,
+          };
+          result.push('[');
+          open_links.push(Some(target));
+        } else {
+          open_links.push(None);
+        }
+      }
+      _ => {}
+    }
+  }
+  result.push_str(rest);
+  // remove trailing spaces and excessive empty lines
+  let mut markdown = String::new();
+  let mut empty_lines = 0;
+  for line in result.trim().split('\n') {
+    let line = line.trim_right();
+    if line.is_empty() {
+      empty_lines += 1;
+      if empty_lines > 1 {
+        continue;
+      }
+    } else {
+      empty_lines = 0;
+    }
+    markdown.push_str(line);
+    markdown.push('\n');
+  }
+  markdown.trim_right().to_string()
+}
+
+pub fn type_doc(type1: &RustTypeDeclaration, links: &DocLinks, crate_name: &str) -> String {
   let auto_doc = match type1.kind {
     RustTypeDeclarationKind::CppTypeWrapper {
       ref cpp_type_name,
@@ -69,13 +190,17 @@ pub fn type_doc(type1: &RustTypeDeclaration) -> String {
           indirection: CppTypeIndirection::None,
           is_const: false,
           is_const2: false,
+          is_volatile: false,
         }
         .to_cpp_pseudo_code();
       let mut doc = format!("C++ type: {}", wrap_inline_cpp_code(&cpp_type_code));
       if let Some(ref cpp_doc) = *cpp_doc {
-        doc += &format!("\n\n<a href=\"{}\">C++ documentation:</a> {}",
+        doc += &format!("\n\n[C++ documentation]({}):\n\n{}",
                         cpp_doc.url,
-                        wrap_cpp_doc_block(&cpp_doc.html));
+                        html_to_markdown(&cpp_doc.html,
+                                         &cpp_doc.cross_references,
+                                         links,
+                                         crate_name));
       }
       doc
     }
@@ -164,7 +289,7 @@ pub fn enum_value_doc(value: &RustEnumValue) -> String {
   }
 }
 
-pub fn method_doc(method: &RustMethod) -> String {
+pub fn method_doc(method: &RustMethod, links: &DocLinks, crate_name: &str) -> String {
 
   let cpp_method_name = match method.arguments {
     RustMethodArguments::SingleVariant(ref v) => v.cpp_method.cpp_method.full_name(),
@@ -237,6 +362,28 @@ pub fn method_doc(method: &RustMethod) -> String {
     }
     doc.push(format!("C++ method: {}", wrap_inline_cpp_code(&doc_item.cpp_fn)));
     doc.push("\n\n".to_string());
+    if !doc_item.omitted_argument_defaults.is_empty() {
+      doc.push(format!("Default values of omitted arguments: {}\n\n",
+                       doc_item
+                         .omitted_argument_defaults
+                         .iter()
+                         .map(|x| format!("```{}```", x))
+                         .join(", ")));
+    }
+    if doc_item.has_aliasing_references {
+      doc.push("Safety: this method receives multiple mutable references \
+                of the same type. The caller must ensure that they don't refer \
+                to the same object.\n\n"
+                   .to_string());
+    }
+    if doc_item.has_closure_arguments {
+      doc.push("Closures passed to this method are moved to the C++ side, which may \
+                keep calling them after the method returns, as long as it holds a copy \
+                of the `std::function`. For this reason, the closures must outlive the call \
+                and are required to be `'static`. A closure is dropped when the last copy \
+                of its `std::function` is destroyed.\n\n"
+                   .to_string());
+    }
     // TODO: use inheritance_chain to generate documentation
     //    if let Some(ref inherited_from) = doc_item.inherited_from {
     //      doc.push(format!("Inherited from {}. Original C++ method: {}\n\n",
@@ -247,14 +394,19 @@ pub fn method_doc(method: &RustMethod) -> String {
     //    }
     if let Some(result) = doc_item.doc {
       let prefix = if let Some(ref declaration) = result.mismatched_declaration {
-        format!("Warning: no exact match found in C++ documentation.\
-                         Below is the <a href=\"{}\">C++ documentation</a> for <code>{}</code>:",
+        format!("Note: no exact match found in C++ documentation. \
+                 Below is the [C++ documentation]({}) for the closest match, `{}`:",
                 result.url,
                 declaration)
       } else {
-        format!("<a href=\"{}\">C++ documentation:</a>", result.url)
+        format!("[C++ documentation]({}):", result.url)
       };
-      doc.push(format!("{} {}", prefix, wrap_cpp_doc_block(&result.html)));
+      doc.push(format!("{}\n\n{}",
+                       prefix,
+                       html_to_markdown(&result.html,
+                                        &result.cross_references,
+                                        links,
+                                        crate_name)));
     }
   }
   let variant_docs = doc.join("");
@@ -286,6 +438,12 @@ pub fn slots_raw_module_doc() -> String {
       .into()
 }
 
+pub fn event_filter_module_doc() -> String {
+  "Installing Rust closures as event filters of Qt objects.\n\n\
+  `EventFilter` allows to intercept events sent to a `QObject` using a Rust closure. \
+  `RawEventFilter` exposes low level C++ API and is used to implement `EventFilter`."
+      .into()
+}
 
 pub fn overloading_module_doc() -> String {
   "Types for emulating overloading for overloaded functions in this module".into()
@@ -339,7 +497,80 @@ pub fn add_special_type_docs(data: &mut RustTypeDeclaration) -> Result<()> {
            ref mut methods,
            ..
          } = data.kind {
-    if let RustTypeWrapperKind::Struct { ref slot_wrapper, .. } = *kind {
+    if let RustTypeWrapperKind::Struct {
+             ref slot_wrapper,
+             ref event_filter_wrapper,
+             ref functor_wrapper,
+             ..
+           } = *kind {
+      if let Some(ref wrapper) = *functor_wrapper {
+        type_doc = Some(format!("Function object with signature `{cpp_signature}` \
+          that forwards its calls to a Rust extern function.\n\n\
+          Use `{public_type_name}` to pass a Rust closure instead.\n\n\
+          Create an object using `new()` and bind your function and payload using `set()`. \
+          If `set()` was not called, calls of the function object have no effect \
+          and return the default value.",
+                                public_type_name = wrapper.public_type_name,
+                                cpp_signature = format!("{}({})",
+                                                        wrapper
+                                                          .return_type
+                                                          .cpp_type
+                                                          .to_cpp_pseudo_code(),
+                                                        wrapper
+                                                          .arguments
+                                                          .iter()
+                                                          .map(|t| t.cpp_type.to_cpp_pseudo_code())
+                                                          .join(", "))));
+        for method in methods.iter_mut() {
+          if method.name.parts.len() != 1 {
+            return Err(unexpected("method name should have one part here").into());
+          }
+          match method.name.parts[0].as_str() {
+            "new" => {
+              method.common_doc = Some("Constructs a new object.".into());
+            }
+            "set" => {
+              method.common_doc = Some("Sets `func` as the callback function \
+                and `data` as the payload. For each call of the function object, \
+                `func(data, args...)` will be called, and its return value \
+                is returned to the caller."
+                                           .into());
+            }
+            _ => {
+              return Err(unexpected("unknown method for function object wrapper").into());
+            }
+          }
+        }
+      }
+      if let Some(ref wrapper) = *event_filter_wrapper {
+        type_doc = Some(format!("Forwards events intercepted by a Qt event filter \
+          to a Rust extern function.\n\n\
+          Use `{public_type_name}` to install a Rust closure as an event filter instead.\n\n\
+          Create an object using `new()`, bind your function and payload using `set()` \
+          and pass the object to `QObject::installEventFilter`. \
+          If `set()` was not called, the filter has no effect.",
+                                public_type_name = wrapper.public_type_name));
+        for method in methods.iter_mut() {
+          if method.name.parts.len() != 1 {
+            return Err(unexpected("method name should have one part here").into());
+          }
+          match method.name.parts[0].as_str() {
+            "new" => {
+              method.common_doc = Some("Constructs a new object.".into());
+            }
+            "set" => {
+              method.common_doc = Some("Sets `func` as the callback function \
+                and `data` as the payload. For each intercepted event, \
+                `func(data, watched, event)` will be called, and its return value \
+                indicates whether the event should be filtered out."
+                                           .into());
+            }
+            _ => {
+              return Err(unexpected("unknown method for event filter wrapper").into());
+            }
+          }
+        }
+      }
       if let Some(ref slot_wrapper) = *slot_wrapper {
         type_doc = Some(format!("Allows to bind Qt signals with arguments `({cpp_args})` to a \
           Rust extern function.\n\n\
@@ -11,7 +11,8 @@ use common::errors::{Result, ChainErr};
 use common::string_utils::CaseOperations;
 use common::file_utils::{PathBufWithAdded, move_files, create_dir_all, save_json, load_bincode,
                          save_bincode, canonicalize, remove_dir_all, remove_dir, read_dir,
-                         create_file, path_to_str};
+                         create_file, path_to_str, save_json_pretty, file_to_string,
+                         remove_file};
 use common::BuildScriptData;
 use common::log;
 use rust_code_generator;
@@ -19,7 +20,7 @@ use rust_generator;
 use rust_info::{RustTypeWrapperKind, RustExportInfo, DependencyInfo};
 
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 
 /// Returns path to the completion marker file
@@ -41,13 +42,74 @@ pub fn completed_marker_path<P: AsRef<Path>>(cache_dir: P) -> PathBuf {
 /// `write_cache` was set to false in `Config` during a previous run.
 /// The marker should only be used if cache usage is set to `CacheUsage::Full`.
 pub fn is_completed<P: AsRef<Path>>(cache_dir: P) -> bool {
-  completed_marker_path(cache_dir).exists()
+  completed_marker_path(cache_dir.as_ref()).exists() && has_current_cache_format(cache_dir)
+}
+
+/// Version of the format of the data saved to the cache directory.
+/// It must be increased when serialized types (e.g. `ParserCppData`) change.
+/// Note that bincode ignores `#[serde(default)]`, so even adding a field
+/// makes previously saved files unreadable or misread.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Names of files in the cache directory that depend on `CACHE_FORMAT_VERSION`.
+const CACHE_FILE_NAMES: &'static [&'static str] = &["parser_cpp_data.bin",
+                                                     "processed_cpp_data.bin",
+                                                     "rust_export_info.bin",
+                                                     "cpp_code_cache.bin",
+                                                     "cpp_to_rust_completed"];
+
+/// Returns path to the file containing the format version
+/// of the data saved to this `cache_dir`.
+fn cache_format_version_path<P: AsRef<Path>>(cache_dir: P) -> PathBuf {
+  cache_dir.as_ref().with_added("cache_format_version")
+}
+
+/// Returns true if the data in this `cache_dir` was saved
+/// with the current `CACHE_FORMAT_VERSION`.
+fn has_current_cache_format<P: AsRef<Path>>(cache_dir: P) -> bool {
+  let path = cache_format_version_path(cache_dir);
+  if !path.exists() {
+    return false;
+  }
+  match file_to_string(&path) {
+    Ok(version) => version.trim() == CACHE_FORMAT_VERSION.to_string(),
+    Err(err) => {
+      err.discard_expected();
+      false
+    }
+  }
+}
+
+/// Removes files saved to `cache_dir` in a format different from
+/// `CACHE_FORMAT_VERSION`. If `write_cache` is true, saves the current
+/// format version, so that the files saved later can be used by the next run.
+fn discard_outdated_cache(cache_dir: &PathBuf, write_cache: bool) -> Result<()> {
+  if has_current_cache_format(cache_dir) {
+    return Ok(());
+  }
+  for name in CACHE_FILE_NAMES {
+    let path = cache_dir.with_added(name);
+    if path.exists() {
+      log::status(format!("Removing outdated cache file: {}", path.display()));
+      remove_file(&path)?;
+    }
+  }
+  if write_cache {
+    create_file(cache_format_version_path(cache_dir))?
+      .write(CACHE_FORMAT_VERSION.to_string())?;
+  }
+  Ok(())
 }
 
 /// Loads `RustExportInfo` and `CppData` or a dependency previously
 /// processed in the cache directory `path`.
 fn load_dependency(path: &PathBuf) -> Result<DependencyInfo> {
   log::status(format!("Loading files from {}", path.display()));
+  if !has_current_cache_format(path) {
+    return Err(format!("cache format of {} is outdated; process the dependency again",
+                       path.display())
+                   .into());
+  }
   let parser_cpp_data_path = path.with_added("parser_cpp_data.bin");
   if !parser_cpp_data_path.exists() {
     return Err(format!("file not found: {}", parser_cpp_data_path.display()).into());
@@ -151,8 +213,21 @@ fn load_or_create_cpp_data<'a>(config: &Config,
       name_blacklist: Vec::from(config.cpp_parser_blocked_names()),
       clang_arguments: Vec::from(config.cpp_parser_arguments()),
     };
-    let mut parser_cpp_data: ParserCppData = cpp_parser::run(parser_config, &dependencies_cpp_data)
-      .chain_err(|| "C++ parser failed")?;
+    let mut parser_cpp_data: ParserCppData =
+      cpp_parser::run(parser_config.clone(), &dependencies_cpp_data)
+        .chain_err(|| "C++ parser failed")?;
+    for feature in config.cpp_features() {
+      log::status(format!("Running C++ parser for feature: {}", feature.name));
+      let mut feature_parser_config = parser_config.clone();
+      for define in &feature.defines {
+        feature_parser_config
+          .clang_arguments
+          .push(format!("-D{}", define));
+      }
+      let feature_cpp_data = cpp_parser::run(feature_parser_config, &dependencies_cpp_data)
+        .chain_err(|| format!("C++ parser failed for feature: {}", feature.name))?;
+      parser_cpp_data.add_feature_methods(feature_cpp_data, &feature.name);
+    }
     parser_cpp_data
       .detect_signals_and_slots(&dependencies_cpp_data)?;
     // TODO: rename `cpp_data_filters` to `parser_cpp_data_filters`
@@ -214,6 +289,9 @@ fn load_or_create_cpp_data<'a>(config: &Config,
     }
     r
   };
+  full_cpp_data
+    .check_dependency_types()
+    .chain_err(|| "dependency check failed")?;
   Ok(full_cpp_data)
 }
 
@@ -223,15 +301,41 @@ pub fn exec_one(config: Config) -> Result<()> {
 }
 
 
+/// Returns `Err` if `config` shares a crate name, an output directory or
+/// a cache directory with a config processed earlier in the same run.
+/// Crates generated side by side (e.g. for different versions of
+/// the same C++ library) must not overwrite each other's data,
+/// and their distinct crate names make their FFI symbols distinct.
+fn check_side_by_side(config: &Config,
+                      crate_names: &mut HashSet<String>,
+                      dirs: &mut HashSet<PathBuf>)
+                      -> Result<()> {
+  if !crate_names.insert(config.crate_properties().name().clone()) {
+    return Err(format!("Multiple configs generate crate with name {}",
+                       config.crate_properties().name())
+                   .into());
+  }
+  for path in &[config.output_dir_path(), config.cache_dir_path()] {
+    if !dirs.insert((*path).clone()) {
+      return Err(format!("Directory is used by multiple configs: {}", path.display()).into());
+    }
+  }
+  Ok(())
+}
+
 /// Executes the generator for multiple configs.
 pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
   let mut dependency_cache = HashMap::new();
+  let mut crate_names = HashSet::new();
+  let mut dirs = HashSet::new();
   for config in configs {
+    check_side_by_side(&config, &mut crate_names, &mut dirs)?;
     if config.cache_usage().can_skip_all() && is_completed(config.cache_dir_path()) {
       continue;
     }
     log::status(format!("Generating crate: {}", config.crate_properties().name()));
     check_all_paths(&config)?;
+    discard_outdated_cache(config.cache_dir_path(), config.write_cache())?;
     {
       let mut logger = log::default_logger();
       logger.set_default_settings(log::LoggerSettings {
@@ -337,16 +441,43 @@ pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
       create_dir_all(&c_lib_tmp_path)?;
       log::status(format!("Generating C++ wrapper library ({})", cpp_ffi_lib_name));
 
+      let ffi_options = cpp_ffi_generator::CppFfiGeneratorOptions {
+        filters: config.cpp_ffi_generator_filters(),
+        failure_dump_dir: config.failure_dump_dir(),
+        symbol_version: config.symbol_version(),
+        metatypes: config.metatypes(),
+        computed_type_constants: config.computed_type_constants(),
+        template_method_instantiations: config.template_method_instantiations(),
+        slicing_conversions: config.slicing_conversions(),
+        functor_template_instantiations: config.functor_template_instantiations(),
+        exposed_protected_methods: config.exposed_protected_methods(),
+        batch_operations: config.batch_operations(),
+        variadic_slice_functions: config.variadic_slice_functions(),
+        errno_methods: config.errno_methods(),
+        method_blocklist: config.method_blocklist(),
+        allocation_benchmarks: config.allocation_benchmarks(),
+        volatile_policy: config.volatile_policy().clone(),
+      };
       let cpp_ffi_headers = cpp_ffi_generator::run(&cpp_data,
                                                    cpp_ffi_lib_name.clone(),
-                                                   config.cpp_ffi_generator_filters())
+                                                   ffi_options)
           .chain_err(|| "FFI generator failed")?;
 
       log::status(format!("Generating C++ wrapper code"));
-      let code_gen = CppCodeGenerator::new(cpp_ffi_lib_name.clone(), c_lib_tmp_path.clone());
+      let mut code_gen = CppCodeGenerator::new(cpp_ffi_lib_name.clone(),
+                                               c_lib_tmp_path.clone(),
+                                               config.ffi_tu_strategy().clone());
+      code_gen.set_fallible_constructors(config.fallible_constructors().to_vec());
+      if config.write_cache() {
+        code_gen.set_code_cache(config.cache_dir_path().with_added("cpp_code_cache.bin"),
+                                config.force_cpp_code_regeneration());
+      }
       code_gen
         .generate_template_files(config.include_directives())?;
-      code_gen.generate_files(&cpp_ffi_headers)?;
+      let generated_headers = code_gen.generate_files(&cpp_ffi_headers)?;
+      log::status(format!("{} of {} headers regenerated",
+                          generated_headers.len(),
+                          cpp_ffi_headers.len()));
 
       let crate_new_path = if output_path_existed {
         let path = config
@@ -368,6 +499,13 @@ pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
         generator_dependencies: &dependencies,
         write_dependencies_local_paths: config.write_dependencies_local_paths(),
         cpp_lib_version: config.cpp_lib_version().map(|s| s.into()),
+        serde_enums: config.serde_enums().clone(),
+        serde_enum_representation: config.serde_enum_representation().clone(),
+        enum_value_migrations: config.enum_value_migrations().clone(),
+        enums_non_exhaustive: config.enums_non_exhaustive(),
+        track_caller: config.track_caller(),
+        tracing_spans: config.tracing_spans(),
+        cpp_features: config.cpp_features().iter().map(|f| f.name.clone()).collect(),
       };
       log::status("Preparing Rust functions");
       let rust_data = rust_generator::RustGeneratorInputData {
@@ -381,6 +519,19 @@ pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
           // TODO: more universal prefix removal (#25)
           remove_qt_prefix: remove_qt_prefix,
           filtered_namespaces: config.cpp_filtered_namespaces().clone(),
+          namespace_mappings: config.namespace_mappings().clone(),
+          owned_void_ptr_returns: config.owned_void_ptr_returns().clone(),
+          owned_ptr_returns: config.owned_ptr_returns().to_vec(),
+          fallible_constructors: config.fallible_constructors().to_vec(),
+          consuming_methods: config.consuming_methods().to_vec(),
+          string_buffer_outs: config.string_buffer_outs().clone(),
+          array_out_params: config.array_out_params().clone(),
+          return_lifetimes: config.return_lifetimes().clone(),
+          ownership_transfer_args: config.ownership_transfer_args().clone(),
+          type_mappings: config.type_mappings().clone(),
+          virtual_clones: config.virtual_clones().clone(),
+          flaggable_scoped_enums: config.flaggable_scoped_enums().to_vec(),
+          safety_policy: config.safety_policy().clone(),
         }
         .run()
         .chain_err(|| "Rust data generator failed")?;
@@ -447,7 +598,12 @@ pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
                    cpp_build_config: config.cpp_build_config().clone(),
                    cpp_wrapper_lib_name: cpp_ffi_lib_name,
                    cpp_lib_version: config.cpp_lib_version().map(|s| s.to_string()),
+                   cpp_features: config.cpp_features().to_vec(),
                  })?;
+      save_json_pretty(config
+                         .output_dir_path()
+                         .with_added("rust_export_info.json"),
+                       &rust_export_info.to_json_export())?;
       if config.write_cache() {
         create_file(completed_marker_path(config.cache_dir_path()))?;
       }
@@ -465,3 +621,73 @@ pub fn exec<T: Iterator<Item = Config>>(configs: T) -> Result<()> {
   log::status("cpp_to_rust generator finished");
   Ok(())
 }
+
+// ---------------------------------
+#[test]
+fn check_side_by_side_test() {
+  use config::CrateProperties;
+
+  let config = |name: &str, output_dir: &str, cache_dir: &str| {
+    Config::new(output_dir, cache_dir, CrateProperties::new(name, "0.0.0"))
+  };
+  let mut crate_names = HashSet::new();
+  let mut dirs = HashSet::new();
+  assert!(check_side_by_side(&config("lib_v1", "/out/v1", "/cache/v1"),
+                             &mut crate_names,
+                             &mut dirs)
+              .is_ok());
+  assert!(check_side_by_side(&config("lib_v2", "/out/v2", "/cache/v2"),
+                             &mut crate_names,
+                             &mut dirs)
+              .is_ok());
+
+  let err = check_side_by_side(&config("lib_v1", "/out/v3", "/cache/v3"),
+                               &mut crate_names,
+                               &mut dirs)
+      .unwrap_err();
+  assert_eq!(err.to_string(), "Multiple configs generate crate with name lib_v1");
+
+  let err = check_side_by_side(&config("lib_v4", "/out/v1", "/cache/v4"),
+                               &mut crate_names,
+                               &mut dirs)
+      .unwrap_err();
+  assert_eq!(err.to_string(), "Directory is used by multiple configs: /out/v1");
+
+  let err = check_side_by_side(&config("lib_v5", "/out/v5", "/cache/v2"),
+                               &mut crate_names,
+                               &mut dirs)
+      .unwrap_err();
+  assert_eq!(err.to_string(), "Directory is used by multiple configs: /cache/v2");
+}
+
+#[test]
+fn cache_format_version() {
+  let dir = ::tempdir::TempDir::new("test_cache_format_version").unwrap();
+  let cache_dir = dir.path().to_path_buf();
+  let cache_file = cache_dir.with_added("parser_cpp_data.bin");
+  create_file(&cache_file).unwrap();
+  create_file(completed_marker_path(&cache_dir)).unwrap();
+  // files saved before the format version was introduced
+  assert!(!has_current_cache_format(&cache_dir));
+  assert!(!is_completed(&cache_dir));
+
+  discard_outdated_cache(&cache_dir, true).unwrap();
+  assert!(!cache_file.exists());
+  assert!(has_current_cache_format(&cache_dir));
+
+  create_file(&cache_file).unwrap();
+  create_file(completed_marker_path(&cache_dir)).unwrap();
+  assert!(is_completed(&cache_dir));
+  discard_outdated_cache(&cache_dir, true).unwrap();
+  assert!(cache_file.exists());
+
+  create_file(cache_format_version_path(&cache_dir))
+    .unwrap()
+    .write("0")
+    .unwrap();
+  assert!(!is_completed(&cache_dir));
+  assert!(load_dependency(&cache_dir).is_err());
+  discard_outdated_cache(&cache_dir, false).unwrap();
+  assert!(!cache_file.exists());
+  assert!(!has_current_cache_format(&cache_dir));
+}
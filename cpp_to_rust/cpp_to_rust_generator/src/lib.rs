@@ -41,6 +41,7 @@ mod doc_formatter;
 mod launcher;
 mod rust_generator;
 mod rust_code_generator;
+mod rust_api_lints;
 mod rust_info;
 mod rust_type;
 mod cpp_parser;
@@ -0,0 +1,164 @@
+//! Detection of generated API items that carry a known risk.
+//!
+//! The generated crate's API mirrors the C++ library, so some of its items
+//! can't be made completely safe. The report produced by this module lists
+//! such items for review. It doesn't affect the generated code.
+
+use cpp_ffi_data::CppFfiMethodKind;
+use rust_code_generator::rust_type_to_code;
+use rust_info::{RustModule, RustMethod, RustMethodArgument, RustMethodArguments,
+                RustMethodArgumentsVariant, RustMethodScope, RustTypeDeclarationKind, TraitImpl};
+use rust_type::{RustName, RustType};
+use std::collections::BTreeMap;
+
+/// Category of a risk associated with a generated API item.
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+#[derive(Serialize, Deserialize)]
+pub enum RustApiLint {
+  /// The item converts a reference to a base class into a reference
+  /// to a derived class using `static_cast` without a runtime check.
+  UnsafeStaticCast,
+  /// The item returns a raw pointer. The pointee's lifetime and ownership
+  /// are not tracked by the Rust compiler.
+  RawPointerReturn,
+  /// The item returns a mutable reference while none of its arguments
+  /// (including `self`) is borrowed mutably, so multiple aliasing
+  /// mutable references can be obtained.
+  AliasingMutableReference,
+}
+
+/// Returns lints applicable to a method variant.
+fn variant_lints(shared_arguments: &[RustMethodArgument],
+                 variant: &RustMethodArgumentsVariant)
+                 -> Vec<RustApiLint> {
+  let mut result = Vec::new();
+  if let CppFfiMethodKind::Cast(ref cast) = variant.cpp_method.kind {
+    if cast.is_unsafe_static_cast() {
+      result.push(RustApiLint::UnsafeStaticCast);
+    }
+  }
+  let return_type = &variant.return_type.rust_api_type;
+  if return_type.is_unsafe_argument() {
+    result.push(RustApiLint::RawPointerReturn);
+  }
+  let is_mut_ref = |t: &RustType| t.is_ref() && t.last_is_const().ok() == Some(false);
+  if is_mut_ref(return_type) &&
+     !shared_arguments
+        .iter()
+        .chain(variant.arguments.iter())
+        .any(|arg| is_mut_ref(&arg.argument_type.rust_api_type)) {
+    result.push(RustApiLint::AliasingMutableReference);
+  }
+  result
+}
+
+/// Helper for collecting lints of all items of the crate.
+struct LintCollector<'a> {
+  crate_name: &'a str,
+  result: BTreeMap<String, Vec<RustApiLint>>,
+}
+
+impl<'a> LintCollector<'a> {
+  fn add(&mut self, key: String, lints: Vec<RustApiLint>) {
+    if lints.is_empty() {
+      return;
+    }
+    let item = self.result.entry(key).or_insert_with(Vec::new);
+    for lint in lints {
+      if !item.contains(&lint) {
+        item.push(lint);
+      }
+    }
+    item.sort();
+  }
+
+  fn method_key(&self, scope: &RustMethodScope, name: &RustName) -> String {
+    match *scope {
+      RustMethodScope::Impl { ref target_type } => {
+        format!("{}::{}",
+                rust_type_to_code(target_type, self.crate_name),
+                name.last_name().map(|x| x.as_str()).unwrap_or("?"))
+      }
+      _ => name.full_name(Some(self.crate_name)),
+    }
+  }
+
+  fn process_method(&mut self, method: &RustMethod, key: String) {
+    // variants of overloaded methods are processed
+    // along with their parameter traits
+    if let RustMethodArguments::SingleVariant(ref variant) = method.arguments {
+      let lints = variant_lints(&[], variant);
+      self.add(key, lints);
+    }
+  }
+
+  fn process_trait_impl(&mut self, trait_impl: &TraitImpl) {
+    for method in &trait_impl.methods {
+      let key = format!("<{} as {}>::{}",
+                        rust_type_to_code(&trait_impl.target_type, self.crate_name),
+                        rust_type_to_code(&trait_impl.trait_type, self.crate_name),
+                        method.name.last_name().map(|x| x.as_str()).unwrap_or("?"));
+      self.process_method(method, key);
+    }
+  }
+
+  fn process_module(&mut self, module: &RustModule) {
+    for type1 in &module.types {
+      match type1.kind {
+        RustTypeDeclarationKind::CppTypeWrapper {
+          ref methods,
+          ref trait_impls,
+          ..
+        } => {
+          for method in methods {
+            let key = self.method_key(&method.scope, &method.name);
+            self.process_method(method, key);
+          }
+          for trait_impl in trait_impls {
+            self.process_trait_impl(trait_impl);
+          }
+        }
+        RustTypeDeclarationKind::MethodParametersTrait {
+          ref shared_arguments,
+          ref impls,
+          ref method_scope,
+          ref method_name,
+          ..
+        } => {
+          let key = self.method_key(method_scope, method_name);
+          for variant in impls {
+            let lints = variant_lints(shared_arguments, variant);
+            self.add(key.clone(), lints);
+          }
+        }
+      }
+    }
+    for method in &module.functions {
+      let key = self.method_key(&method.scope, &method.name);
+      self.process_method(method, key);
+    }
+    for trait_impl in &module.trait_impls {
+      self.process_trait_impl(trait_impl);
+    }
+    for submodule in &module.submodules {
+      self.process_module(submodule);
+    }
+  }
+}
+
+/// Returns all API items of `modules` that carry a known risk
+/// and categories of their risks. Keys of the result are paths of
+/// the methods relative to the crate root (e.g. `::module1::Type1::method1`).
+/// Methods of trait implementations are represented as `<Type as Trait>::method`.
+pub fn collect_api_lints(modules: &[RustModule],
+                         crate_name: &str)
+                         -> BTreeMap<String, Vec<RustApiLint>> {
+  let mut collector = LintCollector {
+    crate_name: crate_name,
+    result: BTreeMap::new(),
+  };
+  for module in modules {
+    collector.process_module(module);
+  }
+  collector.result
+}
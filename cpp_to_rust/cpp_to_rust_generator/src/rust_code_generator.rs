@@ -3,14 +3,15 @@
 use common::errors::{Result, ChainErr, unexpected};
 use common::file_utils::{PathBufWithAdded, copy_recursively, file_to_string, copy_file,
                          create_file, create_dir_all, read_dir, os_str_to_str, save_toml,
-                         path_to_str, repo_crate_local_path};
+                         path_to_str, repo_crate_local_path, save_json};
 use common::log;
 use rust_generator::RustGeneratorOutput;
 use rust_info::{RustTypeDeclarationKind, RustTypeWrapperKind, RustModule, RustMethod,
                 RustMethodArguments, RustMethodArgumentsVariant, RustMethodScope,
                 RustMethodArgument, TraitImpl, TraitImplExtra, RustQtReceiverType, DependencyInfo,
-                RustFFIFunction};
+                RustFFIFunction, RustStdVariantEnum, RustStdMapWrapper, RustValidityCheck};
 use rust_type::{RustName, RustType, RustTypeIndirection, RustToCTypeConversion, CompleteType};
+use rust_api_lints::collect_api_lints;
 use common::string_utils::{JoinWithSeparator, CaseOperations};
 use common::utils::MapIfOk;
 use doc_formatter;
@@ -20,7 +21,9 @@ use common::toml;
 use rustfmt;
 use versions;
 
-use config::CrateProperties;
+use config::{CrateProperties, SerdeEnumRepresentation, EnumValueMigration};
+use rust_info::{RustEnumValue, RustAllocationBenchmark};
+use std::collections::{HashMap, HashSet};
 
 /// Data required for Rust code generation.
 pub struct RustCodeGeneratorConfig<'a> {
@@ -39,26 +42,134 @@ pub struct RustCodeGeneratorConfig<'a> {
   pub generator_dependencies: &'a [DependencyInfo],
   /// As in `Config`.
   pub write_dependencies_local_paths: bool,
+  /// C++ names of enums that should implement `serde` traits,
+  /// with optional fallback variant names, as in `Config`.
+  pub serde_enums: HashMap<String, Option<String>>,
+  /// As in `Config`.
+  pub serde_enum_representation: SerdeEnumRepresentation,
+  /// C++ names of enums and their value migrations, as in `Config`.
+  pub enum_value_migrations: HashMap<String, Vec<EnumValueMigration>>,
+  /// As in `Config`.
+  pub enums_non_exhaustive: bool,
+  /// As in `Config`.
+  pub track_caller: bool,
+  /// As in `Config`.
+  pub tracing_spans: bool,
+  /// Names of optional C++ features, as in `Config`.
+  pub cpp_features: Vec<String>,
 }
 
 /// Generates documentation comments containing
 /// markdown code `doc`.
 fn format_doc(doc: &str) -> String {
-  fn format_line(x: &str) -> String {
+  if doc.is_empty() {
+    return String::new();
+  }
+  let mut in_fenced_block = false;
+  let mut result = String::new();
+  for x in doc.split('\n') {
+    if x.trim_left().starts_with("```") {
+      in_fenced_block = !in_fenced_block;
+    }
     let mut line = format!("/// {}\n", x);
-    if line.starts_with("///     ") {
+    if !in_fenced_block && line.starts_with("///     ") {
       // block doc tests
       line = line.replace("///     ", "/// &#32;   ");
     }
-    line
+    result.push_str(&line);
   }
-  if doc.is_empty() {
-    String::new()
+  result
+}
+
+/// Returns variants of an enum that correspond to values declared in C++,
+/// ordered as in `values`. Dummy variants are skipped, and only the first
+/// variant is kept for each value. Enum values are not assumed to be contiguous.
+fn declared_enum_values(values: &[RustEnumValue]) -> Vec<&RustEnumValue> {
+  let mut used_values = HashSet::new();
+  values
+    .iter()
+    .filter(|v| !v.is_dummy && used_values.insert(v.value))
+    .collect()
+}
+
+/// Returns the smallest of `i32` and `i64` that can hold all `values`.
+/// It's used as the integer type of the conversions of the enum
+/// and as its representation if it doesn't fit in a C `int`.
+fn enum_int_type(values: &[RustEnumValue]) -> &'static str {
+  if values
+       .iter()
+       .all(|v| v.value >= i32::min_value() as i64 && v.value <= i32::max_value() as i64) {
+    "i32"
   } else {
-    doc.split('\n').map(format_line).join("")
+    "i64"
   }
 }
 
+/// Returns true if the generated code calling the FFI function of `variant`
+/// may panic (e.g. if the function returns a null pointer).
+fn variant_may_panic(variant: &RustMethodArgumentsVariant) -> bool {
+  let return_may_panic = match variant.return_type.rust_api_to_c_conversion {
+    RustToCTypeConversion::RefToPtr |
+    RustToCTypeConversion::StringToBuffer { .. } => true,
+    _ => false,
+  };
+  return_may_panic ||
+  variant
+    .arguments
+    .iter()
+    .any(|arg| arg.argument_type.rust_api_to_c_conversion == RustToCTypeConversion::CharToUShort)
+}
+
+/// Returns the `cfg` attribute for the crate feature `variant` depends on,
+/// or an empty string if the variant is always available.
+fn variant_cfg_attribute(variant: &RustMethodArgumentsVariant) -> String {
+  match variant.cpp_method.cpp_method.feature {
+    Some(ref feature) => format!("#[cfg(feature = \"{}\")]\n", feature),
+    None => String::new(),
+  }
+}
+
+/// Returns name of an item defined in the `flags` module of `qt_core`.
+fn flags_module_item(name: &str) -> Result<RustName> {
+  RustName::new(vec!["qt_core".to_string(), "flags".to_string(), name.to_string()])
+}
+
+/// Adds names of non-std traits implemented in `module` and its submodules
+/// to `result`, skipping duplicates.
+fn collect_prelude_traits(module: &RustModule, result: &mut Vec<RustName>) -> Result<()> {
+  fn add_trait_impls(trait_impls: &[TraitImpl], result: &mut Vec<RustName>) {
+    for trait1 in trait_impls {
+      if let RustType::Common { ref base, .. } = trait1.trait_type {
+        if base.parts.len() > 1 && base.parts[0] != "std" && !result.contains(base) {
+          result.push(base.clone());
+        }
+      }
+    }
+  }
+  add_trait_impls(&module.trait_impls, result);
+  for type1 in &module.types {
+    if let RustTypeDeclarationKind::CppTypeWrapper {
+             ref kind,
+             ref trait_impls,
+             ..
+           } = type1.kind {
+      add_trait_impls(trait_impls, result);
+      if let RustTypeWrapperKind::Enum { is_flaggable: true, .. } = *kind {
+        for name in &["FlaggableEnum", "EnumOrFlags"] {
+          let trait_name = flags_module_item(name)?;
+          if !result.contains(&trait_name) {
+            result.push(trait_name);
+          }
+        }
+      }
+    }
+  }
+  for submodule in &module.submodules {
+    collect_prelude_traits(submodule, result)?;
+  }
+  Ok(())
+}
+
 /// Generates Rust code representing type `rust_type` inside crate `crate_name`.
 /// Same as `RustCodeGenerator::rust_type_to_code`, but accessible by other modules.
 pub fn rust_type_to_code(rust_type: &RustType, crate_name: &str) -> String {
@@ -135,9 +246,62 @@ pub fn rust_type_to_code(rust_type: &RustType, crate_name: &str) -> String {
                 return_type => format!(" -> {}", rust_type_to_code(return_type, crate_name)),
               })
     }
+    RustType::Array {
+      ref element,
+      ref size,
+      ref is_const,
+      ref indirection,
+    } => {
+      let array_s = match *size {
+        Some(size) => format!("[{}; {}]", rust_type_to_code(element, crate_name), size),
+        None => format!("[{}]", rust_type_to_code(element, crate_name)),
+      };
+      let mut_text = if *is_const { "" } else { "mut " };
+      match *indirection {
+        RustTypeIndirection::None => array_s,
+        RustTypeIndirection::Ref { ref lifetime } => {
+          let lifetime_text = match *lifetime {
+            Some(ref lifetime) => format!("'{} ", lifetime),
+            None => String::new(),
+          };
+          format!("&{}{}{}", lifetime_text, mut_text, array_s)
+        }
+        RustTypeIndirection::Ptr => {
+          format!("*{} {}", if *is_const { "const" } else { "mut" }, array_s)
+        }
+        RustTypeIndirection::PtrPtr |
+        RustTypeIndirection::PtrRef { .. } => {
+          panic!("unsupported indirection of array type: {:?}", indirection)
+        }
+      }
+    }
+    RustType::Closure {
+      ref return_type,
+      ref arguments,
+    } => {
+      format!("impl {} + Send + 'static",
+              closure_trait_to_code(return_type, arguments, crate_name))
+    }
   }
 }
 
+/// Generates Rust code of the `FnMut` trait implemented by closures
+/// with the specified signature (e.g. `FnMut(i32) -> bool`).
+fn closure_trait_to_code(return_type: &RustType,
+                         arguments: &[RustType],
+                         crate_name: &str)
+                         -> String {
+  format!("FnMut({}){}",
+          arguments
+            .iter()
+            .map(|arg| rust_type_to_code(arg, crate_name))
+            .join(", "),
+          match *return_type {
+            RustType::EmptyTuple => String::new(),
+            ref return_type => format!(" -> {}", rust_type_to_code(return_type, crate_name)),
+          })
+}
+
 /// Executes the code generator with `config` on `data`.
 pub fn run(config: RustCodeGeneratorConfig, data: &RustGeneratorOutput) -> Result<()> {
   let template_rustfmt_config_path = config
@@ -164,15 +328,19 @@ pub fn run(config: RustCodeGeneratorConfig, data: &RustGeneratorOutput) -> Resul
   let generator = RustCodeGenerator {
     config: config,
     rustfmt_config: rustfmt_config,
+    doc_links: &data.doc_links,
   };
-  generator.generate_template()?;
+  generator.generate_template(&data.allocation_benchmarks)?;
   for module in &data.modules {
     generator.generate_module_file(module)?;
   }
   //let mut module_names: Vec<_> = data.modules.iter().map(|x| &x.name).collect();
   //module_names.sort();
   generator.generate_ffi_file(&data.ffi_functions)?;
+  generator.generate_ffi_symbols_file(&data.ffi_functions)?;
+  generator.generate_api_lints_file(&data.modules)?;
   generator.generate_lib_file(&data.modules)?;
+  generator.generate_allocation_benchmark_file(&data.allocation_benchmarks)?;
   Ok(())
 }
 
@@ -182,6 +350,8 @@ struct RustCodeGenerator<'a> {
   config: RustCodeGeneratorConfig<'a>,
   /// Configuration of `rustfmt`.
   rustfmt_config: rustfmt::config::Config,
+  /// Rust types corresponding to C++ documentation pages.
+  doc_links: &'a doc_formatter::DocLinks,
 }
 
 /// Merges `a` and `b` recursively. `b` take precedence over `a`.
@@ -220,7 +390,7 @@ impl<'a> RustCodeGenerator<'a> {
   /// Generates `Cargo.toml` file and skeleton of the crate.
   /// If a crate template was supplied, files from it are
   /// copied to the output location.
-  pub fn generate_template(&self) -> Result<()> {
+  pub fn generate_template(&self, allocation_benchmarks: &[RustAllocationBenchmark]) -> Result<()> {
     let template_rustfmt_config_path = self
       .config
       .crate_template_path
@@ -323,6 +493,19 @@ impl<'a> RustCodeGenerator<'a> {
                                    Some(PathBuf::from(&dep.rust_export_info.output_path)))?);
                                }
                              }
+                             if !self.config.serde_enums.is_empty() {
+                               table.insert("serde".to_string(),
+                                            toml::Value::String(versions::SERDE_VERSION
+                                                                  .to_string()));
+                             }
+                             if self.config.tracing_spans {
+                               let mut value = toml::Table::new();
+                               value.insert("version".to_string(),
+                                            toml::Value::String(versions::TRACING_VERSION
+                                                                  .to_string()));
+                               value.insert("optional".to_string(), toml::Value::Boolean(true));
+                               table.insert("tracing".to_string(), toml::Value::Table(value));
+                             }
                              for dep in self.config.crate_properties.dependencies() {
                                table.insert(dep.name().to_string(),
                                             dep_value(dep.version(), dep.local_path().cloned())?);
@@ -354,6 +537,21 @@ impl<'a> RustCodeGenerator<'a> {
       table.insert("package".to_string(), package);
       table.insert("dependencies".to_string(), dependencies);
       table.insert("build-dependencies".to_string(), build_dependencies);
+      if !self.config.cpp_features.is_empty() {
+        let mut features = toml::Table::new();
+        for feature in &self.config.cpp_features {
+          features.insert(feature.clone(), toml::Value::Array(Vec::new()));
+        }
+        table.insert("features".to_string(), toml::Value::Table(features));
+      }
+      if !allocation_benchmarks.is_empty() {
+        let mut bench = toml::Table::new();
+        bench.insert("name".to_string(),
+                     toml::Value::String("allocation_places".to_string()));
+        bench.insert("harness".to_string(), toml::Value::Boolean(false));
+        table.insert("bench".to_string(),
+                     toml::Value::Array(vec![toml::Value::Table(bench)]));
+      }
       recursive_merge_toml(toml::Value::Table(table),
                            toml::Value::Table(self.config.crate_properties.custom_fields().clone()))
     };
@@ -417,6 +615,10 @@ impl<'a> RustCodeGenerator<'a> {
     if type1.rust_api_to_c_conversion == RustToCTypeConversion::None {
       return Ok(expression);
     }
+    if type1.rust_api_to_c_conversion == RustToCTypeConversion::ConsumedSelf {
+      // the FFI return value is either void or a pointer to `self`
+      return Ok(format!("{};\nself", expression));
+    }
 
     let (code1, source_expr) = if use_ffi_result_var {
       (format!("let ffi_result = {};\n", expression), "ffi_result".to_string())
@@ -467,6 +669,17 @@ impl<'a> RustCodeGenerator<'a> {
                 unsafe_start = unsafe_start,
                 unsafe_end = unsafe_end)
       }
+      RustToCTypeConversion::ResultCppBoxToPtr => {
+        format!("if {source}.is_null() {{\n\
+                   Err(::cpp_utils::ConstructionError {{ class_name: {class_name:?} }})\n\
+                 }} else {{\n\
+                   Ok({unsafe_start}::cpp_utils::CppBox::new({source}){unsafe_end})\n\
+                 }}",
+                source = source_expr,
+                class_name = type1.cpp_type.base.to_cpp_pseudo_code(),
+                unsafe_start = unsafe_start,
+                unsafe_end = unsafe_end)
+      }
       RustToCTypeConversion::QFlagsToUInt => {
         let mut qflags_type = type1.rust_api_type.clone();
         if let RustType::Common { ref mut generic_arguments, .. } = qflags_type {
@@ -478,6 +691,47 @@ impl<'a> RustCodeGenerator<'a> {
                 self.rust_type_to_code(&qflags_type),
                 source_expr)
       }
+      RustToCTypeConversion::OwnedVoidPtrToPtr { ref free_function } => {
+        format!("{unsafe_start}::cpp_utils::OwnedVoidPtr::new({} as *mut ::std::os::raw::c_void, \
+                 |ptr| unsafe {{ {}(ptr as *mut _) }}){unsafe_end}",
+                source_expr,
+                free_function,
+                unsafe_start = unsafe_start,
+                unsafe_end = unsafe_end)
+      }
+      RustToCTypeConversion::StringToBuffer { .. } => {
+        return Err(unexpected("StringToBuffer must be handled by generate_ffi_call").into());
+      }
+      RustToCTypeConversion::ErrnoResult { .. } => {
+        return Err(unexpected("ErrnoResult must be handled by generate_ffi_call").into());
+      }
+      RustToCTypeConversion::ArrayToOutPtr { .. } => {
+        return Err(unexpected("ArrayToOutPtr must be handled by generate_ffi_call").into());
+      }
+      RustToCTypeConversion::CppBoxIntoPtr => {
+        return Err("CppBoxIntoPtr is only supported for arguments".into());
+      }
+      RustToCTypeConversion::StrToStrRef => {
+        return Err("StrToStrRef is only supported for arguments".into());
+      }
+      RustToCTypeConversion::SliceToSliceRef => {
+        return Err("SliceToSliceRef is only supported for arguments".into());
+      }
+      RustToCTypeConversion::VecToVecOut => {
+        return Err(unexpected("VecToVecOut must be handled by generate_ffi_call").into());
+      }
+      RustToCTypeConversion::ClosureToCppClosure => {
+        return Err("ClosureToCppClosure is only supported for arguments".into());
+      }
+      RustToCTypeConversion::CharToUShort => {
+        // arguments of callbacks may contain lone surrogates
+        format!("::std::char::from_u32({} as u32).unwrap_or('\\u{{fffd}}')",
+                source_expr)
+      }
+      RustToCTypeConversion::ConsumedSelf => unreachable!(),
+      RustToCTypeConversion::SliceToPtr { .. } => {
+        return Err("SliceToPtr is only supported for arguments".into());
+      }
     };
     Ok(code1 + &code2)
   }
@@ -527,20 +781,10 @@ impl<'a> RustCodeGenerator<'a> {
         }
         RustToCTypeConversion::ValueToPtr |
         RustToCTypeConversion::CppBoxToPtr => {
-          let is_const = if let RustType::Common {
-                   ref is_const,
-                   ref is_const2,
-                   ref indirection,
-                   ..
-                 } = arg.argument_type.rust_ffi_type {
-            match *indirection {
-              RustTypeIndirection::PtrPtr { .. } |
-              RustTypeIndirection::PtrRef { .. } => *is_const2,
-              _ => *is_const,
-            }
-          } else {
-            return Err(unexpected("void is not expected here at all!").into());
-          };
+          let is_const = arg.argument_type
+            .rust_ffi_type
+            .last_is_const()
+            .chain_err(|| unexpected("void is not expected here at all!"))?;
           if arg.argument_type.rust_api_to_c_conversion == RustToCTypeConversion::CppBoxToPtr {
             let method = if is_const { "as_ptr" } else { "as_mut_ptr" };
             code = format!("{}.{}()", code, method);
@@ -551,9 +795,96 @@ impl<'a> RustCodeGenerator<'a> {
                            self.rust_type_to_code(&arg.argument_type.rust_ffi_type));
           }
         }
+        RustToCTypeConversion::CppBoxIntoPtr => {
+          // the object will be deleted by the C++ library
+          code = format!("{}.into_raw()", code);
+        }
         RustToCTypeConversion::QFlagsToUInt => {
           code = format!("{}.to_int() as ::libc::c_uint", code);
         }
+        RustToCTypeConversion::CharToUShort => {
+          code = format!("{{ assert!(({0} as u32) <= 0xffff, \
+                          \"character outside of the Basic Multilingual Plane \
+                          can't be converted to QChar\"); {0} as u32 as ::libc::c_ushort }}",
+                         code);
+        }
+        RustToCTypeConversion::OwnedVoidPtrToPtr { .. } => {
+          return Err("OwnedVoidPtrToPtr is only supported for return values".into());
+        }
+        RustToCTypeConversion::StringToBuffer { .. } => {
+          return Err("StringToBuffer is only supported for return values".into());
+        }
+        RustToCTypeConversion::ErrnoResult { .. } => {
+          return Err("ErrnoResult is only supported for return values".into());
+        }
+        RustToCTypeConversion::ArrayToOutPtr { .. } => {
+          return Err("ArrayToOutPtr is only supported for return values".into());
+        }
+        RustToCTypeConversion::StrToStrRef => {
+          code = format!("::cpp_utils::StrRef::new({})", code);
+        }
+        RustToCTypeConversion::VecToVecOut => {
+          return Err("VecToVecOut is only supported for return values".into());
+        }
+        RustToCTypeConversion::ClosureToCppClosure => {
+          if let RustType::Closure {
+                   ref return_type,
+                   ref arguments,
+                 } = arg.argument_type.rust_api_type {
+            // closure arguments are plain numbers, so the callback
+            // receives them without conversion
+            let crate_name = self.config.crate_properties.name();
+            code = format!(include_str!("../templates/crate/closure_argument.rs.in"),
+                           closure = code,
+                           trait_type = format!("{} + Send",
+                                                closure_trait_to_code(return_type,
+                                                                      arguments,
+                                                                      &crate_name)),
+                           return_type = self.rust_type_to_code(return_type),
+                           callback_args = arguments
+                             .iter()
+                             .enumerate()
+                             .map(|(num, t)| {
+                                    format!(", arg{}: {}", num, self.rust_type_to_code(t))
+                                  })
+                             .join(""),
+                           func_args = (0..arguments.len())
+                             .map(|num| format!("arg{}", num))
+                             .join(", "));
+          } else {
+            return Err(unexpected("closure type expected").into());
+          }
+        }
+        RustToCTypeConversion::ConsumedSelf => {
+          return Err("ConsumedSelf is only supported for return values".into());
+        }
+        RustToCTypeConversion::ResultCppBoxToPtr => {
+          return Err("ResultCppBoxToPtr is only supported for return values".into());
+        }
+        RustToCTypeConversion::SliceToSliceRef => {
+          let method = if arg.argument_type.rust_api_type.is_const()? {
+            "new"
+          } else {
+            "new_mut"
+          };
+          code = format!("::cpp_utils::SliceRef::{}({})", method, code);
+        }
+        RustToCTypeConversion::SliceToPtr {
+          length_ffi_index,
+          max_length,
+        } => {
+          final_args[length_ffi_index] = Some(match max_length {
+                                                Some(max_length) => {
+                                                  format!("{{ assert!({0}.len() <= {1}, \
+                                                           \"at most {1} items are \
+                                                           supported\"); {0}.len() as _ }}",
+                                                          code,
+                                                          max_length)
+                                                }
+                                                None => format!("{}.len() as _", code),
+                                              });
+          code = format!("{}.as_ptr()", code);
+        }
       }
       final_args[arg.ffi_index] = Some(code);
     }
@@ -586,16 +917,87 @@ impl<'a> RustCodeGenerator<'a> {
       } else {
         self.rust_type_to_code(&variant.return_type.rust_api_type)
       };
-      result.push(format!("{{\nlet mut {var}: {t} = {unsafe_start}\
-                           ::cpp_utils::new_uninitialized::NewUninitialized::new_uninitialized()\
-                           {unsafe_end};\n",
-                          var = return_var_name,
-                          t = struct_name,
-                          unsafe_start = unsafe_start,
-                          unsafe_end = unsafe_end));
-      final_args[*i as usize] = Some(format!("&mut {}", return_var_name));
+      if variant.return_type.rust_api_to_c_conversion == RustToCTypeConversion::VecToVecOut {
+        // the C++ side resizes the vector and copies the elements to it
+        result.push(format!("{{\nlet mut {}: {} = Vec::new();\n", return_var_name, struct_name));
+        final_args[*i as usize] = Some(format!("::cpp_utils::VecOut::new(&mut {})",
+                                               return_var_name));
+      } else {
+        // arrays are plain Rust types and don't implement `NewUninitialized`
+        let uninitialized_func = if let RustType::Array { .. } =
+          variant.return_type.rust_api_type {
+          "::std::mem::zeroed"
+        } else {
+          "::cpp_utils::new_uninitialized::NewUninitialized::new_uninitialized"
+        };
+        result.push(format!("{{\nlet mut {var}: {t} = {unsafe_start}{func}(){unsafe_end};\n",
+                            var = return_var_name,
+                            t = struct_name,
+                            func = uninitialized_func,
+                            unsafe_start = unsafe_start,
+                            unsafe_end = unsafe_end));
+        final_args[*i as usize] = Some(format!("&mut {}", return_var_name));
+      }
       maybe_result_var_name = Some(return_var_name);
     }
+    if let RustToCTypeConversion::StringToBuffer {
+             buffer_ffi_index,
+             length_ffi_index,
+           } = variant.return_type.rust_api_to_c_conversion {
+      final_args[buffer_ffi_index] = Some("buffer.as_mut_ptr() as *mut ::libc::c_char"
+                                            .to_string());
+      final_args[length_ffi_index] = Some("buffer.len() as _".to_string());
+      let final_args = final_args
+        .into_iter()
+        .map_if_ok(|x| x.chain_err(|| "ffi argument is missing"))?;
+      return Ok(format!(include_str!("../templates/crate/string_buffer_call.rs.in"),
+                        ffi_call = format!("{unsafe_start}::ffi::{}({}){unsafe_end}",
+                                           variant.cpp_method.c_name,
+                                           final_args.join(", "),
+                                           unsafe_start = unsafe_start,
+                                           unsafe_end = unsafe_end),
+                        cpp_name = variant.cpp_method.cpp_method.full_name()));
+    }
+    if let RustToCTypeConversion::ErrnoResult {
+             error_flag_ffi_index,
+             error_code_ffi_index,
+           } = variant.return_type.rust_api_to_c_conversion {
+      final_args[error_flag_ffi_index] = Some("&mut error_flag".to_string());
+      final_args[error_code_ffi_index] = Some("&mut error_code".to_string());
+      let final_args = final_args
+        .into_iter()
+        .map_if_ok(|x| x.chain_err(|| "ffi argument is missing"))?;
+      return Ok(format!(include_str!("../templates/crate/errno_call.rs.in"),
+                        ffi_call = format!("{unsafe_start}::ffi::{}({}){unsafe_end}",
+                                           variant.cpp_method.c_name,
+                                           final_args.join(", "),
+                                           unsafe_start = unsafe_start,
+                                           unsafe_end = unsafe_end),
+                        cpp_name = variant.cpp_method.cpp_method.full_name()));
+    }
+    if let RustToCTypeConversion::ArrayToOutPtr { array_ffi_index } =
+      variant.return_type.rust_api_to_c_conversion {
+      // the C++ method fills all elements of the array
+      let mut array_var_name = "array".to_string();
+      let mut ii = 1;
+      while all_args.iter().any(|x| &x.name == &array_var_name) {
+        ii += 1;
+        array_var_name = format!("array{}", ii);
+      }
+      final_args[array_ffi_index] = Some(format!("&mut {}", array_var_name));
+      let final_args = final_args
+        .into_iter()
+        .map_if_ok(|x| x.chain_err(|| "ffi argument is missing"))?;
+      return Ok(format!("{{\nlet mut {var}: {t} = {unsafe_start}::std::mem::zeroed()\
+                         {unsafe_end};\n{unsafe_start}::ffi::{func}({args}){unsafe_end};\n\
+                         {var}\n}}",
+                        var = array_var_name,
+                        t = self.rust_type_to_code(&variant.return_type.rust_api_type),
+                        func = variant.cpp_method.c_name,
+                        args = final_args.join(", "),
+                        unsafe_start = unsafe_start,
+                        unsafe_end = unsafe_end));
+    }
     let final_args = final_args
       .into_iter()
       .map_if_ok(|x| x.chain_err(|| "ffi argument is missing"))?;
@@ -642,7 +1044,7 @@ impl<'a> RustCodeGenerator<'a> {
                     } = self_type {
                let maybe_mut = if *is_const { "" } else { "mut " };
                match *indirection {
-                 RustTypeIndirection::None => "self".to_string(),
+                 RustTypeIndirection::None => format!("{}self", maybe_mut),
                  RustTypeIndirection::Ref { ref lifetime } => {
                    match *lifetime {
                      Some(ref lifetime) => format!("&'{} {}self", lifetime, maybe_mut),
@@ -684,6 +1086,19 @@ impl<'a> RustCodeGenerator<'a> {
   }
 
 
+  /// Generates a statement opening a `tracing` span named after the C++ method
+  /// called by `variant`, or an empty string if `tracing_spans` is disabled.
+  /// The statement is only compiled if the `tracing` feature of the crate is enabled.
+  fn tracing_span_code(&self, variant: &RustMethodArgumentsVariant) -> String {
+    if self.config.tracing_spans {
+      format!("#[cfg(feature = \"tracing\")]\n\
+               let _tracing_span = ::tracing::trace_span!({:?}).entered();\n",
+              variant.cpp_method.cpp_method.full_name())
+    } else {
+      String::new()
+    }
+  }
+
   /// Generates complete code of a Rust wrapper function.
   fn generate_rust_final_function(&self, func: &RustMethod) -> Result<String> {
     let maybe_pub = match func.scope {
@@ -693,8 +1108,8 @@ impl<'a> RustCodeGenerator<'a> {
     let maybe_unsafe = if func.is_unsafe { "unsafe " } else { "" };
     Ok(match func.arguments {
          RustMethodArguments::SingleVariant(ref variant) => {
-      let body = self
-        .generate_ffi_call(variant, &Vec::new(), func.is_unsafe)?;
+      let body = self.tracing_span_code(variant) +
+                 &self.generate_ffi_call(variant, &Vec::new(), func.is_unsafe)?;
       let return_type_for_signature = if variant.return_type.rust_api_type ==
                                          RustType::EmptyTuple {
         String::new()
@@ -707,7 +1122,8 @@ impl<'a> RustCodeGenerator<'a> {
         .iter()
         .filter_map(|x| x.argument_type.rust_api_type.lifetime())
         .collect();
-      let lifetimes_text = if all_lifetimes.is_empty() {
+      // named lifetimes of trait implementation methods are declared by the impl
+      let lifetimes_text = if all_lifetimes.is_empty() || func.scope == RustMethodScope::TraitImpl {
         String::new()
       } else {
         format!("<{}>",
@@ -717,9 +1133,18 @@ impl<'a> RustCodeGenerator<'a> {
                   .join(", "))
       };
 
-      format!("{doc}{maybe_pub}{maybe_unsafe}fn {name}{lifetimes_text}({args}){return_type} \
-                 {{\n{body}}}\n\n",
-              doc = format_doc(&doc_formatter::method_doc(&func)),
+      let track_caller = if self.config.track_caller && variant_may_panic(variant) {
+        "#[track_caller]\n"
+      } else {
+        ""
+      };
+      format!("{doc}{cfg}{track_caller}{maybe_pub}{maybe_unsafe}fn {name}{lifetimes_text}\
+                 ({args}){return_type} {{\n{body}}}\n\n",
+              doc = format_doc(&doc_formatter::method_doc(&func,
+                                                          self.doc_links,
+                                                          &self.config.crate_properties.name())),
+              cfg = variant_cfg_attribute(variant),
+              track_caller = track_caller,
               maybe_pub = maybe_pub,
               maybe_unsafe = maybe_unsafe,
               lifetimes_text = lifetimes_text,
@@ -734,6 +1159,7 @@ impl<'a> RustCodeGenerator<'a> {
            ref common_return_type,
            ref shared_arguments,
            ref variant_argument_name,
+           ref variants,
            ..
          } => {
       let tpl_type = variant_argument_name.to_class_case();
@@ -769,8 +1195,18 @@ impl<'a> RustCodeGenerator<'a> {
       } else {
         format!("{}::ReturnType", tpl_type)
       };
+      // the variants are generated separately, so the function
+      // is annotated if any of them may panic
+      let track_caller = if self.config.track_caller && variants.iter().any(variant_may_panic) {
+        "#[track_caller]\n"
+      } else {
+        ""
+      };
       format!(include_str!("../templates/crate/overloaded_function.rs.in"),
-              doc = format_doc(&doc_formatter::method_doc(&func)),
+              doc = format_doc(&doc_formatter::method_doc(&func,
+                                                          self.doc_links,
+                                                          &self.config.crate_properties.name())),
+              track_caller = track_caller,
               maybe_pub = maybe_pub,
               maybe_unsafe = maybe_unsafe,
               tpl_decl = tpl_decl,
@@ -793,6 +1229,12 @@ impl<'a> RustCodeGenerator<'a> {
 
     code.push_str("pub extern crate libc;\n");
     code.push_str("pub extern crate cpp_utils;\n\n");
+    if !self.config.serde_enums.is_empty() {
+      code.push_str("pub extern crate serde;\n\n");
+    }
+    if self.config.tracing_spans {
+      code.push_str("#[cfg(feature = \"tracing\")]\npub extern crate tracing;\n\n");
+    }
     for dep in self.config.generator_dependencies {
       code.push_str(&format!("pub extern crate {};\n\n", &dep.rust_export_info.crate_name));
     }
@@ -806,7 +1248,7 @@ impl<'a> RustCodeGenerator<'a> {
       mod type_sizes { \ninclude!(concat!(env!(\"OUT_DIR\"), \
               \"/type_sizes.rs\")); \n}\n\n");
 
-    for name in &["ffi", "type_sizes"] {
+    for name in &["ffi", "type_sizes", "prelude"] {
       if modules.iter().any(|x| &x.name.as_str() == name) {
         return Err(format!("Automatically generated module '{}' conflicts with a mandatory \
                             module",
@@ -814,6 +1256,8 @@ impl<'a> RustCodeGenerator<'a> {
                        .into());
       }
     }
+    code.push_str(&self.generate_prelude(modules)?);
+
     for name in &["lib", "main"] {
       if modules.iter().any(|x| &x.name.as_str() == name) {
         return Err(format!("Automatically generated module '{}' conflicts with a reserved name",
@@ -839,6 +1283,103 @@ impl<'a> RustCodeGenerator<'a> {
     Ok(())
   }
 
+  /// Generates `prelude` module re-exporting all non-std traits
+  /// implemented in the crate, so that a glob import of the prelude
+  /// is enough to use trait methods and operators of the generated types.
+  fn generate_prelude(&self, modules: &[RustModule]) -> Result<String> {
+    let mut traits = Vec::new();
+    for module in modules {
+      collect_prelude_traits(module, &mut traits)?;
+    }
+    let crate_name = self.config.crate_properties.name();
+    let mut names = Vec::new();
+    let mut items = Vec::new();
+    for trait_name in traits {
+      let last_name = trait_name.last_name()?.clone();
+      if names.contains(&last_name) {
+        log::llog(log::DebugRustSkips, || {
+          format!("Prelude: skipping {:?} because of name conflict", trait_name)
+        });
+        continue;
+      }
+      items.push(format!("pub use {};\n", trait_name.full_name(Some(&crate_name))));
+      names.push(last_name);
+    }
+    Ok(format!("/// Traits required to use the generated API.\n\
+                ///\n\
+                /// Import all items of this module with `use {}::prelude::*;`.\n\
+                pub mod prelude {{\n{}}}\n\n",
+               crate_name,
+               items.join("")))
+  }
+
+  /// Generates the enum borrowing the active alternative
+  /// of a `std::variant` wrapper and its `get` method.
+  fn generate_std_variant_enum(&self, type_name: &str, data: &RustStdVariantEnum) -> String {
+    let variants = data
+      .variants
+      .iter()
+      .map(|v| format!("  {}({}),\n", v.name, self.rust_type_to_code(&v.value_type)))
+      .join("");
+    let checks = data
+      .variants
+      .iter()
+      .map(|v| {
+             format!("      if let Some(value) = \
+                      ::ffi::{}(self as *const Self).as_ref() {{\n        \
+                      return {}::{}(value);\n      }}\n",
+                     v.get_if_function,
+                     data.name,
+                     v.name)
+           })
+      .join("");
+    format!(include_str!("../templates/crate/std_variant_enum.rs.in"),
+            type_name = type_name,
+            enum_name = data.name,
+            variants = variants,
+            checks = checks)
+  }
+
+  /// Generates accessors, the iterator and conversions
+  /// of a `std::map` or `std::unordered_map` wrapper.
+  fn generate_std_map_wrapper(&self, type_name: &str, data: &RustStdMapWrapper) -> String {
+    let key_type = self.rust_type_to_code(&data.key_type);
+    let value_type = self.rust_type_to_code(&data.value_type);
+    let mut code = format!(include_str!("../templates/crate/std_map_wrapper.rs.in"),
+                           type_name = type_name,
+                           iterator_name = data.iterator_name,
+                           key_type = key_type,
+                           value_type = value_type,
+                           collection_path = data.collection_path,
+                           get_function = data.get_function,
+                           insert_function = data.insert_function,
+                           next_entry_function = data.next_entry_function);
+    if let Some(ref constructor_function) = data.stack_constructor_function {
+      code.push_str(&format!(include_str!("../templates/crate/std_map_from_collection.rs.in"),
+                             type_name = type_name,
+                             key_type = key_type,
+                             value_type = value_type,
+                             collection_path = data.collection_path,
+                             constructor_function = constructor_function));
+    }
+    code
+  }
+
+  /// Generates `is_valid`, `is_null` and `into_option` methods
+  /// of a class defining `operator bool` and `operator!`.
+  fn generate_validity_check(&self, type_name: &str, data: &RustValidityCheck) -> String {
+    let into_option = if data.has_into_option {
+      include_str!("../templates/crate/into_option.rs.in")
+    } else {
+      ""
+    };
+    format!(include_str!("../templates/crate/validity_check.rs.in"),
+            type_name = type_name,
+            bool_function = data.bool_function,
+            not_function = data.not_function,
+            into_option = into_option)
+  }
+
   /// Generates Rust code for given trait implementations.
   fn generate_trait_impls(&self, trait_impls: &[TraitImpl]) -> Result<String> {
     let mut results = Vec::new();
@@ -849,6 +1390,138 @@ impl<'a> RustCodeGenerator<'a> {
         .map(|t| format!("type {} = {};", t.name, self.rust_type_to_code(&t.value)))
         .join("\n");
 
+      match trait1.extra {
+        Some(TraitImplExtra::Extend {
+               ref append_function,
+               ref size_and_reserve_functions,
+             }) => {
+          let reserve = if let Some((ref size_function, ref reserve_function)) =
+            *size_and_reserve_functions {
+            format!(include_str!("../templates/crate/extend_reserve.rs.in"),
+                    size_function = size_function,
+                    reserve_function = reserve_function)
+          } else {
+            String::new()
+          };
+          results.push(format!(include_str!("../templates/crate/impl_extend.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               item_type = self.trait_item_type_code(&trait1.trait_type)?,
+                               reserve = reserve,
+                               append_function = append_function));
+          continue;
+        }
+        Some(TraitImplExtra::FromIterator { ref constructor_function }) => {
+          results.push(format!(include_str!("../templates/crate/impl_from_iterator.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               item_type = self.trait_item_type_code(&trait1.trait_type)?,
+                               constructor_function = constructor_function));
+          continue;
+        }
+        Some(TraitImplExtra::VariantConversion {
+               ref value_type,
+               ref value_return_type,
+               ref variant_arg_type,
+             }) => {
+          results.push(format!(include_str!("../templates/crate/impl_variant_conversion.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               variant_type = self.rust_type_to_code(&trait1.target_type),
+                               value_type = self.rust_type_to_code(value_type),
+                               value_return_type = self.rust_type_to_code(value_return_type),
+                               variant_arg_type = self.rust_type_to_code(variant_arg_type)));
+          continue;
+        }
+        Some(TraitImplExtra::SlicingConversion) |
+        Some(TraitImplExtra::PrimitiveLhsOperator) => {
+          results.push(format!("impl<'a> {} for {} {{\n{}{}}}\n\n",
+                               self.rust_type_to_code(&trait1.trait_type),
+                               self.rust_type_to_code(&trait1.target_type),
+                               associated_types_text,
+                               trait1
+                                 .methods
+                                 .iter()
+                                 .map_if_ok(|method| self.generate_rust_final_function(method))?
+                                 .join("")));
+          continue;
+        }
+        Some(TraitImplExtra::Debug {
+               ref type_name,
+               ref fields,
+             }) => {
+          let fields_text = fields
+            .iter()
+            .map(|field| if let Some(ref getter_name) = field.getter_name {
+                   format!("\n      .field(\"{}\", &self.{}())", field.name, getter_name)
+                 } else {
+                   format!("\n      .field(\"{}\", &format_args!(\"<opaque>\"))", field.name)
+                 })
+            .join("");
+          results.push(format!(include_str!("../templates/crate/impl_debug.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               type_name = type_name,
+                               fields = fields_text));
+          continue;
+        }
+        Some(TraitImplExtra::Display { ref display_function }) => {
+          results.push(format!(include_str!("../templates/crate/impl_display.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               display_function = display_function));
+          continue;
+        }
+        Some(TraitImplExtra::VirtualClone {
+               ref clone_function,
+               is_base_return_type,
+             }) => {
+          let call = format!("::ffi::{}(self as *const Self)", clone_function);
+          let ptr = if is_base_return_type {
+            format!("::cpp_utils::unsafe_static_cast_mut({})", call)
+          } else {
+            call
+          };
+          results.push(format!(include_str!("../templates/crate/impl_virtual_clone.rs.in"),
+                               trait_type = self.rust_type_to_code(&trait1.trait_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               ptr = ptr));
+          continue;
+        }
+        Some(TraitImplExtra::QObjectTryFrom {
+               ref cast_trait_type,
+               is_const,
+             }) => {
+          let crate_name = self.config.crate_properties.name();
+          let (trait_name, source_type) = if let RustType::Common {
+                   ref base,
+                   generic_arguments: Some(ref args),
+                   ..
+                 } = trait1.trait_type {
+            (base.full_name(Some(&crate_name)),
+             args.get(0).chain_err(|| "TryFrom must have a generic argument")?)
+          } else {
+            return Err(unexpected("invalid TryFrom trait type").into());
+          };
+          let error_type = RustName::new(vec!["qt_core".to_string(),
+                                              "object".to_string(),
+                                              "CastError".to_string()])?
+            .full_name(Some(&crate_name));
+          results.push(format!(include_str!("../templates/crate/impl_qobject_try_from.rs.in"),
+                               trait_name = trait_name,
+                               source_type = self.rust_type_to_code(source_type),
+                               target_type = self.rust_type_to_code(&trait1.target_type),
+                               cast_trait = self.rust_type_to_code(cast_trait_type),
+                               cast_method = if is_const {
+                                 "qobject_cast"
+                               } else {
+                                 "qobject_cast_mut"
+                               },
+                               ref_kind = if is_const { "&'a " } else { "&'a mut " },
+                               error_type = error_type));
+          continue;
+        }
+        _ => {}
+      }
       let trait_content = if let Some(TraitImplExtra::CppDeletable { ref deleter_name }) =
         trait1.extra {
         format!("fn deleter() -> ::cpp_utils::Deleter<Self> {{\n  ::ffi::{}\n}}\n",
@@ -869,13 +1542,135 @@ impl<'a> RustCodeGenerator<'a> {
     Ok(results.join(""))
   }
 
+  /// Generates code of the item type of an iterator-related trait
+  /// (the only generic argument of `trait_type`).
+  fn trait_item_type_code(&self, trait_type: &RustType) -> Result<String> {
+    if let RustType::Common { ref generic_arguments, .. } = *trait_type {
+      let arg = generic_arguments
+        .as_ref()
+        .and_then(|args| args.get(0))
+        .chain_err(|| "trait item type is missing")?;
+      Ok(self.rust_type_to_code(arg))
+    } else {
+      Err(unexpected("trait type expected").into())
+    }
+  }
+
+  /// Generates `serde::Serialize` and `serde::Deserialize` implementations
+  /// for enum `name`. If `fallback` is set, unknown values are deserialized
+  /// as this variant instead of producing an error.
+  fn generate_serde_enum_impl(&self,
+                              name: &str,
+                              values: &[RustEnumValue],
+                              fallback: Option<&String>)
+                              -> Result<String> {
+    let unknown_value = if let Some(fallback) = fallback {
+      if !values.iter().any(|v| &v.name == fallback) {
+        return Err(format!("serde fallback variant not found in enum {}: {}",
+                           name,
+                           fallback)
+                       .into());
+      }
+      format!("Ok({}::{})", name, fallback)
+    } else {
+      format!("Err(::serde::de::Error::custom(format!(\"unknown {} value: {{}}\", value)))",
+              name)
+    };
+    let (serialize, deserialize) = match self.config.serde_enum_representation {
+      SerdeEnumRepresentation::Name => {
+        (format!("serializer.serialize_str(match *self {{\n{}\n}})",
+                 values
+                   .iter()
+                   .map(|v| format!("{}::{} => \"{}\",", name, v.name, v.name))
+                   .join("\n")),
+         format!("let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;\n\
+                  match value.as_str() {{\n{}\n_ => {},\n}}",
+                 values
+                   .iter()
+                   .map(|v| format!("\"{}\" => Ok({}::{}),", v.name, name, v.name))
+                   .join("\n"),
+                 unknown_value))
+      }
+      SerdeEnumRepresentation::Value => {
+        (format!("serializer.serialize_i64(match *self {{\n{}\n}})",
+                 values
+                   .iter()
+                   .map(|v| format!("{}::{} => {},", name, v.name, v.value))
+                   .join("\n")),
+         format!("let value = <i64 as ::serde::Deserialize>::deserialize(deserializer)?;\n\
+                  match value {{\n{}\n_ => {},\n}}",
+                 values
+                   .iter()
+                   .map(|v| format!("{} => Ok({}::{}),", v.value, name, v.name))
+                   .join("\n"),
+                 unknown_value))
+      }
+    };
+    Ok(format!(include_str!("../templates/crate/impl_serde_enum.rs.in"),
+               name = name,
+               serialize = serialize,
+               deserialize = deserialize))
+  }
+
+  /// Generates `TryFrom<i32>` implementation of enum `name`
+  /// (`TryFrom<i64>` if its values don't fit in `i32`).
+  /// Dummy variants are not accepted by the conversion.
+  fn generate_enum_try_from(&self, name: &str, values: &[RustEnumValue]) -> String {
+    let value_to_variant = declared_enum_values(values)
+      .into_iter()
+      .map(|v| format!("{} => Ok({}::{}),", v.value, name, v.name))
+      .join("\n");
+    format!(include_str!("../templates/crate/impl_enum_try_from.rs.in"),
+            name = name,
+            int_type = enum_int_type(values),
+            value_to_variant = value_to_variant)
+  }
+
+  /// Generates `migrate_from_v*` functions of enum `name`
+  /// for migrations added with `Config::add_enum_value_migration`.
+  fn generate_enum_migrations(&self,
+                              name: &str,
+                              values: &[RustEnumValue],
+                              migrations: &[EnumValueMigration])
+                              -> Result<String> {
+    let mut code = String::new();
+    for migration in migrations {
+      if migration.old_values.len() != migration.new_values.len() {
+        return Err(format!("enum value migration of {} from version {} has {} old values \
+                            and {} new values",
+                           name,
+                           migration.version,
+                           migration.old_values.len(),
+                           migration.new_values.len())
+                       .into());
+      }
+      code.push_str(&format!(include_str!("../templates/crate/enum_migration.rs.in"),
+                             name = name,
+                             version = migration.version,
+                             old_to_new = migration
+                               .old_values
+                               .iter()
+                               .zip(migration.new_values.iter())
+                               .map(|(old, new)| format!("{} => {},", old, new))
+                               .join("\n"),
+                             new_to_variant = values
+                               .iter()
+                               .filter(|v| !v.is_dummy)
+                               .map(|v| format!("{} => Some({}::{}),", v.value, name, v.name))
+                               .join("\n")));
+    }
+    Ok(code)
+  }
+
   /// Generates code for a module of the output crate.
   /// This may be a top level or nested module.
   #[cfg_attr(feature="clippy", allow(single_match_else))]
   fn generate_module_code(&self, data: &RustModule) -> Result<String> {
     let mut results = Vec::new();
     for type1 in &data.types {
-      results.push(format_doc(&doc_formatter::type_doc(type1)));
+      results.push(format_doc(&doc_formatter::type_doc(type1,
+                                                       self.doc_links,
+                                                       &self.config.crate_properties.name())));
       let maybe_pub = if type1.is_public { "pub " } else { "" };
       match type1.kind {
         RustTypeDeclarationKind::CppTypeWrapper {
@@ -884,16 +1679,33 @@ impl<'a> RustCodeGenerator<'a> {
           ref methods,
           ref trait_impls,
           ref qt_receivers,
+          ref associated_consts,
+          ref anonymous_enum_consts,
+          ref computed_constants,
+          ref std_variant_enum,
+          ref std_map_wrapper,
+          ref validity_check,
           ..
         } => {
           let r = match *kind {
             RustTypeWrapperKind::Enum {
               ref values,
               ref is_flaggable,
+              ..
             } => {
               let mut r = format!(include_str!("../templates/crate/enum_declaration.rs.in"),
                                   maybe_pub = maybe_pub,
+                                  maybe_non_exhaustive = if self.config.enums_non_exhaustive {
+                                    "#[non_exhaustive]\n"
+                                  } else {
+                                    ""
+                                  },
                                   name = type1.name.last_name()?,
+                                  repr = if enum_int_type(values) == "i32" {
+                                    "C"
+                                  } else {
+                                    "i64"
+                                  },
                                   variants = values
                                     .iter()
                                     .map(|item| {
@@ -904,29 +1716,69 @@ impl<'a> RustCodeGenerator<'a> {
                                          })
                                     .join(", \n"));
               if *is_flaggable {
+                let crate_name = self.config.crate_properties.name();
+                let name = type1.name.last_name()?;
                 r = r +
                     &format!(include_str!("../templates/crate/impl_flaggable.rs.in"),
-                             name = type1.name.last_name()?,
-                             trait_type =
-                               RustName::new(vec!["qt_core".to_string(),
-                                                  "flags".to_string(),
-                                                  "FlaggableEnum".to_string()])?
-                                   .full_name(Some(&self.config.crate_properties.name())));
+                             name = name,
+                             flag_values = values
+                               .iter()
+                               .filter(|item| !item.is_dummy)
+                               .map(|item| format!("{}::{}", name, item.name))
+                               .join(", "),
+                             trait_type = flags_module_item("FlaggableEnum")?
+                               .full_name(Some(&crate_name)),
+                             enum_or_flags_type = flags_module_item("EnumOrFlags")?
+                               .full_name(Some(&crate_name)),
+                             flags_type = flags_module_item("Flags")?
+                               .full_name(Some(&crate_name)));
+              }
+              r = r +
+                  &format!(include_str!("../templates/crate/impl_enum_conversions.rs.in"),
+                           name = type1.name.last_name()?,
+                           int_type = enum_int_type(values));
+              let name = type1.name.last_name()?;
+              r = r +
+                  &format!(include_str!("../templates/crate/impl_enum_values.rs.in"),
+                           name = name,
+                           values = declared_enum_values(values)
+                             .into_iter()
+                             .map(|v| format!("{}::{}", name, v.name))
+                             .join(", "));
+              if !*is_flaggable {
+                r = r + &self.generate_enum_try_from(&type1.name.last_name()?, values);
+              }
+              if let Some(migrations) = self.config.enum_value_migrations.get(cpp_type_name) {
+                r = r +
+                    &self.generate_enum_migrations(&type1.name.last_name()?,
+                                                   values,
+                                                   migrations)?;
+              }
+              if let Some(fallback) = self.config.serde_enums.get(cpp_type_name) {
+                r = r +
+                    &self.generate_serde_enum_impl(&type1.name.last_name()?,
+                                                   values,
+                                                   fallback.as_ref())?;
               }
               r
             }
             RustTypeWrapperKind::Struct {
               ref size_const_name,
               ref slot_wrapper,
+              ref event_filter_wrapper,
+              ref functor_wrapper,
               ..
             } => {
+              // the object's content is wrapped in `UnsafeCell` because C++ const methods
+              // may modify it (e.g. `mutable` fields), so it must not be treated as
+              // immutable behind `&self`
               let mut r = if let Some(ref size_const_name) = *size_const_name {
                 format!(include_str!("../templates/crate/struct_declaration.rs.in"),
                         maybe_pub = maybe_pub,
                         name = type1.name.last_name()?,
                         size_const_name = size_const_name)
               } else {
-                format!("#[repr(C)]\n{maybe_pub}struct {}(u8);\n\n",
+                format!("#[repr(C)]\n{maybe_pub}struct {}(::std::cell::UnsafeCell<u8>);\n\n",
                         type1.name.last_name()?,
                         maybe_pub = maybe_pub)
               };
@@ -956,6 +1808,59 @@ impl<'a> RustCodeGenerator<'a> {
                                     connections_mod = connections_mod,
                                     object_type_name = object_type_name));
               }
+              if let Some(ref wrapper) = *event_filter_wrapper {
+                let crate_name = self.config.crate_properties.name();
+                r.push_str(&format!(include_str!("../templates/crate/closure_event_filter.rs.in"),
+                                    type_name = type1.name.last_name()?,
+                                    pub_type_name = wrapper.public_type_name,
+                                    callback_name = wrapper.callback_name,
+                                    object_type_name =
+                                      wrapper.object_type.full_name(Some(&crate_name)),
+                                    event_type_name =
+                                      wrapper.event_type.full_name(Some(&crate_name))));
+              }
+              if let Some(ref wrapper) = *functor_wrapper {
+                let args = wrapper
+                  .arguments
+                  .iter()
+                  .map(|t| self.rust_type_to_code(&t.rust_api_type))
+                  .join(", ");
+                let cpp_signature = format!("{}({})",
+                                            wrapper.return_type.cpp_type.to_cpp_pseudo_code(),
+                                            wrapper
+                                              .arguments
+                                              .iter()
+                                              .map(|t| t.cpp_type.to_cpp_pseudo_code())
+                                              .join(", "));
+                let callback_args = wrapper
+                  .arguments
+                  .iter()
+                  .enumerate()
+                  .map(|(num, t)| {
+                         format!(", arg{}: {}", num, self.rust_type_to_code(&t.rust_ffi_type))
+                       })
+                  .join("");
+                let func_args = wrapper
+                  .arguments
+                  .iter()
+                  .enumerate()
+                  .map_if_ok(|(num, t)| {
+                               self.convert_type_from_ffi(t, format!("arg{}", num), false, false)
+                             })?
+                  .join(", ");
+                r.push_str(&format!(include_str!("../templates/crate/closure_functor.rs.in"),
+                                    type_name = type1.name.last_name()?,
+                                    pub_type_name = wrapper.public_type_name,
+                                    callback_name = wrapper.callback_name,
+                                    cpp_signature = cpp_signature,
+                                    args = args,
+                                    return_type =
+                                      self.rust_type_to_code(&wrapper.return_type.rust_api_type),
+                                    ffi_return_type =
+                                      self.rust_type_to_code(&wrapper.return_type.rust_ffi_type),
+                                    callback_args = callback_args,
+                                    func_args = func_args));
+              }
               r
             }
           };
@@ -970,6 +1875,70 @@ impl<'a> RustCodeGenerator<'a> {
                                               })?
                                    .join("")));
           }
+          if !associated_consts.is_empty() {
+            let crate_name = self.config.crate_properties.name();
+            results.push(format!("impl {} {{\n{}}}\n\n",
+                                 type1.name.last_name()?,
+                                 associated_consts
+                                   .iter()
+                                   .map(|c| {
+                                          format!("  /// Same as `{enum_name}::{variant}`.\n  \
+                                                   pub const {name}: {enum_name} = \
+                                                   {enum_name}::{variant};\n",
+                                                  name = c.name,
+                                                  enum_name = c.enum_name
+                                                    .full_name(Some(&crate_name)),
+                                                  variant = c.variant_name)
+                                        })
+                                   .join("")));
+          }
+          if !anonymous_enum_consts.is_empty() {
+            results.push(format!("impl {} {{\n{}}}\n\n",
+                                 type1.name.last_name()?,
+                                 anonymous_enum_consts
+                                   .iter()
+                                   .map(|c| {
+              // C++ anonymous enums are commonly used as integer constants
+              let value_type = if c.value >= i32::min_value() as i64 &&
+                                  c.value <= i32::max_value() as i64 {
+                "i32"
+              } else {
+                "i64"
+              };
+              format!("  /// C++ enum variant: `{name} = {value}`.\n  \
+                       #[allow(non_upper_case_globals)]\n  \
+                       pub const {name}: {value_type} = {value};\n",
+                      name = c.name,
+                      value = c.value,
+                      value_type = value_type)
+            })
+                                   .join("")));
+          }
+          if !computed_constants.is_empty() {
+            results.push(format!("impl {} {{\n{}}}\n\n",
+                                 type1.name.last_name()?,
+                                 computed_constants
+                                   .iter()
+                                   .map(|c| {
+              format!(include_str!("../templates/crate/computed_type_constant.rs.in"),
+                      name = c.name,
+                      value_type = self.rust_type_to_code(&c.value_type),
+                      ffi_function = c.ffi_function,
+                      cpp_initializer = c.cpp_initializer)
+            })
+                                   .join("")));
+          }
+          if let Some(ref std_variant_enum) = *std_variant_enum {
+            results.push(self.generate_std_variant_enum(type1.name.last_name()?,
+                                                        std_variant_enum));
+          }
+          if let Some(ref std_map_wrapper) = *std_map_wrapper {
+            results.push(self.generate_std_map_wrapper(type1.name.last_name()?,
+                                                       std_map_wrapper));
+          }
+          if let Some(ref validity_check) = *validity_check {
+            results.push(self.generate_validity_check(type1.name.last_name()?, validity_check));
+          }
           results.push(self.generate_trait_impls(trait_impls)?);
           if !qt_receivers.is_empty() {
             let connections_mod = RustName::new(vec!["qt_core".to_string(),
@@ -1047,6 +2016,28 @@ impl<'a> {connections_mod}::Receiver for {type_name}<'a> {{
                 content.push(format!("impl<'a> {}<'a> {{\n{}\n}}\n",
                                      struct_type,
                                      struct_content.join("")));
+                if *receiver_type == RustQtReceiverType::Signal {
+                  // signals are also accessible directly (`object.clicked()`)
+                  // unless the name is taken by a method of the type
+                  for receiver in qt_receivers {
+                    if receiver.receiver_type == RustQtReceiverType::Signal &&
+                       receiver.method_name != "signals" &&
+                       receiver.method_name != "slots" &&
+                       !methods
+                          .iter()
+                          .any(|m| m.name.last_name().ok() == Some(&receiver.method_name)) {
+                      type_impl_content.push(format!("\
+{doc}pub fn {method_name}(&self) -> {type_name} {{
+  {type_name}(self)
+}}\n",
+                                                     type_name = receiver.type_name,
+                                                     method_name = receiver.method_name,
+                                                     doc = format_doc(
+                        &doc_formatter::doc_for_qt_builtin_receiver_method(cpp_type_name,
+                                                                           receiver))));
+                    }
+                  }
+                }
                 type_impl_content.push(format!("\
 {doc}pub fn {struct_method}(&self) -> {struct_type} {{
   {struct_type}(self)
@@ -1151,8 +2142,15 @@ impl<'a> {connections_mod}::Receiver for {type_name}<'a> {{
             } else {
               format!("type ReturnType = {};", return_type_string)
             };
-            results.push(format!(include_str!("../templates/crate/impl_overloading_trait.rs.in"),
+            results.push(variant_cfg_attribute(variant) +
+                         &format!(include_str!("../templates/crate/impl_overloading_trait.rs.in"),
                                  maybe_unsafe = maybe_unsafe,
+                                 track_caller = if self.config.track_caller &&
+                                                   variant_may_panic(variant) {
+                                   "#[track_caller]\n  "
+                                 } else {
+                                   ""
+                                 },
                                  lifetime_specifier = lifetime_specifier,
                                  trait_lifetime_specifier = trait_lifetime_specifier,
                                  trait_name = type1.name.last_name()?,
@@ -1165,9 +2163,10 @@ impl<'a> {connections_mod}::Receiver for {type_name}<'a> {{
                                  return_type_decl = return_type_decl,
                                  return_type_string = return_type_string,
                                  tmp_vars = tmp_vars.join("\n"),
-                                 body =
-                                   self
-                                     .generate_ffi_call(variant, shared_arguments, *is_unsafe)?));
+                                 body = self.tracing_span_code(variant) +
+                                        &self.generate_ffi_call(variant,
+                                                                shared_arguments,
+                                                                *is_unsafe)?));
 
           }
         }
@@ -1300,6 +2299,87 @@ impl<'a> {connections_mod}::Receiver for {type_name}<'a> {{
     Ok(())
   }
 
+  /// Generates `ffi_symbols.json` file containing sorted list of names
+  /// of all FFI functions the C++ wrapper library is expected to export.
+  /// It can be compared with the symbols of the compiled library
+  /// to diagnose link errors. Functions bound directly to the C++ library
+  /// are not exported by the wrapper library and are not listed.
+  pub fn generate_ffi_symbols_file(&self,
+                                   functions: &[(String, Vec<RustFFIFunction>)])
+                                   -> Result<()> {
+    let mut names: Vec<_> = functions
+      .iter()
+      .flat_map(|&(_, ref functions)| functions.iter())
+      .filter(|f| !f.is_direct_extern_c)
+      .map(|f| f.name.clone())
+      .collect();
+    names.sort();
+    save_json(self.config.output_path.with_added("ffi_symbols.json"),
+              &names)
+  }
+
+  /// Generates `api_lints.json` file listing generated API items
+  /// that carry a known risk (e.g. unchecked casts or raw pointer
+  /// return values), so that they can be reviewed by the crate's users.
+  pub fn generate_api_lints_file(&self, modules: &[RustModule]) -> Result<()> {
+    let lints = collect_api_lints(modules, self.config.crate_properties.name());
+    save_json(self.config.output_path.with_added("api_lints.json"), &lints)
+  }
+
+  /// Generates `benches/allocation_places.rs` comparing performance of
+  /// creating and destroying objects of `allocation_benchmarks` classes
+  /// stored as structs and in boxes.
+  pub fn generate_allocation_benchmark_file(&self,
+                                            allocation_benchmarks: &[RustAllocationBenchmark])
+                                            -> Result<()> {
+    if allocation_benchmarks.is_empty() {
+      return Ok(());
+    }
+    let mut ffi_functions = String::new();
+    let mut benchmarks = String::new();
+    for item in allocation_benchmarks {
+      ffi_functions.push_str(&format!("  fn {}(output: *mut c_void);
+  \
+                                       fn {}(this_ptr: *mut c_void);
+  \
+                                       fn {}() -> *mut c_void;
+  \
+                                       fn {}(this_ptr: *mut c_void);
+",
+                                      item.stack_constructor,
+                                      item.stack_destructor,
+                                      item.heap_constructor,
+                                      item.heap_destructor));
+      benchmarks.push_str(&format!("  {{
+    let as_struct = measure(|| unsafe {{
+      let mut value: {rust_type} = mem::zeroed();
+      {stack_constructor}(&mut value as *mut {rust_type} as *mut c_void);
+      {stack_destructor}(&mut value as *mut {rust_type} as *mut c_void);
+      mem::forget(value);
+    }});
+    let as_box = measure(|| unsafe {{ {heap_destructor}({heap_constructor}()); }});
+    report(\"{cpp_name}\", as_struct, as_box);
+  }}
+",
+                                   rust_type = item.rust_type.full_name(None),
+                                   stack_constructor = item.stack_constructor,
+                                   stack_destructor = item.stack_destructor,
+                                   heap_constructor = item.heap_constructor,
+                                   heap_destructor = item.heap_destructor,
+                                   cpp_name = item.cpp_name));
+    }
+    let code = format!(include_str!("../templates/crate/allocation_benchmark.rs.in"),
+                       crate_name = self.config.crate_properties.name(),
+                       ffi_functions = ffi_functions,
+                       benchmarks = benchmarks);
+    let benches_path = self.config.output_path.with_added("benches");
+    create_dir_all(&benches_path)?;
+    let file_path = benches_path.with_added("allocation_places.rs");
+    let mut file = create_file(&file_path)?;
+    file.write(code)?;
+    Ok(())
+  }
+
   /// Creates new Rust source file or merges it with the existing file.
   fn save_src_file(&self, path: &Path, code: &str) -> Result<()> {
     const INCLUDE_GENERATED_MARKER: &'static str = "include_generated!();";
@@ -1,14 +1,18 @@
 //! Generates Rust public API and FFI functions
 
-use caption_strategy::TypeCaptionStrategy;
-use cpp_data::{CppTypeKind, CppEnumValue, CppTypeAllocationPlace, CppDataWithDeps};
+use caption_strategy::{TypeCaptionStrategy, MethodCaptionStrategy};
+use config::SafetyPolicy;
+use cpp_data::{CppTypeKind, CppEnumValue, CppTypeAllocationPlace, CppDataWithDeps,
+               CppVisibility};
 use cpp_ffi_data::{CppAndFfiMethod, CppFfiArgumentMeaning, CppFfiType, CppIndirectionChange,
-                   CppFfiMethodKind, CppCast, CppFfiHeaderData};
-use cpp_method::{CppMethod, ReturnValueAllocationPlace};
+                   CppFfiMethodKind, CppCast, CppFfiHeaderData, CppFfiMethodArgument,
+                   CppVariantConversion, CppFieldAccessorType, CppFallibleConstructor};
+use cpp_method::{CppMethod, CppRefQualifier, ReturnValueAllocationPlace};
 use cpp_operator::CppOperator;
 use cpp_type::{CppType, CppTypeBase, CppBuiltInNumericType, CppTypeIndirection,
                CppSpecificNumericTypeKind, CppSpecificNumericType, CppTypeClassBase, CppTypeRole,
-               CppFunctionPointerType};
+               CppFunctionPointerType, STR_REF_FFI_TYPE_NAME, SLICE_REF_FFI_TYPE_NAME,
+               VEC_OUT_FFI_TYPE_NAME, CLOSURE_FFI_TYPE_NAME};
 use common::errors::{Result, ChainErr, unexpected};
 use common::log;
 use rust_info::{RustTypeDeclaration, RustTypeDeclarationKind, RustTypeWrapperKind, RustModule,
@@ -16,7 +20,12 @@ use rust_info::{RustTypeDeclaration, RustTypeDeclarationKind, RustTypeWrapperKin
                 RustMethodArguments, TraitImpl, TraitImplExtra, RustEnumValue,
                 RustProcessedTypeInfo, RustMethodDocItem, RustQtReceiverDeclaration,
                 RustQtReceiverType, RustQtSlotWrapper, RustMethodSelfArgKind,
-                RustMethodCaptionStrategy, RustFFIFunction, RustFFIArgument, TraitAssociatedType};
+                RustMethodCaptionStrategy, RustFFIFunction, RustFFIArgument, TraitAssociatedType,
+                RustAssociatedConst, RustAnonymousEnumConst, RustDebugField,
+                RustComputedConstant,
+                RustQtEventFilterWrapper, RustFunctorWrapper, RustStdVariantEnum,
+                RustStdVariantEnumItem, RustStdMapWrapper, RustValidityCheck,
+                RustTypeIndex, RustAllocationBenchmark};
 use rust_type::{RustName, RustType, CompleteType, RustTypeIndirection, RustToCTypeConversion};
 use common::string_utils::{CaseOperations, WordIterator};
 use common::utils::{add_to_multihash, MapIfOk};
@@ -215,6 +224,100 @@ fn prepare_enum_values(values: &[CppEnumValue]) -> Vec<RustEnumValue> {
   result
 }
 
+/// Returns full Rust path of the variant of the enum `enum_name` corresponding to
+/// `item`, a possibly qualified C++ enum value (e.g. `Qt::AlignLeft`).
+/// `rust_values` are the variants of the enum.
+fn enum_value_to_rust(item: &str,
+                      rust_values: &[RustEnumValue],
+                      enum_name: &RustName,
+                      crate_name: &str)
+                      -> Result<String> {
+  let item = item.trim_matches(|c: char| c == '(' || c == ')');
+  let cpp_value_name = item
+    .split("::")
+    .last()
+    .chain_err(|| "split can't return empty iterator")?;
+  let rust_value = rust_values
+    .iter()
+    .find(|value| {
+            value
+              .cpp_docs
+              .iter()
+              .any(|doc| doc.variant_name == cpp_value_name)
+          })
+    .chain_err(|| format!("unknown enum value: {}", item))?;
+  Ok(format!("{}::{}", enum_name.full_name(Some(crate_name)), rust_value.name))
+}
+
+/// Returns true if `expression` (with whitespace removed) is a default-constructed
+/// value of a type, like `Qt::Alignment()` or `QFlags<Qt::AlignmentFlag>()`.
+/// Function calls like `Qt::defaultAlignment()` are told apart from types
+/// by the case of the first letter, following the Qt naming convention.
+fn is_default_constructed_value(expression: &str) -> bool {
+  if !expression.ends_with("()") {
+    return false;
+  }
+  let type_name = &expression[..expression.len() - 2];
+  if type_name.starts_with("QFlags<") {
+    return true;
+  }
+  let last_name = type_name.split("::").last().unwrap_or(type_name);
+  last_name.chars().next().map_or(false, |c| c.is_uppercase()) &&
+  last_name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Converts `expression`, the C++ default value of an argument of enum type,
+/// to equivalent Rust code. `cpp_values` are the values of the enum,
+/// and `enum_name` is its Rust name. Only enum values are supported.
+fn enum_default_value_to_rust(expression: &str,
+                              cpp_values: &[CppEnumValue],
+                              enum_name: &RustName,
+                              crate_name: &str)
+                              -> Result<String> {
+  enum_value_to_rust(&expression.replace(" ", ""),
+                     &prepare_enum_values(cpp_values),
+                     enum_name,
+                     crate_name)
+}
+
+/// Converts `expression`, the C++ default value of an argument of `QFlags<T>` type,
+/// to equivalent Rust code. `cpp_values` are the values of the enum `T`,
+/// and `enum_name` is its Rust name. Supported expressions are enum values
+/// combined with `|` (e.g. `Qt::AlignLeft | Qt::AlignVCenter`) and empty flags
+/// (`0` or `QFlags<T>()`). `0` is not accepted if `is_scoped` is true
+/// because scoped enums are not implicitly convertible from integers.
+fn flags_default_value_to_rust(expression: &str,
+                               cpp_values: &[CppEnumValue],
+                               is_scoped: bool,
+                               enum_name: &RustName,
+                               crate_name: &str)
+                               -> Result<String> {
+  let flags_name = RustName::new(vec!["qt_core".to_string(),
+                                      "flags".to_string(),
+                                      "Flags".to_string()])?
+    .full_name(Some(crate_name));
+  let expression = expression.replace(" ", "");
+  if expression == "0" && is_scoped {
+    return Err(format!("integer default value is not allowed for scoped enum flags: {}",
+                       expression)
+                 .into());
+  }
+  if expression == "0" || is_default_constructed_value(&expression) {
+    return Ok(format!("{}::from_int(0)", flags_name));
+  }
+  let rust_values = prepare_enum_values(cpp_values);
+  let mut items = Vec::new();
+  for item in expression.split('|') {
+    items.push(enum_value_to_rust(item, &rust_values, enum_name, crate_name)
+                 .chain_err(|| format!("unsupported flags default value: {}", expression))?);
+  }
+  let mut result = format!("{}::from_enum({})", flags_name, items[0]);
+  for item in &items[1..] {
+    result = format!("{} | {}", result, item);
+  }
+  Ok(result)
+}
+
 /// Generator of the Rust public API of the crate.
 pub struct RustGenerator<'a> {
   /// Data collected on previous step of the generator workflow
@@ -223,6 +326,8 @@ pub struct RustGenerator<'a> {
   top_module_names: HashMap<String, RustName>,
   /// Type wrappers created for this crate
   processed_types: Vec<RustProcessedTypeInfo>,
+  /// Type wrappers found in all dependencies, indexed by C++ name
+  dependency_types: RustTypeIndex<'a>,
 }
 
 /// Results of adapting API for Rust wrapper.
@@ -234,6 +339,11 @@ pub struct RustGeneratorOutput {
   pub ffi_functions: Vec<(String, Vec<RustFFIFunction>)>,
   /// List of processed C++ types and their corresponding Rust names
   pub processed_types: Vec<RustProcessedTypeInfo>,
+  /// Rust types corresponding to C++ documentation pages
+  /// of this crate and its dependencies
+  pub doc_links: doc_formatter::DocLinks,
+  /// Classes compared by the allocation benchmark
+  pub allocation_benchmarks: Vec<RustAllocationBenchmark>,
 }
 
 // TODO: implement removal of arbitrary prefixes (#25)
@@ -253,6 +363,40 @@ pub struct RustGeneratorInputData<'a> {
   pub remove_qt_prefix: bool,
   /// List of namespaces to filter out during code generation
   pub filtered_namespaces: Vec<String>,
+  /// C++ namespaces and paths of Rust modules replacing them,
+  /// as in `Config::add_namespace_mapping`
+  pub namespace_mappings: HashMap<String, Vec<String>>,
+  /// Methods returning owned `void*` pointers and Rust paths
+  /// to the corresponding free functions
+  pub owned_void_ptr_returns: HashMap<String, String>,
+  /// Methods returning pointers to objects owned by the caller
+  pub owned_ptr_returns: Vec<String>,
+  /// Constructors that may fail, as in `Config::add_fallible_constructor`
+  pub fallible_constructors: Vec<CppFallibleConstructor>,
+  /// Methods taking `self` by value and returning it, as in `Config::add_consuming_method`
+  pub consuming_methods: Vec<String>,
+  /// Methods filling `char*` buffers and names of
+  /// their buffer and length arguments
+  pub string_buffer_outs: HashMap<String, (String, String)>,
+  /// Methods filling fixed-size arrays passed by reference
+  /// and names of these arguments
+  pub array_out_params: HashMap<String, String>,
+  /// Methods returning references borrowed from one of their arguments
+  /// and indexes of these arguments
+  pub return_lifetimes: HashMap<String, usize>,
+  /// Methods taking ownership of objects passed as pointers
+  /// and indexes of these arguments
+  pub ownership_transfer_args: HashMap<String, Vec<usize>>,
+  /// C++ names of classes mapped to existing Rust types,
+  /// as in `Config::add_type_mapping`
+  pub type_mappings: HashMap<String, (RustName, RustToCTypeConversion)>,
+  /// Classes that can be copied using a virtual method and
+  /// names of these methods
+  pub virtual_clones: HashMap<String, String>,
+  /// Scoped enums allowed to implement `FlaggableEnum`
+  pub flaggable_scoped_enums: Vec<String>,
+  /// Policy determining which methods are `unsafe`
+  pub safety_policy: SafetyPolicy,
 }
 
 impl<'a> RustGeneratorInputData<'a> {
@@ -260,9 +404,14 @@ impl<'a> RustGeneratorInputData<'a> {
   #[cfg_attr(feature="clippy", allow(extend_from_slice))]
   #[cfg_attr(feature="clippy", allow(block_in_if_condition_stmt))]
   pub fn run(self) -> Result<RustGeneratorOutput> {
+    let dependency_types = RustTypeIndex::new(self
+                                                .dependency_types
+                                                .iter()
+                                                .flat_map(|&x| x.iter()));
     let mut generator = RustGenerator {
       top_module_names: HashMap::new(),
       processed_types: Vec::new(),
+      dependency_types: dependency_types,
       input_data: self,
     };
     generator.top_module_names = generator.calc_top_module_names()?;
@@ -276,6 +425,9 @@ impl<'a> RustGeneratorInputData<'a> {
       }
       let mut module_names_set = HashSet::new();
       for item in &generator.processed_types {
+        if item.mapped_conversion.is_some() {
+          continue;
+        }
         if !module_names_set.contains(&item.rust_name.parts[1]) {
           module_names_set.insert(item.rust_name.parts[1].clone());
         }
@@ -289,7 +441,8 @@ impl<'a> RustGeneratorInputData<'a> {
                   .iter()
                   .any(|t| {
                          t.cpp_name == info.class_type.name &&
-                         t.cpp_template_arguments == info.class_type.template_arguments
+                         t.cpp_template_arguments == info.class_type.template_arguments &&
+                         t.mapped_conversion.is_none()
                        }) {
               log::llog(log::DebugRustSkips,
                         || "Warning: method is skipped because class type is not available in Rust:");
@@ -300,6 +453,9 @@ impl<'a> RustGeneratorInputData<'a> {
           true
         })
         .collect();
+      if !generator.input_data.namespace_mappings.is_empty() {
+        generator.check_namespace_mapping_collisions(&cpp_methods)?;
+      }
       for method in cpp_methods.clone() {
         if method.cpp_method.class_membership.is_none() {
           let rust_name = generator.free_function_rust_name(&method.cpp_method)?;
@@ -347,7 +503,7 @@ impl<'a> RustGeneratorInputData<'a> {
     }
     let mut any_not_declared = false;
     for type1 in &generator.processed_types {
-      if !type1.is_declared_in(&modules) {
+      if type1.mapped_conversion.is_none() && !type1.is_declared_in(&modules) {
         log::error(format!("type is not processed: {:?}", type1));
         any_not_declared = true;
       }
@@ -355,10 +511,28 @@ impl<'a> RustGeneratorInputData<'a> {
     if any_not_declared {
       return Err(unexpected("unprocessed cpp types left").into());
     }
+    let mut doc_links = doc_formatter::DocLinks::new();
+    for type1 in generator
+          .input_data
+          .dependency_types
+          .iter()
+          .flat_map(|x| x.iter())
+          .chain(generator.processed_types.iter()) {
+      if type1.mapped_conversion.is_some() {
+        continue;
+      }
+      if let Some(ref cpp_doc) = type1.cpp_doc {
+        let page_url = cpp_doc.url.split('#').next().unwrap();
+        doc_links.insert(page_url.to_string(), type1.rust_name.clone());
+      }
+    }
+    let allocation_benchmarks = generator.calc_allocation_benchmarks()?;
     Ok(RustGeneratorOutput {
          ffi_functions: generator.generate_ffi_functions(),
          modules: modules,
          processed_types: generator.processed_types,
+         doc_links: doc_links,
+         allocation_benchmarks: allocation_benchmarks,
        })
   }
 }
@@ -382,10 +556,19 @@ struct ProcessFunctionsResult {
   overloading_types: Vec<RustTypeDeclaration>,
 }
 
+/// Returns true if any of `arguments` is a closure passed to
+/// a `std::function` argument.
+fn has_closure_arguments(arguments: &[RustMethodArgument]) -> bool {
+  arguments
+    .iter()
+    .any(|arg| arg.argument_type.rust_api_to_c_conversion ==
+               RustToCTypeConversion::ClosureToCppClosure)
+}
+
 /// Generates `CompleteType` from `CppFfiType`, adding
 /// Rust API type, Rust FFI type and conversion between them.
 fn complete_type(processed_types: &[RustProcessedTypeInfo],
-                 dependency_types: &[&[RustProcessedTypeInfo]],
+                 dependency_types: &RustTypeIndex,
                  cpp_ffi_type: &CppFfiType,
                  argument_meaning: &CppFfiArgumentMeaning,
                  is_template_argument: bool,
@@ -412,9 +595,7 @@ fn complete_type(processed_types: &[RustProcessedTypeInfo],
       CppIndirectionChange::ValueToPointer => {
         assert!(indirection == &RustTypeIndirection::Ptr);
         if argument_meaning == &CppFfiArgumentMeaning::ReturnValue {
-          if let Some(info) = find_type_info(processed_types,
-                                             dependency_types,
-                                             |x| &x.rust_name == base) {
+          if let Some(info) = find_type_info_by_rust_name(processed_types, dependency_types, base) {
             match info.kind {
               RustTypeWrapperKind::Struct { ref is_deletable, .. } => {
                 if !*is_deletable {
@@ -480,9 +661,182 @@ fn complete_type(processed_types: &[RustProcessedTypeInfo],
         }
         rust_api_to_c_conversion = RustToCTypeConversion::RefToPtr;
       }
-      CppIndirectionChange::QFlagsToUInt => {}
+      CppIndirectionChange::RValueReferenceToPointer => {
+        // the moved object is consumed by value (or by `CppBox` if
+        // the type is not stack-allocated) and dropped after the call
+        assert!(indirection == &RustTypeIndirection::Ptr);
+        let is_stack_allocated = match find_type_info_by_rust_name(processed_types,
+                                                                   dependency_types,
+                                                                   base) {
+          Some(info) => {
+            match info.kind {
+              RustTypeWrapperKind::Struct { ref size_const_name, .. } => {
+                size_const_name.is_some()
+              }
+              RustTypeWrapperKind::Enum { .. } => {
+                return Err(unexpected("class type expected here").into())
+              }
+            }
+          }
+          None => {
+            return Err(unexpected("find_type_info failed in complete_type() after success in \
+                                   ffi_type()")
+                           .into());
+          }
+        };
+        *indirection = RustTypeIndirection::None;
+        *is_const = false;
+        if is_stack_allocated {
+          rust_api_to_c_conversion = RustToCTypeConversion::ValueToPtr;
+        } else {
+          rust_api_to_c_conversion = RustToCTypeConversion::CppBoxToPtr;
+          let new_generic_argument = RustType::Common {
+            base: base.clone(),
+            generic_arguments: generic_arguments.clone(),
+            is_const: false,
+            is_const2: false,
+            indirection: RustTypeIndirection::None,
+          };
+          *base = RustName::new(vec!["cpp_utils".to_string(), "CppBox".to_string()])?;
+          *generic_arguments = Some(vec![new_generic_argument]);
+        }
+      }
+      CppIndirectionChange::UniquePtrToPtr => {
+        // the object released from the unique_ptr is owned by the caller
+        assert!(indirection == &RustTypeIndirection::Ptr);
+        match find_type_info_by_rust_name(processed_types, dependency_types, base) {
+          Some(info) => {
+            match info.kind {
+              RustTypeWrapperKind::Struct { ref is_deletable, .. } => {
+                if !*is_deletable {
+                  return Err(format!("{} is not deletable", base.full_name(None)).into());
+                }
+              }
+              RustTypeWrapperKind::Enum { .. } => {
+                return Err(unexpected("class type expected here").into())
+              }
+            }
+          }
+          None => {
+            return Err(unexpected("find_type_info failed in complete_type() after success in \
+                                   ffi_type()")
+                           .into());
+          }
+        }
+        *indirection = RustTypeIndirection::None;
+        rust_api_to_c_conversion = RustToCTypeConversion::CppBoxToPtr;
+        let new_generic_argument = RustType::Common {
+          base: base.clone(),
+          generic_arguments: generic_arguments.clone(),
+          is_const: false,
+          is_const2: false,
+          indirection: RustTypeIndirection::None,
+        };
+        *base = RustName::new(vec!["cpp_utils".to_string(), "CppBox".to_string()])?;
+        *generic_arguments = Some(vec![new_generic_argument]);
+      }
+      CppIndirectionChange::QFlagsToUInt |
+      CppIndirectionChange::QCharToUShort |
+      CppIndirectionChange::StringViewToStrRef |
+      CppIndirectionChange::SpanToSliceRef |
+      CppIndirectionChange::InitializerListToVecOut |
+      CppIndirectionChange::StdFunctionToClosure => {}
+    }
+  }
+  if let RustType::Array { ref mut indirection, .. } = rust_api_type {
+    if cpp_ffi_type.conversion == CppIndirectionChange::ReferenceToPointer {
+      if indirection != &RustTypeIndirection::Ptr {
+        return Err(unexpected("invalid indirection for ReferenceToPointer").into());
+      }
+      *indirection = RustTypeIndirection::Ref { lifetime: None };
+      rust_api_to_c_conversion = RustToCTypeConversion::RefToPtr;
+    } else if cpp_ffi_type.conversion == CppIndirectionChange::ValueToPointer {
+      // `std::array` passed or returned by value
+      if indirection != &RustTypeIndirection::Ptr {
+        return Err(unexpected("invalid indirection for ValueToPointer").into());
+      }
+      if argument_meaning == &CppFfiArgumentMeaning::ReturnValue &&
+         allocation_place != &ReturnValueAllocationPlace::Stack {
+        return Err("std::array return values can only be allocated on stack".into());
+      }
+      *indirection = RustTypeIndirection::None;
+      rust_api_to_c_conversion = RustToCTypeConversion::ValueToPtr;
     }
   }
+  if cpp_ffi_type.conversion == CppIndirectionChange::StringViewToStrRef {
+    // the slice is borrowed for the duration of the call,
+    // and the C++ side receives a view of its bytes without copying
+    rust_api_to_c_conversion = RustToCTypeConversion::StrToStrRef;
+    rust_api_type = RustType::Common {
+      base: RustName::new(vec!["str".to_string()])?,
+      generic_arguments: None,
+      indirection: RustTypeIndirection::Ref { lifetime: None },
+      is_const: true,
+      is_const2: false,
+    };
+  }
+  if cpp_ffi_type.conversion == CppIndirectionChange::SpanToSliceRef {
+    // the C++ side receives a span over the slice's elements without copying,
+    // and a span of non-const elements requires a mutable slice
+    let element = if let CppTypeBase::Span { ref element } = cpp_ffi_type.original_type.base {
+      element
+    } else {
+      return Err(unexpected("invalid original type for SpanToSliceRef").into());
+    };
+    rust_api_to_c_conversion = RustToCTypeConversion::SliceToSliceRef;
+    rust_api_type = RustType::Array {
+      element: Box::new(ffi_type(processed_types, dependency_types, element)?),
+      size: None,
+      is_const: element.is_const,
+      indirection: RustTypeIndirection::Ref { lifetime: None },
+    };
+  }
+  if cpp_ffi_type.conversion == CppIndirectionChange::InitializerListToVecOut {
+    // the backing array of the list may be destroyed right after the call,
+    // so the elements are copied to an owned vector
+    let element = if let CppTypeBase::InitializerList { ref element } =
+      cpp_ffi_type.original_type.base {
+      element
+    } else {
+      return Err(unexpected("invalid original type for InitializerListToVecOut").into());
+    };
+    rust_api_to_c_conversion = RustToCTypeConversion::VecToVecOut;
+    rust_api_type = RustType::Common {
+      base: RustName::new(vec!["Vec".to_string()])?,
+      generic_arguments: Some(vec![ffi_type(processed_types, dependency_types, element)?]),
+      indirection: RustTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+    };
+  }
+  if cpp_ffi_type.conversion == CppIndirectionChange::StdFunctionToClosure {
+    // the closure is moved to the C++ side and dropped when the last copy
+    // of the `std::function` is destroyed, so it must not borrow anything
+    let function = match cpp_ffi_type.original_type.base {
+      CppTypeBase::Class(ref class_type) => class_type.std_function_type(),
+      _ => None,
+    };
+    let function = function
+      .chain_err(|| unexpected("invalid original type for StdFunctionToClosure"))?;
+    rust_api_to_c_conversion = RustToCTypeConversion::ClosureToCppClosure;
+    rust_api_type = RustType::Closure {
+      return_type: Box::new(ffi_type(processed_types, dependency_types, &function.return_type)?),
+      arguments: function
+        .arguments
+        .iter()
+        .map_if_ok(|arg| ffi_type(processed_types, dependency_types, arg))?,
+    };
+  }
+  if cpp_ffi_type.conversion == CppIndirectionChange::QCharToUShort {
+    rust_api_to_c_conversion = RustToCTypeConversion::CharToUShort;
+    rust_api_type = RustType::Common {
+      base: RustName::new(vec!["char".to_string()])?,
+      generic_arguments: None,
+      indirection: RustTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+    };
+  }
   if cpp_ffi_type.conversion == CppIndirectionChange::QFlagsToUInt {
     rust_api_to_c_conversion = RustToCTypeConversion::QFlagsToUInt;
     let enum_type = if let CppTypeBase::Class(CppTypeClassBase {
@@ -495,9 +849,15 @@ fn complete_type(processed_types: &[RustProcessedTypeInfo],
         return Err("QFlags type must have exactly 1 template argument".into());
       }
       if let CppTypeBase::Enum { ref name } = args[0].base {
-        match find_type_info(processed_types, dependency_types, |x| &x.cpp_name == name) {
+        match find_type_info_by_cpp_name(processed_types, dependency_types, name, &None) {
           None => return Err(format!("type has no Rust equivalent: {}", name).into()),
-          Some(info) => info.rust_name.clone(),
+          Some(info) => {
+            if let RustTypeWrapperKind::Enum { is_flaggable: false, is_scoped: true, .. } =
+              info.kind {
+              return Err(format!("scoped enum is not allowed in QFlags: {}", name).into());
+            }
+            info.rust_name.clone()
+          }
         }
       } else {
         return Err(unexpected("invalid original type for QFlags").into());
@@ -522,6 +882,18 @@ fn complete_type(processed_types: &[RustProcessedTypeInfo],
     }
   }
 
+  if let CppTypeBase::Class(ref class_base) = cpp_ffi_type.ffi_type.base {
+    let info = find_type_info_by_cpp_name(processed_types,
+                                          dependency_types,
+                                          &class_base.name,
+                                          &class_base.template_arguments);
+    if let Some(&RustProcessedTypeInfo { mapped_conversion: Some(ref conversion), .. }) = info {
+      // the conversion requested by the user replaces the default one
+      rust_api_type = mapped_api_type(&rust_ffi_type, conversion)?;
+      rust_api_to_c_conversion = conversion.clone();
+    }
+  }
+
   Ok(CompleteType {
        cpp_ffi_type: cpp_ffi_type.ffi_type.clone(),
        cpp_type: cpp_ffi_type.original_type.clone(),
@@ -532,28 +904,89 @@ fn complete_type(processed_types: &[RustProcessedTypeInfo],
      })
 }
 
-fn find_type_info<'a, F>(processed_types: &'a [RustProcessedTypeInfo],
-                         dependency_types: &'a [&'a [RustProcessedTypeInfo]],
-                         f: F)
-                         -> Option<&'a RustProcessedTypeInfo>
-  where F: Fn(&RustProcessedTypeInfo) -> bool
-{
-  match processed_types.iter().find(|x| f(x)) {
-    None => {
-      for dep in dependency_types {
-        if let Some(r) = dep.iter().find(|x| f(x)) {
-          return Some(r);
-        }
+/// Returns Rust API type of a class mapped to an existing Rust type
+/// by `Config::add_type_mapping`, given its FFI type and the conversion
+/// requested for it.
+fn mapped_api_type(rust_ffi_type: &RustType,
+                   conversion: &RustToCTypeConversion)
+                   -> Result<RustType> {
+  if conversion == &RustToCTypeConversion::None {
+    return Ok(rust_ffi_type.clone());
+  }
+  let mut rust_api_type = rust_ffi_type.clone();
+  if let RustType::Common {
+           ref mut indirection,
+           ref mut base,
+           ref mut generic_arguments,
+           ..
+         } = rust_api_type {
+    if indirection != &RustTypeIndirection::Ptr {
+      return Err(format!("mapped type must be passed by pointer: {:?}", rust_ffi_type).into());
+    }
+    match *conversion {
+      RustToCTypeConversion::RefToPtr => {
+        *indirection = RustTypeIndirection::Ref { lifetime: None };
       }
-      None
-    }//dependency_types.iter().find(|x| x.iter().find(|y| f(y))),
+      RustToCTypeConversion::ValueToPtr => {
+        *indirection = RustTypeIndirection::None;
+      }
+      RustToCTypeConversion::CppBoxToPtr => {
+        *indirection = RustTypeIndirection::None;
+        let new_generic_argument = RustType::Common {
+          base: base.clone(),
+          generic_arguments: generic_arguments.clone(),
+          is_const: false,
+          is_const2: false,
+          indirection: RustTypeIndirection::None,
+        };
+        *base = RustName::new(vec!["cpp_utils".to_string(), "CppBox".to_string()])?;
+        *generic_arguments = Some(vec![new_generic_argument]);
+      }
+      _ => {
+        return Err(format!("conversion is not supported for mapped types: {:?}",
+                           conversion)
+                       .into())
+      }
+    }
+  } else {
+    return Err(unexpected("RustType::Common expected for mapped type").into());
+  }
+  Ok(rust_api_type)
+}
+
+/// Returns the type with Rust name `rust_name` from types of this crate
+/// or its dependencies.
+fn find_type_info_by_rust_name<'a>(processed_types: &'a [RustProcessedTypeInfo],
+                                   dependency_types: &'a RustTypeIndex,
+                                   rust_name: &RustName)
+                                   -> Option<&'a RustProcessedTypeInfo> {
+  match processed_types.iter().find(|x| &x.rust_name == rust_name) {
+    None => dependency_types.find_by_rust_name(rust_name),
+    Some(info) => Some(info),
+  }
+}
+
+/// Returns the type corresponding to C++ type `cpp_name` with
+/// template arguments `cpp_template_arguments` from types of this crate
+/// or its dependencies.
+fn find_type_info_by_cpp_name<'a>(processed_types: &'a [RustProcessedTypeInfo],
+                                  dependency_types: &'a RustTypeIndex,
+                                  cpp_name: &str,
+                                  cpp_template_arguments: &Option<Vec<CppType>>)
+                                  -> Option<&'a RustProcessedTypeInfo> {
+  match processed_types
+          .iter()
+          .find(|x| {
+                  &x.cpp_name == cpp_name && &x.cpp_template_arguments == cpp_template_arguments
+                }) {
+    None => dependency_types.find_type(cpp_name, cpp_template_arguments),
     Some(info) => Some(info),
   }
 }
 
 /// Converts `CppType` to its exact Rust equivalent (FFI-compatible)
 fn ffi_type(processed_types: &[RustProcessedTypeInfo],
-            dependency_types: &[&[RustProcessedTypeInfo]],
+            dependency_types: &RustTypeIndex,
             cpp_ffi_type: &CppType)
             -> Result<RustType> {
   let rust_name = match cpp_ffi_type.base {
@@ -599,16 +1032,28 @@ fn ffi_type(processed_types: &[RustProcessedTypeInfo],
       RustName::new(vec![if *is_signed { "isize" } else { "usize" }.to_string()])?
     }
     CppTypeBase::Enum { ref name } => {
-      match find_type_info(processed_types, dependency_types, |x| &x.cpp_name == name) {
+      match find_type_info_by_cpp_name(processed_types, dependency_types, name, &None) {
         None => return Err(format!("type has no Rust equivalent: {}", name).into()),
         Some(info) => info.rust_name.clone(),
       }
     }
+    CppTypeBase::Class(ref name_and_args) if name_and_args.name == STR_REF_FFI_TYPE_NAME => {
+      RustName::new(vec!["cpp_utils".to_string(), "StrRef".to_string()])?
+    }
+    CppTypeBase::Class(ref name_and_args) if name_and_args.name == SLICE_REF_FFI_TYPE_NAME => {
+      RustName::new(vec!["cpp_utils".to_string(), "SliceRef".to_string()])?
+    }
+    CppTypeBase::Class(ref name_and_args) if name_and_args.name == VEC_OUT_FFI_TYPE_NAME => {
+      RustName::new(vec!["cpp_utils".to_string(), "VecOut".to_string()])?
+    }
+    CppTypeBase::Class(ref name_and_args) if name_and_args.name == CLOSURE_FFI_TYPE_NAME => {
+      RustName::new(vec!["cpp_utils".to_string(), "Closure".to_string()])?
+    }
     CppTypeBase::Class(ref name_and_args) => {
-      match find_type_info(processed_types, dependency_types, |x| {
-        &x.cpp_name == &name_and_args.name &&
-        &x.cpp_template_arguments == &name_and_args.template_arguments
-      }) {
+      match find_type_info_by_cpp_name(processed_types,
+                                       dependency_types,
+                                       &name_and_args.name,
+                                       &name_and_args.template_arguments) {
         None => return Err(format!("type has no Rust equivalent: {:?}", name_and_args).into()),
         Some(info) => info.rust_name.clone(),
       }
@@ -617,10 +1062,14 @@ fn ffi_type(processed_types: &[RustProcessedTypeInfo],
                                    ref return_type,
                                    ref arguments,
                                    ref allows_variadic_arguments,
+                                   ref class_type,
                                  }) => {
       if *allows_variadic_arguments {
         return Err("function pointers with variadic arguments are not supported".into());
       }
+      if class_type.is_some() {
+        return Err("pointers to member functions are not supported".into());
+      }
       let mut rust_args = Vec::new();
       for arg in arguments {
         rust_args.push(ffi_type(processed_types, dependency_types, arg)?);
@@ -631,7 +1080,35 @@ fn ffi_type(processed_types: &[RustProcessedTypeInfo],
                   return_type: Box::new(rust_return_type),
                 });
     }
-    CppTypeBase::TemplateParameter { .. } => return Err(unexpected("invalid cpp type").into()),
+    CppTypeBase::Array {
+      ref element,
+      ref size,
+      ref is_std_array,
+    } => {
+      return Ok(RustType::Array {
+                  element: Box::new(ffi_type(processed_types, dependency_types, element)?),
+                  size: Some(*size),
+                  is_const: if *is_std_array {
+                    cpp_ffi_type.is_const
+                  } else {
+                    element.is_const
+                  },
+                  indirection: match cpp_ffi_type.indirection {
+                    CppTypeIndirection::None => RustTypeIndirection::None,
+                    CppTypeIndirection::Ptr => RustTypeIndirection::Ptr,
+                    _ => {
+                      return Err(format!("invalid FFI type indirection: {:?}",
+                                         cpp_ffi_type.indirection)
+                                     .into())
+                    }
+                  },
+                });
+    }
+    CppTypeBase::TemplateParameter { .. } |
+    CppTypeBase::StringView |
+    CppTypeBase::Span { .. } |
+    CppTypeBase::InitializerList { .. } |
+    CppTypeBase::UniquePtr { .. } => return Err(unexpected("invalid cpp type").into()),
   };
   Ok(RustType::Common {
        base: rust_name,
@@ -699,6 +1176,12 @@ impl<'aa> RustGenerator<'aa> {
                   methods: Vec::new(),
                   trait_impls: Vec::new(),
                   qt_receivers: Vec::new(),
+                  associated_consts: Vec::new(),
+                  anonymous_enum_consts: Vec::new(),
+                  computed_constants: Vec::new(),
+                  std_variant_enum: None,
+                  std_map_wrapper: None,
+                  validity_check: None,
                 },
                 is_public: info.is_public,
                 rust_doc: None,
@@ -722,20 +1205,149 @@ impl<'aa> RustGenerator<'aa> {
         template_arguments: info.cpp_template_arguments.clone(),
       };
       let mut good_methods = Vec::new();
+      let mut computed_constants = Vec::new();
+      let mut slicing_methods = Vec::new();
+      let mut variant_getters = Vec::new();
+      let mut map_accessors = Vec::new();
+      let mut stream_outputs = Vec::new();
+      let mut secondary_base_casts = Vec::new();
       let mut tmp_cpp_methods = Vec::new();
       for method in cpp_methods {
+        if let CppFfiMethodKind::Cast(ref cast) = method.kind {
+          if !cast.is_unsafe_static_cast() && cast.is_direct_static_cast() &&
+             !cast.is_primary_static_cast() &&
+             method.cpp_method.arguments.len() == 1 &&
+             method.cpp_method.arguments[0].argument_type.base ==
+             CppTypeBase::Class(class_type.clone()) {
+            // the cast itself is still processed as a free function
+            secondary_base_casts.push(method);
+          }
+        }
         if let Some(ref info) = method.cpp_method.class_membership {
           if &info.class_type == &class_type {
-            good_methods.push(method);
+            if let CppFfiMethodKind::ComputedTypeConstant { ref ffi_initializer } = method.kind {
+              computed_constants.push(RustComputedConstant {
+                name: self.method_rust_name(method)?.last_name()?.clone(),
+                ffi_function: method.c_name.clone(),
+                value_type: complete_type(&self.processed_types,
+                                          &self.dependency_types,
+                                          &method.c_signature.return_type,
+                                          &CppFfiArgumentMeaning::ReturnValue,
+                                          false,
+                                          &method.allocation_place)?
+                    .rust_api_type,
+                cpp_initializer: ffi_initializer.clone(),
+              });
+            } else if method.kind == CppFfiMethodKind::SlicingConversion {
+              slicing_methods.push(method);
+            } else if let CppFfiMethodKind::StdVariantGetIf { .. } = method.kind {
+              variant_getters.push(method);
+            } else if method.kind == CppFfiMethodKind::StdMapGet ||
+                      method.kind == CppFfiMethodKind::StdMapInsert ||
+                      method.kind == CppFfiMethodKind::StdMapNextEntry {
+              map_accessors.push(method);
+            } else if method.kind == CppFfiMethodKind::StreamOutput {
+              stream_outputs.push(method);
+            } else if method.kind == CppFfiMethodKind::AllocationBenchmark {
+              // only used by the allocation benchmark
+            } else {
+              good_methods.push(method);
+            }
             continue;
           }
         }
         tmp_cpp_methods.push(method);
       }
       cpp_methods = tmp_cpp_methods;
-      let functions_result =
+      let mut functions_result =
         self
-          .process_all_sibling_functions(good_methods.into_iter(), &methods_scope)?;
+          .process_all_sibling_functions(good_methods.iter().cloned(), &methods_scope)?;
+      // trait implementations can't be conditionally compiled per method,
+      // so methods behind C++ features don't participate in them
+      let good_methods: Vec<_> = good_methods
+        .into_iter()
+        .filter(|m| m.cpp_method.feature.is_none())
+        .collect();
+      match self.process_extend(&good_methods, &class_type, &methods_scope) {
+        Ok(mut r) => functions_result.trait_impls.append(&mut r),
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate Extend implementation: {}", msg))
+        }
+      }
+      match self.process_variant_conversions(&good_methods, &methods_scope) {
+        Ok(mut r) => functions_result.trait_impls.append(&mut r),
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate QVariant conversions: {}", msg))
+        }
+      }
+      for method in slicing_methods {
+        match self.process_slicing_conversion(method, &methods_scope) {
+          Ok(r) => functions_result.trait_impls.push(r),
+          Err(msg) => {
+            log::llog(log::DebugRustSkips,
+                      || format!("Failed to generate slicing conversion: {}", msg))
+          }
+        }
+      }
+      for method in secondary_base_casts {
+        match self.process_secondary_base_cast(method, &methods_scope) {
+          Ok(mut r) => functions_result.methods.append(&mut r),
+          Err(msg) => {
+            log::llog(log::DebugRustSkips,
+                      || format!("Failed to generate base class accessor: {}", msg))
+          }
+        }
+      }
+      match self.process_debug_impl(&good_methods, info, &methods_scope) {
+        Ok(mut r) => functions_result.trait_impls.append(&mut r),
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate Debug implementation: {}", msg))
+        }
+      }
+      match self.process_virtual_clone(&good_methods, info, &methods_scope) {
+        Ok(mut r) => functions_result.trait_impls.append(&mut r),
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate CppVirtualClone implementation: {}", msg))
+        }
+      }
+      match self.process_display_impl(&stream_outputs, &methods_scope) {
+        Ok(mut r) => functions_result.trait_impls.append(&mut r),
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate Display implementation: {}", msg))
+        }
+      }
+      let std_variant_enum = match self.process_std_variant_enum(&variant_getters, info) {
+        Ok(r) => r,
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate std::variant enum: {}", msg));
+          None
+        }
+      };
+      let std_map_wrapper = match self.process_std_map_wrapper(&map_accessors,
+                                                               &good_methods,
+                                                               info) {
+        Ok(r) => r,
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate std::map accessors: {}", msg));
+          None
+        }
+      };
+      let validity_check =
+        match self.process_validity_check(&good_methods, info, &functions_result.methods) {
+          Ok(r) => r,
+          Err(msg) => {
+            log::llog(log::DebugRustSkips,
+                      || format!("Failed to generate validity checks: {}", msg));
+            None
+          }
+        };
 
       let mut qt_receivers_by_name: HashMap<String, Vec<_>> = HashMap::new();
       if self
@@ -758,9 +1370,11 @@ impl<'aa> RustGenerator<'aa> {
                        .iter()) {
           if let Some(ref info) = method.class_membership {
             if &info.class_type == &class_type && (info.is_signal || info.is_slot) {
+              // the signature is only used to resolve names of overloaded receivers
+              let signature = method.c_signature(ReturnValueAllocationPlace::NotApplicable)?;
               add_to_multihash(&mut qt_receivers_by_name,
                                method.name.clone(),
-                               RustQtReceiverDeclaration {
+                               (RustQtReceiverDeclaration {
                                  type_name: method.name.to_class_case(),
                                  method_name: method.name.to_snake_case(),
                                  receiver_type: if info.is_signal {
@@ -775,7 +1389,7 @@ impl<'aa> RustGenerator<'aa> {
                                    .iter()
                                    .map_if_ok(|arg| -> Result<_> {
                 Ok(complete_type(&self.processed_types,
-                                 &self.input_data.dependency_types,
+                                 &self.dependency_types,
                                  &arg
                                     .argument_type
                                     .to_cpp_ffi_type(CppTypeRole::NotReturnType)?,
@@ -785,37 +1399,51 @@ impl<'aa> RustGenerator<'aa> {
                        .rust_api_type
                        .with_lifetime("static".to_string()))
               })?,
-                               });
+                                },
+                                signature));
             }
           }
         }
       }
-      let qt_receivers = qt_receivers_by_name
-        .into_iter()
-        .flat_map(|(_, receivers)| if receivers.len() == 1 {
-                    receivers
-                  } else {
-                    receivers
-                      .into_iter()
-                      .map(|r| {
-            let name = format!("{}_{}",
-                               r.method_name,
-                               r.arguments
-                                 .iter()
-                                 .map(|x| {
-                                        x.caption(&info.rust_name)
-                                          .expect("receiver argument caption failed")
-                                      })
-                                 .join("_"));
-            RustQtReceiverDeclaration {
-              type_name: name.to_class_case(),
-              method_name: name.to_snake_case(),
-              ..r
+      let mut qt_receivers = Vec::new();
+      for (name, mut receivers) in qt_receivers_by_name {
+        if receivers.len() == 1 {
+          qt_receivers.push(receivers.remove(0).0);
+          continue;
+        }
+        // overloaded receivers are named the same way as overloaded FFI functions
+        let mut found_strategy = None;
+        for strategy in MethodCaptionStrategy::all() {
+          let mut captions = HashSet::new();
+          let mut ok = true;
+          for &(_, ref signature) in &receivers {
+            if !captions.insert(signature.caption(strategy.clone())?) {
+              ok = false;
+              break;
             }
-          })
-                      .collect()
-                  })
-        .collect();
+          }
+          if ok {
+            found_strategy = Some(strategy);
+            break;
+          }
+        }
+        let strategy =
+          found_strategy.chain_err(|| format!("all caption strategies have failed for {}", name))?;
+        for (receiver, signature) in receivers {
+          let caption = signature.caption(strategy.clone())?;
+          let name = if caption.is_empty() {
+            receiver.method_name.clone()
+          } else {
+            format!("{}_{}", receiver.method_name, caption.to_snake_case())
+          };
+          qt_receivers.push(RustQtReceiverDeclaration {
+                              type_name: name.to_class_case(),
+                              method_name: name.to_snake_case(),
+                              ..receiver
+                            });
+        }
+      }
+      qt_receivers.sort_by(|a, b| a.method_name.cmp(&b.method_name));
 
       (GenerateTypeResult {
          main_type: RustTypeDeclaration {
@@ -828,6 +1456,12 @@ impl<'aa> RustGenerator<'aa> {
              methods: functions_result.methods,
              trait_impls: functions_result.trait_impls,
              qt_receivers: qt_receivers,
+             associated_consts: self.associated_enum_consts(info)?,
+             anonymous_enum_consts: self.anonymous_enum_consts(info),
+             computed_constants: computed_constants,
+             std_variant_enum: std_variant_enum,
+             std_map_wrapper: std_map_wrapper,
+             validity_check: validity_check,
            },
            is_public: info.is_public,
            rust_doc: None,
@@ -856,8 +1490,37 @@ impl<'aa> RustGenerator<'aa> {
     } else {
       let x = if method.cpp_method.is_constructor() {
         "new".to_string()
+      } else if method
+                  .cpp_method
+                  .class_membership
+                  .as_ref()
+                  .map_or(false, |info| info.is_move_assignment) {
+        "assign_moved".to_string()
+      } else if method.cpp_method.operator == Some(CppOperator::FunctionCall) &&
+                method
+                  .cpp_method
+                  .class_membership
+                  .as_ref()
+                  .map_or(false, |info| info.class_type.std_function_type().is_some()) {
+        // callable wrapper of `std::function`
+        "call".to_string()
       } else if let Some(ref operator) = method.cpp_method.operator {
         operator_rust_name(operator)?
+      } else if let Some(ref args) = method.cpp_method.template_arguments_values {
+        // instantiation of a template method
+        let mut parts = vec![method.cpp_method.name.to_snake_case()];
+        for arg in args {
+          if self.is_functor_wrapper(arg) {
+            // the function object's type is evident from the argument,
+            // so the method keeps its original name
+            continue;
+          }
+          let caption = arg.base.caption(TypeCaptionStrategy::Short)?;
+          parts.push(remove_qt_prefix_and_convert_case(&caption,
+                                                       Case::Snake,
+                                                       self.input_data.remove_qt_prefix));
+        }
+        parts.join("_")
       } else {
         method.cpp_method.name.to_snake_case()
       };
@@ -867,6 +1530,104 @@ impl<'aa> RustGenerator<'aa> {
   }
 
 
+  /// Returns true if `cpp_type` is a function object class generated
+  /// for `Config::add_functor_template_instantiation`.
+  fn is_functor_wrapper(&self, cpp_type: &CppType) -> bool {
+    if let CppTypeBase::Class(CppTypeClassBase { ref name, .. }) = cpp_type.base {
+      self
+        .input_data
+        .cpp_ffi_headers
+        .iter()
+        .any(|header| header.functor_wrappers.iter().any(|w| &w.class_name == name))
+    } else {
+      false
+    }
+  }
+
+  /// Returns Rust code of the default value of an argument of `cpp_type` type,
+  /// if the type is supported. Currently enums and `QFlags<T>` are supported.
+  fn argument_default_value(&self,
+                            cpp_type: &CppType,
+                            expression: &str)
+                            -> Result<Option<String>> {
+    let (enum_name, is_flags) = match cpp_type.base {
+      CppTypeBase::Enum { ref name } if cpp_type.indirection == CppTypeIndirection::None => {
+        (name, false)
+      }
+      CppTypeBase::Class(CppTypeClassBase {
+                           ref name,
+                           template_arguments: Some(ref args),
+                         }) if name == "QFlags" && args.len() == 1 => {
+        if let CppTypeBase::Enum { ref name } = args[0].base {
+          (name, true)
+        } else {
+          return Err(unexpected("invalid template argument for QFlags").into());
+        }
+      }
+      _ => return Ok(None),
+    };
+    let rust_name = find_type_info_by_cpp_name(&self.processed_types,
+                                               &self.dependency_types,
+                                               enum_name,
+                                               &None)
+        .chain_err(|| format!("type has no Rust equivalent: {}", enum_name))?
+        .rust_name
+        .clone();
+    let type_info = self
+      .input_data
+      .cpp_data
+      .find_type_info(|t| &t.name == enum_name)
+      .chain_err(|| format!("type info not found: {}", enum_name))?;
+    if let CppTypeKind::Enum { ref values, is_scoped, .. } = type_info.kind {
+      let code = if is_flags {
+        flags_default_value_to_rust(expression,
+                                    values,
+                                    is_scoped,
+                                    &rust_name,
+                                    &self.input_data.crate_name)?
+      } else {
+        enum_default_value_to_rust(expression, values, &rust_name, &self.input_data.crate_name)?
+      };
+      Ok(Some(code))
+    } else {
+      Err(format!("not an enum: {}", enum_name).into())
+    }
+  }
+
+  /// Returns Rust equivalents of default values of the arguments
+  /// omitted in `method`, formatted as `name = value`. They are only used
+  /// in documentation: the values are passed by the C++ wrapper function.
+  fn omitted_argument_defaults(&self, method: &CppAndFfiMethod) -> Vec<String> {
+    let all_arguments = match method.kind {
+      CppFfiMethodKind::RealWithOmittedArguments { arguments_before_omitting: Some(ref args) } => {
+        args
+      }
+      _ => return Vec::new(),
+    };
+    let mut result = Vec::new();
+    for arg in &all_arguments[method.cpp_method.arguments.len()..] {
+      if let Some(ref expression) = arg.default_value {
+        match self.argument_default_value(&arg.argument_type, expression) {
+          Ok(Some(code)) => {
+            result.push(format!("{} = {}",
+                                sanitize_rust_identifier(&arg.name.to_snake_case()),
+                                code));
+          }
+          Ok(None) => {}
+          Err(err) => {
+            log::llog(log::DebugGeneral, || {
+              format!("Can't convert default value of {} in {}: {}",
+                      arg.name,
+                      method.short_text(),
+                      err)
+            });
+          }
+        }
+      }
+    }
+    result
+  }
+
   /// Converts one function to a `RustSingleMethod`.
   fn generate_rust_single_method(&self,
                                  method: &CppAndFfiMethod,
@@ -877,7 +1638,7 @@ impl<'aa> RustGenerator<'aa> {
     for (arg_index, arg) in method.c_signature.arguments.iter().enumerate() {
       if arg.meaning != CppFfiArgumentMeaning::ReturnValue {
         let arg_type = complete_type(&self.processed_types,
-                                     &self.input_data.dependency_types,
+                                     &self.dependency_types,
                                      &arg.argument_type,
                                      &arg.meaning,
                                      false,
@@ -893,6 +1654,39 @@ impl<'aa> RustGenerator<'aa> {
                        });
       }
     }
+    if method
+         .cpp_method
+         .class_membership
+         .as_ref()
+         .map_or(false, |info| info.ref_qualifier == Some(CppRefQualifier::RValue)) {
+      // `&&`-qualified methods consume the object, so `self` is taken by value
+      // if the type can be stored by value in Rust
+      if let Some(arg) = arguments.iter_mut().find(|arg| arg.name == "self") {
+        if let RustType::Common {
+                 ref base,
+                 ref mut indirection,
+                 ..
+               } = arg.argument_type.rust_api_type {
+          let is_stack_allocated = match find_type_info_by_rust_name(&self.processed_types,
+                                                                     &self.dependency_types,
+                                                                     base) {
+            Some(info) => {
+              match info.kind {
+                RustTypeWrapperKind::Struct { ref size_const_name, .. } => {
+                  size_const_name.is_some()
+                }
+                RustTypeWrapperKind::Enum { .. } => false,
+              }
+            }
+            None => false,
+          };
+          if is_stack_allocated {
+            *indirection = RustTypeIndirection::None;
+            arg.argument_type.rust_api_to_c_conversion = RustToCTypeConversion::ValueToPtr;
+          }
+        }
+      }
+    }
     let (mut return_type, return_arg_index) = if let Some((arg_index, arg)) =
       method
         .c_signature
@@ -904,7 +1698,7 @@ impl<'aa> RustGenerator<'aa> {
       // FFI return type must be void
       assert!(method.c_signature.return_type == CppFfiType::void());
       (complete_type(&self.processed_types,
-                     &self.input_data.dependency_types,
+                     &self.dependency_types,
                      &arg.argument_type,
                      &arg.meaning,
                      false,
@@ -914,21 +1708,477 @@ impl<'aa> RustGenerator<'aa> {
       // none of the arguments has return value meaning,
       // so FFI return value must be used
       let return_type = complete_type(&self.processed_types,
-                                      &self.input_data.dependency_types,
+                                      &self.dependency_types,
                                       &method.c_signature.return_type,
                                       &CppFfiArgumentMeaning::ReturnValue,
                                       false,
                                       &method.allocation_place)?;
       (return_type, None)
     };
-    if return_type.rust_api_type.is_ref() && return_type.rust_api_type.lifetime().is_none() {
-      let mut found = false;
-      for arg in &arguments {
-        if let Some(lifetime) = arg.argument_type.rust_api_type.lifetime() {
-          return_type.rust_api_type = return_type.rust_api_type.with_lifetime(lifetime.clone());
-          found = true;
-          break;
-        }
+    if let Some(free_function) = self
+         .input_data
+         .owned_void_ptr_returns
+         .get(&method.cpp_method.full_name()) {
+      let void_ptr = CppType {
+        base: CppTypeBase::Void,
+        indirection: CppTypeIndirection::Ptr,
+        is_const: false,
+        is_const2: false,
+        is_volatile: false,
+      };
+      if return_type.cpp_type != void_ptr {
+        return Err(format!("owned void pointer return is configured for {}, \
+                            but the method doesn't return void*",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      return_type.rust_api_type = RustType::Common {
+        base: RustName::new(vec!["cpp_utils".to_string(), "OwnedVoidPtr".to_string()])?,
+        generic_arguments: None,
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_to_c_conversion =
+        RustToCTypeConversion::OwnedVoidPtrToPtr { free_function: free_function.clone() };
+    }
+    if self
+         .input_data
+         .owned_ptr_returns
+         .contains(&method.cpp_method.full_name()) {
+      let error = || {
+        format!("owned pointer return is configured for {}, \
+                 but the method doesn't return a pointer to a deletable class",
+                method.cpp_method.full_name())
+      };
+      if return_arg_index.is_some() ||
+         return_type.cpp_type.indirection != CppTypeIndirection::Ptr ||
+         return_type.cpp_type.is_const {
+        return Err(error().into());
+      }
+      let class_type = if let RustType::Common { ref base, .. } = return_type.rust_api_type {
+        match find_type_info_by_rust_name(&self.processed_types, &self.dependency_types, base) {
+          Some(&RustProcessedTypeInfo {
+                 kind: RustTypeWrapperKind::Struct { is_deletable: true, .. }, ..
+               }) => {
+            RustType::Common {
+              base: base.clone(),
+              generic_arguments: None,
+              indirection: RustTypeIndirection::None,
+              is_const: false,
+              is_const2: false,
+            }
+          }
+          _ => return Err(error().into()),
+        }
+      } else {
+        return Err(error().into());
+      };
+      return_type.rust_api_type = RustType::Common {
+        base: RustName::new(vec!["cpp_utils".to_string(), "CppBox".to_string()])?,
+        generic_arguments: Some(vec![class_type]),
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::CppBoxToPtr;
+    }
+    if self
+         .input_data
+         .fallible_constructors
+         .iter()
+         .any(|c| c.matches(&method.cpp_method)) {
+      if return_type.rust_api_to_c_conversion != RustToCTypeConversion::CppBoxToPtr {
+        return Err(format!("fallible constructor is configured for {}, \
+                            but the class is not allocated on heap",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      let error_type = RustType::Common {
+        base: RustName::new(vec!["cpp_utils".to_string(), "ConstructionError".to_string()])?,
+        generic_arguments: None,
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_type = RustType::Common {
+        base: RustName::new(vec!["std".to_string(), "result".to_string(), "Result".to_string()])?,
+        generic_arguments: Some(vec![return_type.rust_api_type.clone(), error_type]),
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::ResultCppBoxToPtr;
+    }
+    if let Some(&(ref buffer_arg, ref length_arg)) = self
+         .input_data
+         .string_buffer_outs
+         .get(&method.cpp_method.full_name()) {
+      fn is_integer(cpp_type: &CppType) -> bool {
+        cpp_type.indirection == CppTypeIndirection::None &&
+        match cpp_type.base {
+          CppTypeBase::BuiltInNumeric(ref numeric) => {
+            numeric.is_signed_integer() || numeric.is_unsigned_integer()
+          }
+          CppTypeBase::SpecificNumeric(CppSpecificNumericType {
+                                         kind: CppSpecificNumericTypeKind::Integer { .. }, ..
+                                       }) |
+          CppTypeBase::PointerSizedInteger { .. } => true,
+          _ => false,
+        }
+      }
+      let (buffer_ffi_index, length_ffi_index) = {
+        fn find_arg<'b>(arguments: &'b [RustMethodArgument],
+                        method: &CppAndFfiMethod,
+                        name: &str)
+                        -> Result<&'b RustMethodArgument> {
+          arguments
+            .iter()
+            .find(|arg| method.c_signature.arguments[arg.ffi_index].name == name)
+            .chain_err(|| {
+                         format!("string buffer argument not found in {}: {}",
+                                 method.cpp_method.full_name(),
+                                 name)
+                       })
+        }
+        let buffer = find_arg(&arguments, method, buffer_arg)?;
+        let char_ptr = CppType {
+          base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char),
+          indirection: CppTypeIndirection::Ptr,
+          is_const: false,
+          is_const2: false,
+          is_volatile: false,
+        };
+        if buffer.argument_type.cpp_type != char_ptr {
+          return Err(format!("string buffer argument of {} must have char* type",
+                             method.cpp_method.full_name())
+                         .into());
+        }
+        let length = find_arg(&arguments, method, length_arg)?;
+        if !is_integer(&length.argument_type.cpp_type) {
+          return Err(format!("string buffer length argument of {} must have integer type",
+                             method.cpp_method.full_name())
+                         .into());
+        }
+        (buffer.ffi_index, length.ffi_index)
+      };
+      if return_arg_index.is_some() || !is_integer(&return_type.cpp_type) {
+        return Err(format!("string buffer is configured for {}, \
+                            but the method doesn't return an integer",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      arguments.retain(|arg| {
+                         arg.ffi_index != buffer_ffi_index && arg.ffi_index != length_ffi_index
+                       });
+      return_type.rust_api_type = RustType::Common {
+        base: RustName::new(vec!["String".to_string()])?,
+        generic_arguments: None,
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::StringToBuffer {
+        buffer_ffi_index: buffer_ffi_index,
+        length_ffi_index: length_ffi_index,
+      };
+    }
+    if let Some(array_arg) = self
+         .input_data
+         .array_out_params
+         .get(&method.cpp_method.full_name()) {
+      let (array_ffi_index, array_type) = {
+        let arg = arguments
+          .iter()
+          .find(|arg| &method.c_signature.arguments[arg.ffi_index].name == array_arg)
+          .chain_err(|| {
+                       format!("array argument not found in {}: {}",
+                               method.cpp_method.full_name(),
+                               array_arg)
+                     })?;
+        // the array is passed as a mutable reference to a fixed-size array
+        let array_type = match arg.argument_type.rust_api_type {
+          RustType::Array {
+            ref element,
+            size: Some(size),
+            is_const: false,
+            indirection: RustTypeIndirection::Ref { .. },
+          } => {
+            RustType::Array {
+              element: element.clone(),
+              size: Some(size),
+              is_const: false,
+              indirection: RustTypeIndirection::None,
+            }
+          }
+          _ => {
+            return Err(format!("array argument of {} must be a non-const reference \
+                                to a fixed-size array",
+                               method.cpp_method.full_name())
+                           .into())
+          }
+        };
+        (arg.ffi_index, array_type)
+      };
+      if return_arg_index.is_some() || return_type.rust_api_type != RustType::EmptyTuple {
+        return Err(format!("array argument is configured for {}, \
+                            but the method doesn't return void",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      arguments.retain(|arg| arg.ffi_index != array_ffi_index);
+      return_type.rust_api_type = array_type;
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::ArrayToOutPtr {
+        array_ffi_index: array_ffi_index,
+      };
+    }
+    if let CppFfiMethodKind::ErrnoMethod { .. } = method.kind {
+      if return_arg_index.is_some() ||
+         return_type.rust_api_to_c_conversion != RustToCTypeConversion::None {
+        return Err(format!("errno method {} must return a numeric type or a pointer",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      let (error_flag_ffi_index, error_code_ffi_index) = {
+        let find_arg = |name: &str| {
+          arguments
+            .iter()
+            .find(|arg| method.c_signature.arguments[arg.ffi_index].name == name)
+            .map(|arg| arg.ffi_index)
+            .chain_err(|| format!("{} argument of {} not found", name, method.c_name))
+        };
+        (find_arg("error_flag")?, find_arg("error_code")?)
+      };
+      arguments.retain(|arg| {
+                         arg.ffi_index != error_flag_ffi_index &&
+                         arg.ffi_index != error_code_ffi_index
+                       });
+      let error_type = RustType::Common {
+        base: RustName::new(vec!["cpp_utils".to_string(), "CppError".to_string()])?,
+        generic_arguments: None,
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_type = RustType::Common {
+        base: RustName::new(vec!["std".to_string(), "result".to_string(), "Result".to_string()])?,
+        generic_arguments: Some(vec![return_type.rust_api_type.clone(), error_type]),
+        indirection: RustTypeIndirection::None,
+        is_const: false,
+        is_const2: false,
+      };
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::ErrnoResult {
+        error_flag_ffi_index: error_flag_ffi_index,
+        error_code_ffi_index: error_code_ffi_index,
+      };
+    }
+    let slice_max_length = match method.kind {
+      CppFfiMethodKind::BatchOperation { .. } => Some(None),
+      CppFfiMethodKind::VariadicSlice { max_count } => Some(Some(max_count)),
+      _ => None,
+    };
+    if let Some(max_length) = slice_max_length {
+      let (items_index, count_index) = {
+        let find_arg = |name: &str| {
+          arguments
+            .iter()
+            .position(|arg| method.c_signature.arguments[arg.ffi_index].name == name)
+            .chain_err(|| format!("{} argument of {} not found", name, method.c_name))
+        };
+        (find_arg("items")?, find_arg("count")?)
+      };
+      let count_ffi_index = arguments[count_index].ffi_index;
+      {
+        let items_type = &mut arguments[items_index].argument_type;
+        let element_type = if let RustType::Common {
+                 ref base,
+                 ref generic_arguments,
+                 ref indirection,
+                 is_const,
+                 ..
+               } = items_type.rust_api_type {
+          // elements of `T**` items are pointers
+          let is_ptr = indirection == &RustTypeIndirection::PtrPtr;
+          RustType::Common {
+            base: base.clone(),
+            generic_arguments: generic_arguments.clone(),
+            indirection: if is_ptr {
+              RustTypeIndirection::Ptr
+            } else {
+              RustTypeIndirection::None
+            },
+            is_const: is_ptr && is_const,
+            is_const2: false,
+          }
+        } else {
+          return Err(unexpected("unexpected type of slice items").into());
+        };
+        items_type.rust_api_type = RustType::Array {
+          element: Box::new(element_type),
+          size: None,
+          is_const: true,
+          indirection: RustTypeIndirection::Ref { lifetime: None },
+        };
+        items_type.rust_api_to_c_conversion =
+          RustToCTypeConversion::SliceToPtr {
+            length_ffi_index: count_ffi_index,
+            max_length: max_length,
+          };
+      }
+      arguments.remove(count_index);
+    }
+    if let Some(arg_indexes) = self
+         .input_data
+         .ownership_transfer_args
+         .get(&method.cpp_method.full_name()) {
+      for &arg_index in arg_indexes {
+        let error = || {
+          format!("ownership transfer is configured for argument {} of {}, \
+                   but it's not a pointer to a deletable class",
+                  arg_index,
+                  method.cpp_method.full_name())
+        };
+        let arg = arguments
+          .iter_mut()
+          .find(|arg| {
+                  method.c_signature.arguments[arg.ffi_index].meaning ==
+                  CppFfiArgumentMeaning::Argument(arg_index as i8)
+                })
+          .chain_err(|| {
+                       format!("ownership transfer argument not found in {}: {}",
+                               method.cpp_method.full_name(),
+                               arg_index)
+                     })?;
+        if arg.argument_type.cpp_type.indirection != CppTypeIndirection::Ptr ||
+           arg.argument_type.cpp_type.is_const {
+          return Err(error().into());
+        }
+        let class_type = match arg.argument_type.rust_api_type {
+          RustType::Common { ref base, .. } => {
+            match find_type_info_by_rust_name(&self.processed_types, &self.dependency_types, base) {
+              Some(&RustProcessedTypeInfo {
+                     kind: RustTypeWrapperKind::Struct { is_deletable: true, .. }, ..
+                   }) => {
+                RustType::Common {
+                  base: base.clone(),
+                  generic_arguments: None,
+                  indirection: RustTypeIndirection::None,
+                  is_const: false,
+                  is_const2: false,
+                }
+              }
+              _ => return Err(error().into()),
+            }
+          }
+          _ => return Err(error().into()),
+        };
+        arg.argument_type.rust_api_type = RustType::Common {
+          base: RustName::new(vec!["cpp_utils".to_string(), "CppBox".to_string()])?,
+          generic_arguments: Some(vec![class_type]),
+          indirection: RustTypeIndirection::None,
+          is_const: false,
+          is_const2: false,
+        };
+        arg.argument_type.rust_api_to_c_conversion = RustToCTypeConversion::CppBoxIntoPtr;
+      }
+    }
+    if self
+         .input_data
+         .consuming_methods
+         .contains(&method.cpp_method.full_name()) {
+      let error = |reason: &str| {
+        format!("consuming method is configured for {}, but {}",
+                method.cpp_method.full_name(),
+                reason)
+      };
+      let self_arg = arguments
+        .iter_mut()
+        .find(|arg| arg.name == "self")
+        .chain_err(|| error("the method is not an instance method"))?;
+      let self_type = match self_arg.argument_type.rust_api_type {
+        RustType::Common { ref base, .. } => {
+          match find_type_info_by_rust_name(&self.processed_types, &self.dependency_types, base) {
+            Some(&RustProcessedTypeInfo {
+                   kind: RustTypeWrapperKind::Struct { size_const_name: Some(_), .. }, ..
+                 }) => {
+              RustType::Common {
+                base: base.clone(),
+                generic_arguments: None,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+              }
+            }
+            _ => return Err(error("the class is not stack-allocated").into()),
+          }
+        }
+        _ => return Err(unexpected("invalid self argument type").into()),
+      };
+      // the object is returned back to the caller, so the C++ method
+      // may only return nothing or a reference to the object itself
+      let returns_self = match return_type.cpp_type.base {
+        CppTypeBase::Class(ref base) => {
+          return_type.cpp_type.indirection != CppTypeIndirection::None &&
+          method
+            .cpp_method
+            .class_membership
+            .as_ref()
+            .map_or(false, |info| &info.class_type == base)
+        }
+        _ => false,
+      };
+      if return_arg_index.is_some() || !(return_type.cpp_type.is_void() || returns_self) {
+        return Err(error("the method doesn't return void or a reference to the object").into());
+      }
+      self_arg.argument_type.rust_api_type = self_type.clone();
+      self_arg.argument_type.rust_api_to_c_conversion = RustToCTypeConversion::ValueToPtr;
+      return_type.rust_api_type = self_type;
+      return_type.rust_api_to_c_conversion = RustToCTypeConversion::ConsumedSelf;
+    }
+    if let Some(&arg_index) = self
+         .input_data
+         .return_lifetimes
+         .get(&method.cpp_method.full_name()) {
+      if !return_type.rust_api_type.is_ref() {
+        return Err(format!("return lifetime is configured for {}, \
+                            but the method doesn't return a reference",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      let arg = arguments
+        .iter_mut()
+        .find(|arg| {
+                method.c_signature.arguments[arg.ffi_index].meaning ==
+                CppFfiArgumentMeaning::Argument(arg_index as i8)
+              })
+        .chain_err(|| {
+                     format!("return lifetime argument not found in {}: {}",
+                             method.cpp_method.full_name(),
+                             arg_index)
+                   })?;
+      if !arg.argument_type.rust_api_type.is_ref() {
+        return Err(format!("return lifetime argument of {} must be a reference",
+                           method.cpp_method.full_name())
+                       .into());
+      }
+      let lifetime = match arg.argument_type.rust_api_type.lifetime() {
+        Some(lifetime) => lifetime.clone(),
+        None => "l0".to_string(),
+      };
+      arg.argument_type.rust_api_type = arg
+        .argument_type
+        .rust_api_type
+        .with_lifetime(lifetime.clone());
+      return_type.rust_api_type = return_type.rust_api_type.with_lifetime(lifetime);
+    }
+    if return_type.rust_api_type.is_ref() && return_type.rust_api_type.lifetime().is_none() {
+      let mut found = false;
+      for arg in &arguments {
+        if let Some(lifetime) = arg.argument_type.rust_api_type.lifetime() {
+          return_type.rust_api_type = return_type.rust_api_type.with_lifetime(lifetime.clone());
+          found = true;
+          break;
+        }
       }
       if !found {
         let mut next_lifetime_num = 0;
@@ -943,6 +2193,19 @@ impl<'aa> RustGenerator<'aa> {
           }
         }
         let return_lifetime = if next_lifetime_num == 0 {
+          if arguments.iter().any(|arg| {
+                                    arg.argument_type.rust_api_to_c_conversion ==
+                                    RustToCTypeConversion::CppBoxToPtr ||
+                                    arg.argument_type.rust_api_to_c_conversion ==
+                                    RustToCTypeConversion::ValueToPtr
+                                  }) {
+            // the reference may point into an object consumed by the call,
+            // so it would dangle as soon as the temporary is dropped
+            return Err(format!("method returns a reference but only receives objects \
+                                by value: {}",
+                               method.short_text())
+                           .into());
+          }
           log::llog(log::DebugGeneral, || {
             format!("Method returns a reference but doesn't receive a reference: {}",
                     method.short_text())
@@ -962,13 +2225,27 @@ impl<'aa> RustGenerator<'aa> {
              cpp_fn: method.short_text(),
              rust_fns: Vec::new(),
              doc: method.cpp_method.doc.clone(),
+             has_aliasing_references: method.cpp_method.has_aliasing_mutable_references(),
+             has_closure_arguments: has_closure_arguments(&arguments),
+             omitted_argument_defaults: self.omitted_argument_defaults(method),
            })
     } else {
       None
     };
-    let is_unsafe = arguments
+    let has_unsafe_arguments = arguments
       .iter()
       .any(|arg| arg.argument_type.rust_api_type.is_unsafe_argument());
+    // multiple `&mut` arguments of the same type may alias in C++,
+    // so the caller must guarantee they refer to different objects
+    let has_unsafe_arguments = has_unsafe_arguments ||
+                               method.cpp_method.has_aliasing_mutable_references();
+    let is_unsafe = match self.input_data.safety_policy {
+      SafetyPolicy::RawPointerArguments => has_unsafe_arguments,
+      SafetyPolicy::Strict => {
+        has_unsafe_arguments || return_type.rust_api_type.is_unsafe_argument()
+      }
+      SafetyPolicy::AllUnsafe => true,
+    };
     Ok(RustSingleMethod {
          name: self.method_rust_name(method)?,
          scope: scope.clone(),
@@ -995,6 +2272,8 @@ impl<'aa> RustGenerator<'aa> {
           let mut method = self.generate_rust_single_method(method, scope, true)?;
           method.name = RustName::new(vec!["drop".to_string()])?;
           method.scope = RustMethodScope::TraitImpl;
+          // `Drop::drop` can't be unsafe regardless of the safety policy
+          method.is_unsafe = false;
           Ok(TraitImpl {
                target_type: target_type.clone(),
                associated_types: Vec::new(),
@@ -1033,11 +2312,793 @@ impl<'aa> RustGenerator<'aa> {
     }
   }
 
+  /// Generates `Extend` and `FromIterator` trait implementations for a container
+  /// class that has an `append` method accepting a const reference to an item.
+  /// If the class also has `size` (or `count`) and `reserve` methods, the generated
+  /// implementations reserve space using the iterator's size hint.
+  /// `FromIterator` is only implemented if the class has a stack allocated
+  /// default constructor.
+  fn process_extend(&self,
+                    methods: &[&CppAndFfiMethod],
+                    class_type: &CppTypeClassBase,
+                    scope: &RustMethodScope)
+                    -> Result<Vec<TraitImpl>> {
+    let target_type = if let RustMethodScope::Impl { ref target_type } = *scope {
+      target_type
+    } else {
+      return Err(unexpected("class scope expected").into());
+    };
+    let is_int = |t: &CppType| {
+      t.indirection == CppTypeIndirection::None &&
+      t.base == CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int)
+    };
+    let is_non_static = |method: &CppAndFfiMethod, is_const: bool| {
+      method.allocation_place == ReturnValueAllocationPlace::NotApplicable &&
+      method
+        .cpp_method
+        .class_membership
+        .as_ref()
+        .map_or(false, |info| !info.is_static && info.is_const == is_const)
+    };
+    fn item_argument(method: &CppAndFfiMethod) -> Option<&CppFfiMethodArgument> {
+      method
+        .c_signature
+        .arguments
+        .iter()
+        .find(|arg| arg.meaning == CppFfiArgumentMeaning::Argument(0))
+    }
+    let mut append_methods: Vec<_> = methods
+      .iter()
+      .filter(|method| {
+        &method.cpp_method.name == "append" && is_non_static(method, false) &&
+        method.cpp_method.arguments.len() == 1 &&
+        item_argument(method).map_or(false, |arg| {
+          arg.argument_type.conversion == CppIndirectionChange::ReferenceToPointer &&
+          arg.argument_type.original_type.is_const
+        })
+      })
+      .collect();
+    if append_methods.len() > 1 {
+      // e.g. `QList::append(const T&)` and `QList::append(const QList<T>&)`
+      if let Some(ref args) = class_type.template_arguments {
+        append_methods.retain(|method| {
+          item_argument(method).map_or(false, |arg| {
+            args.get(0) == Some(&CppType {
+                                  indirection: CppTypeIndirection::None,
+                                  is_const: false,
+                                  is_const2: false,
+                                  is_volatile: false,
+                                  ..arg.argument_type.original_type.clone()
+                                })
+          })
+        });
+      }
+    }
+    let append_method = match append_methods.len() {
+      0 => return Ok(Vec::new()),
+      1 => append_methods[0],
+      _ => return Err(format!("ambiguous append method in {:?}", class_type).into()),
+    };
+    let item_arg = item_argument(append_method).chain_err(|| "append argument is missing")?;
+    let item_type = complete_type(&self.processed_types,
+                                  &self.dependency_types,
+                                  &item_arg.argument_type,
+                                  &item_arg.meaning,
+                                  false,
+                                  &append_method.allocation_place)?
+        .rust_api_type
+        .with_lifetime("a".to_string());
+
+    let size_method = methods
+      .iter()
+      .find(|method| {
+              (&method.cpp_method.name == "size" || &method.cpp_method.name == "count") &&
+              is_non_static(method, true) && method.cpp_method.arguments.is_empty() &&
+              is_int(&method.cpp_method.return_type)
+            });
+    let reserve_method = methods
+      .iter()
+      .find(|method| {
+              &method.cpp_method.name == "reserve" && is_non_static(method, false) &&
+              method.cpp_method.arguments.len() == 1 &&
+              is_int(&method.cpp_method.arguments[0].argument_type)
+            });
+    let size_and_reserve_functions = match (size_method, reserve_method) {
+      (Some(size_method), Some(reserve_method)) => {
+        Some((size_method.c_name.clone(), reserve_method.c_name.clone()))
+      }
+      _ => None,
+    };
+    let trait_type = |name: &str| -> Result<RustType> {
+      Ok(RustType::Common {
+           base: RustName::new(vec!["std".to_string(), "iter".to_string(), name.to_string()])?,
+           indirection: RustTypeIndirection::None,
+           is_const: false,
+           is_const2: false,
+           generic_arguments: Some(vec![item_type.clone()]),
+         })
+    };
+    let mut results = vec![TraitImpl {
+                             target_type: target_type.clone(),
+                             associated_types: Vec::new(),
+                             trait_type: trait_type("Extend")?,
+                             extra: Some(TraitImplExtra::Extend {
+                                           append_function: append_method.c_name.clone(),
+                                           size_and_reserve_functions:
+                                             size_and_reserve_functions,
+                                         }),
+                             methods: Vec::new(),
+                           }];
+    if let Some(constructor) = methods
+         .iter()
+         .find(|method| {
+                 method.cpp_method.is_constructor() && method.cpp_method.arguments.is_empty() &&
+                 method.allocation_place == ReturnValueAllocationPlace::Stack
+               }) {
+      results.push(TraitImpl {
+                     target_type: target_type.clone(),
+                     associated_types: Vec::new(),
+                     trait_type: trait_type("FromIterator")?,
+                     extra: Some(TraitImplExtra::FromIterator {
+                                   constructor_function: constructor.c_name.clone(),
+                                 }),
+                     methods: Vec::new(),
+                   });
+    }
+    Ok(results)
+  }
+
+  /// Generates `From` implementation converting a reference to a type
+  /// added with `Config::add_metatype` to `Variant`.
+  /// The implementation is accompanied by `try_from_variant` method.
+  fn process_variant_conversions(&self,
+                                 methods: &[&CppAndFfiMethod],
+                                 scope: &RustMethodScope)
+                                 -> Result<Vec<TraitImpl>> {
+    let value_type = if let RustMethodScope::Impl { ref target_type } = *scope {
+      target_type
+    } else {
+      return Err(unexpected("class scope expected").into());
+    };
+    fn find_method<'b>(methods: &[&'b CppAndFfiMethod],
+                       conversion: CppVariantConversion)
+                       -> Option<&'b CppAndFfiMethod> {
+      let kind = CppFfiMethodKind::VariantConversion(conversion);
+      methods
+        .iter()
+        .find(|method| method.kind == kind)
+        .map(|method| *method)
+    }
+    let to_variant = match find_method(methods, CppVariantConversion::ToVariant) {
+      Some(method) => method,
+      None => return Ok(Vec::new()),
+    };
+    let from_variant = find_method(methods, CppVariantConversion::FromVariant)
+      .chain_err(|| "fromVariant method is missing")?;
+    let return_type = |method: &CppAndFfiMethod| -> Result<RustType> {
+      let ffi_type = match method
+              .c_signature
+              .arguments
+              .iter()
+              .find(|arg| arg.meaning == CppFfiArgumentMeaning::ReturnValue) {
+        Some(arg) => &arg.argument_type,
+        None => &method.c_signature.return_type,
+      };
+      Ok(complete_type(&self.processed_types,
+                       &self.dependency_types,
+                       ffi_type,
+                       &CppFfiArgumentMeaning::ReturnValue,
+                       false,
+                       &method.allocation_place)?
+             .rust_api_type)
+    };
+    let variant_arg = from_variant
+      .c_signature
+      .arguments
+      .iter()
+      .find(|arg| arg.meaning == CppFfiArgumentMeaning::Argument(0))
+      .chain_err(|| "fromVariant argument is missing")?;
+    let variant_arg_type = complete_type(&self.processed_types,
+                                         &self.dependency_types,
+                                         &variant_arg.argument_type,
+                                         &variant_arg.meaning,
+                                         false,
+                                         &from_variant.allocation_place)?
+        .rust_api_type;
+    let value_ref_type = if let RustType::Common {
+             ref base,
+             ref generic_arguments,
+             ..
+           } = *value_type {
+      RustType::Common {
+        base: base.clone(),
+        generic_arguments: generic_arguments.clone(),
+        indirection: RustTypeIndirection::Ref { lifetime: Some("a".to_string()) },
+        is_const: true,
+        is_const2: false,
+      }
+    } else {
+      return Err(unexpected("common type expected").into());
+    };
+    Ok(vec![TraitImpl {
+              target_type: return_type(to_variant)?,
+              associated_types: Vec::new(),
+              trait_type: RustType::Common {
+                base: RustName::new(vec!["std".to_string(),
+                                         "convert".to_string(),
+                                         "From".to_string()])?,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+                generic_arguments: Some(vec![value_ref_type]),
+              },
+              extra: Some(TraitImplExtra::VariantConversion {
+                            value_type: value_type.clone(),
+                            value_return_type: return_type(from_variant)?,
+                            variant_arg_type: variant_arg_type,
+                          }),
+              methods: Vec::new(),
+            }])
+  }
+
+  /// Generates `From` implementation for a conversion
+  /// added with `Config::add_slicing_conversion`.
+  fn process_slicing_conversion(&self,
+                                method: &CppAndFfiMethod,
+                                scope: &RustMethodScope)
+                                -> Result<TraitImpl> {
+    let mut method = self.generate_rust_single_method(method, scope, false)?;
+    if method.arguments.arguments.len() != 1 {
+      return Err(unexpected("1 argument expected").into());
+    }
+    method.name = RustName::new(vec!["from".to_string()])?;
+    method.scope = RustMethodScope::TraitImpl;
+    method.is_unsafe = false;
+    {
+      let arg = &mut method.arguments.arguments[0];
+      arg.name = "value".to_string();
+      arg.argument_type.rust_api_type = arg
+        .argument_type
+        .rust_api_type
+        .with_lifetime("a".to_string());
+    }
+    let target_type = method.arguments.return_type.rust_api_type.clone();
+    let value_type = method.arguments.arguments[0]
+      .argument_type
+      .rust_api_type
+      .clone();
+    Ok(TraitImpl {
+         target_type: target_type,
+         associated_types: Vec::new(),
+         trait_type: RustType::Common {
+           base: RustName::new(vec!["std".to_string(),
+                                    "convert".to_string(),
+                                    "From".to_string()])?,
+           indirection: RustTypeIndirection::None,
+           is_const: false,
+           is_const2: false,
+           generic_arguments: Some(vec![value_type]),
+         },
+         extra: Some(TraitImplExtra::SlicingConversion),
+         methods: vec![method.to_rust_method()],
+       })
+  }
+
+  /// Generates `as_base_*` and `as_base_*_mut` methods from an upcast
+  /// to a direct base that is not the primary base of the class.
+  /// Only the primary base is reachable through `Deref`.
+  fn process_secondary_base_cast(&self,
+                                 method: &CppAndFfiMethod,
+                                 scope: &RustMethodScope)
+                                 -> Result<Vec<RustMethod>> {
+    let rust_method = self.generate_rust_single_method(method, &RustMethodScope::Free, false)?;
+    if rust_method.arguments.arguments.len() != 1 {
+      return Err(unexpected("1 argument expected").into());
+    }
+    let base_name = if let RustType::Common { ref base, .. } =
+      rust_method.arguments.return_type.rust_api_type {
+      base.last_name()?.to_snake_case()
+    } else {
+      return Err(unexpected("class pointer expected").into());
+    };
+    let mut results = Vec::new();
+    for &is_const in &[true, false] {
+      let mut final_method = rust_method.clone();
+      final_method.name = RustName::new(vec![if is_const {
+                                               format!("as_base_{}", base_name)
+                                             } else {
+                                               format!("as_base_{}_mut", base_name)
+                                             }])?;
+      final_method.scope = scope.clone();
+      final_method.is_unsafe = false;
+      final_method.arguments.return_type = final_method
+        .arguments
+        .return_type
+        .ptr_to_ref(is_const)?;
+      final_method.arguments.arguments[0].argument_type = final_method.arguments.arguments[0]
+        .argument_type
+        .ptr_to_ref(is_const)?;
+      final_method.arguments.arguments[0].name = "self".to_string();
+      results.push(final_method.to_rust_method());
+    }
+    Ok(results)
+  }
+
+  /// Generates `CppVirtualClone` implementation for a class
+  /// added with `Config::add_virtual_clone`.
+  fn process_virtual_clone(&self,
+                           methods: &[&CppAndFfiMethod],
+                           info: &RustProcessedTypeInfo,
+                           scope: &RustMethodScope)
+                           -> Result<Vec<TraitImpl>> {
+    let target_type = if let RustMethodScope::Impl { ref target_type } = *scope {
+      target_type
+    } else {
+      return Err(unexpected("class scope expected").into());
+    };
+    let method_name = match self.input_data.virtual_clones.get(&info.cpp_name) {
+      Some(name) => name,
+      None => return Ok(Vec::new()),
+    };
+    match info.kind {
+      RustTypeWrapperKind::Struct { is_deletable: true, .. } => {}
+      _ => return Err(format!("{} is not a deletable class", info.cpp_name).into()),
+    }
+    let method = methods
+      .iter()
+      .find(|method| {
+              &method.cpp_method.name == method_name &&
+              method.cpp_method.arguments.is_empty() &&
+              method.allocation_place == ReturnValueAllocationPlace::NotApplicable &&
+              method
+                .cpp_method
+                .class_membership
+                .as_ref()
+                .map_or(false, |membership| !membership.is_static && membership.is_const)
+            })
+      .chain_err(|| format!("clone method not found: {}::{}", info.cpp_name, method_name))?;
+    let return_type = &method.cpp_method.return_type;
+    let return_class_name = match return_type.base {
+      CppTypeBase::Class(CppTypeClassBase { ref name, .. })
+        if return_type.indirection == CppTypeIndirection::Ptr && !return_type.is_const => name,
+      _ => {
+        return Err(format!("clone method must return a pointer to a class: {}",
+                           method.c_name)
+                       .into())
+      }
+    };
+    let is_base_return_type = if return_class_name == &info.cpp_name {
+      false
+    } else if self
+                .input_data
+                .cpp_data
+                .inherits(&info.cpp_name, return_class_name) {
+      true
+    } else {
+      return Err(format!("clone method returns unrelated type: {}", method.c_name).into());
+    };
+    Ok(vec![TraitImpl {
+              target_type: target_type.clone(),
+              associated_types: Vec::new(),
+              trait_type: RustType::Common {
+                base: RustName::new(vec!["cpp_utils".to_string(),
+                                         "CppVirtualClone".to_string()])?,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+                generic_arguments: None,
+              },
+              extra: Some(TraitImplExtra::VirtualClone {
+                            clone_function: method.c_name.clone(),
+                            is_base_return_type: is_base_return_type,
+                          }),
+              methods: Vec::new(),
+            }])
+  }
+
+  /// Generates the enum borrowing the active alternative of a `std::variant`
+  /// instantiation from its `std::get_if` accessors. Variants of the enum
+  /// are named after Rust types of the alternatives.
+  fn process_std_variant_enum(&self,
+                              getters: &[&CppAndFfiMethod],
+                              info: &RustProcessedTypeInfo)
+                              -> Result<Option<RustStdVariantEnum>> {
+    if getters.is_empty() {
+      return Ok(None);
+    }
+    let mut variants: Vec<RustStdVariantEnumItem> = Vec::new();
+    for getter in getters {
+      let pointer_type = complete_type(&self.processed_types,
+                                       &self.dependency_types,
+                                       &getter.c_signature.return_type,
+                                       &CppFfiArgumentMeaning::ReturnValue,
+                                       false,
+                                       &getter.allocation_place)?
+          .rust_api_type;
+      let (base, generic_arguments) = match pointer_type {
+        RustType::Common {
+          base,
+          generic_arguments,
+          indirection: RustTypeIndirection::Ptr,
+          ..
+        } => (base, generic_arguments),
+        _ => return Err(format!("unexpected return type of {}", getter.c_name).into()),
+      };
+      let mut value_type = RustType::Common {
+        base: base,
+        generic_arguments: generic_arguments,
+        indirection: RustTypeIndirection::None,
+        is_const: true,
+        is_const2: false,
+      };
+      let name = value_type.caption(&info.rust_name)?.to_class_case();
+      if let RustType::Common { ref mut indirection, .. } = value_type {
+        *indirection = RustTypeIndirection::Ref { lifetime: Some("a".to_string()) };
+      }
+      if variants.iter().any(|v| v.name == name) {
+        return Err(format!("duplicate variant name: {}", name).into());
+      }
+      variants.push(RustStdVariantEnumItem {
+                      name: name,
+                      value_type: value_type,
+                      get_if_function: getter.c_name.clone(),
+                    });
+    }
+    Ok(Some(RustStdVariantEnum {
+              name: format!("{}Ref", info.rust_name.last_name()?),
+              variants: variants,
+            }))
+  }
+
+  /// Finds `operator bool` and `operator!` of a class. If both are present,
+  /// `is_valid` and `is_null` methods (and `into_option` for types
+  /// held by value) are generated from a template.
+  fn process_validity_check(&self,
+                            methods: &[&CppAndFfiMethod],
+                            info: &RustProcessedTypeInfo,
+                            generated_methods: &[RustMethod])
+                            -> Result<Option<RustValidityCheck>> {
+    let is_bool = |t: &CppType| {
+      t.indirection == CppTypeIndirection::None &&
+      t.base == CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool)
+    };
+    let find_operator = |is_conversion: bool| {
+      methods
+        .iter()
+        .find(|m| {
+          m.cpp_method.arguments.is_empty() &&
+          m.cpp_method
+            .class_membership
+            .as_ref()
+            .map_or(false, |info| !info.is_static) &&
+          match m.cpp_method.operator {
+            Some(CppOperator::Conversion(ref t)) => is_conversion && is_bool(t),
+            Some(CppOperator::LogicalNot) => {
+              !is_conversion && is_bool(&m.cpp_method.return_type)
+            }
+            _ => false,
+          }
+        })
+    };
+    let (bool_method, not_method) = match (find_operator(true), find_operator(false)) {
+      (Some(bool_method), Some(not_method)) => (bool_method, not_method),
+      _ => return Ok(None),
+    };
+    for method in &[bool_method, not_method] {
+      if !method
+            .cpp_method
+            .class_membership
+            .as_ref()
+            .map_or(false, |info| info.is_const) {
+        return Err(format!("{} is not const", method.cpp_method.short_text()).into());
+      }
+    }
+    let has_into_option = match info.kind {
+      RustTypeWrapperKind::Struct { ref size_const_name, .. } => size_const_name.is_some(),
+      RustTypeWrapperKind::Enum { .. } => false,
+    };
+    let mut names = vec!["is_valid", "is_null"];
+    if has_into_option {
+      names.push("into_option");
+    }
+    for name in names {
+      if generated_methods
+           .iter()
+           .any(|m| m.name.last_name().map(|n| n == name).unwrap_or(false)) {
+        return Err(format!("{} already has {} method", info.cpp_name, name).into());
+      }
+    }
+    Ok(Some(RustValidityCheck {
+              bool_function: bool_method.c_name.clone(),
+              not_function: not_method.c_name.clone(),
+              has_into_option: has_into_option,
+            }))
+  }
+
+  /// Collects FFI functions and Rust types for accessors of a `std::map` or
+  /// `std::unordered_map` instantiation. The accessors themselves are
+  /// generated from a template and don't go through the usual method processing.
+  /// `methods` are other methods of the class, used to find the constructor
+  /// for conversions from the Rust collection.
+  fn process_std_map_wrapper(&self,
+                             accessors: &[&CppAndFfiMethod],
+                             methods: &[&CppAndFfiMethod],
+                             info: &RustProcessedTypeInfo)
+                             -> Result<Option<RustStdMapWrapper>> {
+    if accessors.is_empty() {
+      return Ok(None);
+    }
+    let find_accessor = |kind: CppFfiMethodKind| {
+      accessors
+        .iter()
+        .find(|method| method.kind == kind)
+        .chain_err(|| format!("{:?} accessor not found for {}", kind, info.cpp_name))
+    };
+    let insert = find_accessor(CppFfiMethodKind::StdMapInsert)?;
+    let argument_type = |index: i8| -> Result<RustType> {
+      let arg = insert
+        .c_signature
+        .arguments
+        .iter()
+        .find(|arg| arg.meaning == CppFfiArgumentMeaning::Argument(index))
+        .chain_err(|| "insert argument is missing")?;
+      Ok(complete_type(&self.processed_types,
+                       &self.dependency_types,
+                       &arg.argument_type,
+                       &arg.meaning,
+                       false,
+                       &insert.allocation_place)?
+             .rust_api_type)
+    };
+    let collection_path = if &info.cpp_name == "std::map" {
+      "::std::collections::BTreeMap"
+    } else {
+      "::std::collections::HashMap"
+    };
+    Ok(Some(RustStdMapWrapper {
+              iterator_name: format!("{}Iter", info.rust_name.last_name()?),
+              key_type: argument_type(0)?,
+              value_type: argument_type(1)?,
+              collection_path: collection_path.to_string(),
+              get_function: find_accessor(CppFfiMethodKind::StdMapGet)?.c_name.clone(),
+              insert_function: insert.c_name.clone(),
+              next_entry_function: find_accessor(CppFfiMethodKind::StdMapNextEntry)?
+                .c_name
+                .clone(),
+              stack_constructor_function: methods
+                .iter()
+                .find(|method| {
+                        method.cpp_method.is_constructor() &&
+                        method.cpp_method.arguments.is_empty() &&
+                        method.allocation_place == ReturnValueAllocationPlace::Stack
+                      })
+                .map(|method| method.c_name.clone()),
+            }))
+  }
+
+  /// Generates `Debug` implementation for a class that has only public fields.
+  /// Values of the fields are read using the generated getters. Fields of types
+  /// that don't implement `Debug` are displayed as `<opaque>`.
+  fn process_debug_impl(&self,
+                        methods: &[&CppAndFfiMethod],
+                        info: &RustProcessedTypeInfo,
+                        scope: &RustMethodScope)
+                        -> Result<Vec<TraitImpl>> {
+    let target_type = if let RustMethodScope::Impl { ref target_type } = *scope {
+      target_type
+    } else {
+      return Err(unexpected("class scope expected").into());
+    };
+    if info.cpp_template_arguments.is_some() {
+      return Ok(Vec::new());
+    }
+    let fields = match self
+            .input_data
+            .cpp_data
+            .current
+            .parser
+            .types
+            .iter()
+            .find(|t| t.name == info.cpp_name) {
+      Some(type_info) => {
+        match type_info.kind {
+          CppTypeKind::Class { ref fields, .. } => fields,
+          _ => return Err(unexpected("class type expected").into()),
+        }
+      }
+      None => return Ok(Vec::new()),
+    };
+    if fields.is_empty() || fields.iter().any(|f| f.visibility != CppVisibility::Public) {
+      return Ok(Vec::new());
+    }
+    let mut debug_fields = Vec::new();
+    for field in fields {
+      let is_debug = field.field_type.indirection == CppTypeIndirection::None &&
+                     match field.field_type.base {
+                       CppTypeBase::BuiltInNumeric(_) |
+                       CppTypeBase::SpecificNumeric(_) |
+                       CppTypeBase::PointerSizedInteger { .. } |
+                       CppTypeBase::Enum { .. } => true,
+                       _ => false,
+                     };
+      let getter = methods
+        .iter()
+        .find(|method| match method.kind {
+                CppFfiMethodKind::FieldAccessor {
+                  ref accessor_type,
+                  ref field_name,
+                } => {
+                  accessor_type == &CppFieldAccessorType::CopyGetter && field_name == &field.name
+                }
+                _ => false,
+              });
+      let getter_name = match getter {
+        Some(getter) if is_debug => Some(self.method_rust_name(getter)?.last_name()?.clone()),
+        _ => None,
+      };
+      debug_fields.push(RustDebugField {
+                          name: field.name.clone(),
+                          getter_name: getter_name,
+                        });
+    }
+    Ok(vec![TraitImpl {
+              target_type: target_type.clone(),
+              associated_types: Vec::new(),
+              trait_type: RustType::Common {
+                base: RustName::new(vec!["std".to_string(),
+                                         "fmt".to_string(),
+                                         "Debug".to_string()])?,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+                generic_arguments: None,
+              },
+              extra: Some(TraitImplExtra::Debug {
+                            type_name: info.rust_name.last_name()?.clone(),
+                            fields: debug_fields,
+                          }),
+              methods: Vec::new(),
+            }])
+  }
+
+  /// Generates `Display` implementation for a class with a stream output
+  /// operator (`operator<<(std::ostream&, const T&)`). `methods` contains
+  /// `StreamOutput` methods of the class.
+  fn process_display_impl(&self,
+                          methods: &[&CppAndFfiMethod],
+                          scope: &RustMethodScope)
+                          -> Result<Vec<TraitImpl>> {
+    let target_type = if let RustMethodScope::Impl { ref target_type } = *scope {
+      target_type
+    } else {
+      return Err(unexpected("class scope expected").into());
+    };
+    // trait implementations can't depend on C++ features
+    let method = match methods.iter().find(|m| m.cpp_method.feature.is_none()) {
+      Some(method) => method,
+      None => return Ok(Vec::new()),
+    };
+    Ok(vec![TraitImpl {
+              target_type: target_type.clone(),
+              associated_types: Vec::new(),
+              trait_type: RustType::Common {
+                base: RustName::new(vec!["std".to_string(),
+                                         "fmt".to_string(),
+                                         "Display".to_string()])?,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+                generic_arguments: None,
+              },
+              extra: Some(TraitImplExtra::Display { display_function: method.c_name.clone() }),
+              methods: Vec::new(),
+            }])
+  }
+
+  /// Generates an operator trait implementation for a primitive type from
+  /// a free C++ operator with a primitive left operand and a class as
+  /// the right operand, e.g. `impl<'a> Mul<&'a QVector3D> for f64`
+  /// from `operator*(double, const QVector3D&)`. The orphan rule only allows
+  /// such implementation if the right operand's type is defined in this crate.
+  /// Returns `None` if `method` is not such an operator.
+  fn process_primitive_lhs_operator(&self,
+                                    method: &CppAndFfiMethod)
+                                    -> Result<Option<TraitImpl>> {
+    let (trait_name, method_name) = match method.cpp_method.operator {
+      Some(CppOperator::Addition) => ("Add", "add"),
+      Some(CppOperator::Subtraction) => ("Sub", "sub"),
+      Some(CppOperator::Multiplication) => ("Mul", "mul"),
+      Some(CppOperator::Division) => ("Div", "div"),
+      Some(CppOperator::Modulo) => ("Rem", "rem"),
+      Some(CppOperator::BitwiseAnd) => ("BitAnd", "bitand"),
+      Some(CppOperator::BitwiseOr) => ("BitOr", "bitor"),
+      Some(CppOperator::BitwiseXor) => ("BitXor", "bitxor"),
+      _ => return Ok(None),
+    };
+    if method.cpp_method.class_membership.is_some() || method.kind != CppFfiMethodKind::Real ||
+       method.cpp_method.arguments.len() != 2 {
+      return Ok(None);
+    }
+    let lhs = &method.cpp_method.arguments[0].argument_type;
+    let lhs_is_primitive = lhs.indirection == CppTypeIndirection::None &&
+                           match lhs.base {
+                             CppTypeBase::BuiltInNumeric(..) |
+                             CppTypeBase::SpecificNumeric(..) |
+                             CppTypeBase::PointerSizedInteger { .. } => true,
+                             _ => false,
+                           };
+    let rhs = &method.cpp_method.arguments[1].argument_type;
+    let rhs_is_class = rhs.base.is_class() &&
+                       (rhs.indirection == CppTypeIndirection::None ||
+                        (rhs.indirection == CppTypeIndirection::Ref && rhs.is_const));
+    if !lhs_is_primitive || !rhs_is_class {
+      return Ok(None);
+    }
+    let mut rust_method = self.generate_rust_single_method(method, &RustMethodScope::Free, false)?;
+    if rust_method.is_unsafe {
+      return Err(format!("operator wrapper is unsafe: {}", method.short_text()).into());
+    }
+    if rust_method.arguments.arguments.len() != 2 {
+      return Err(unexpected("2 arguments expected").into());
+    }
+    let rhs_type = rust_method.arguments.arguments[1]
+      .argument_type
+      .rust_api_type
+      .with_lifetime("a".to_string());
+    if let RustType::Common {
+             ref base,
+             indirection: RustTypeIndirection::Ref { .. },
+             is_const: true,
+             ..
+           } = rhs_type {
+      if base.parts.get(0) != Some(&self.input_data.crate_name) {
+        log::llog(log::DebugRustSkips, || {
+          format!("Skipping operator with a right operand from another crate: {}",
+                  method.short_text())
+        });
+        return Ok(None);
+      }
+    } else {
+      return Err(unexpected(format!("unexpected right operand type: {:?}", rhs_type)).into());
+    }
+    rust_method.name = RustName::new(vec![method_name.to_string()])?;
+    rust_method.scope = RustMethodScope::TraitImpl;
+    rust_method.arguments.arguments[0].name = "self".to_string();
+    rust_method.arguments.arguments[1].name = "rhs".to_string();
+    rust_method.arguments.arguments[1]
+      .argument_type
+      .rust_api_type = rhs_type.clone();
+    Ok(Some(TraitImpl {
+              target_type: rust_method.arguments.arguments[0]
+                .argument_type
+                .rust_api_type
+                .clone(),
+              associated_types: vec![TraitAssociatedType {
+                                       name: "Output".to_string(),
+                                       value: rust_method
+                                         .arguments
+                                         .return_type
+                                         .rust_api_type
+                                         .clone(),
+                                     }],
+              trait_type: RustType::Common {
+                base: RustName::new(vec!["std".to_string(),
+                                         "ops".to_string(),
+                                         trait_name.to_string()])?,
+                indirection: RustTypeIndirection::None,
+                is_const: false,
+                is_const2: false,
+                generic_arguments: Some(vec![rhs_type]),
+              },
+              extra: Some(TraitImplExtra::PrimitiveLhsOperator),
+              methods: vec![rust_method.to_rust_method()],
+            }))
+  }
+
   /// Generates trait implementations from `static_cast`, `dynamic_cast`
-  /// or `qobject_cast` (to be implemented) C++ function wrappers.
+  /// or `qobject_cast` C++ function wrappers.
   fn process_cpp_cast(&self, method: RustSingleMethod) -> Result<Vec<TraitImpl>> {
     let mut results = Vec::new();
-    // TODO: qobject_cast
     let mut final_methods = vec![(method.clone(), false), (method.clone(), true)];
     let args = &method.arguments;
     let cpp_cast = if let CppFfiMethodKind::Cast(ref cast) = args.cpp_method.kind {
@@ -1098,7 +3159,7 @@ impl<'aa> RustGenerator<'aa> {
         .ptr_to_ref(*final_is_const)?;
       final_method.arguments.arguments[0].name = "self".to_string();
 
-      if !cpp_cast.is_unsafe_static_cast() && cpp_cast.is_direct_static_cast() {
+      if !cpp_cast.is_unsafe_static_cast() && cpp_cast.is_primary_static_cast() {
 
         let mut deref_method = final_method.clone();
         deref_method.name = RustName::new(vec![if *final_is_const {
@@ -1133,6 +3194,31 @@ impl<'aa> RustGenerator<'aa> {
                      });
 
       }
+      if let CppCast::Static { is_unsafe: false, .. } = *cpp_cast {
+        // reference upcasts (unlike by-value conversions) don't cause object slicing
+        let mut as_ref_method = final_method.clone();
+        let (method_name, as_ref_trait_name) = if *final_is_const {
+          ("as_ref", "AsRef")
+        } else {
+          ("as_mut", "AsMut")
+        };
+        as_ref_method.name = RustName::new(vec![method_name.to_string()])?;
+        results.push(TraitImpl {
+                       target_type: from_type.ptr_to_value()?.rust_api_type,
+                       associated_types: Vec::new(),
+                       trait_type: RustType::Common {
+                         base: RustName::new(vec!["std".to_string(),
+                                                  "convert".to_string(),
+                                                  as_ref_trait_name.to_string()])?,
+                         indirection: RustTypeIndirection::None,
+                         is_const: false,
+                         is_const2: false,
+                         generic_arguments: Some(vec![to_type.ptr_to_value()?.rust_api_type]),
+                       },
+                       extra: None,
+                       methods: vec![as_ref_method.to_rust_method()],
+                     });
+      }
     }
     let trait_type = RustType::Common {
       base: RustName::new(trait_name)?,
@@ -1141,6 +3227,28 @@ impl<'aa> RustGenerator<'aa> {
       is_const2: false,
       generic_arguments: Some(vec![to_type.ptr_to_value()?.rust_api_type]),
     };
+    if *cpp_cast == CppCast::QObject {
+      for &is_const in &[true, false] {
+        results.push(TraitImpl {
+                       target_type: to_type.ptr_to_value()?.rust_api_type,
+                       associated_types: Vec::new(),
+                       trait_type: RustType::Common {
+                         base: RustName::new(vec!["std".to_string(),
+                                                  "convert".to_string(),
+                                                  "TryFrom".to_string()])?,
+                         indirection: RustTypeIndirection::None,
+                         is_const: false,
+                         is_const2: false,
+                         generic_arguments: Some(vec![from_type.ptr_to_value()?.rust_api_type]),
+                       },
+                       extra: Some(TraitImplExtra::QObjectTryFrom {
+                                     cast_trait_type: trait_type.clone(),
+                                     is_const: is_const,
+                                   }),
+                       methods: Vec::new(),
+                     });
+      }
+    }
     results.push(TraitImpl {
                    target_type: from_type.ptr_to_value()?.rust_api_type,
                    associated_types: Vec::new(),
@@ -1241,6 +3349,10 @@ impl<'aa> RustGenerator<'aa> {
         doc_items.push(RustMethodDocItem {
                          doc: cpp_method.doc.clone(),
                          cpp_fn: cpp_method.short_text(),
+                         has_aliasing_references: cpp_method.has_aliasing_mutable_references(),
+                         has_closure_arguments: has_closure_arguments(&variants[0].arguments),
+                         omitted_argument_defaults: self
+                           .omitted_argument_defaults(&variants[0].cpp_method),
                          rust_fns: variants
                            .iter()
                            .map_if_ok(|args| -> Result<_> {
@@ -1307,7 +3419,7 @@ impl<'aa> RustGenerator<'aa> {
                                 },
                                 kind: RustTypeDeclarationKind::MethodParametersTrait {
                                   shared_arguments: shared_arguments_for_trait,
-                                  impls: args_variants,
+                                  impls: args_variants.clone(),
                                   lifetime: params_trait_lifetime.clone(),
                                   common_return_type: common_return_type.clone(),
                                   method_name: method_name.clone(),
@@ -1328,6 +3440,7 @@ impl<'aa> RustGenerator<'aa> {
           shared_arguments: shared_arguments,
           variant_argument_name: "args".to_string(),
           cpp_method_name: cpp_method_name,
+          variants: args_variants,
         },
         variant_docs: doc_items,
         common_doc: None,
@@ -1354,6 +3467,14 @@ impl<'aa> RustGenerator<'aa> {
                           cpp_fn: method.arguments.cpp_method.cpp_method.short_text(),
                           rust_fns: Vec::new(),
                           doc: method.arguments.cpp_method.cpp_method.doc.clone(),
+                          has_aliasing_references: method
+                            .arguments
+                            .cpp_method
+                            .cpp_method
+                            .has_aliasing_mutable_references(),
+                          has_closure_arguments: method.has_closure_arguments(),
+                          omitted_argument_defaults: self
+                            .omitted_argument_defaults(&method.arguments.cpp_method),
                         });
       method.to_rust_method()
     };
@@ -1384,6 +3505,24 @@ impl<'aa> RustGenerator<'aa> {
     for bucket in &buckets {
       all_self_args.insert(bucket[0].self_arg_kind()?.clone());
     }
+    // const and non-const overloads are only named as a `_mut` pair
+    // if they return the same type (apart from constness)
+    let mut has_mismatched_const_pair = false;
+    for const_bucket in &buckets {
+      if const_bucket[0].self_arg_kind()? != RustMethodSelfArgKind::ConstRef {
+        continue;
+      }
+      for mut_bucket in &buckets {
+        if mut_bucket[0].self_arg_kind()? != RustMethodSelfArgKind::MutRef {
+          continue;
+        }
+        if const_bucket
+             .iter()
+             .any(|m1| mut_bucket.iter().any(|m2| m1.is_mismatched_const_overload_of(m2))) {
+          has_mismatched_const_pair = true;
+        }
+      }
+    }
 
     let mut final_names = None;
     {
@@ -1409,7 +3548,25 @@ impl<'aa> RustGenerator<'aa> {
         Ok(result)
       };
 
+      let all_constructors = buckets
+        .iter()
+        .all(|b| b.iter().all(|m| m.arguments.cpp_method.cpp_method.is_constructor()));
       for strategy in RustMethodCaptionStrategy::all() {
+        let is_skipped = if has_mismatched_const_pair {
+          strategy == &RustMethodCaptionStrategy::SelfOnly
+        } else {
+          strategy == &RustMethodCaptionStrategy::ReturnType
+        };
+        if is_skipped {
+          continue;
+        }
+        if all_constructors && strategy == &RustMethodCaptionStrategy::SelfAndIndex {
+          // constructors are numbered in declaration order (`new`, `new2`, `new3`)
+          final_names = Some((0..buckets.len())
+                               .map(|i| if i == 0 { None } else { Some((i + 1).to_string()) })
+                               .collect());
+          break;
+        }
         if let Ok(names) = try_strategy(&strategy) {
           final_names = Some(names);
           break;
@@ -1441,6 +3598,13 @@ impl<'aa> RustGenerator<'aa> {
     let mut single_rust_methods: HashMap<String, Vec<RustSingleMethod>> = HashMap::new();
     let mut result = ProcessFunctionsResult::default();
     for method in methods {
+      if method.cpp_method.feature.is_some() &&
+         (method.cpp_method.is_destructor() || method.cpp_method.name.ends_with("_cast")) {
+        log::llog(log::DebugRustSkips,
+                  || format!("Trait implementation can't depend on a C++ feature: {:?}\n",
+                             method));
+        continue;
+      }
       if method.cpp_method.is_destructor() {
         match self.process_destructor(method, scope) {
           Ok(r) => result.trait_impls.push(r),
@@ -1537,6 +3701,8 @@ impl<'aa> RustGenerator<'aa> {
         } else {
           return Err(unexpected("unknown slots submodule").into());
         }
+      } else if module_name.parts.len() == 2 && module_name.parts[1] == "event_filter" {
+        Some(doc_formatter::event_filter_module_doc())
       } else {
         cpp_header
           .as_ref()
@@ -1565,7 +3731,7 @@ impl<'aa> RustGenerator<'aa> {
       };
 
       for type_data in &self.processed_types {
-        if check_name(&type_data.rust_name) {
+        if type_data.mapped_conversion.is_none() && check_name(&type_data.rust_name) {
           let (mut result, tmp_cpp_methods) = self.generate_type(type_data, cpp_methods)?;
           cpp_methods = tmp_cpp_methods;
           if let Some(ref cpp_header) = cpp_header {
@@ -1577,7 +3743,10 @@ impl<'aa> RustGenerator<'aa> {
                   if let Some(index) = doc.find("\n") {
                     doc = &doc[0..index];
                   }
-                  module.doc = Some(doc.to_string());
+                  module.doc = Some(doc_formatter::html_to_markdown(doc,
+                                                                    &[],
+                                                                    &doc_formatter::DocLinks::new(),
+                                                                    &self.input_data.crate_name));
                 }
               }
             }
@@ -1613,8 +3782,30 @@ impl<'aa> RustGenerator<'aa> {
     }
     let mut free_functions_result =
       self
-        .process_all_sibling_functions(good_methods.into_iter(), &RustMethodScope::Free)?;
+        .process_all_sibling_functions(good_methods.iter().cloned(), &RustMethodScope::Free)?;
     module.trait_impls = free_functions_result.trait_impls;
+    for method in good_methods {
+      match self.process_primitive_lhs_operator(method) {
+        Ok(Some(r)) => {
+          if module
+               .trait_impls
+               .iter()
+               .any(|t| t.trait_type == r.trait_type && t.target_type == r.target_type) {
+            log::llog(log::DebugRustSkips, || {
+              format!("Skipping duplicate operator implementation: {}",
+                      method.cpp_method.short_text())
+            });
+          } else {
+            module.trait_impls.push(r);
+          }
+        }
+        Ok(None) => {}
+        Err(msg) => {
+          log::llog(log::DebugRustSkips,
+                    || format!("Failed to generate operator implementation: {}", msg))
+        }
+      }
+    }
     module.functions = free_functions_result.methods;
     rust_overloading_types.append(&mut free_functions_result.overloading_types);
     if !rust_overloading_types.is_empty() {
@@ -1646,7 +3837,7 @@ impl<'aa> RustGenerator<'aa> {
     let mut args = Vec::new();
     for arg in &data.c_signature.arguments {
       let rust_type = ffi_type(&self.processed_types,
-                               &self.input_data.dependency_types,
+                               &self.dependency_types,
                                &arg.argument_type.ffi_type)?;
       args.push(RustFFIArgument {
                   name: sanitize_rust_identifier(&arg.name),
@@ -1655,13 +3846,78 @@ impl<'aa> RustGenerator<'aa> {
     }
     Ok(RustFFIFunction {
          return_type: ffi_type(&self.processed_types,
-                               &self.input_data.dependency_types,
+                               &self.dependency_types,
                                &data.c_signature.return_type.ffi_type)?,
          name: data.c_name.clone(),
          arguments: args,
+         is_direct_extern_c: data.is_direct_extern_c(),
        })
   }
 
+  /// Finds FFI functions used by the benchmark comparing allocation places
+  /// of classes added with `Config::add_allocation_benchmark`.
+  fn calc_allocation_benchmarks(&self) -> Result<Vec<RustAllocationBenchmark>> {
+    let mut result = Vec::new();
+    for header in &self.input_data.cpp_ffi_headers {
+      for method in &header.methods {
+        if method.kind != CppFfiMethodKind::AllocationBenchmark ||
+           !method.cpp_method.is_constructor() {
+          continue;
+        }
+        let class_type = &method
+                            .cpp_method
+                            .class_membership
+                            .as_ref()
+                            .chain_err(|| unexpected("constructor without class membership"))?
+                            .class_type;
+        let find_function = |kind: CppFfiMethodKind,
+                             is_constructor: bool,
+                             place: ReturnValueAllocationPlace|
+                             -> Result<String> {
+          header
+            .methods
+            .iter()
+            .find(|m| {
+              m.kind == kind && m.allocation_place == place &&
+              m.cpp_method.class_membership.as_ref().map(|x| &x.class_type) == Some(class_type) &&
+              if is_constructor {
+                m.cpp_method.is_constructor() && m.cpp_method.arguments.is_empty()
+              } else {
+                m.cpp_method.is_destructor()
+              }
+            })
+            .map(|m| m.c_name.clone())
+            .chain_err(|| {
+                         format!("allocation benchmark function not found for {}",
+                                 class_type.name)
+                       })
+        };
+        let rust_type = find_type_info_by_cpp_name(&self.processed_types,
+                                                   &self.dependency_types,
+                                                   &class_type.name,
+                                                   &class_type.template_arguments)
+            .chain_err(|| format!("type has no Rust equivalent: {}", class_type.name))?
+            .rust_name
+            .clone();
+        result.push(RustAllocationBenchmark {
+                      cpp_name: class_type.name.clone(),
+                      rust_type: rust_type,
+                      stack_constructor: find_function(CppFfiMethodKind::Real,
+                                                       true,
+                                                       ReturnValueAllocationPlace::Stack)?,
+                      stack_destructor: find_function(CppFfiMethodKind::Real,
+                                                      false,
+                                                      ReturnValueAllocationPlace::Stack)?,
+                      heap_constructor: method.c_name.clone(),
+                      heap_destructor: find_function(CppFfiMethodKind::AllocationBenchmark,
+                                                     false,
+                                                     ReturnValueAllocationPlace::Heap)?,
+                    });
+      }
+    }
+    Ok(result)
+  }
+
   /// Generates Rust representations of all FFI functions
   pub fn generate_ffi_functions(&self) -> Vec<(String, Vec<RustFFIFunction>)> {
     log::status("Generating Rust FFI functions");
@@ -1697,10 +3953,14 @@ impl<'aa> RustGenerator<'aa> {
           continue;
         }
       }
+      if let CppTypeKind::Enum { is_anonymous: true, .. } = type_info.kind {
+        // values are added to the enclosing class instead
+        continue;
+      }
       let rust_name =
         self
           .calculate_rust_name(&type_info.name, &type_info.include_file, false, None)?;
-      let rust_type_info = RustProcessedTypeInfo {
+      let mut rust_type_info = RustProcessedTypeInfo {
         cpp_name: type_info.name.clone(),
         cpp_doc: type_info.doc.clone(),
         cpp_template_arguments: None,
@@ -1731,53 +3991,80 @@ impl<'aa> RustGenerator<'aa> {
                                                                  template_arguments: None,
                                                                }),
                   slot_wrapper: None,
+                  event_filter_wrapper: None,
+                  functor_wrapper: None,
                 }
               }
             }
           }
-          CppTypeKind::Enum { ref values } => {
+          CppTypeKind::Enum { ref values, is_scoped, .. } => {
 
             let mut is_flaggable = false;
             let template_arg_sample = CppType {
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               indirection: CppTypeIndirection::None,
               base: CppTypeBase::Enum { name: type_info.name.clone() },
             };
 
-            for flag_owner_name in &["QFlags", "QUrlTwoFlags"] {
-              if let Some(instantiations) =
-                self
-                  .input_data
-                  .cpp_data
-                  .current
-                  .processed
-                  .template_instantiations
-                  .iter()
-                  .find(|x| &x.class_name == &flag_owner_name.to_string()) {
-                if instantiations
-                     .instantiations
-                     .iter()
-                     .any(|ins| {
-                            ins
-                              .template_arguments
-                              .iter()
-                              .any(|arg| arg == &template_arg_sample)
-                          }) {
-                  is_flaggable = true;
-                  break;
+            // scoped enums don't convert to integers implicitly,
+            // so `QFlags<T>` can't be used with them unless requested
+            let can_be_flaggable = !is_scoped ||
+                                   self
+                                     .input_data
+                                     .flaggable_scoped_enums
+                                     .iter()
+                                     .any(|x| x == &type_info.name);
+            if can_be_flaggable {
+              for flag_owner_name in &["QFlags", "QUrlTwoFlags"] {
+                if let Some(instantiations) =
+                  self
+                    .input_data
+                    .cpp_data
+                    .current
+                    .processed
+                    .template_instantiations
+                    .iter()
+                    .find(|x| &x.class_name == &flag_owner_name.to_string()) {
+                  if instantiations
+                       .instantiations
+                       .iter()
+                       .any(|ins| {
+                              ins
+                                .template_arguments
+                                .iter()
+                                .any(|arg| arg == &template_arg_sample)
+                            }) {
+                    is_flaggable = true;
+                    break;
+                  }
                 }
               }
             }
             RustTypeWrapperKind::Enum {
               values: prepare_enum_values(values),
               is_flaggable: is_flaggable,
+              is_scoped: is_scoped,
             }
           }
         },
         rust_name: rust_name,
         is_public: true,
+        mapped_conversion: None,
       };
+      if let Some(&(ref mapped_name, ref conversion)) =
+        self.input_data.type_mappings.get(&type_info.name) {
+        if let RustTypeWrapperKind::Struct { ref mut size_const_name, .. } = rust_type_info.kind {
+          // the Rust type is not generated, so its size is not needed
+          *size_const_name = None;
+          rust_type_info.rust_name = mapped_name.clone();
+          rust_type_info.mapped_conversion = Some(conversion.clone());
+        } else {
+          return Err(format!("type mapping is only supported for classes: {}", type_info.name)
+                       .into());
+        }
+      }
       result.push(rust_type_info);
     }
     let template_final_name =
@@ -1790,8 +4077,13 @@ impl<'aa> RustGenerator<'aa> {
         let mut arg_captions = Vec::new();
         if let Some(ref args) = item.cpp_template_arguments {
           for x in args {
+            if let CppTypeBase::FunctionPointer(..) = x.base {
+              // function type of `std::function`
+              arg_captions.push(x.caption(TypeCaptionStrategy::Full)?.to_class_case());
+              continue;
+            }
             let rust_type = complete_type(result,
-                                          &self.input_data.dependency_types,
+                                          &self.dependency_types,
                                           &x.to_cpp_ffi_type(CppTypeRole::NotReturnType)?,
                                           &CppFfiArgumentMeaning::Argument(0),
                                           true,
@@ -1824,9 +4116,22 @@ impl<'aa> RustGenerator<'aa> {
         // special processing is implemented for QFlags
         continue;
       }
+      // `std::function`, `std::variant` and maps are placed in the module of
+      // the header using them instead of a separate `std` module
+      let class_name = if template_instantiations.class_name == "std::function" {
+        "function"
+      } else if template_instantiations.class_name == "std::variant" {
+        "variant"
+      } else if template_instantiations.class_name == "std::map" {
+        "map"
+      } else if template_instantiations.class_name == "std::unordered_map" {
+        "unordered_map"
+      } else {
+        &template_instantiations.class_name
+      };
       for ins in &template_instantiations.instantiations {
         let rust_name = self
-          .calculate_rust_name(&template_instantiations.class_name,
+          .calculate_rust_name(class_name,
                                &type_info.include_file,
                                false,
                                None)?;
@@ -1852,9 +4157,12 @@ impl<'aa> RustGenerator<'aa> {
                                                                            .clone()),
                                                                 }),
                                slot_wrapper: None,
+                               event_filter_wrapper: None,
+                               functor_wrapper: None,
                              },
                              rust_name: rust_name,
                              is_public: true,
+                             mapped_conversion: None,
                            });
       }
     }
@@ -1921,7 +4229,7 @@ impl<'aa> RustGenerator<'aa> {
           .iter()
           .map_if_ok(|x| -> Result<_> {
             let rust_type = complete_type(&result,
-                                          &self.input_data.dependency_types,
+                                          &self.dependency_types,
                                           x,
                                           &CppFfiArgumentMeaning::Argument(0),
                                           false,
@@ -1943,6 +4251,7 @@ impl<'aa> RustGenerator<'aa> {
                                  false,
                                  None)?,
           is_public: true,
+          mapped_conversion: None,
           kind: RustTypeWrapperKind::Struct {
             size_const_name: None,
             is_deletable: true,
@@ -1952,7 +4261,7 @@ impl<'aa> RustGenerator<'aa> {
                                    .iter()
                                    .map_if_ok(|t| -> Result<_> {
               let mut t = complete_type(&result,
-                                        &self.input_data.dependency_types,
+                                        &self.dependency_types,
                                         t,
                                         &CppFfiArgumentMeaning::Argument(0),
                                         false,
@@ -1965,14 +4274,247 @@ impl<'aa> RustGenerator<'aa> {
                                  callback_name: format!("slot_{}_callback", args_text)
                                    .to_snake_case(),
                                }),
+            event_filter_wrapper: None,
+            functor_wrapper: None,
           },
         };
         result.push(rust_type_info);
       }
+      if let Some(ref wrapper) = header.qt_event_filter_wrapper {
+        let rust_type_info = {
+          let find_rust_name = |cpp_name: &str| -> Result<RustName> {
+            Ok(result
+                 .iter()
+                 .find(|t| &t.cpp_name == cpp_name)
+                 .chain_err(|| format!("type not found: {}", cpp_name))?
+                 .rust_name
+                 .clone())
+          };
+          RustProcessedTypeInfo {
+            cpp_name: wrapper.class_name.clone(),
+            cpp_template_arguments: None,
+            cpp_doc: None,
+            rust_name: self
+              .calculate_rust_name("raw_event_filter",
+                                   &header.include_file_base_name,
+                                   false,
+                                   None)?,
+            is_public: true,
+            mapped_conversion: None,
+            kind: RustTypeWrapperKind::Struct {
+              size_const_name: None,
+              is_deletable: true,
+              slot_wrapper: None,
+              event_filter_wrapper: Some(RustQtEventFilterWrapper {
+                                           public_type_name: "EventFilter".to_string(),
+                                           callback_name: "event_filter_callback".to_string(),
+                                           object_type: find_rust_name("QObject")?,
+                                           event_type: find_rust_name("QEvent")?,
+                                         }),
+              functor_wrapper: None,
+            },
+          }
+        };
+        result.push(rust_type_info);
+      }
+      for wrapper in &header.functor_wrappers {
+        // trampolines for member function pointers expose the same
+        // closure API as function objects
+        let name_prefix = if wrapper.base_class.is_some() {
+          "member_callback"
+        } else {
+          "functor"
+        };
+        let rust_type_info = {
+          let complete = |t: &CppFfiType, meaning: CppFfiArgumentMeaning| {
+            complete_type(&result,
+                          &self.dependency_types,
+                          t,
+                          &meaning,
+                          false,
+                          &ReturnValueAllocationPlace::NotApplicable)
+          };
+          RustProcessedTypeInfo {
+            cpp_name: wrapper.class_name.clone(),
+            cpp_template_arguments: None,
+            cpp_doc: None,
+            rust_name: self
+              .calculate_rust_name(&format!("raw_{}_{}", name_prefix, wrapper.caption),
+                                   &header.include_file_base_name,
+                                   false,
+                                   None)?,
+            is_public: true,
+            mapped_conversion: None,
+            kind: RustTypeWrapperKind::Struct {
+              size_const_name: None,
+              is_deletable: true,
+              slot_wrapper: None,
+              event_filter_wrapper: None,
+              functor_wrapper: Some(RustFunctorWrapper {
+                                      arguments: wrapper
+                                        .arguments
+                                        .iter()
+                                        .map_if_ok(|t| {
+                                                     complete(t,
+                                                              CppFfiArgumentMeaning::Argument(0))
+                                                   })?,
+                                      return_type: complete(&wrapper.return_type,
+                                                            CppFfiArgumentMeaning::ReturnValue)?,
+                                      public_type_name: format!("{}_{}",
+                                                                name_prefix,
+                                                                wrapper.caption)
+                                        .to_class_case(),
+                                      callback_name: format!("{}_{}_callback",
+                                                             name_prefix,
+                                                             wrapper.caption)
+                                        .to_snake_case(),
+                                    }),
+            },
+          }
+        };
+        result.push(rust_type_info);
+      }
+    }
+    Ok(result)
+  }
+
+  /// Generates associated constants for values of enums nested
+  /// in the class `info`. Rust doesn't support inherent associated types,
+  /// so the enum itself is placed in a module named after the class,
+  /// but its values are also made accessible through the class wrapper.
+  /// Enums nested in namespaces are not affected.
+  fn associated_enum_consts(&self,
+                            info: &RustProcessedTypeInfo)
+                            -> Result<Vec<RustAssociatedConst>> {
+    let mut result: Vec<RustAssociatedConst> = Vec::new();
+    if info.cpp_template_arguments.is_some() {
+      return Ok(result);
+    }
+    let prefix = format!("{}::", info.cpp_name);
+    for type_info in &self.processed_types {
+      if !type_info.is_public || !type_info.cpp_name.starts_with(&prefix) ||
+         type_info.cpp_name[prefix.len()..].contains("::") {
+        continue;
+      }
+      if let RustTypeWrapperKind::Enum { ref values, is_scoped, .. } = type_info.kind {
+        // values of scoped enums are not visible in the enclosing scope in C++
+        if is_scoped {
+          continue;
+        }
+        let enum_last_name = type_info.rust_name.last_name()?;
+        for value in values {
+          if value.is_dummy {
+            continue;
+          }
+          let name = format!("{}_{}", enum_last_name, value.name).to_upper_case_words();
+          if result.iter().any(|c| c.name == name) {
+            continue;
+          }
+          result.push(RustAssociatedConst {
+                        name: name,
+                        enum_name: type_info.rust_name.clone(),
+                        variant_name: value.name.clone(),
+                      });
+        }
+      }
     }
     Ok(result)
   }
 
+  /// Generates associated constants for values of anonymous enums nested
+  /// in the class `info`. Such enums are not wrapped as Rust enums
+  /// because they can't be referred to in C++ by name.
+  fn anonymous_enum_consts(&self, info: &RustProcessedTypeInfo) -> Vec<RustAnonymousEnumConst> {
+    let mut result: Vec<RustAnonymousEnumConst> = Vec::new();
+    if info.cpp_template_arguments.is_some() {
+      return result;
+    }
+    let prefix = format!("{}::", info.cpp_name);
+    for type_info in &self.input_data.cpp_data.current.parser.types {
+      if !type_info.name.starts_with(&prefix) ||
+         type_info.name[prefix.len()..].contains("::") {
+        continue;
+      }
+      if let CppTypeKind::Enum {
+               ref values,
+               is_anonymous: true,
+               ..
+             } = type_info.kind {
+        for value in values {
+          if result.iter().any(|c| c.name == value.name) {
+            continue;
+          }
+          result.push(RustAnonymousEnumConst {
+                        name: value.name.clone(),
+                        value: value.value,
+                      });
+        }
+      }
+    }
+    result
+  }
+
+  /// Finds the longest namespace in `namespace_parts` that has a mapping
+  /// added by `Config::add_namespace_mapping`. Returns number of
+  /// parts of this namespace and the corresponding Rust module path.
+  fn namespace_mapping(&self, namespace_parts: &[&str]) -> Option<(usize, &Vec<String>)> {
+    (1..namespace_parts.len() + 1)
+      .rev()
+      .filter_map(|count| {
+                    self
+                      .input_data
+                      .namespace_mappings
+                      .get(&namespace_parts[..count].join("::"))
+                      .map(|path| (count, path))
+                  })
+      .next()
+  }
+
+  /// Returns true if the Rust name of the C++ entity `cpp_name`
+  /// is affected by `Config::add_namespace_mapping`.
+  fn is_namespace_mapped(&self, cpp_name: &str) -> bool {
+    let mut parts: Vec<_> = cpp_name.split("::").collect();
+    parts.pop();
+    self.namespace_mapping(&parts).is_some()
+  }
+
+  /// Returns an error if a type or a free function moved by
+  /// `Config::add_namespace_mapping` has the same Rust name as
+  /// another C++ type or free function.
+  fn check_namespace_mapping_collisions(&self, cpp_methods: &[&CppAndFfiMethod]) -> Result<()> {
+    let mut items = Vec::new();
+    for type1 in &self.processed_types {
+      if type1.mapped_conversion.is_none() {
+        items.push((type1.rust_name.clone(), type1.cpp_name.clone()));
+      }
+    }
+    for method in cpp_methods {
+      if method.cpp_method.class_membership.is_none() {
+        items.push((self.free_function_rust_name(&method.cpp_method)?,
+                    method.cpp_method.name.clone()));
+      }
+    }
+    let mut names: HashMap<RustName, String> = HashMap::new();
+    for (rust_name, cpp_name) in items {
+      match names.entry(rust_name) {
+        hash_map::Entry::Vacant(entry) => {
+          entry.insert(cpp_name);
+        }
+        hash_map::Entry::Occupied(entry) => {
+          if entry.get() != &cpp_name &&
+             (self.is_namespace_mapped(entry.get()) || self.is_namespace_mapped(&cpp_name)) {
+            return Err(format!("namespace mapping makes {} and {} have the same Rust name: {}",
+                               entry.get(),
+                               cpp_name,
+                               entry.key().full_name(None))
+                           .into());
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
   /// Generates `RustName` for specified function or type name,
   /// including crate name and modules list.
   fn calculate_rust_name(&self,
@@ -2010,7 +4552,21 @@ impl<'aa> RustGenerator<'aa> {
     }
     //    parts.push(config.crate_name.clone());
     //    parts.push(include_file_to_module_name(include_file, config.remove_qt_prefix));
+    let mut cpp_prefix = String::new();
+    if let Some((count, module_path)) = self.namespace_mapping(&split_parts) {
+      parts.extend(module_path.iter().cloned());
+      cpp_prefix = split_parts[..count].join("::");
+      split_parts.drain(..count);
+    }
     for part in split_parts {
+      if !cpp_prefix.is_empty() {
+        cpp_prefix.push_str("::");
+      }
+      cpp_prefix.push_str(part);
+      if self.input_data.cpp_data.is_inline_namespace(&cpp_prefix) {
+        // inline namespaces are transparent in C++, so they don't get their own module
+        continue;
+      }
       let part = part.to_string();
       if self.input_data.filtered_namespaces.contains(&part) {
         continue;
@@ -2064,32 +4620,69 @@ fn calculate_rust_name_test_part(name: &'static str,
                                  include_file: &'static str,
                                  is_function: bool,
                                  expected: &[&'static str]) {
+  calculate_rust_name_test_part_with_data(&Default::default(),
+                                          name,
+                                          include_file,
+                                          is_function,
+                                          expected);
+}
+
+#[cfg(test)]
+fn calculate_rust_name_test_part_with_data(cpp_data: &CppDataWithDeps,
+                                           name: &'static str,
+                                           include_file: &'static str,
+                                           is_function: bool,
+                                           expected: &[&'static str]) {
+  let generator = calculate_rust_name_test_generator(cpp_data, include_file, HashMap::new());
+  assert_eq!(generator
+               .calculate_rust_name(&name.to_string(),
+                                    &include_file.to_string(),
+                                    is_function,
+                                    None)
+               .unwrap(),
+             RustName::new(expected.into_iter().map(|x| x.to_string()).collect()).unwrap());
+}
+
+#[cfg(test)]
+fn calculate_rust_name_test_generator<'a>(cpp_data: &'a CppDataWithDeps<'a>,
+                                          include_file: &'static str,
+                                          namespace_mappings: HashMap<String, Vec<String>>)
+                                          -> RustGenerator<'a> {
   let header = ::cpp_ffi_data::CppFfiHeaderData {
     include_file_base_name: include_file.to_string(),
     methods: Vec::new(),
     qt_slot_wrappers: Vec::new(),
+    qt_event_filter_wrapper: None,
+    functor_wrappers: Vec::new(),
   };
   let mut generator = RustGenerator {
     top_module_names: HashMap::new(),
     processed_types: Vec::new(),
+    dependency_types: RustTypeIndex::default(),
     input_data: RustGeneratorInputData {
       cpp_ffi_headers: vec![header],
-      cpp_data: &Default::default(),
+      cpp_data: cpp_data,
       dependency_types: Vec::new(),
       crate_name: "qt_core".to_string(),
       remove_qt_prefix: true,
       filtered_namespaces: Vec::new(),
+      namespace_mappings: namespace_mappings,
+      owned_void_ptr_returns: HashMap::new(),
+      owned_ptr_returns: Vec::new(),
+      fallible_constructors: Vec::new(),
+      string_buffer_outs: HashMap::new(),
+      array_out_params: HashMap::new(),
+      return_lifetimes: HashMap::new(),
+      ownership_transfer_args: HashMap::new(),
+      consuming_methods: Vec::new(),
+      type_mappings: HashMap::new(),
+      virtual_clones: HashMap::new(),
+      flaggable_scoped_enums: Vec::new(),
+      safety_policy: SafetyPolicy::default(),
     },
   };
   generator.top_module_names = generator.calc_top_module_names().unwrap();
-
-  assert_eq!(generator
-               .calculate_rust_name(&name.to_string(),
-                                    &include_file.to_string(),
-                                    is_function,
-                                    None)
-               .unwrap(),
-             RustName::new(expected.into_iter().map(|x| x.to_string()).collect()).unwrap());
+  generator
 }
 
 #[test]
@@ -2116,6 +4709,97 @@ fn calculate_rust_name_test() {
                                 &["qt_core", "rect", "ns", "func1"]);
 }
 
+#[test]
+fn calculate_rust_name_inline_namespace_test() {
+  let mut cpp_data: CppDataWithDeps = Default::default();
+  cpp_data.current.parser.inline_namespaces = vec!["ns::v1".to_string()];
+  calculate_rust_name_test_part_with_data(&cpp_data,
+                                          "ns::v1::func1",
+                                          "QRect",
+                                          true,
+                                          &["qt_core", "rect", "ns", "func1"]);
+  calculate_rust_name_test_part_with_data(&cpp_data,
+                                          "ns::v1::Class1",
+                                          "QRect",
+                                          false,
+                                          &["qt_core", "rect", "ns", "Class1"]);
+  // only the declared inline namespace is omitted
+  calculate_rust_name_test_part_with_data(&cpp_data,
+                                          "other::v1::func1",
+                                          "QRect",
+                                          true,
+                                          &["qt_core", "rect", "other", "v1", "func1"]);
+}
+
+#[test]
+fn calculate_rust_name_namespace_mapping_test() {
+  let cpp_data: CppDataWithDeps = Default::default();
+  let mut namespace_mappings = HashMap::new();
+  namespace_mappings.insert("lib::v2::detail".to_string(), vec!["private".to_string()]);
+  namespace_mappings.insert("lib::v2".to_string(), Vec::new());
+  let generator = calculate_rust_name_test_generator(&cpp_data, "QRect", namespace_mappings);
+  let rust_name = |name: &str, is_function: bool| {
+    generator
+      .calculate_rust_name(name, "QRect", is_function, None)
+      .unwrap()
+      .parts
+  };
+  assert_eq!(rust_name("lib::v2::detail::Impl", false),
+             vec!["qt_core", "rect", "private", "Impl"]);
+  assert_eq!(rust_name("lib::v2::detail::inner::helper", true),
+             vec!["qt_core", "rect", "private", "inner", "helper"]);
+  assert_eq!(rust_name("lib::v2::Class1", false),
+             vec!["qt_core", "rect", "Class1"]);
+  // unmapped namespaces are not affected
+  assert_eq!(rust_name("lib::v1::Class1", false),
+             vec!["qt_core", "rect", "lib", "v1", "Class1"]);
+  assert_eq!(rust_name("other::detail::Impl", false),
+             vec!["qt_core", "rect", "other", "detail", "Impl"]);
+}
+
+#[test]
+fn namespace_mapping_collision_test() {
+  let cpp_data: CppDataWithDeps = Default::default();
+  let mut namespace_mappings = HashMap::new();
+  namespace_mappings.insert("lib::v2".to_string(), vec!["lib".to_string()]);
+  let mut generator = calculate_rust_name_test_generator(&cpp_data, "QRect", namespace_mappings);
+  for cpp_name in &["lib::Class1", "lib::v2::Class2"] {
+    let rust_name = generator
+      .calculate_rust_name(cpp_name, "QRect", false, None)
+      .unwrap();
+    generator
+      .processed_types
+      .push(RustProcessedTypeInfo {
+              cpp_name: cpp_name.to_string(),
+              cpp_doc: None,
+              cpp_template_arguments: None,
+              kind: RustTypeWrapperKind::Enum {
+                values: Vec::new(),
+                is_flaggable: false,
+                is_scoped: false,
+              },
+              rust_name: rust_name,
+              is_public: true,
+              mapped_conversion: None,
+            });
+  }
+  assert!(generator.check_namespace_mapping_collisions(&[]).is_ok());
+
+  let rust_name = generator
+    .calculate_rust_name("lib::v2::Class1", "QRect", false, None)
+    .unwrap();
+  assert_eq!(rust_name.parts, vec!["qt_core", "rect", "lib", "Class1"]);
+  let mut type1 = generator.processed_types[0].clone();
+  type1.cpp_name = "lib::v2::Class1".to_string();
+  type1.rust_name = rust_name;
+  generator.processed_types.push(type1);
+  let err = generator
+    .check_namespace_mapping_collisions(&[])
+    .unwrap_err()
+    .to_string();
+  assert!(err.contains("lib::Class1 and lib::v2::Class1"));
+}
+
 #[test]
 fn prepare_enum_values_test_simple() {
   let r = prepare_enum_values(&[CppEnumValue {
@@ -2182,6 +4866,175 @@ fn prepare_enum_values_test_prefix() {
   assert_eq!(r[2].name, "NecessaryEvil");
 }
 
+#[test]
+fn flags_default_value_to_rust_test() {
+  let values = vec![CppEnumValue {
+                      name: "AlignLeft".to_string(),
+                      value: 1,
+                      doc: None,
+                    },
+                    CppEnumValue {
+                      name: "AlignRight".to_string(),
+                      value: 2,
+                      doc: None,
+                    },
+                    CppEnumValue {
+                      name: "AlignVCenter".to_string(),
+                      value: 0x80,
+                      doc: None,
+                    }];
+  let enum_name = RustName::new(vec!["qt_core".to_string(),
+                                     "qt".to_string(),
+                                     "AlignmentFlag".to_string()])
+      .unwrap();
+  assert_eq!(flags_default_value_to_rust("Qt :: AlignLeft | Qt :: AlignVCenter",
+                                         &values,
+                                         false,
+                                         &enum_name,
+                                         "qt_widgets")
+                 .unwrap(),
+             "::qt_core::flags::Flags::from_enum(::qt_core::qt::AlignmentFlag::Left) | \
+              ::qt_core::qt::AlignmentFlag::VCenter");
+  assert_eq!(flags_default_value_to_rust("Qt :: AlignRight", &values, false, &enum_name, "qt_core")
+                 .unwrap(),
+             "::flags::Flags::from_enum(::qt::AlignmentFlag::Right)");
+  assert_eq!(flags_default_value_to_rust("Qt :: Alignment ( )",
+                                         &values,
+                                         false,
+                                         &enum_name,
+                                         "qt_core")
+                 .unwrap(),
+             "::flags::Flags::from_int(0)");
+  assert!(flags_default_value_to_rust("Qt :: AlignTop", &values, false, &enum_name, "qt_core")
+            .is_err());
+  assert_eq!(flags_default_value_to_rust("0", &values, false, &enum_name, "qt_core").unwrap(),
+             "::flags::Flags::from_int(0)");
+  assert!(flags_default_value_to_rust("0", &values, true, &enum_name, "qt_core").is_err());
+  assert_eq!(flags_default_value_to_rust("Qt :: Alignment ( )",
+                                         &values,
+                                         true,
+                                         &enum_name,
+                                         "qt_core")
+                 .unwrap(),
+             "::flags::Flags::from_int(0)");
+}
+
+#[test]
+fn omitted_argument_defaults_test() {
+  use cpp_data::{CppTypeData, CppOriginLocation};
+  use cpp_ffi_data::CppFfiMethodSignature;
+  use cpp_method::CppMethodArgument;
+
+  let values = vec![CppEnumValue {
+                      name: "AlignLeft".to_string(),
+                      value: 1,
+                      doc: None,
+                    },
+                    CppEnumValue {
+                      name: "AlignRight".to_string(),
+                      value: 2,
+                      doc: None,
+                    },
+                    CppEnumValue {
+                      name: "AlignVCenter".to_string(),
+                      value: 0x80,
+                      doc: None,
+                    }];
+  let mut cpp_data: CppDataWithDeps = Default::default();
+  cpp_data
+    .current
+    .parser
+    .types
+    .push(CppTypeData {
+            name: "Qt::AlignmentFlag".to_string(),
+            include_file: "qnamespace.h".to_string(),
+            origin_location: CppOriginLocation {
+              include_file_path: "/qnamespace.h".to_string(),
+              line: 1,
+              column: 1,
+            },
+            kind: CppTypeKind::Enum {
+              values: values.clone(),
+              is_scoped: false,
+              is_anonymous: false,
+            },
+            doc: None,
+          });
+  let mut generator = calculate_rust_name_test_generator(&cpp_data, "QtCore", HashMap::new());
+  generator
+    .processed_types
+    .push(RustProcessedTypeInfo {
+            cpp_name: "Qt::AlignmentFlag".to_string(),
+            cpp_doc: None,
+            cpp_template_arguments: None,
+            kind: RustTypeWrapperKind::Enum {
+              values: prepare_enum_values(&values),
+              is_flaggable: true,
+              is_scoped: false,
+            },
+            rust_name: RustName::new(vec!["qt_core".to_string(),
+                                          "qt".to_string(),
+                                          "AlignmentFlag".to_string()])
+                .unwrap(),
+            is_public: true,
+            mapped_conversion: None,
+          });
+  let enum_type = CppType {
+    base: CppTypeBase::Enum { name: "Qt::AlignmentFlag".to_string() },
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let flags_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "QFlags".to_string(),
+                               template_arguments: Some(vec![enum_type.clone()]),
+                             }),
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let argument = |name: &str, argument_type: &CppType, default_value: &str| {
+    CppMethodArgument {
+      name: name.to_string(),
+      argument_type: argument_type.clone(),
+      has_default_value: true,
+      default_value: Some(default_value.to_string()),
+    }
+  };
+  let mut cpp_method = ::tests::cpp_method::empty_regular_method();
+  cpp_method.name = "setAlignment".to_string();
+  let method = CppAndFfiMethod {
+    cpp_method: cpp_method,
+    kind: CppFfiMethodKind::RealWithOmittedArguments {
+      arguments_before_omitting: Some(vec![argument("alignment",
+                                                    &flags_type,
+                                                    "Qt :: AlignLeft | Qt :: AlignVCenter"),
+                                           argument("fallback", &enum_type, "Qt :: AlignRight"),
+                                           argument("extra", &flags_type, "Qt :: Alignment ( )"),
+                                           argument("other",
+                                                    &flags_type,
+                                                    "Qt :: defaultAlignment ( )"),
+                                           argument("count", &CppType::void(), "1")]),
+    },
+    allocation_place: ReturnValueAllocationPlace::NotApplicable,
+    c_signature: CppFfiMethodSignature {
+      arguments: Vec::new(),
+      return_type: CppFfiType::void(),
+    },
+    c_name: "qt_core_setAlignment".to_string(),
+  };
+  // function calls and unsupported types are not documented
+  assert_eq!(generator.omitted_argument_defaults(&method),
+             vec!["alignment = ::flags::Flags::from_enum(::qt::AlignmentFlag::Left) | \
+                   ::qt::AlignmentFlag::VCenter"
+                    .to_string(),
+                  "fallback = ::qt::AlignmentFlag::Right".to_string(),
+                  "extra = ::flags::Flags::from_int(0)".to_string()]);
+}
+
 #[test]
 fn prepare_enum_values_test_suffix() {
   let r = prepare_enum_values(&[CppEnumValue {
@@ -2256,6 +5109,646 @@ fn prepare_enum_values_test_suffix_partial() {
   assert_eq!(r[1].name, "Coarse");
 }
 
+#[test]
+fn complete_type_const_ptr_to_const() {
+  // `const int* const` argument
+  let cpp_type = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::Ptr,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+  let complete = complete_type(&[],
+                               &RustTypeIndex::default(),
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::Argument(0),
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  for rust_type in &[&complete.rust_ffi_type, &complete.rust_api_type] {
+    if let RustType::Common {
+             ref indirection,
+             ref is_const,
+             ..
+           } = **rust_type {
+      assert_eq!(indirection, &RustTypeIndirection::Ptr);
+      assert!(*is_const);
+    } else {
+      panic!("unexpected rust type: {:?}", rust_type);
+    }
+  }
+}
+
+#[test]
+fn complete_type_array_reference() {
+  use rust_code_generator::rust_type_to_code;
+
+  // `const int32_t (&)[4]` return type
+  let element = CppType {
+    base: CppTypeBase::SpecificNumeric(CppSpecificNumericType {
+                                         name: "int32_t".to_string(),
+                                         bits: 32,
+                                         kind: CppSpecificNumericTypeKind::Integer {
+                                           is_signed: true,
+                                         },
+                                       }),
+    indirection: CppTypeIndirection::None,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let cpp_type = CppType {
+    base: CppTypeBase::Array {
+      element: Box::new(element),
+      size: 4,
+      is_std_array: false,
+    },
+    indirection: CppTypeIndirection::Ref,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap();
+  assert_eq!(ffi_type.conversion, CppIndirectionChange::ReferenceToPointer);
+  assert_eq!(ffi_type.ffi_type.to_cpp_code(Some("func")).unwrap(),
+             "const int32_t (*func)[4]");
+  let complete = complete_type(&[],
+                               &RustTypeIndex::default(),
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::ReturnValue,
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  assert_eq!(rust_type_to_code(&complete.rust_ffi_type, "crate1"),
+             "*const [i32; 4]");
+  assert_eq!(rust_type_to_code(&complete.rust_api_type, "crate1"),
+             "&[i32; 4]");
+  assert_eq!(complete.rust_api_to_c_conversion, RustToCTypeConversion::RefToPtr);
+}
+
+#[test]
+fn complete_type_mapped_class() {
+  use rust_code_generator::rust_type_to_code;
+
+  let processed_types = vec![RustProcessedTypeInfo {
+                               cpp_name: "MyString".to_string(),
+                               cpp_doc: None,
+                               cpp_template_arguments: None,
+                               kind: RustTypeWrapperKind::Struct {
+                                 size_const_name: None,
+                                 is_deletable: true,
+                                 slot_wrapper: None,
+                                 event_filter_wrapper: None,
+                                 functor_wrapper: None,
+                               },
+                               rust_name: RustName::new(vec!["my_crate".to_string(),
+                                                             "MyString".to_string()])
+                                   .unwrap(),
+                               is_public: true,
+                               mapped_conversion: Some(RustToCTypeConversion::ValueToPtr),
+                             }];
+  // `const MyString&` argument
+  let cpp_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "MyString".to_string(),
+                               template_arguments: None,
+                             }),
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+  let complete = complete_type(&processed_types,
+                               &RustTypeIndex::default(),
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::Argument(0),
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  assert_eq!(rust_type_to_code(&complete.rust_ffi_type, "crate1"),
+             "*const ::my_crate::MyString");
+  assert_eq!(rust_type_to_code(&complete.rust_api_type, "crate1"),
+             "::my_crate::MyString");
+  assert_eq!(complete.rust_api_to_c_conversion, RustToCTypeConversion::ValueToPtr);
+}
+
+#[test]
+fn type_mapping_suppresses_wrapper() {
+  use config::{Config, CrateProperties};
+  use cpp_data::{CppData, ParserCppData, ProcessedCppData};
+  use cpp_method::CppMethodArgument;
+  use rust_code_generator::rust_type_to_code;
+  use tests::cpp_ffi_generator::{class_type, class_type_data};
+  use tests::cpp_method::empty_regular_method;
+
+  let mut config = Config::new("/tmp/out",
+                               "/tmp/cache",
+                               CrateProperties::new("myfakelib", "0.0.0"));
+  config.add_type_mapping("MyString",
+                          RustName::new(vec!["my_strings".to_string(), "MyString".to_string()])
+                            .unwrap(),
+                          RustToCTypeConversion::RefToPtr);
+
+  // void func1(const MyString& arg1, const Other& arg2)
+  let mut method = empty_regular_method();
+  method.name = "func1".to_string();
+  method.include_file = "myfakelib.h".to_string();
+  for (index, name) in ["MyString", "Other"].iter().enumerate() {
+    method
+      .arguments
+      .push(CppMethodArgument {
+              name: format!("arg{}", index + 1),
+              argument_type: CppType {
+                indirection: CppTypeIndirection::Ref,
+                is_const: true,
+                ..class_type(name)
+              },
+              has_default_value: false,
+              default_value: None,
+            });
+  }
+  let ffi_method = CppAndFfiMethod {
+    c_signature: method
+      .c_signature(ReturnValueAllocationPlace::NotApplicable)
+      .unwrap(),
+    cpp_method: method,
+    kind: CppFfiMethodKind::Real,
+    allocation_place: ReturnValueAllocationPlace::NotApplicable,
+    c_name: "myfakelib_G_func1".to_string(),
+  };
+  let header = ::cpp_ffi_data::CppFfiHeaderData {
+    include_file_base_name: "myfakelib".to_string(),
+    methods: vec![ffi_method],
+    qt_slot_wrappers: Vec::new(),
+    qt_event_filter_wrapper: None,
+    functor_wrappers: Vec::new(),
+  };
+  let mut processed = ProcessedCppData::default();
+  for name in &["MyString", "Other"] {
+    processed
+      .type_allocation_places
+      .insert(name.to_string(), CppTypeAllocationPlace::Heap);
+  }
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("MyString", Vec::new()),
+                    class_type_data("Other", Vec::new())],
+        ..Default::default()
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let input_data = RustGeneratorInputData {
+    cpp_ffi_headers: vec![header],
+    cpp_data: &cpp_data,
+    dependency_types: Vec::new(),
+    crate_name: "myfakelib".to_string(),
+    remove_qt_prefix: false,
+    filtered_namespaces: Vec::new(),
+    namespace_mappings: HashMap::new(),
+    owned_void_ptr_returns: HashMap::new(),
+    owned_ptr_returns: Vec::new(),
+    fallible_constructors: Vec::new(),
+    string_buffer_outs: HashMap::new(),
+    array_out_params: HashMap::new(),
+    return_lifetimes: HashMap::new(),
+    ownership_transfer_args: HashMap::new(),
+    consuming_methods: Vec::new(),
+    type_mappings: config.type_mappings().clone(),
+    virtual_clones: HashMap::new(),
+    flaggable_scoped_enums: Vec::new(),
+    safety_policy: SafetyPolicy::default(),
+  };
+  let output = input_data.run().unwrap();
+
+  fn declared_types(module: &RustModule, result: &mut Vec<String>) {
+    result.extend(module.types.iter().map(|t| t.name.last_name().unwrap().clone()));
+    for submodule in &module.submodules {
+      declared_types(submodule, result);
+    }
+  }
+  let mut types = Vec::new();
+  for module in &output.modules {
+    declared_types(module, &mut types);
+  }
+  assert!(types.contains(&"Other".to_string()));
+  assert!(!types.contains(&"MyString".to_string()));
+
+  let my_string = output
+    .processed_types
+    .iter()
+    .find(|t| t.cpp_name == "MyString")
+    .unwrap();
+  assert_eq!(my_string.mapped_conversion, Some(RustToCTypeConversion::RefToPtr));
+  assert_eq!(my_string.rust_name.full_name(None), "::my_strings::MyString");
+
+  let func1 = output
+    .modules
+    .iter()
+    .flat_map(|module| module.functions.iter())
+    .find(|f| f.name.last_name().unwrap() == "func1")
+    .unwrap();
+  if let RustMethodArguments::SingleVariant(ref variant) = func1.arguments {
+    let api_types: Vec<_> = variant
+      .arguments
+      .iter()
+      .map(|arg| rust_type_to_code(&arg.argument_type.rust_api_type, "myfakelib"))
+      .collect();
+    assert_eq!(api_types, vec!["&::my_strings::MyString", "&::myfakelib::Other"]);
+  } else {
+    panic!("unexpected arguments: {:?}", func1.arguments);
+  }
+}
+
+#[test]
+fn complete_type_qchar_argument() {
+  use rust_code_generator::rust_type_to_code;
+
+  // `const QChar&` argument
+  let cpp_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "QChar".to_string(),
+                               template_arguments: None,
+                             }),
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+  let complete = complete_type(&[],
+                               &RustTypeIndex::default(),
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::Argument(0),
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  assert_eq!(rust_type_to_code(&complete.rust_ffi_type, "crate1"),
+             "::libc::c_ushort");
+  assert_eq!(rust_type_to_code(&complete.rust_api_type, "crate1"), "char");
+  assert_eq!(complete.rust_api_to_c_conversion, RustToCTypeConversion::CharToUShort);
+}
+
+#[test]
+fn complete_type_unique_ptr_return() {
+  use rust_code_generator::rust_type_to_code;
+
+  let mut processed_types = vec![RustProcessedTypeInfo {
+                                   cpp_name: "QObject".to_string(),
+                                   cpp_doc: None,
+                                   cpp_template_arguments: None,
+                                   kind: RustTypeWrapperKind::Struct {
+                                     size_const_name: None,
+                                     is_deletable: true,
+                                     slot_wrapper: None,
+                                     event_filter_wrapper: None,
+                                     functor_wrapper: None,
+                                   },
+                                   rust_name: RustName::new(vec!["qt_core".to_string(),
+                                                                 "object".to_string(),
+                                                                 "Object".to_string()])
+                                       .unwrap(),
+                                   is_public: true,
+                                   mapped_conversion: None,
+                                 }];
+  // `std::unique_ptr<QObject>` return type
+  let cpp_type = CppType {
+    base: CppTypeBase::UniquePtr {
+      pointee: Box::new(CppType {
+                          base: CppTypeBase::Class(CppTypeClassBase {
+                                                     name: "QObject".to_string(),
+                                                     template_arguments: None,
+                                                   }),
+                          indirection: CppTypeIndirection::None,
+                          is_const: false,
+                          is_const2: false,
+                          is_volatile: false,
+                        }),
+    },
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap();
+  let complete = complete_type(&processed_types,
+                               &RustTypeIndex::default(),
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::ReturnValue,
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  assert_eq!(complete.cpp_to_ffi_conversion, CppIndirectionChange::UniquePtrToPtr);
+  assert_eq!(rust_type_to_code(&complete.rust_ffi_type, "crate1"),
+             "*mut ::qt_core::object::Object");
+  assert_eq!(rust_type_to_code(&complete.rust_api_type, "crate1"),
+             "::cpp_utils::CppBox<::qt_core::object::Object>");
+  assert_eq!(complete.rust_api_to_c_conversion, RustToCTypeConversion::CppBoxToPtr);
+
+  if let RustTypeWrapperKind::Struct { ref mut is_deletable, .. } = processed_types[0].kind {
+    *is_deletable = false;
+  }
+  assert!(complete_type(&processed_types,
+                        &RustTypeIndex::default(),
+                        &ffi_type,
+                        &CppFfiArgumentMeaning::ReturnValue,
+                        false,
+                        &ReturnValueAllocationPlace::NotApplicable)
+              .is_err());
+}
+
+#[test]
+fn type_index_large_set() {
+  use rust_code_generator::rust_type_to_code;
+
+  let type_info = |cpp_name: &str, template_arguments: Option<Vec<CppType>>, rust_name: &str| {
+    RustProcessedTypeInfo {
+      cpp_name: cpp_name.to_string(),
+      cpp_doc: None,
+      cpp_template_arguments: template_arguments,
+      kind: RustTypeWrapperKind::Struct {
+        size_const_name: None,
+        is_deletable: true,
+        slot_wrapper: None,
+        event_filter_wrapper: None,
+        functor_wrapper: None,
+      },
+      rust_name: RustName::new(vec!["dep".to_string(), rust_name.to_string()]).unwrap(),
+      is_public: true,
+      mapped_conversion: None,
+    }
+  };
+  let int_type = |is_const: bool| {
+    CppType {
+      base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+      indirection: CppTypeIndirection::None,
+      is_const: is_const,
+      is_const2: false,
+      is_volatile: false,
+    }
+  };
+  let count = 20000;
+  let mut dependency_types: Vec<_> = (0..count)
+    .map(|i| type_info(&format!("ns::Class{}", i), None, &format!("Class{}", i)))
+    .collect();
+  dependency_types.push(type_info("ns::Vector", Some(vec![int_type(false)]), "VectorInt"));
+  dependency_types.push(type_info("ns::Vector",
+                                  Some(vec![int_type(true)]),
+                                  "VectorConstInt"));
+  let index = RustTypeIndex::new(&dependency_types);
+  assert_eq!(index.types().len(), count + 2);
+  for i in 0..count {
+    let info = index.find_type(&format!("ns::Class{}", i), &None).unwrap();
+    assert_eq!(info.rust_name.last_name().unwrap(), &format!("Class{}", i));
+  }
+  let rust_name = RustName::new(vec!["dep".to_string(), "Class777".to_string()]).unwrap();
+  assert_eq!(index.find_by_rust_name(&rust_name).unwrap().cpp_name,
+             "ns::Class777");
+  assert!(index.find_type("ns::Class0", &Some(vec![int_type(false)])).is_none());
+  assert!(index.find_type("ns::Missing", &None).is_none());
+  assert!(index.find_type("ns::Vector", &None).is_none());
+  assert_eq!(index
+               .find_type("ns::Vector", &Some(vec![int_type(false)]))
+               .unwrap()
+               .rust_name
+               .last_name()
+               .unwrap(),
+             "VectorInt");
+  assert_eq!(index
+               .find_type("ns::Vector", &Some(vec![int_type(true)]))
+               .unwrap()
+               .rust_name
+               .last_name()
+               .unwrap(),
+             "VectorConstInt");
+
+  // `ns::Class12345*` argument resolved through dependency types
+  let cpp_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "ns::Class12345".to_string(),
+                               template_arguments: None,
+                             }),
+    indirection: CppTypeIndirection::Ptr,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let ffi_type = cpp_type.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+  let complete = complete_type(&[],
+                               &index,
+                               &ffi_type,
+                               &CppFfiArgumentMeaning::Argument(0),
+                               false,
+                               &ReturnValueAllocationPlace::NotApplicable)
+      .unwrap();
+  assert_eq!(rust_type_to_code(&complete.rust_ffi_type, "crate1"),
+             "*mut ::dep::Class12345");
+}
+
+#[cfg(test)]
+fn safety_policy_test_part(policy: SafetyPolicy,
+                           argument_types: Vec<CppType>,
+                           return_type: CppType)
+                           -> bool {
+  use cpp_method::CppMethodArgument;
+  use tests::cpp_method::empty_regular_method;
+
+  let mut method = empty_regular_method();
+  method.name = "func1".to_string();
+  method.include_file = "myfakelib".to_string();
+  method.return_type = return_type;
+  for (index, argument_type) in argument_types.into_iter().enumerate() {
+    method
+      .arguments
+      .push(CppMethodArgument {
+              name: format!("arg{}", index + 1),
+              argument_type: argument_type,
+              has_default_value: false,
+              default_value: None,
+            });
+  }
+  let ffi_method = CppAndFfiMethod {
+    c_signature: method
+      .c_signature(ReturnValueAllocationPlace::NotApplicable)
+      .unwrap(),
+    cpp_method: method,
+    kind: CppFfiMethodKind::Real,
+    allocation_place: ReturnValueAllocationPlace::NotApplicable,
+    c_name: "myfakelib_G_func1".to_string(),
+  };
+  let header = ::cpp_ffi_data::CppFfiHeaderData {
+    include_file_base_name: "myfakelib".to_string(),
+    methods: Vec::new(),
+    qt_slot_wrappers: Vec::new(),
+    qt_event_filter_wrapper: None,
+    functor_wrappers: Vec::new(),
+  };
+  let mut generator = RustGenerator {
+    top_module_names: HashMap::new(),
+    processed_types: Vec::new(),
+    dependency_types: RustTypeIndex::default(),
+    input_data: RustGeneratorInputData {
+      cpp_ffi_headers: vec![header],
+      cpp_data: &Default::default(),
+      dependency_types: Vec::new(),
+      crate_name: "myfakelib".to_string(),
+      remove_qt_prefix: false,
+      filtered_namespaces: Vec::new(),
+      namespace_mappings: HashMap::new(),
+      owned_void_ptr_returns: HashMap::new(),
+      owned_ptr_returns: Vec::new(),
+      fallible_constructors: Vec::new(),
+      string_buffer_outs: HashMap::new(),
+      array_out_params: HashMap::new(),
+      return_lifetimes: HashMap::new(),
+      ownership_transfer_args: HashMap::new(),
+      consuming_methods: Vec::new(),
+      type_mappings: HashMap::new(),
+      virtual_clones: HashMap::new(),
+      flaggable_scoped_enums: Vec::new(),
+      safety_policy: policy,
+    },
+  };
+  generator.top_module_names = generator.calc_top_module_names().unwrap();
+  generator
+    .generate_rust_single_method(&ffi_method, &RustMethodScope::Free, false)
+    .unwrap()
+    .is_unsafe
+}
+
+#[test]
+fn safety_policy_test() {
+  let int = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let int_ptr = CppType { indirection: CppTypeIndirection::Ptr, ..int.clone() };
+
+  // void func1(int* arg1)
+  assert!(safety_policy_test_part(SafetyPolicy::RawPointerArguments,
+                                  vec![int_ptr.clone()],
+                                  CppType::void()));
+  assert!(safety_policy_test_part(SafetyPolicy::Strict, vec![int_ptr.clone()], CppType::void()));
+  // int func1(int arg1)
+  assert!(!safety_policy_test_part(SafetyPolicy::RawPointerArguments,
+                                   vec![int.clone()],
+                                   int.clone()));
+  assert!(!safety_policy_test_part(SafetyPolicy::Strict, vec![int.clone()], int.clone()));
+  assert!(safety_policy_test_part(SafetyPolicy::AllUnsafe, vec![int.clone()], int.clone()));
+  // int* func1()
+  assert!(!safety_policy_test_part(SafetyPolicy::RawPointerArguments,
+                                   Vec::new(),
+                                   int_ptr.clone()));
+  assert!(safety_policy_test_part(SafetyPolicy::Strict, Vec::new(), int_ptr.clone()));
+  // void func1(int& arg1, int& arg2)
+  let int_ref = CppType { indirection: CppTypeIndirection::Ref, ..int.clone() };
+  assert!(safety_policy_test_part(SafetyPolicy::RawPointerArguments,
+                                  vec![int_ref.clone(), int_ref.clone()],
+                                  CppType::void()));
+  // void func1(int& arg1, const int& arg2)
+  let int_const_ref = CppType { is_const: true, ..int_ref.clone() };
+  assert!(!safety_policy_test_part(SafetyPolicy::RawPointerArguments,
+                                   vec![int_ref.clone(), int_const_ref.clone()],
+                                   CppType::void()));
+}
+
+
+#[cfg(test)]
+fn owned_void_ptr_return_test_part(return_type: CppType) -> Result<RustSingleMethod> {
+  use tests::cpp_method::empty_regular_method;
+
+  let mut method = empty_regular_method();
+  method.name = "allocate".to_string();
+  method.include_file = "myfakelib".to_string();
+  method.return_type = return_type;
+  let ffi_method = CppAndFfiMethod {
+    c_signature: method
+      .c_signature(ReturnValueAllocationPlace::NotApplicable)
+      .unwrap(),
+    cpp_method: method,
+    kind: CppFfiMethodKind::Real,
+    allocation_place: ReturnValueAllocationPlace::NotApplicable,
+    c_name: "myfakelib_G_allocate".to_string(),
+  };
+  let header = ::cpp_ffi_data::CppFfiHeaderData {
+    include_file_base_name: "myfakelib".to_string(),
+    methods: Vec::new(),
+    qt_slot_wrappers: Vec::new(),
+    qt_event_filter_wrapper: None,
+    functor_wrappers: Vec::new(),
+  };
+  let mut owned_void_ptr_returns = HashMap::new();
+  owned_void_ptr_returns.insert("allocate".to_string(), "::libc::free".to_string());
+  let mut generator = RustGenerator {
+    top_module_names: HashMap::new(),
+    processed_types: Vec::new(),
+    dependency_types: RustTypeIndex::default(),
+    input_data: RustGeneratorInputData {
+      cpp_ffi_headers: vec![header],
+      cpp_data: &Default::default(),
+      dependency_types: Vec::new(),
+      crate_name: "myfakelib".to_string(),
+      remove_qt_prefix: false,
+      filtered_namespaces: Vec::new(),
+      namespace_mappings: HashMap::new(),
+      owned_void_ptr_returns: owned_void_ptr_returns,
+      owned_ptr_returns: Vec::new(),
+      fallible_constructors: Vec::new(),
+      string_buffer_outs: HashMap::new(),
+      array_out_params: HashMap::new(),
+      return_lifetimes: HashMap::new(),
+      ownership_transfer_args: HashMap::new(),
+      consuming_methods: Vec::new(),
+      type_mappings: HashMap::new(),
+      virtual_clones: HashMap::new(),
+      flaggable_scoped_enums: Vec::new(),
+      safety_policy: SafetyPolicy::default(),
+    },
+  };
+  generator.top_module_names = generator.calc_top_module_names().unwrap();
+  generator.generate_rust_single_method(&ffi_method, &RustMethodScope::Free, false)
+}
+
+#[test]
+fn owned_void_ptr_return_test() {
+  use rust_code_generator::rust_type_to_code;
+
+  // void* allocate()
+  let void_ptr = CppType {
+    base: CppTypeBase::Void,
+    indirection: CppTypeIndirection::Ptr,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let method = owned_void_ptr_return_test_part(void_ptr).unwrap();
+  let return_type = &method.arguments.return_type;
+  assert_eq!(rust_type_to_code(&return_type.rust_ffi_type, "myfakelib"),
+             "*mut ::libc::c_void");
+  assert_eq!(rust_type_to_code(&return_type.rust_api_type, "myfakelib"),
+             "::cpp_utils::OwnedVoidPtr");
+  let free_function = "::libc::free".to_string();
+  assert_eq!(return_type.rust_api_to_c_conversion,
+             RustToCTypeConversion::OwnedVoidPtrToPtr { free_function: free_function });
+
+  // int allocate()
+  let int = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  assert!(owned_void_ptr_return_test_part(int).is_err());
+}
 
 impl RustSingleMethod {
   /// Converts this method to a final Rust method
@@ -2306,6 +5799,12 @@ impl RustSingleMethod {
        })
   }
 
+  /// Returns true if this method takes a closure passed to
+  /// a `std::function` argument.
+  fn has_closure_arguments(&self) -> bool {
+    has_closure_arguments(&self.arguments.arguments)
+  }
+
   /// Returns true if all  requirements for overloading
   /// `self` with `other_method` are satisfied.
   fn can_be_overloaded_with(&self, other_method: &RustSingleMethod) -> Result<bool> {
@@ -2315,6 +5814,11 @@ impl RustSingleMethod {
     if self.self_arg_kind()? != other_method.self_arg_kind()? {
       return Ok(false);
     }
+    // closure argument types can't be a part of the tuple types
+    // implementing the overloading trait
+    if self.has_closure_arguments() || other_method.has_closure_arguments() {
+      return Ok(false);
+    }
     if self.arguments.arguments.len() == other_method.arguments.arguments.len() {
       if self
            .arguments
@@ -2335,6 +5839,23 @@ impl RustSingleMethod {
     Ok(true)
   }
 
+  /// Returns true if this method and `other` take the same arguments
+  /// but return different types, apart from constness. Such methods
+  /// can't be named as a const and mutable pair.
+  fn is_mismatched_const_overload_of(&self, other: &RustSingleMethod) -> bool {
+    let method1 = &self.arguments.cpp_method.cpp_method;
+    let method2 = &other.arguments.cpp_method.cpp_method;
+    let without_constness = |t: &CppType| {
+      CppType {
+        is_const: false,
+        is_const2: false,
+        ..t.clone()
+      }
+    };
+    method1.argument_types_equal(method2) &&
+    without_constness(&method1.return_type) != without_constness(&method2.return_type)
+  }
+
   /// Generates name suffix for this method using `caption_strategy`.
   /// `all_self_args` should contain all kinds of arguments found in
   /// the methods that have to be disambiguated using the name suffix.
@@ -2351,6 +5872,16 @@ impl RustSingleMethod {
                   None
                 });
     }
+    if caption_strategy == &RustMethodCaptionStrategy::ReturnType {
+      let caption = self
+        .arguments
+        .cpp_method
+        .cpp_method
+        .return_type
+        .base
+        .caption(TypeCaptionStrategy::Short)?;
+      return Ok(Some(caption.to_snake_case()));
+    }
     let result = {
       let self_arg_kind = self.self_arg_kind()?;
       let self_arg_kind_caption = if all_self_args.len() == 1 ||
@@ -2369,7 +5900,8 @@ impl RustSingleMethod {
       };
       let other_caption = match *caption_strategy {
         RustMethodCaptionStrategy::SelfOnly => None,
-        RustMethodCaptionStrategy::UnsafeOnly => unreachable!(),
+        RustMethodCaptionStrategy::UnsafeOnly |
+        RustMethodCaptionStrategy::ReturnType => unreachable!(),
         RustMethodCaptionStrategy::SelfAndIndex => Some(index.to_string()),
         RustMethodCaptionStrategy::SelfAndArgNames => {
           if self.arguments.arguments.is_empty() {
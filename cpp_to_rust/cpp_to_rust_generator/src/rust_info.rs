@@ -3,10 +3,11 @@
 use cpp_ffi_data::CppAndFfiMethod;
 use cpp_type::CppType;
 use cpp_data::CppData;
-use rust_type::{RustName, CompleteType, RustType};
+use rust_type::{RustName, CompleteType, RustType, RustToCTypeConversion};
 use cpp_method::CppMethodDoc;
 use cpp_data::CppTypeDoc;
 use std::path::PathBuf;
+use std::collections::HashMap;
 
 /// One variant of a Rust enum
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -48,6 +49,34 @@ pub struct RustQtSlotWrapper {
   pub callback_name: String,
 }
 
+/// Information about a Qt event filter wrapper on Rust side
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct RustQtEventFilterWrapper {
+  /// Name of the public Rust struct of this wrapper
+  pub public_type_name: String,
+  /// Name of the extern callback function of this wrapper
+  pub callback_name: String,
+  /// Rust name of `QObject` wrapper
+  pub object_type: RustName,
+  /// Rust name of `QEvent` wrapper
+  pub event_type: RustName,
+}
+
+/// Information about a function object wrapper on Rust side
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct RustFunctorWrapper {
+  /// Argument types of the function object
+  pub arguments: Vec<CompleteType>,
+  /// Return type of the function object
+  pub return_type: CompleteType,
+  /// Name of the public Rust struct of this wrapper
+  pub public_type_name: String,
+  /// Name of the extern callback function of this wrapper
+  pub callback_name: String,
+}
+
 /// Information about a Rust type wrapper
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[derive(Serialize, Deserialize)]
@@ -60,6 +89,9 @@ pub enum RustTypeWrapperKind {
     /// for this type, i.e. if `QFlags<T>` with this C++ type
     /// is used in API.
     is_flaggable: bool,
+    /// True if the C++ enum is a scoped enum (`enum class`)
+    #[serde(default)]
+    is_scoped: bool,
   },
   /// Struct wrapper
   Struct {
@@ -75,6 +107,10 @@ pub enum RustTypeWrapperKind {
     is_deletable: bool,
     /// Additional information for a Qt slot wrapper struct
     slot_wrapper: Option<RustQtSlotWrapper>,
+    /// Additional information for a Qt event filter wrapper struct
+    event_filter_wrapper: Option<RustQtEventFilterWrapper>,
+    /// Additional information for a function object wrapper struct
+    functor_wrapper: Option<RustFunctorWrapper>,
   },
 }
 
@@ -94,9 +130,95 @@ pub struct RustProcessedTypeInfo {
   pub rust_name: RustName,
   /// Indicates whether this type is public
   pub is_public: bool,
+  /// If the C++ type is mapped to an existing Rust type by
+  /// `Config::add_type_mapping`, contains the conversion used between
+  /// the Rust API type and the FFI type. Such types are not generated.
+  #[serde(default)]
+  pub mapped_conversion: Option<RustToCTypeConversion>,
+}
+
+
+/// FFI functions used by the generated benchmark comparing allocation
+/// places of a class, as in `Config::add_allocation_benchmark`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustAllocationBenchmark {
+  /// Full name of the C++ class
+  pub cpp_name: String,
+  /// Rust type of the class
+  pub rust_type: RustName,
+  /// FFI function constructing an object in the provided memory
+  pub stack_constructor: String,
+  /// FFI function destroying an object without freeing its memory
+  pub stack_destructor: String,
+  /// FFI function allocating an object with `new`
+  pub heap_constructor: String,
+  /// FFI function deleting an object allocated with `new`
+  pub heap_destructor: String,
 }
 
+/// Index of `RustProcessedTypeInfo` values by C++ and Rust names, used to
+/// resolve types of dependencies without scanning all of them.
+/// Instantiations of a template class share its C++ name, so
+/// multiple types may be stored under one name.
+#[derive(Debug, Clone, Default)]
+pub struct RustTypeIndex<'a> {
+  /// All indexed types in their original order
+  types: Vec<&'a RustProcessedTypeInfo>,
+  /// Indexed types grouped by `cpp_name`
+  by_cpp_name: HashMap<String, Vec<&'a RustProcessedTypeInfo>>,
+  /// Indexed types by `rust_name`
+  by_rust_name: HashMap<RustName, &'a RustProcessedTypeInfo>,
+}
+
+impl<'a> RustTypeIndex<'a> {
+  /// Builds the index. If several types match a lookup,
+  /// the one that comes first in `types` is returned.
+  pub fn new<I>(types: I) -> RustTypeIndex<'a>
+    where I: IntoIterator<Item = &'a RustProcessedTypeInfo>
+  {
+    let mut index = RustTypeIndex::default();
+    for type1 in types {
+      index.types.push(type1);
+      index
+        .by_cpp_name
+        .entry(type1.cpp_name.clone())
+        .or_insert_with(Vec::new)
+        .push(type1);
+      index
+        .by_rust_name
+        .entry(type1.rust_name.clone())
+        .or_insert(type1);
+    }
+    index
+  }
+
+  /// Returns the type corresponding to C++ type `cpp_name` with
+  /// template arguments `cpp_template_arguments`.
+  pub fn find_type(&self,
+                   cpp_name: &str,
+                   cpp_template_arguments: &Option<Vec<CppType>>)
+                   -> Option<&'a RustProcessedTypeInfo> {
+    self
+      .by_cpp_name
+      .get(cpp_name)
+      .and_then(|types| {
+                  types
+                    .iter()
+                    .find(|x| &x.cpp_template_arguments == cpp_template_arguments)
+                })
+      .cloned()
+  }
 
+  /// Returns the type with Rust name `rust_name`.
+  pub fn find_by_rust_name(&self, rust_name: &RustName) -> Option<&'a RustProcessedTypeInfo> {
+    self.by_rust_name.get(rust_name).cloned()
+  }
+
+  /// Returns all indexed types in their original order.
+  pub fn types(&self) -> &[&'a RustProcessedTypeInfo] {
+    &self.types
+  }
+}
 
 /// Exported information about generated crate
 /// for future use of it as a dependency. This information
@@ -116,6 +238,79 @@ pub struct RustExportInfo {
   pub rust_types: Vec<RustProcessedTypeInfo>,
 }
 
+/// Current value of `RustExportInfoJson::format_version`.
+pub const RUST_EXPORT_INFO_FORMAT_VERSION: u32 = 1;
+
+/// Stable representation of `RustExportInfo` that is saved
+/// to `rust_export_info.json` in the output crate directory
+/// for use by external tools (documentation sites, autocomplete
+/// indexes, etc.).
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct RustExportInfoJson {
+  /// Version of this format. It's incremented when a change
+  /// that breaks existing consumers is made.
+  pub format_version: u32,
+  /// Name of the crate
+  pub crate_name: String,
+  /// Version of the crate
+  pub crate_version: String,
+  /// List of types known to the crate
+  pub types: Vec<RustExportTypeJson>,
+}
+
+/// Information about a single type in `RustExportInfoJson`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct RustExportTypeJson {
+  /// Full name of corresponding C++ type (class or enum)
+  pub cpp_name: String,
+  /// Template arguments formatted as C++ code.
+  /// None if C++ type is not a template class.
+  pub cpp_template_arguments: Option<Vec<String>>,
+  /// Full path of the Rust type, e.g. `::crate_name::module::Type`
+  pub rust_name: String,
+  /// `"enum"` or `"struct"`
+  pub kind: String,
+  /// Indicates whether this type is public
+  pub is_public: bool,
+  /// True if the C++ type is mapped to an existing Rust type
+  /// instead of a generated wrapper
+  pub is_mapped: bool,
+}
+
+impl RustExportInfo {
+  /// Converts this value to its stable JSON representation.
+  pub fn to_json_export(&self) -> RustExportInfoJson {
+    RustExportInfoJson {
+      format_version: RUST_EXPORT_INFO_FORMAT_VERSION,
+      crate_name: self.crate_name.clone(),
+      crate_version: self.crate_version.clone(),
+      types: self
+        .rust_types
+        .iter()
+        .map(|t| {
+          RustExportTypeJson {
+            cpp_name: t.cpp_name.clone(),
+            cpp_template_arguments: t
+              .cpp_template_arguments
+              .as_ref()
+              .map(|args| args.iter().map(|arg| arg.to_cpp_pseudo_code()).collect()),
+            rust_name: t.rust_name.full_name(None),
+            kind: match t.kind {
+                RustTypeWrapperKind::Enum { .. } => "enum",
+                RustTypeWrapperKind::Struct { .. } => "struct",
+              }
+              .to_string(),
+            is_public: t.is_public,
+            is_mapped: t.mapped_conversion.is_some(),
+          }
+        })
+        .collect(),
+    }
+  }
+}
+
 /// Information for generating Rust documentation for a method
 /// or an item of information for an overloaded method.
 /// One value of `RustMethodDocItem` corresponds to a single
@@ -130,6 +325,14 @@ pub struct RustMethodDocItem {
   pub rust_fns: Vec<String>,
   /// C++ code containing declaration of the corresponding C++ method.
   pub cpp_fn: String,
+  /// True if the C++ method receives multiple mutable references
+  /// that may point to the same object.
+  pub has_aliasing_references: bool,
+  /// True if the method takes closures passed to `std::function` arguments.
+  pub has_closure_arguments: bool,
+  /// Rust equivalents of default values of the arguments omitted
+  /// in this variant, formatted as `name = value`.
+  pub omitted_argument_defaults: Vec<String>,
 }
 
 
@@ -196,6 +399,8 @@ pub enum RustMethodArguments {
     /// Fully qualified name of the corresponding C++ method
     /// (used for generating documentation).
     cpp_method_name: String,
+    /// Argument variants (the same as implementations of the parameters trait).
+    variants: Vec<RustMethodArgumentsVariant>,
   },
 }
 
@@ -236,6 +441,82 @@ pub enum TraitImplExtra {
   /// For `CppDeletable` trait implementation,
   /// `deleter_name` contains name of the FFI function used as deleter.
   CppDeletable { deleter_name: String },
+  /// For `Extend` trait implementation based on a container's `append` method.
+  Extend {
+    /// Name of the FFI function appending one item.
+    append_function: String,
+    /// Names of the FFI functions returning current size of the container
+    /// and reserving space, if the container has both of them.
+    size_and_reserve_functions: Option<(String, String)>,
+  },
+  /// For `FromIterator` trait implementation. The container
+  /// is created using its default constructor and filled using
+  /// its `Extend` implementation.
+  FromIterator {
+    /// Name of the FFI function of the default constructor (stack allocated).
+    constructor_function: String,
+  },
+  /// For `From` implementation converting a reference to a type added with
+  /// `Config::add_metatype` to `Variant`. A `try_from_variant` method
+  /// is generated for the type along with the implementation.
+  VariantConversion {
+    /// The type added with `Config::add_metatype`.
+    value_type: RustType,
+    /// Return type of the type's `from_variant` method.
+    value_return_type: RustType,
+    /// Type of the `variant` argument of the type's `from_variant` method.
+    variant_arg_type: RustType,
+  },
+  /// For `From` implementation converting a reference to a derived class
+  /// to a value of its base class, added with `Config::add_slicing_conversion`.
+  /// The implementation has a lifetime parameter named `'a`.
+  SlicingConversion,
+  /// For an arithmetic operator trait implementation for a primitive type
+  /// generated from a free C++ operator with a primitive left operand
+  /// (e.g. `impl<'a> Mul<&'a QVector3D> for f64`).
+  /// The implementation has a lifetime parameter named `'a`.
+  PrimitiveLhsOperator,
+  /// For `Debug` implementation of a class with only public fields.
+  Debug {
+    /// Name of the struct displayed in the output.
+    type_name: String,
+    /// Fields displayed in the output.
+    fields: Vec<RustDebugField>,
+  },
+  /// For `Display` implementation of a class with a stream output operator.
+  Display {
+    /// Name of the FFI function writing the text to a buffer.
+    display_function: String,
+  },
+  /// For `CppVirtualClone` implementation of a class
+  /// added with `Config::add_virtual_clone`.
+  VirtualClone {
+    /// Name of the FFI function calling the clone method.
+    clone_function: String,
+    /// True if the clone method returns a pointer to a base class,
+    /// so the result must be converted using `static_cast`.
+    is_base_return_type: bool,
+  },
+  /// For `TryFrom` implementation converting a reference to `QObject`
+  /// to a reference to a derived class using `qobject_cast`.
+  /// `target_type` and the argument of `trait_type` contain types
+  /// without indirection. The implementation has a lifetime parameter named `'a`.
+  QObjectTryFrom {
+    /// Type of `qt_core::object::Cast` trait used for the conversion.
+    cast_trait_type: RustType,
+    /// True if the conversion is performed on const references.
+    is_const: bool,
+  },
+}
+
+/// Field of a class displayed by a generated `Debug` implementation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustDebugField {
+  /// Name of the field
+  pub name: String,
+  /// Name of the getter method used to read the field, or `None`
+  /// if the field's type doesn't implement `Debug`.
+  pub getter_name: Option<String>,
 }
 
 /// Information about an associated type value
@@ -310,6 +591,18 @@ pub enum RustTypeDeclarationKind {
     /// List of Qt receiver types for signals and slots of
     /// this C++ type.
     qt_receivers: Vec<RustQtReceiverDeclaration>,
+    /// Associated constants for values of enums nested in this C++ class.
+    associated_consts: Vec<RustAssociatedConst>,
+    /// Associated constants for values of anonymous enums nested in this C++ class.
+    anonymous_enum_consts: Vec<RustAnonymousEnumConst>,
+    /// Constants added with `Config::add_computed_type_constant`.
+    computed_constants: Vec<RustComputedConstant>,
+    /// Enum providing access to the value of a `std::variant` instantiation.
+    std_variant_enum: Option<RustStdVariantEnum>,
+    /// Accessors of a `std::map` or `std::unordered_map` instantiation.
+    std_map_wrapper: Option<RustStdMapWrapper>,
+    /// Validity checks of a class defining both `operator bool` and `operator!`.
+    validity_check: Option<RustValidityCheck>,
   },
   /// Information about a Rust trait created for overloading emulation.
   MethodParametersTrait {
@@ -334,6 +627,104 @@ pub enum RustTypeDeclarationKind {
   },
 }
 
+/// Rust enum borrowing the active alternative of a `std::variant`
+/// instantiation. It's returned by the `get` method of the variant's wrapper.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustStdVariantEnum {
+  /// Name of the enum
+  pub name: String,
+  /// Variants of the enum in the order of the C++ alternatives
+  pub variants: Vec<RustStdVariantEnumItem>,
+}
+
+/// Variant of a `RustStdVariantEnum`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustStdVariantEnumItem {
+  /// Name of the variant
+  pub name: String,
+  /// Type of the reference to the alternative's value
+  /// with `'a` lifetime
+  pub value_type: RustType,
+  /// Name of the FFI function returning a pointer to the alternative's value
+  /// or null if another alternative is active
+  pub get_if_function: String,
+}
+
+/// Key-based access, iteration and conversions generated for
+/// a `std::map` or `std::unordered_map` instantiation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustStdMapWrapper {
+  /// Name of the iterator type returned by the `iter` method
+  pub iterator_name: String,
+  /// Type of keys
+  pub key_type: RustType,
+  /// Type of values
+  pub value_type: RustType,
+  /// Path to the Rust collection the map can be converted to
+  /// (`BTreeMap` for `std::map` and `HashMap` for `std::unordered_map`)
+  pub collection_path: String,
+  /// Name of the FFI function returning a pointer to the value for a key
+  pub get_function: String,
+  /// Name of the FFI function setting the value for a key
+  pub insert_function: String,
+  /// Name of the FFI function returning the entry after a key
+  pub next_entry_function: String,
+  /// Name of the FFI function constructing an empty map in place.
+  /// If set, conversions from the Rust collection are generated.
+  /// Heap-allocated maps don't have them because `CppBox<Map>`
+  /// is not a local type of the generated crate.
+  pub stack_constructor_function: Option<String>,
+}
+
+/// Validity checks generated for a class that defines
+/// both `operator bool` and `operator!`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustValidityCheck {
+  /// Name of the FFI function calling `operator bool`
+  pub bool_function: String,
+  /// Name of the FFI function calling `operator!`
+  pub not_function: String,
+  /// True if the type can be held by value, so
+  /// `into_option(self) -> Option<Self>` is generated
+  pub has_into_option: bool,
+}
+
+/// Associated constant of a class wrapper that refers to
+/// a value of an enum nested in the C++ class.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustAssociatedConst {
+  /// Name of the constant
+  pub name: String,
+  /// Full name of the enum type
+  pub enum_name: RustName,
+  /// Name of the enum variant
+  pub variant_name: String,
+}
+
+/// Associated constant of a class wrapper that contains
+/// a value of an anonymous enum nested in the C++ class.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustAnonymousEnumConst {
+  /// Name of the constant (same as the name of the C++ enumerator)
+  pub name: String,
+  /// Value of the enumerator
+  pub value: i64,
+}
+
+/// Constant of a class wrapper computed by an FFI function
+/// on first access.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RustComputedConstant {
+  /// Name of the function returning the constant
+  pub name: String,
+  /// Name of the FFI function computing the value
+  pub ffi_function: String,
+  /// Type of the value
+  pub value_type: RustType,
+  /// C++ expression computing the value
+  pub cpp_initializer: String,
+}
+
 /// Information about a Rust type declaration.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RustTypeDeclaration {
@@ -384,6 +775,10 @@ pub struct DependencyInfo {
 pub enum RustMethodCaptionStrategy {
   /// Only type of `self` is used.
   SelfOnly,
+  /// Only the return type is used. This strategy is only used
+  /// for const and non-const overloads returning different types,
+  /// and `SelfOnly` is not used for them.
+  ReturnType,
   /// Unsafe methods have `unsafe` suffix, and safe methods have no suffix.
   UnsafeOnly,
   /// Type of `self` and types of other arguments are used.
@@ -400,6 +795,7 @@ impl RustMethodCaptionStrategy {
   pub fn all() -> &'static [RustMethodCaptionStrategy] {
     use self::RustMethodCaptionStrategy::*;
     const LIST: &'static [RustMethodCaptionStrategy] = &[SelfOnly,
+                                                         ReturnType,
                                                          UnsafeOnly,
                                                          SelfAndArgTypes,
                                                          SelfAndArgNames,
@@ -458,4 +854,7 @@ pub struct RustFFIFunction {
   pub name: String,
   /// Arguments of the function.
   pub arguments: Vec<RustFFIArgument>,
+  /// True if this is an `extern "C"` function of the C++ library
+  /// bound directly, without a function in the C++ wrapper library.
+  pub is_direct_extern_c: bool,
 }
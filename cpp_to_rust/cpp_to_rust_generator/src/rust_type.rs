@@ -34,8 +34,70 @@ pub enum RustToCTypeConversion {
   ValueToPtr,
   /// `CppBox<T>` to `*const T` (or similar mutable type)
   CppBoxToPtr,
+  /// `CppBox<T>` to `*mut T` transferring ownership of the object
+  /// to the C++ side (only for arguments)
+  CppBoxIntoPtr,
+  /// `Result<CppBox<T>, cpp_utils::ConstructionError>` to `*mut T`,
+  /// where a null pointer is converted to `Err`
+  /// (only for return values of fallible constructors)
+  ResultCppBoxToPtr,
   /// `qt_core::flags::Flags<T>` to `libc::c_uint`
   QFlagsToUInt,
+  /// `char` to `libc::c_ushort` containing a UTF-16 code unit
+  /// (only for `QChar` arguments). Characters outside of
+  /// the Basic Multilingual Plane cause a panic, and lone surrogates
+  /// passed to callbacks are replaced with U+FFFD.
+  CharToUShort,
+  /// `cpp_utils::OwnedVoidPtr` to `*mut libc::c_void`
+  /// (only for return values)
+  OwnedVoidPtrToPtr {
+    /// Rust path to the function that releases the pointer
+    free_function: String,
+  },
+  /// `String` to a `char*` buffer filled by the function
+  /// (only for return values)
+  StringToBuffer {
+    /// Index of the FFI argument receiving the buffer
+    buffer_ffi_index: usize,
+    /// Index of the FFI argument receiving the buffer's size
+    length_ffi_index: usize,
+  },
+  /// `Result<T, cpp_utils::CppError>` to `T`, where the error is reported
+  /// through output arguments (only for return values of errno methods)
+  ErrnoResult {
+    /// Index of the FFI argument receiving the error flag
+    error_flag_ffi_index: usize,
+    /// Index of the FFI argument receiving the error code
+    error_code_ffi_index: usize,
+  },
+  /// `[T; N]` to a pointer to an array filled by the callee
+  /// (only for return values of methods added with `Config::add_array_out_param`)
+  ArrayToOutPtr {
+    /// Index of the FFI argument receiving the pointer to the array
+    array_ffi_index: usize,
+  },
+  /// `&str` to `cpp_utils::StrRef` (only for arguments)
+  StrToStrRef,
+  /// `&[T]` or `&mut [T]` to `cpp_utils::SliceRef` (only for arguments)
+  SliceToSliceRef,
+  /// `Vec<T>` to a `cpp_utils::VecOut` output argument receiving
+  /// copies of the elements (only for return values)
+  VecToVecOut,
+  /// A closure to `cpp_utils::Closure` that takes ownership of it
+  /// (only for arguments)
+  ClosureToCppClosure,
+  /// The FFI return value (if any) is discarded, and the `self` argument
+  /// taken by value is returned instead (only for return values
+  /// of consuming methods)
+  ConsumedSelf,
+  /// `&[T]` to `*const T` and the number of elements
+  /// (only for arguments of batch operations and variadic slice functions)
+  SliceToPtr {
+    /// Index of the FFI argument receiving the number of elements
+    length_ffi_index: usize,
+    /// Maximal allowed number of elements, if limited
+    max_length: Option<usize>,
+  },
 }
 
 /// Information about a completely processed type
@@ -107,6 +169,26 @@ pub enum RustType {
     /// Argument types of the function.
     arguments: Vec<RustType>,
   },
+  /// A fixed-size array type, like `[i32; 4]`, or a slice type, like `[i32]`,
+  /// with some indirection
+  Array {
+    /// Type of the array's elements
+    element: Box<RustType>,
+    /// Number of elements, or `None` for a slice
+    size: Option<usize>,
+    /// Constness of the indirection (see `RustType::Common`).
+    is_const: bool,
+    /// Indirection of this type.
+    indirection: RustTypeIndirection,
+  },
+  /// A closure accepted as `impl FnMut(...) -> R + Send + 'static`
+  /// (only for arguments).
+  Closure {
+    /// Return type of the closure.
+    return_type: Box<RustType>,
+    /// Argument types of the closure.
+    arguments: Vec<RustType>,
+  },
 }
 
 
@@ -240,6 +322,17 @@ impl RustType {
       name
     }
          RustType::FunctionPointer { .. } => "fn".to_string(),
+         RustType::Closure { .. } => "closure".to_string(),
+         RustType::Array {
+           ref element,
+           ref size,
+           ..
+         } => {
+           match *size {
+             Some(size) => format!("{}_array_{}", element.caption(context)?, size),
+             None => format!("{}_slice", element.caption(context)?),
+           }
+         }
        })
   }
 
@@ -247,7 +340,8 @@ impl RustType {
   #[allow(dead_code)]
   pub fn is_ref(&self) -> bool {
     match *self {
-      RustType::Common { ref indirection, .. } => {
+      RustType::Common { ref indirection, .. } |
+      RustType::Array { ref indirection, .. } => {
         match *indirection {
           RustTypeIndirection::Ref { .. } |
           RustTypeIndirection::PtrRef { .. } => true,
@@ -255,14 +349,16 @@ impl RustType {
         }
       }
       RustType::EmptyTuple |
-      RustType::FunctionPointer { .. } => false,
+      RustType::FunctionPointer { .. } |
+      RustType::Closure { .. } => false,
     }
   }
 
   /// Returns a copy of this type with `new_lifetime` added, if possible.
   pub fn with_lifetime(&self, new_lifetime: String) -> RustType {
     let mut r = self.clone();
-    if let RustType::Common { ref mut indirection, .. } = r {
+    if let RustType::Common { ref mut indirection, .. } |
+           RustType::Array { ref mut indirection, .. } = r {
       match *indirection {
         RustTypeIndirection::Ref { ref mut lifetime } |
         RustTypeIndirection::PtrRef { ref mut lifetime } => *lifetime = Some(new_lifetime),
@@ -276,7 +372,8 @@ impl RustType {
   /// or `None` if there isn't any lifetime in this type.
   pub fn lifetime(&self) -> Option<&String> {
     match *self {
-      RustType::Common { ref indirection, .. } => {
+      RustType::Common { ref indirection, .. } |
+      RustType::Array { ref indirection, .. } => {
         match *indirection {
           RustTypeIndirection::Ref { ref lifetime } |
           RustTypeIndirection::PtrRef { ref lifetime } => lifetime.as_ref(),
@@ -299,6 +396,8 @@ impl RustType {
         RustTypeIndirection::PtrRef { .. } => Ok(*is_const2),
         _ => Ok(*is_const),
       }
+    } else if let RustType::Array { ref is_const, .. } = *self {
+      Ok(*is_const)
     } else {
       Err("not a Common type".into())
     }
@@ -307,7 +406,8 @@ impl RustType {
   /// Returns true if this type (or first indirection of the type) is const.
   pub fn is_const(&self) -> Result<bool> {
     match *self {
-      RustType::Common { ref is_const, .. } => Ok(*is_const),
+      RustType::Common { ref is_const, .. } |
+      RustType::Array { ref is_const, .. } => Ok(*is_const),
       _ => Err("not a Common type".into()),
     }
   }
@@ -315,7 +415,8 @@ impl RustType {
   /// Sets value of `is_const` for a common type.
   pub fn set_const(&mut self, value: bool) -> Result<()> {
     match *self {
-      RustType::Common { ref mut is_const, .. } => {
+      RustType::Common { ref mut is_const, .. } |
+      RustType::Array { ref mut is_const, .. } => {
         *is_const = value;
         Ok(())
       }
@@ -354,7 +455,19 @@ impl RustType {
         }
         false
       }
-      RustType::EmptyTuple => false,
+      RustType::Array {
+        ref indirection,
+        ref element,
+        ..
+      } => {
+        match *indirection {
+          RustTypeIndirection::None |
+          RustTypeIndirection::Ref { .. } => element.is_unsafe_argument(),
+          _ => true,
+        }
+      }
+      RustType::EmptyTuple |
+      RustType::Closure { .. } => false,
       RustType::FunctionPointer { .. } => true,
     }
   }
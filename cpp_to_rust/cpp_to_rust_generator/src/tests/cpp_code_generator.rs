@@ -0,0 +1,103 @@
+use cpp_code_generator::CppCodeGenerator;
+use cpp_data::*;
+use cpp_ffi_data::CppFfiHeaderData;
+use cpp_type::*;
+use config::FfiTuStrategy;
+use common::file_utils::{file_to_string, read_dir, PathBufWithAdded};
+use tests::TempTestDir;
+use tests::cpp_ffi_generator::run_ffi_generator;
+use tests::cpp_method::empty_regular_method;
+
+/// Returns FFI data of two headers containing one function each.
+fn two_headers() -> Vec<CppFfiHeaderData> {
+  let mut method1 = empty_regular_method();
+  method1.name = "func1".to_string();
+  method1.include_file = "header1.h".to_string();
+  method1.return_type = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let mut method2 = method1.clone();
+  method2.name = "func2".to_string();
+  method2.include_file = "header2.h".to_string();
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: Vec::new(),
+        methods: vec![method1, method2],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 2);
+  headers
+}
+
+#[test]
+fn bundled_translation_unit() {
+  let headers = two_headers();
+  let dir = TempTestDir::new("test_bundled_translation_unit");
+  let lib_path = dir.path().to_path_buf();
+  let generator = CppCodeGenerator::new("myfakelib_c".to_string(),
+                                        lib_path.clone(),
+                                        FfiTuStrategy::Bundled(1));
+  generator.generate_template_files(&[]).unwrap();
+  generator.generate_files(&headers).unwrap();
+
+  let sources: Vec<_> = read_dir(lib_path.with_added("src"))
+    .unwrap()
+    .map(|x| x.unwrap().path())
+    .collect();
+  assert_eq!(sources.len(), 1);
+  let code = file_to_string(&sources[0]).unwrap();
+  let include1 = code.find("#include \"myfakelib_c_header1.h\"").unwrap();
+  let include2 = code.find("#include \"myfakelib_c_header2.h\"").unwrap();
+  assert!(include1 < include2);
+  let func1 = code.find("func1").unwrap();
+  let func2 = code.find("func2").unwrap();
+  assert!(include2 < func1);
+  assert!(include2 < func2);
+  assert!(lib_path
+            .with_added("include")
+            .with_added("myfakelib_c_header2.h")
+            .exists());
+}
+
+#[test]
+fn code_cache() {
+  let headers = two_headers();
+  let dir = TempTestDir::new("test_code_cache");
+  let lib_path = dir.path().with_added("lib");
+  let cache_path = dir.path().with_added("cpp_code_cache.bin");
+  let generate = |force_regeneration: bool| {
+    let mut generator = CppCodeGenerator::new("myfakelib_c".to_string(),
+                                              lib_path.clone(),
+                                              FfiTuStrategy::PerHeader);
+    generator.set_code_cache(cache_path.clone(), force_regeneration);
+    generator.generate_template_files(&[]).unwrap();
+    generator.generate_files(&headers).unwrap()
+  };
+  assert_eq!(generate(false),
+             vec!["header1".to_string(), "header2".to_string()]);
+  let header2_path = lib_path
+    .with_added("include")
+    .with_added("myfakelib_c_header2.h");
+  let header2_code = file_to_string(&header2_path).unwrap();
+  let source2_path = lib_path
+    .with_added("src")
+    .with_added("myfakelib_c_header2.cpp");
+  let source2_code = file_to_string(&source2_path).unwrap();
+
+  assert!(generate(false).is_empty());
+  assert_eq!(file_to_string(&header2_path).unwrap(), header2_code);
+  assert_eq!(file_to_string(&source2_path).unwrap(), source2_code);
+
+  assert_eq!(generate(true).len(), 2);
+}
@@ -0,0 +1,131 @@
+use cpp_data::*;
+use cpp_method::*;
+use cpp_type::*;
+use common::file_utils::{PathBufWithAdded, create_file, load_bincode, load_json, save_bincode,
+                         save_json};
+use tests::TempTestDir;
+use tests::cpp_ffi_generator::class_type_data;
+use tests::cpp_method::{empty_membership, empty_regular_method};
+
+#[test]
+fn check_dependency_types() {
+  let mut method = empty_regular_method();
+  method.name = "func1".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.include_file = "myfakelib.h".to_string();
+  method.arguments = vec![CppMethodArgument {
+                            name: "arg1".to_string(),
+                            argument_type: CppType {
+                              base: CppTypeBase::Class(CppTypeClassBase {
+                                                         name: "DepClass".to_string(),
+                                                         template_arguments: None,
+                                                       }),
+                              indirection: CppTypeIndirection::Ptr,
+                              is_const: false,
+                              is_const2: false,
+                              is_volatile: false,
+                            },
+                            has_default_value: false,
+                            default_value: None,
+                          }];
+  let dependency = CppData {
+    parser: ParserCppData {
+      types: vec![class_type_data("DepClass", Vec::new())],
+      methods: Vec::new(),
+      inline_namespaces: Vec::new(),
+    },
+    processed: Default::default(),
+  };
+  let mut cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: vec![&dependency],
+  };
+  assert!(cpp_data.check_dependency_types().is_ok());
+
+  cpp_data.dependencies.clear();
+  let err = cpp_data.check_dependency_types().unwrap_err();
+  assert!(err.to_string().contains("DepClass"));
+}
+
+#[test]
+fn scoped_enum_serialization() {
+  let dir = TempTestDir::new("test_scoped_enum_serialization");
+  let enum_type = CppTypeData {
+    name: "Enum1".to_string(),
+    include_file: "myfakelib.h".to_string(),
+    origin_location: CppOriginLocation {
+      include_file_path: "/myfakelib.h".to_string(),
+      line: 1,
+      column: 1,
+    },
+    kind: CppTypeKind::Enum {
+      values: vec![CppEnumValue {
+                     name: "Value1".to_string(),
+                     value: 1,
+                     doc: None,
+                   }],
+      is_scoped: true,
+      is_anonymous: false,
+    },
+    doc: None,
+  };
+  let bincode_path = dir.path().with_added("types.bin");
+  save_bincode(&bincode_path, &vec![enum_type.clone()]).unwrap();
+  let loaded: Vec<CppTypeData> = load_bincode(&bincode_path).unwrap();
+  assert_eq!(loaded, vec![enum_type.clone()]);
+
+  let json_path = dir.path().with_added("types.json");
+  save_json(&json_path, &enum_type.kind).unwrap();
+  let loaded: CppTypeKind = load_json(&json_path).unwrap();
+  assert_eq!(loaded, enum_type.kind);
+
+  // data saved before `is_scoped` was introduced
+  {
+    let mut file = create_file(&json_path).unwrap();
+    file
+      .write(r#"{"Enum":{"values":[{"name":"Value1","value":1,"doc":null}]}}"#)
+      .unwrap();
+  }
+  let loaded: CppTypeKind = load_json(&json_path).unwrap();
+  assert_eq!(loaded,
+             CppTypeKind::Enum {
+               values: vec![CppEnumValue {
+                              name: "Value1".to_string(),
+                              value: 1,
+                              doc: None,
+                            }],
+               is_scoped: false,
+               is_anonymous: false,
+             });
+}
+
+#[test]
+fn outdated_bincode_cache() {
+  // `CppTypeKind::Enum` as it was saved before `is_scoped` was introduced
+  #[derive(Serialize)]
+  enum OldCppTypeKind {
+    Enum { values: Vec<CppEnumValue> },
+  }
+
+  let dir = TempTestDir::new("test_outdated_bincode_cache");
+  let path = dir.path().with_added("types.bin");
+  save_bincode(&path,
+               &OldCppTypeKind::Enum {
+                  values: vec![CppEnumValue {
+                                 name: "Value1".to_string(),
+                                 value: 1,
+                                 doc: None,
+                               }],
+                })
+    .unwrap();
+  // unlike JSON, bincode doesn't apply `#[serde(default)]` to missing fields,
+  // so the cache format version must be increased instead
+  assert!(load_bincode::<_, CppTypeKind>(&path).is_err());
+}
@@ -2,7 +2,7 @@ use cpp_ffi_data::*;
 use cpp_type::*;
 use caption_strategy::*;
 use tests::cpp_method::{empty_regular_method, empty_membership};
-use cpp_method::{CppMethodKind, ReturnValueAllocationPlace};
+use cpp_method::{CppMethodKind, CppMethodArgument, ReturnValueAllocationPlace};
 use cpp_operator::CppOperator;
 
 #[test]
@@ -26,12 +26,14 @@ fn argument_int() {
         indirection: CppTypeIndirection::None,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       },
       ffi_type: CppType {
         indirection: CppTypeIndirection::None,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       },
       conversion: CppIndirectionChange::NoChange,
@@ -68,12 +70,14 @@ fn argument_int_ptr() {
         indirection: CppTypeIndirection::Ptr,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       },
       ffi_type: CppType {
         indirection: CppTypeIndirection::Ptr,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       },
       conversion: CppIndirectionChange::NoChange,
@@ -106,25 +110,30 @@ fn argument_func() {
   let type1 = CppType {
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     indirection: CppTypeIndirection::None,
     base: CppTypeBase::FunctionPointer(CppFunctionPointerType {
       allows_variadic_arguments: false,
+      class_type: None,
       return_type: Box::new(CppType {
         indirection: CppTypeIndirection::None,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       }),
       arguments: vec![CppType {
                         indirection: CppTypeIndirection::None,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                       },
                       CppType {
                         indirection: CppTypeIndirection::Ptr,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                       }],
     }),
@@ -170,12 +179,14 @@ fn signature_two_numbers() {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                         },
                         ffi_type: CppType {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                         },
                         conversion: CppIndirectionChange::NoChange,
@@ -189,12 +200,14 @@ fn signature_two_numbers() {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         ffi_type: CppType {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         conversion: CppIndirectionChange::NoChange,
@@ -250,6 +263,7 @@ fn signature_class_method() {
                           indirection: CppTypeIndirection::Ptr,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::Class(CppTypeClassBase {
                                                      name: "Class1".to_string(),
                                                      template_arguments: None,
@@ -259,6 +273,7 @@ fn signature_class_method() {
                           indirection: CppTypeIndirection::Ptr,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::Class(CppTypeClassBase {
                                                      name: "Class1".to_string(),
                                                      template_arguments: None,
@@ -275,12 +290,14 @@ fn signature_class_method() {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         ffi_type: CppType {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         conversion: CppIndirectionChange::NoChange,
@@ -322,6 +339,7 @@ fn signature_class_method_const() {
                           indirection: CppTypeIndirection::Ptr,
                           is_const: true,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::Class(CppTypeClassBase {
                                                      name: "Class1".to_string(),
                                                      template_arguments: None,
@@ -331,6 +349,7 @@ fn signature_class_method_const() {
                           indirection: CppTypeIndirection::Ptr,
                           is_const: true,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::Class(CppTypeClassBase {
                                                      name: "Class1".to_string(),
                                                      template_arguments: None,
@@ -347,12 +366,14 @@ fn signature_class_method_const() {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         ffi_type: CppType {
                           indirection: CppTypeIndirection::None,
                           is_const: false,
                           is_const2: false,
+                          is_volatile: false,
                           base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                         },
                         conversion: CppIndirectionChange::NoChange,
@@ -571,6 +592,7 @@ fn c_base_name_conversion_operator() {
     Some(CppOperator::Conversion(CppType {
                                    is_const: true,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::Class(CppTypeClassBase {
                                                               name: "QPoint".to_string(),
                                                               template_arguments: None,
@@ -588,3 +610,41 @@ fn c_base_name_conversion_operator() {
   assert_eq!(c_base_name(&method, &ReturnValueAllocationPlace::Heap, &include_file).unwrap(),
              "MyClass_convert_to_const_QPoint_ref_as_ptr");
 }
+
+#[test]
+fn fallible_constructor_matches() {
+  let mut method = empty_regular_method();
+  method.name = "QFile".to_string();
+  method.class_membership = Some({
+                                   let mut info = empty_membership("QFile");
+                                   info.kind = CppMethodKind::Constructor;
+                                   info
+                                 });
+  method.arguments.push(CppMethodArgument {
+                          name: "name".to_string(),
+                          argument_type: CppType {
+                            is_const: true,
+                            is_const2: false,
+                            is_volatile: false,
+                            base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char),
+                            indirection: CppTypeIndirection::Ptr,
+                          },
+                          has_default_value: false,
+                          default_value: None,
+                        });
+  let constructor = CppFallibleConstructor {
+    constructor_name: "QFile::QFile".to_string(),
+    argument_types: vec!["const char *".to_string()],
+    validity_check: "object->isOpen()".to_string(),
+  };
+  assert!(constructor.matches(&method));
+
+  let mut other_overload = method.clone();
+  other_overload.arguments.clear();
+  assert!(!constructor.matches(&other_overload));
+
+  let mut other_class = method.clone();
+  other_class.class_membership.as_mut().unwrap().class_type.name = "QDir".to_string();
+  other_class.name = "QDir".to_string();
+  assert!(!constructor.matches(&other_class));
+}
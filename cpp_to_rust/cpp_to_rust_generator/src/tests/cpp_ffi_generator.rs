@@ -0,0 +1,1128 @@
+use cpp_ffi_generator::{self, CppFfiGeneratorOptions};
+use cpp_ffi_data::{CppFfiFailureDump, CppFfiHeaderData, CppFfiMethodKind, CppIndirectionChange,
+                   CppTemplateMethodInstantiation};
+use cpp_data::*;
+use cpp_method::*;
+use cpp_type::*;
+use cpp_operator::CppOperator;
+use config::{Config, CrateProperties, FfiTuStrategy, SafetyPolicy, VolatilePolicy};
+use cpp_code_generator::CppCodeGenerator;
+use rust_generator::RustGeneratorInputData;
+use regex::Regex;
+use common::errors::Result;
+use common::file_utils::{file_to_string, load_json, read_dir, PathBufWithAdded};
+use tests::TempTestDir;
+use std::collections::HashMap;
+use tests::cpp_method::{empty_membership, empty_regular_method};
+
+/// Runs the FFI generator for the `myfakelib_c` wrapper library.
+pub fn run_ffi_generator(cpp_data: &CppDataWithDeps,
+                         options: CppFfiGeneratorOptions)
+                         -> Result<Vec<CppFfiHeaderData>> {
+  cpp_ffi_generator::run(cpp_data, "myfakelib_c".to_string(), options)
+}
+
+pub fn class_type_data(name: &str, bases: Vec<CppBaseSpecifier>) -> CppTypeData {
+  CppTypeData {
+    name: name.to_string(),
+    include_file: "myfakelib.h".to_string(),
+    origin_location: CppOriginLocation {
+      include_file_path: "/myfakelib.h".to_string(),
+      line: 1,
+      column: 1,
+    },
+    kind: CppTypeKind::Class {
+      bases: bases,
+      fields: Vec::new(),
+      template_arguments: None,
+      using_directives: Vec::new(),
+      is_final: false,
+    },
+    doc: None,
+  }
+}
+
+pub fn class_type(name: &str) -> CppType {
+  CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: name.to_string(),
+                               template_arguments: None,
+                             }),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  }
+}
+
+fn int_type() -> CppType {
+  CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  }
+}
+
+#[test]
+fn failure_dump() {
+  let base_specifier = CppBaseSpecifier {
+    base_type: class_type("Base1"),
+    is_virtual: false,
+    visibility: CppVisibility::Public,
+  };
+  let mut good_method = empty_regular_method();
+  good_method.name = "func1".to_string();
+  good_method.class_membership = Some(empty_membership("Class1"));
+  good_method.include_file = "myfakelib.h".to_string();
+  good_method.return_type = int_type();
+
+  let mut bad_method = good_method.clone();
+  bad_method.name = "func2".to_string();
+  bad_method.allows_variadic_arguments = true;
+  bad_method
+    .arguments
+    .push(CppMethodArgument {
+            name: "format".to_string(),
+            argument_type: int_type(),
+            has_default_value: false,
+            default_value: None,
+          });
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Base1", Vec::new()),
+                    class_type_data("Class1", vec![base_specifier]),
+                    class_type_data("Unrelated1", Vec::new())],
+        methods: vec![good_method, bad_method.clone()],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let dir = TempTestDir::new("test_failure_dump");
+  let dump_dir = dir.path().to_path_buf();
+  let headers = run_ffi_generator(&cpp_data,
+                                  CppFfiGeneratorOptions {
+                                    failure_dump_dir: Some(&dump_dir),
+                                    ..Default::default()
+                                  })
+      .unwrap();
+  assert_eq!(headers.len(), 1);
+  assert!(headers[0]
+            .methods
+            .iter()
+            .all(|m| &m.cpp_method.name != "func2"));
+
+  let files: Vec<_> = read_dir(&dump_dir)
+    .unwrap()
+    .map(|x| x.unwrap().path())
+    .collect();
+  assert_eq!(files.len(), 1);
+  let dump: CppFfiFailureDump = load_json(&files[0]).unwrap();
+  assert_eq!(dump.method, bad_method);
+  assert!(dump.error.contains("Variable arguments"));
+  let mut type_names: Vec<_> = dump.types.iter().map(|t| t.name.as_str()).collect();
+  type_names.sort();
+  assert_eq!(type_names, vec!["Base1", "Class1"]);
+  assert_eq!(dump.method.c_signature(ReturnValueAllocationPlace::NotApplicable)
+               .unwrap_err()
+               .to_string(),
+             dump.error);
+}
+
+#[test]
+fn method_blocklist() {
+  let mut method1 = empty_regular_method();
+  method1.name = "func1".to_string();
+  method1.class_membership = Some(empty_membership("Class1"));
+  method1.include_file = "myfakelib.h".to_string();
+  method1.return_type = int_type();
+  let mut method2 = method1.clone();
+  method2.name = "func2".to_string();
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method1, method2],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let blocklist = vec![Regex::new(r"^Class1::func1\b").unwrap()];
+  let headers = run_ffi_generator(&cpp_data,
+                                  CppFfiGeneratorOptions {
+                                    method_blocklist: &blocklist,
+                                    ..Default::default()
+                                  })
+      .unwrap();
+  assert_eq!(headers.len(), 1);
+  let names: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .map(|m| m.cpp_method.name.as_str())
+    .collect();
+  assert_eq!(names, vec!["func2"]);
+}
+
+#[test]
+fn method_blocklist_invalid_pattern() {
+  let mut config = Config::new("/tmp/1", "/tmp/2", CrateProperties::new("a", "0.0.0"));
+  assert!(config.add_method_blocklist("Class1::func1(").is_err());
+  assert!(config.add_method_blocklist(r"^Class1::func1\(").is_ok());
+  assert_eq!(config.method_blocklist().len(), 1);
+}
+
+#[test]
+fn symbol_version() {
+  let mut method = empty_regular_method();
+  method.name = "func1".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.include_file = "myfakelib.h".to_string();
+  method.return_type = int_type();
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data,
+                                  CppFfiGeneratorOptions {
+                                    symbol_version: Some(&"2.1-beta".to_string()),
+                                    ..Default::default()
+                                  })
+      .unwrap();
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 1);
+  assert_eq!(headers[0].methods[0].c_name,
+             "myfakelib_c_Class1_func1_2_1_beta");
+}
+
+#[test]
+fn volatile_policy() {
+  let mut method1 = empty_regular_method();
+  method1.name = "func1".to_string();
+  method1.class_membership = Some(empty_membership("Class1"));
+  method1.include_file = "myfakelib.h".to_string();
+  method1.arguments.push(CppMethodArgument {
+                           name: "x".to_string(),
+                           argument_type: CppType {
+                             indirection: CppTypeIndirection::Ptr,
+                             is_volatile: true,
+                             ..int_type()
+                           },
+                           has_default_value: false,
+                           default_value: None,
+                         });
+  let mut method2 = method1.clone();
+  method2.name = "func2".to_string();
+  method2.arguments.clear();
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method1, method2],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let run = |policy: VolatilePolicy| {
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        volatile_policy: policy,
+                        ..Default::default()
+                      })
+        .unwrap()
+  };
+  let headers = run(VolatilePolicy::Reject);
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 1);
+  assert_eq!(&headers[0].methods[0].cpp_method.name, "func2");
+
+  let headers = run(VolatilePolicy::Preserve);
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 2);
+  let method = headers[0]
+    .methods
+    .iter()
+    .find(|m| &m.cpp_method.name == "func1")
+    .unwrap();
+  let arg = method
+    .c_signature
+    .arguments
+    .iter()
+    .find(|arg| &arg.name == "x")
+    .unwrap();
+  assert_eq!(arg.to_cpp_code().unwrap(), "volatile int* x");
+}
+
+#[test]
+fn side_by_side_versions() {
+  // two versions of the same library differ in signature of `func1`
+  let mut processed = ProcessedCppData::default();
+  processed
+    .type_allocation_places
+    .insert("Class1".to_string(), CppTypeAllocationPlace::Heap);
+  let cpp_data_version = |arguments: Vec<CppMethodArgument>| {
+    let mut method = empty_regular_method();
+    method.name = "func1".to_string();
+    method.class_membership = Some(empty_membership("Class1"));
+    method.include_file = "myfakelib.h".to_string();
+    method.return_type = int_type();
+    method.arguments = arguments;
+    CppDataWithDeps {
+      current: CppData {
+        parser: ParserCppData {
+          types: vec![class_type_data("Class1", Vec::new())],
+          methods: vec![method],
+          inline_namespaces: Vec::new(),
+        },
+        processed: processed.clone(),
+      },
+      dependencies: Vec::new(),
+    }
+  };
+  let cpp_data1 = cpp_data_version(Vec::new());
+  let cpp_data2 = cpp_data_version(vec![CppMethodArgument {
+                                          name: "arg1".to_string(),
+                                          argument_type: int_type(),
+                                          has_default_value: false,
+                                          default_value: None,
+                                        }]);
+  // Returns names of the C++ wrapper functions, generated C++ code
+  // and names of the Rust FFI imports.
+  let generate = |cpp_data: &CppDataWithDeps, lib_name: &str, version: &str| {
+    let headers = cpp_ffi_generator::run(cpp_data,
+                                         lib_name.to_string(),
+                                         CppFfiGeneratorOptions {
+                                           symbol_version: Some(&version.to_string()),
+                                           ..Default::default()
+                                         })
+        .unwrap();
+    let c_names: Vec<String> = headers
+      .iter()
+      .flat_map(|header| header.methods.iter().map(|m| m.c_name.clone()))
+      .collect();
+
+    let dir = TempTestDir::new(&format!("test_side_by_side_versions_{}", lib_name));
+    let lib_path = dir.path().to_path_buf();
+    let code_generator =
+      CppCodeGenerator::new(lib_name.to_string(), lib_path.clone(), FfiTuStrategy::PerHeader);
+    code_generator.generate_template_files(&[]).unwrap();
+    code_generator.generate_files(&headers).unwrap();
+    let mut cpp_code = String::new();
+    for dir_name in &["include", "src"] {
+      for item in read_dir(lib_path.with_added(dir_name)).unwrap() {
+        cpp_code.push_str(&file_to_string(item.unwrap().path()).unwrap());
+      }
+    }
+
+    let rust_data = RustGeneratorInputData {
+        cpp_data: cpp_data,
+        cpp_ffi_headers: headers,
+        dependency_types: Vec::new(),
+        crate_name: lib_name.trim_right_matches("_c").to_string(),
+        remove_qt_prefix: false,
+        filtered_namespaces: Vec::new(),
+        namespace_mappings: HashMap::new(),
+        owned_void_ptr_returns: HashMap::new(),
+        owned_ptr_returns: Vec::new(),
+        fallible_constructors: Vec::new(),
+        consuming_methods: Vec::new(),
+        string_buffer_outs: HashMap::new(),
+        array_out_params: HashMap::new(),
+        return_lifetimes: HashMap::new(),
+        ownership_transfer_args: HashMap::new(),
+        type_mappings: HashMap::new(),
+        virtual_clones: HashMap::new(),
+        flaggable_scoped_enums: Vec::new(),
+        safety_policy: SafetyPolicy::default(),
+      }
+      .run()
+      .unwrap();
+    let rust_ffi_names: Vec<String> = rust_data
+      .ffi_functions
+      .iter()
+      .flat_map(|&(_, ref functions)| functions.iter().map(|f| f.name.clone()))
+      .collect();
+    (c_names, cpp_code, rust_ffi_names)
+  };
+  let (names1, cpp_code1, rust_names1) = generate(&cpp_data1, "myfakelib1_c", "1.0");
+  let (names2, cpp_code2, rust_names2) = generate(&cpp_data2, "myfakelib2_c", "2.0");
+  assert_eq!(names1, vec!["myfakelib1_c_Class1_func1_1_0".to_string()]);
+  assert_eq!(names2, vec!["myfakelib2_c_Class1_func1_2_0".to_string()]);
+  assert!(names1.iter().all(|name| !names2.contains(name)));
+  // the C++ wrapper library and the Rust crate of each version
+  // only refer to the symbols of that version
+  assert!(cpp_code1.contains("myfakelib1_c_Class1_func1_1_0("));
+  assert!(!cpp_code1.contains("myfakelib2_c"));
+  assert!(cpp_code2.contains("myfakelib2_c_Class1_func1_2_0("));
+  assert!(!cpp_code2.contains("myfakelib1_c"));
+  assert_eq!(rust_names1, names1);
+  assert_eq!(rust_names2, names2);
+}
+
+#[test]
+fn missing_metatypes_are_skipped() {
+  let variant_conversions_count = |types: Vec<CppTypeData>| {
+    let mut processed = ProcessedCppData::default();
+    for type1 in &types {
+      processed
+        .type_allocation_places
+        .insert(type1.name.clone(), CppTypeAllocationPlace::Heap);
+    }
+    let cpp_data = CppDataWithDeps {
+      current: CppData {
+        parser: ParserCppData {
+          types: types,
+          methods: Vec::new(),
+          inline_namespaces: Vec::new(),
+        },
+        processed: processed,
+      },
+      dependencies: Vec::new(),
+    };
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        metatypes: &["Class1".to_string(), "Missing1".to_string()],
+                        ..Default::default()
+                      })
+        .unwrap()
+        .into_iter()
+        .flat_map(|header| header.methods.into_iter())
+        .filter(|method| match method.kind {
+                  CppFfiMethodKind::VariantConversion(..) => true,
+                  _ => false,
+                })
+        .count()
+  };
+  // QVariant is not available
+  assert_eq!(variant_conversions_count(vec![class_type_data("Class1", Vec::new())]), 0);
+  // conversions are generated only for the existing metatype
+  assert_eq!(variant_conversions_count(vec![class_type_data("Class1", Vec::new()),
+                                            class_type_data("QVariant", Vec::new())]),
+             3);
+}
+
+#[test]
+fn slicing_conversion_is_opt_in() {
+  let base_specifier = CppBaseSpecifier {
+    base_type: class_type("Base1"),
+    is_virtual: false,
+    visibility: CppVisibility::Public,
+  };
+  let mut processed = ProcessedCppData::default();
+  for name in &["Base1", "Derived1"] {
+    processed
+      .type_allocation_places
+      .insert(name.to_string(), CppTypeAllocationPlace::Heap);
+  }
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Base1", Vec::new()),
+                    class_type_data("Derived1", vec![base_specifier])],
+        methods: Vec::new(),
+        inline_namespaces: Vec::new(),
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let slicing_methods = |slicing_conversions: &[(String, String)]| -> Vec<String> {
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        slicing_conversions: slicing_conversions,
+                        ..Default::default()
+                      })
+        .unwrap()
+        .into_iter()
+        .flat_map(|header| header.methods.into_iter())
+        .filter(|m| m.kind == CppFfiMethodKind::SlicingConversion)
+        .map(|m| m.c_name)
+        .collect()
+  };
+  assert!(slicing_methods(&[]).is_empty());
+  let methods = slicing_methods(&[("Derived1".to_string(), "Base1".to_string())]);
+  assert_eq!(methods.len(), 1);
+  assert!(methods[0].contains("Base1"));
+}
+
+#[test]
+fn extern_c_function_bound_directly() {
+  let mut method = empty_regular_method();
+  method.name = "func1".to_string();
+  method.include_file = "myfakelib.h".to_string();
+  method.return_type = int_type();
+  method.arguments = vec![CppMethodArgument {
+                            name: "arg1".to_string(),
+                            argument_type: int_type(),
+                            has_default_value: false,
+                            default_value: None,
+                          }];
+  method.is_extern_c = true;
+  let mut cpp_method = method.clone();
+  cpp_method.name = "func2".to_string();
+  cpp_method.is_extern_c = false;
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: Vec::new(),
+        methods: vec![method, cpp_method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  let methods = &headers[0].methods;
+  assert_eq!(methods.len(), 2);
+  assert_eq!(&methods[0].c_name, "func1");
+  assert!(methods[0].is_direct_extern_c());
+  assert_eq!(&methods[1].c_name, "myfakelib_c_myfakelib_G_func2");
+  assert!(!methods[1].is_direct_extern_c());
+}
+
+#[test]
+fn constructors_with_colliding_captions() {
+  // the constructors have the same argument types and names,
+  // so all caption strategies fail for them, but they are not
+  // identical declarations because they belong to different features
+  let constructor = |feature: Option<&str>| {
+    let mut method = empty_regular_method();
+    method.name = "Class1".to_string();
+    let mut membership = empty_membership("Class1");
+    membership.kind = CppMethodKind::Constructor;
+    method.class_membership = Some(membership);
+    method.include_file = "myfakelib.h".to_string();
+    method.arguments = vec![CppMethodArgument {
+                              name: "arg1".to_string(),
+                              argument_type: int_type(),
+                              has_default_value: false,
+                              default_value: None,
+                            }];
+    method.feature = feature.map(|x| x.to_string());
+    method
+  };
+  let mut processed = ProcessedCppData::default();
+  processed
+    .type_allocation_places
+    .insert("Class1".to_string(), CppTypeAllocationPlace::Heap);
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![constructor(None),
+                      constructor(Some("feature1")),
+                      constructor(Some("feature2"))],
+        inline_namespaces: Vec::new(),
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  let names: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .map(|m| m.c_name.as_str())
+    .collect();
+  assert_eq!(names,
+             vec!["myfakelib_c_Class1_new",
+                  "myfakelib_c_Class1_new_2",
+                  "myfakelib_c_Class1_new_3"]);
+}
+
+#[test]
+fn rvalue_reference_argument() {
+  let mut method = empty_regular_method();
+  method.name = "setName".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.include_file = "myfakelib.h".to_string();
+  let mut arg_type = class_type("Class2");
+  arg_type.indirection = CppTypeIndirection::RValueRef;
+  method.arguments = vec![CppMethodArgument {
+                            name: "name".to_string(),
+                            argument_type: arg_type,
+                            has_default_value: false,
+                            default_value: None,
+                          }];
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new()),
+                    class_type_data("Class2", Vec::new())],
+        methods: vec![method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 1);
+  let arg = &headers[0].methods[0].c_signature.arguments[1];
+  assert_eq!(arg.name, "name");
+  assert_eq!(arg.argument_type.conversion,
+             CppIndirectionChange::RValueReferenceToPointer);
+  assert_eq!(arg.argument_type.ffi_type.indirection, CppTypeIndirection::Ptr);
+}
+
+#[test]
+fn exposed_protected_method() {
+  let mut method1 = empty_regular_method();
+  method1.name = "paintEvent".to_string();
+  method1.class_membership = Some(empty_membership("Class1"));
+  method1.class_membership.as_mut().unwrap().visibility = CppVisibility::Protected;
+  method1.include_file = "myfakelib.h".to_string();
+  let mut method2 = method1.clone();
+  method2.name = "hiddenHelper".to_string();
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method1, method2],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let run = |exposed_protected_methods: &[String]| {
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        exposed_protected_methods: exposed_protected_methods,
+                        ..Default::default()
+                      })
+        .unwrap()
+  };
+  let headers = run(&[]);
+  assert!(headers.iter().all(|header| header.methods.is_empty()));
+
+  let headers = run(&["Class1::paintEvent".to_string()]);
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 1);
+  assert_eq!(&headers[0].methods[0].cpp_method.name, "paintEvent");
+
+  let headers = run(&["Class1::*".to_string()]);
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 2);
+}
+
+#[test]
+fn batch_operation() {
+  let mut method = empty_regular_method();
+  method.name = "append".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.include_file = "myfakelib.h".to_string();
+  method.arguments = vec![CppMethodArgument {
+                            name: "value".to_string(),
+                            argument_type: CppType {
+                              base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                              indirection: CppTypeIndirection::Ref,
+                              is_const: true,
+                              is_const2: false,
+                              is_volatile: false,
+                            },
+                            has_default_value: false,
+                            default_value: None,
+                          }];
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data,
+                                  CppFfiGeneratorOptions {
+                                    batch_operations: &["Class1::append".to_string()],
+                                    ..Default::default()
+                                  })
+      .unwrap();
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 2);
+  let batch = headers[0]
+    .methods
+    .iter()
+    .find(|m| m.kind != CppFfiMethodKind::Real)
+    .unwrap();
+  assert_eq!(batch.kind,
+             CppFfiMethodKind::BatchOperation { method_name: "append".to_string() });
+  assert_eq!(&batch.cpp_method.name, "append_batch");
+  assert_eq!(&batch.c_name, "myfakelib_c_Class1_append_batch");
+  let args = &batch.c_signature.arguments;
+  assert_eq!(args.len(), 3);
+  assert_eq!(args[1].name, "items");
+  assert_eq!(args[1].argument_type.ffi_type.to_cpp_code(None).unwrap(),
+             "const int*");
+  assert_eq!(args[2].name, "count");
+  assert_eq!(args[2].argument_type.ffi_type.to_cpp_code(None).unwrap(),
+             "size_t");
+}
+
+#[test]
+fn template_method_instantiation() {
+  let mut method = empty_regular_method();
+  method.name = "findChild".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.class_membership.as_mut().unwrap().is_const = true;
+  method.include_file = "myfakelib.h".to_string();
+  method.template_arguments = Some(TemplateArgumentsDeclaration {
+                                     nested_level: 0,
+                                     names: vec!["T".to_string()],
+                                     defaults: Vec::new(),
+                                   });
+  method.return_type = CppType {
+    base: CppTypeBase::TemplateParameter {
+      nested_level: 0,
+      index: 0,
+    },
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new()),
+                    class_type_data("Class2", Vec::new())],
+        methods: vec![method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let run = |instantiations: &[CppTemplateMethodInstantiation]| {
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        template_method_instantiations: instantiations,
+                        ..Default::default()
+                      })
+  };
+  let headers = run(&[]).unwrap();
+  assert!(headers.iter().all(|header| header.methods.is_empty()));
+
+  let headers = run(&[CppTemplateMethodInstantiation {
+                        method_name: "Class1::findChild".to_string(),
+                        template_arguments: vec!["Class2*".to_string()],
+                      }])
+      .unwrap();
+  assert_eq!(headers.len(), 1);
+  assert_eq!(headers[0].methods.len(), 1);
+  let method = &headers[0].methods[0];
+  assert_eq!(method.cpp_method.template_arguments_values,
+             Some(vec![CppType {
+                         indirection: CppTypeIndirection::Ptr,
+                         ..class_type("Class2")
+                       }]));
+  assert!(!method.cpp_method.return_type.is_or_contains_template_parameter());
+  assert_eq!(method.c_signature.return_type.ffi_type.to_cpp_code(None).unwrap(),
+             "Class2*");
+  assert!(method
+            .c_signature
+            .arguments
+            .iter()
+            .all(|arg| !arg.argument_type.ffi_type.is_or_contains_template_parameter()));
+
+  assert!(run(&[CppTemplateMethodInstantiation {
+                  method_name: "Class1::findChildren".to_string(),
+                  template_arguments: vec!["Class2*".to_string()],
+                }])
+              .is_err());
+}
+
+#[test]
+fn deterministic_method_names() {
+  let double_type = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let mut methods = Vec::new();
+  for index in 0..10 {
+    for (arg_index, arg_type) in vec![int_type(), double_type.clone()].into_iter().enumerate() {
+      let mut method = empty_regular_method();
+      method.name = format!("set{}", index);
+      method.class_membership = Some(empty_membership("Class1"));
+      method.include_file = "myfakelib.h".to_string();
+      method.origin_location = Some(CppOriginLocation {
+                                      include_file_path: "/myfakelib.h".to_string(),
+                                      line: index * 2 + arg_index as u32 + 1,
+                                      column: 1,
+                                    });
+      method.arguments = vec![CppMethodArgument {
+                                name: "value".to_string(),
+                                argument_type: arg_type,
+                                has_default_value: false,
+                                default_value: None,
+                              }];
+      methods.push(method);
+    }
+  }
+  let run = |methods: Vec<CppMethod>| {
+    let mut processed = ProcessedCppData::default();
+    processed
+      .type_allocation_places
+      .insert("Class1".to_string(), CppTypeAllocationPlace::Heap);
+    let cpp_data = CppDataWithDeps {
+      current: CppData {
+        parser: ParserCppData {
+          types: vec![class_type_data("Class1", Vec::new())],
+          methods: methods,
+          inline_namespaces: Vec::new(),
+        },
+        processed: processed,
+      },
+      dependencies: Vec::new(),
+    };
+    let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+    assert_eq!(headers.len(), 1);
+    headers[0].methods.clone()
+  };
+  let first = run(methods.clone());
+  assert_eq!(first.len(), 20);
+  assert_eq!(format!("{:?}", first), format!("{:?}", run(methods.clone())));
+  methods.reverse();
+  assert_eq!(format!("{:?}", first), format!("{:?}", run(methods)));
+}
+
+#[test]
+fn const_overloads() {
+  // `int& at(int index)` and `const int& at(int index) const`
+  let at_method = |is_const: bool| {
+    let mut method = empty_regular_method();
+    method.name = "at".to_string();
+    let mut membership = empty_membership("Class1");
+    membership.is_const = is_const;
+    method.class_membership = Some(membership);
+    method.include_file = "myfakelib.h".to_string();
+    method.return_type = CppType {
+      indirection: CppTypeIndirection::Ref,
+      is_const: is_const,
+      ..int_type()
+    };
+    method.arguments = vec![CppMethodArgument {
+                              name: "index".to_string(),
+                              argument_type: int_type(),
+                              has_default_value: false,
+                              default_value: None,
+                            }];
+    method
+  };
+  let mut processed = ProcessedCppData::default();
+  processed
+    .type_allocation_places
+    .insert("Class1".to_string(), CppTypeAllocationPlace::Heap);
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![at_method(false), at_method(true)],
+        inline_namespaces: Vec::new(),
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  let methods: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .map(|m| (m.c_name.as_str(), m.c_signature.has_const_this()))
+    .collect();
+  assert_eq!(methods,
+             vec![("myfakelib_c_Class1_at", false), ("myfakelib_c_Class1_at_const", true)]);
+}
+
+#[test]
+fn identical_methods_are_merged() {
+  let mut method = empty_regular_method();
+  method.name = "size".to_string();
+  method.class_membership = Some(empty_membership("Class1"));
+  method.include_file = "myfakelib.h".to_string();
+  method.return_type = int_type();
+  let mut located_method = method.clone();
+  located_method.origin_location = Some(CppOriginLocation {
+                                          include_file_path: "/myfakelib_fwd.h".to_string(),
+                                          line: 10,
+                                          column: 3,
+                                        });
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![method.clone(), located_method.clone(), method],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  let methods: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .filter(|m| m.cpp_method.name == "size")
+    .collect();
+  assert_eq!(methods.len(), 1);
+  assert_eq!(methods[0].c_name, "myfakelib_c_Class1_size");
+  assert_eq!(methods[0].cpp_method, located_method);
+}
+
+#[test]
+fn stream_output_methods() {
+  let ostream_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    ..class_type("std::ostream")
+  };
+  let stream_operator = |value_type: CppType| {
+    let mut method = empty_regular_method();
+    method.name = "operator<<".to_string();
+    method.operator = Some(CppOperator::BitwiseLeftShift);
+    method.include_file = "myfakelib.h".to_string();
+    method.return_type = ostream_ref.clone();
+    method.arguments = vec![CppMethodArgument {
+                              name: "stream".to_string(),
+                              argument_type: ostream_ref.clone(),
+                              has_default_value: false,
+                              default_value: None,
+                            },
+                            CppMethodArgument {
+                              name: "value".to_string(),
+                              argument_type: CppType {
+                                indirection: CppTypeIndirection::Ref,
+                                is_const: true,
+                                ..value_type
+                              },
+                              has_default_value: false,
+                              default_value: None,
+                            }];
+    method
+  };
+  let template_parameter = CppType {
+    base: CppTypeBase::TemplateParameter {
+      nested_level: 0,
+      index: 0,
+    },
+    ..int_type()
+  };
+  let mut template_operator = stream_operator(CppType {
+                                                base: CppTypeBase::Class(CppTypeClassBase {
+                                                  name: "Box".to_string(),
+                                                  template_arguments:
+                                                    Some(vec![template_parameter]),
+                                                }),
+                                                ..int_type()
+                                              });
+  template_operator.template_arguments = Some(TemplateArgumentsDeclaration {
+                                                nested_level: 0,
+                                                names: vec!["T".to_string()],
+                                                defaults: Vec::new(),
+                                              });
+  let mut box_type_data = class_type_data("Box", Vec::new());
+  if let CppTypeKind::Class { ref mut template_arguments, .. } = box_type_data.kind {
+    *template_arguments = Some(TemplateArgumentsDeclaration {
+                                 nested_level: 0,
+                                 names: vec!["T".to_string()],
+                                 defaults: Vec::new(),
+                               });
+  }
+  let mut processed = ProcessedCppData::default();
+  processed
+    .template_instantiations
+    .push(CppTemplateInstantiations {
+            class_name: "Box".to_string(),
+            instantiations: vec![CppTemplateInstantiation { template_arguments: vec![int_type()] }],
+          });
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new()),
+                    class_type_data("Class2", Vec::new()),
+                    box_type_data],
+        methods: vec![stream_operator(class_type("Class1")), template_operator],
+        inline_namespaces: Vec::new(),
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  assert_eq!(headers.len(), 1);
+  let mut classes: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .filter(|m| m.kind == CppFfiMethodKind::StreamOutput)
+    .map(|m| m.cpp_method.class_membership.as_ref().unwrap().class_type.to_cpp_pseudo_code())
+    .collect();
+  classes.sort();
+  assert_eq!(classes, vec!["Box<int>".to_string(), "Class1".to_string()]);
+}
+
+#[test]
+fn allocation_benchmark() {
+  let class_method = |kind: CppMethodKind| {
+    let mut method = empty_regular_method();
+    method.name = if kind == CppMethodKind::Destructor {
+      "~Class1".to_string()
+    } else {
+      "Class1".to_string()
+    };
+    let mut membership = empty_membership("Class1");
+    membership.kind = kind;
+    method.class_membership = Some(membership);
+    method.include_file = "myfakelib.h".to_string();
+    method
+  };
+  let mut processed = ProcessedCppData::default();
+  processed
+    .type_allocation_places
+    .insert("Class1".to_string(), CppTypeAllocationPlace::Stack);
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new())],
+        methods: vec![class_method(CppMethodKind::Constructor),
+                      class_method(CppMethodKind::Destructor)],
+        inline_namespaces: Vec::new(),
+      },
+      processed: processed,
+    },
+    dependencies: Vec::new(),
+  };
+  let run = |allocation_benchmarks: &[String]| {
+    run_ffi_generator(&cpp_data,
+                      CppFfiGeneratorOptions {
+                        allocation_benchmarks: allocation_benchmarks,
+                        ..Default::default()
+                      })
+  };
+  let headers = run(&[]).unwrap();
+  assert_eq!(headers.len(), 1);
+  assert!(headers[0].methods.iter().all(|m| m.kind == CppFfiMethodKind::Real));
+
+  let headers = run(&["Class1".to_string()]).unwrap();
+  assert_eq!(headers.len(), 1);
+  let methods: Vec<_> = headers[0]
+    .methods
+    .iter()
+    .map(|m| (m.c_name.as_str(), m.kind.clone()))
+    .collect();
+  assert_eq!(methods,
+             vec![("myfakelib_c_Class1_constructor", CppFfiMethodKind::Real),
+                  ("myfakelib_c_Class1_destructor", CppFfiMethodKind::Real),
+                  ("myfakelib_c_Class1_new", CppFfiMethodKind::AllocationBenchmark),
+                  ("myfakelib_c_Class1_delete", CppFfiMethodKind::AllocationBenchmark)]);
+
+  assert!(run(&["Class2".to_string()]).is_err());
+}
+
+#[test]
+fn member_function_trampoline_requirements() {
+  let member_pointer_type = |class_name: &str| {
+    CppType {
+      base: CppTypeBase::FunctionPointer(CppFunctionPointerType {
+                                           return_type: Box::new(CppType::void()),
+                                           arguments: Vec::new(),
+                                           allows_variadic_arguments: false,
+                                           class_type: Some(CppTypeClassBase {
+                                                              name: class_name.to_string(),
+                                                              template_arguments: None,
+                                                            }),
+                                         }),
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+    }
+  };
+  let call_method = |name: &str, class_name: &str| {
+    let mut method = empty_regular_method();
+    method.name = name.to_string();
+    method.include_file = "myfakelib.h".to_string();
+    let mut object_type = class_type(class_name);
+    object_type.indirection = CppTypeIndirection::Ptr;
+    method.arguments = vec![CppMethodArgument {
+                              name: "object".to_string(),
+                              argument_type: object_type,
+                              has_default_value: false,
+                              default_value: None,
+                            },
+                            CppMethodArgument {
+                              name: "method".to_string(),
+                              argument_type: member_pointer_type(class_name),
+                              has_default_value: false,
+                              default_value: None,
+                            }];
+    method
+  };
+  let mut final_class = class_type_data("Class2", Vec::new());
+  if let CppTypeKind::Class { ref mut is_final, .. } = final_class.kind {
+    *is_final = true;
+  }
+  let mut private_constructor = empty_regular_method();
+  private_constructor.name = "Class3".to_string();
+  private_constructor.include_file = "myfakelib.h".to_string();
+  let mut membership = empty_membership("Class3");
+  membership.kind = CppMethodKind::Constructor;
+  membership.visibility = CppVisibility::Private;
+  private_constructor.class_membership = Some(membership);
+
+  let cpp_data = CppDataWithDeps {
+    current: CppData {
+      parser: ParserCppData {
+        types: vec![class_type_data("Class1", Vec::new()),
+                    final_class,
+                    class_type_data("Class3", Vec::new())],
+        methods: vec![call_method("call1", "Class1"),
+                      call_method("call2", "Class2"),
+                      call_method("call3", "Class3"),
+                      private_constructor],
+        inline_namespaces: Vec::new(),
+      },
+      processed: Default::default(),
+    },
+    dependencies: Vec::new(),
+  };
+  let headers = run_ffi_generator(&cpp_data, Default::default()).unwrap();
+  let trampoline_methods: Vec<_> = headers
+    .iter()
+    .flat_map(|header| header.methods.iter())
+    .filter(|m| match m.kind {
+              CppFfiMethodKind::MemberFunctionTrampoline { .. } => true,
+              _ => false,
+            })
+    .map(|m| m.cpp_method.name.as_str())
+    .collect();
+  assert_eq!(trampoline_methods, vec!["call1"]);
+}
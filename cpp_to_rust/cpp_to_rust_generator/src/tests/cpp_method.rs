@@ -29,6 +29,8 @@ pub fn empty_membership(class_name: &'static str) -> CppMethodClassMembership {
     visibility: CppVisibility::Public,
     is_signal: false,
     is_slot: false,
+    is_move_assignment: false,
+    ref_qualifier: None,
     class_type: CppTypeClassBase {
       name: class_name.to_string(),
       template_arguments: None,
@@ -52,6 +54,8 @@ pub fn empty_regular_method() -> CppMethod {
     operator: None,
     declaration_code: None,
     is_ffi_whitelisted: false,
+    is_extern_c: false,
+    feature: None,
   }
 }
 
@@ -74,10 +78,12 @@ fn argument_types_equal2() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   assert!(!method1.argument_types_equal(&method2));
   assert!(!method2.argument_types_equal(&method1));
@@ -94,10 +100,12 @@ fn argument_types_equal3() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   method2
     .arguments
@@ -106,10 +114,12 @@ fn argument_types_equal3() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "x".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   assert!(method1.argument_types_equal(&method2));
   assert!(method2.argument_types_equal(&method1));
@@ -126,10 +136,12 @@ fn argument_types_equal4() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   method2
     .arguments
@@ -138,10 +150,12 @@ fn argument_types_equal4() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: true,
+            default_value: None,
           });
   assert!(method1.argument_types_equal(&method2));
   assert!(method2.argument_types_equal(&method1));
@@ -158,10 +172,12 @@ fn argument_types_equal5() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   method2
     .arguments
@@ -170,10 +186,12 @@ fn argument_types_equal5() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Enum { name: "Enum1".to_string() },
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   assert!(!method1.argument_types_equal(&method2));
   assert!(!method2.argument_types_equal(&method1));
@@ -190,10 +208,12 @@ fn argument_types_equal6() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   method2
     .arguments
@@ -202,10 +222,12 @@ fn argument_types_equal6() {
               indirection: CppTypeIndirection::Ptr,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   assert!(!method1.argument_types_equal(&method2));
   assert!(!method2.argument_types_equal(&method1));
@@ -219,10 +241,12 @@ fn argument_types_equal7() {
       indirection: CppTypeIndirection::None,
       is_const: false,
       is_const2: false,
+      is_volatile: false,
       base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
     },
     name: "arg1".to_string(),
     has_default_value: false,
+    default_value: None,
   };
   let mut method2 = empty_regular_method();
   method1.arguments.push(int.clone());
@@ -240,6 +264,7 @@ fn argument_types_equal8() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   assert!(method1.argument_types_equal(&method2));
@@ -270,6 +295,7 @@ fn c_signature_simple_func() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   method1
@@ -279,10 +305,12 @@ fn c_signature_simple_func() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Enum { name: "Enum1".to_string() },
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   let r = method1
     .c_signature(ReturnValueAllocationPlace::NotApplicable)
@@ -306,6 +334,7 @@ fn c_signature_method_with_this() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   method1
@@ -315,6 +344,7 @@ fn c_signature_method_with_this() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Class(CppTypeClassBase {
                                          name: "MyClass2".to_string(),
                                          template_arguments: None,
@@ -322,6 +352,7 @@ fn c_signature_method_with_this() {
             },
             name: "my_arg".to_string(),
             has_default_value: false,
+            default_value: None,
           });
 
   assert!(!method1.is_constructor());
@@ -370,6 +401,7 @@ fn c_signature_static_method() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   method1
@@ -379,10 +411,12 @@ fn c_signature_static_method() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Enum { name: "Enum1".to_string() },
             },
             name: "arg1".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   let r = method1
     .c_signature(ReturnValueAllocationPlace::NotApplicable)
@@ -413,10 +447,12 @@ fn c_signature_constructor() {
               indirection: CppTypeIndirection::Ref,
               is_const: true,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Enum { name: "Enum1".to_string() },
             },
             name: "arg1".to_string(),
             has_default_value: true,
+            default_value: None,
           });
 
   assert!(method1.is_constructor());
@@ -434,6 +470,7 @@ fn c_signature_constructor() {
                indirection: CppTypeIndirection::Ptr,
                is_const: true,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Enum { name: "Enum1".to_string() },
              });
   assert_eq!(r_stack.arguments[0].argument_type.conversion,
@@ -447,6 +484,7 @@ fn c_signature_constructor() {
                indirection: CppTypeIndirection::Ptr,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Class(CppTypeClassBase {
                                           name: "MyClass".to_string(),
                                           template_arguments: None,
@@ -469,6 +507,7 @@ fn c_signature_constructor() {
                indirection: CppTypeIndirection::Ptr,
                is_const: true,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Enum { name: "Enum1".to_string() },
              });
   assert_eq!(r_heap.arguments[0].argument_type.conversion,
@@ -480,6 +519,7 @@ fn c_signature_constructor() {
                indirection: CppTypeIndirection::Ptr,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Class(CppTypeClassBase {
                                           name: "MyClass".to_string(),
                                           template_arguments: None,
@@ -552,6 +592,7 @@ fn c_signature_method_returning_class() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "MyClass3".to_string(),
                                template_arguments: None,
@@ -564,6 +605,7 @@ fn c_signature_method_returning_class() {
               indirection: CppTypeIndirection::None,
               is_const: false,
               is_const2: false,
+              is_volatile: false,
               base: CppTypeBase::Class(CppTypeClassBase {
                                          name: "MyClass2".to_string(),
                                          template_arguments: None,
@@ -571,6 +613,7 @@ fn c_signature_method_returning_class() {
             },
             name: "my_arg".to_string(),
             has_default_value: false,
+            default_value: None,
           });
   let r_stack = method1
     .c_signature(ReturnValueAllocationPlace::Stack)
@@ -606,6 +649,7 @@ fn c_signature_method_returning_class() {
                indirection: CppTypeIndirection::Ptr,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Class(CppTypeClassBase {
                                           name: "MyClass3".to_string(),
                                           template_arguments: None,
@@ -651,6 +695,7 @@ fn c_signature_method_returning_class() {
                indirection: CppTypeIndirection::Ptr,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Class(CppTypeClassBase {
                                           name: "MyClass3".to_string(),
                                           template_arguments: None,
@@ -708,6 +753,8 @@ fn short_text1() {
                              visibility: CppVisibility::Protected,
                              is_signal: false,
                              is_slot: false,
+                             is_move_assignment: false,
+                             ref_qualifier: None,
                              class_type: CppTypeClassBase {
                                name: "Class1".to_string(),
                                template_arguments: None,
@@ -718,6 +765,7 @@ fn short_text1() {
       indirection: CppTypeIndirection::None,
       is_const: false,
       is_const2: false,
+      is_volatile: false,
       base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
     },
     arguments: vec![CppMethodArgument {
@@ -725,20 +773,24 @@ fn short_text1() {
                         indirection: CppTypeIndirection::None,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                       },
                       name: "arg1".to_string(),
                       has_default_value: false,
+                      default_value: None,
                     },
                     CppMethodArgument {
                       argument_type: CppType {
                         indirection: CppTypeIndirection::None,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Double),
                       },
                       name: "arg2".to_string(),
                       has_default_value: true,
+                      default_value: None,
                     }],
     doc: None,
     inheritance_chain: Vec::new(),
@@ -749,7 +801,48 @@ fn short_text1() {
     template_arguments_values: None,
     declaration_code: None,
     is_ffi_whitelisted: false,
+    is_extern_c: false,
+    feature: None,
   };
   assert_eq!(method.short_text(),
              "protected int Class1::method1(int arg1, double arg2 = ?) const");
 }
+
+#[test]
+fn has_aliasing_mutable_references() {
+  let int_ref = CppType {
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+    indirection: CppTypeIndirection::Ref,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  let mut method = empty_regular_method();
+  method
+    .arguments
+    .push(CppMethodArgument {
+            name: "a".to_string(),
+            argument_type: int_ref.clone(),
+            has_default_value: false,
+            default_value: None,
+          });
+  assert!(!method.has_aliasing_mutable_references());
+  method
+    .arguments
+    .push(CppMethodArgument {
+            name: "b".to_string(),
+            argument_type: CppType { is_const: true, ..int_ref.clone() },
+            has_default_value: false,
+            default_value: None,
+          });
+  assert!(!method.has_aliasing_mutable_references());
+  method
+    .arguments
+    .push(CppMethodArgument {
+            name: "c".to_string(),
+            argument_type: int_ref.clone(),
+            has_default_value: false,
+            default_value: None,
+          });
+  assert!(method.has_aliasing_mutable_references());
+}
@@ -10,6 +10,10 @@ use common::file_utils::{create_dir, create_file, PathBufWithAdded};
 use std::path::PathBuf;
 
 fn run_parser(code: &'static str) -> ParserCppData {
+  run_parser_with_args(code, Vec::new())
+}
+
+fn run_parser_with_args(code: &'static str, clang_arguments: Vec<String>) -> ParserCppData {
   let dir = tempdir::TempDir::new("test_cpp_parser_run").unwrap();
   let include_dir = dir.path().with_added("include");
   create_dir(&include_dir).unwrap();
@@ -27,7 +31,7 @@ fn run_parser(code: &'static str) -> ParserCppData {
                                      tmp_cpp_path: dir.path().with_added("1.cpp"),
                                      name_blacklist: Vec::new(),
                                      framework_paths: Vec::new(),
-                                     clang_arguments: Vec::new(),
+                                     clang_arguments: clang_arguments,
                                    },
                                    &[])
       .unwrap();
@@ -58,6 +62,7 @@ fn simple_func() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                },
                arguments: vec![CppMethodArgument {
@@ -66,9 +71,11 @@ fn simple_func() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -79,6 +86,8 @@ fn simple_func() {
                template_arguments_values: None,
                declaration_code: Some("int func1 ( int x )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -96,6 +105,7 @@ fn simple_func_with_default_value() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                },
                arguments: vec![CppMethodArgument {
@@ -104,9 +114,11 @@ fn simple_func_with_default_value() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                                  },
                                  has_default_value: true,
+                                 default_value: Some("42".to_string()),
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -117,9 +129,27 @@ fn simple_func_with_default_value() {
                template_arguments_values: None,
                declaration_code: Some("bool func1 ( int x = 42 )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
+#[test]
+fn func_with_or_combined_default_value() {
+  let data = run_parser("namespace ns1 {
+  enum Flag { Left = 1, Top = 2 };
+  int func1(int x = ns1::Left | ns1::Top, int y = 3);
+}");
+  assert!(data.methods.len() == 1);
+  let args = &data.methods[0].arguments;
+  assert_eq!(args.len(), 2);
+  assert!(args[0].has_default_value);
+  assert_eq!(args[0].default_value,
+             Some("ns1 :: Left | ns1 :: Top".to_string()));
+  assert!(args[1].has_default_value);
+  assert_eq!(args[1].default_value, Some("3".to_string()));
+}
+
 #[test]
 fn functions_with_class_arg() {
   let data = run_parser("class Magic { public: int a, b; };
@@ -133,9 +163,11 @@ fn functions_with_class_arg() {
            ref fields,
            ref template_arguments,
            ref using_directives,
+           ref is_final,
          } = data.types[0].kind {
     assert!(template_arguments.is_none());
     assert!(using_directives.is_empty());
+    assert!(!is_final);
     assert!(bases.is_empty());
     assert_eq!(fields.len(), 2);
     assert_eq!(fields[0].name, "a");
@@ -144,6 +176,7 @@ fn functions_with_class_arg() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                });
     assert_eq!(fields[0].visibility, CppVisibility::Public);
@@ -154,6 +187,7 @@ fn functions_with_class_arg() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                });
     assert_eq!(fields[1].visibility, CppVisibility::Public);
@@ -170,6 +204,7 @@ fn functions_with_class_arg() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                },
                arguments: vec![CppMethodArgument {
@@ -178,12 +213,14 @@ fn functions_with_class_arg() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::Class(CppTypeClassBase {
                                                               name: "Magic".to_string(),
                                                               template_arguments: None,
                                                             }),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -194,6 +231,8 @@ fn functions_with_class_arg() {
                template_arguments_values: None,
                declaration_code: Some("bool func1 ( Magic x )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
   assert_eq!(data.methods[1],
              CppMethod {
@@ -204,6 +243,7 @@ fn functions_with_class_arg() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                },
                arguments: vec![CppMethodArgument {
@@ -212,12 +252,14 @@ fn functions_with_class_arg() {
                                    indirection: CppTypeIndirection::Ptr,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::Class(CppTypeClassBase {
                                                               name: "Magic".to_string(),
                                                               template_arguments: None,
                                                             }),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -228,6 +270,8 @@ fn functions_with_class_arg() {
                template_arguments_values: None,
                declaration_code: Some("bool func1 ( Magic * x )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
   assert_eq!(data.methods[2],
              CppMethod {
@@ -238,6 +282,7 @@ fn functions_with_class_arg() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                },
                arguments: vec![CppMethodArgument {
@@ -246,12 +291,14 @@ fn functions_with_class_arg() {
                                    indirection: CppTypeIndirection::Ref,
                                    is_const: true,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::Class(CppTypeClassBase {
                                                               name: "Magic".to_string(),
                                                               template_arguments: None,
                                                             }),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -262,6 +309,8 @@ fn functions_with_class_arg() {
                template_arguments_values: None,
                declaration_code: Some("bool func2 ( const Magic & )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -286,6 +335,7 @@ fn variadic_func() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                },
                arguments: vec![CppMethodArgument {
@@ -294,9 +344,11 @@ fn variadic_func() {
                                    indirection: CppTypeIndirection::Ptr,
                                    is_const: true,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -307,6 +359,8 @@ fn variadic_func() {
                template_arguments_values: None,
                declaration_code: Some("int my_printf ( const char * format , ... )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -324,6 +378,7 @@ fn free_template_func() {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::TemplateParameter {
                    nested_level: 0,
                    index: 0,
@@ -335,12 +390,14 @@ fn free_template_func() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::TemplateParameter {
                                      nested_level: 0,
                                      index: 0,
                                    },
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -350,10 +407,13 @@ fn free_template_func() {
                template_arguments: Some(TemplateArgumentsDeclaration {
                                           nested_level: 0,
                                           names: vec!["T".to_string()],
+                                          defaults: Vec::new(),
                                         }),
                template_arguments_values: None,
                declaration_code: Some("template < typename T > T abs ( T value )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -373,6 +433,7 @@ fn free_func_operator_sub() {
                    indirection: CppTypeIndirection::None,
                    is_const: false,
                    is_const2: false,
+                   is_volatile: false,
                    base: CppTypeBase::Class(CppTypeClassBase {
                                               name: "C1".to_string(),
                                               template_arguments: None,
@@ -384,12 +445,14 @@ fn free_func_operator_sub() {
                                      indirection: CppTypeIndirection::None,
                                      is_const: false,
                                      is_const2: false,
+                                     is_volatile: false,
                                      base: CppTypeBase::Class(CppTypeClassBase {
                                                                 name: "C1".to_string(),
                                                                 template_arguments: None,
                                                               }),
                                    },
                                    has_default_value: false,
+                                   default_value: None,
                                  },
                                  CppMethodArgument {
                                    name: "b".to_string(),
@@ -397,12 +460,14 @@ fn free_func_operator_sub() {
                                      indirection: CppTypeIndirection::None,
                                      is_const: false,
                                      is_const2: false,
+                                     is_volatile: false,
                                      base: CppTypeBase::Class(CppTypeClassBase {
                                                                 name: "C1".to_string(),
                                                                 template_arguments: None,
                                                               }),
                                    },
                                    has_default_value: false,
+                                   default_value: None,
                                  }],
                  doc: None,
                  inheritance_chain: Vec::new(),
@@ -413,6 +478,8 @@ fn free_func_operator_sub() {
                  template_arguments_values: None,
                  declaration_code: Some("C1 operator - ( C1 a , C1 b )".to_string()),
                  is_ffi_whitelisted: false,
+                 is_extern_c: false,
+                 feature: None,
                });
   }
 }
@@ -456,12 +523,15 @@ fn simple_class_method() {
                                         visibility: CppVisibility::Public,
                                         is_signal: false,
                                         is_slot: false,
+                                        is_move_assignment: false,
+                                        ref_qualifier: None,
                                       }),
                operator: None,
                return_type: CppType {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                },
                arguments: vec![CppMethodArgument {
@@ -470,9 +540,11 @@ fn simple_class_method() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -483,6 +555,8 @@ fn simple_class_method() {
                template_arguments_values: None,
                declaration_code: Some("int func1 ( int x )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -585,6 +659,7 @@ fn advanced_class_methods() {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
              })));
   assert_eq!(data.methods[6].return_type,
@@ -592,6 +667,7 @@ fn advanced_class_methods() {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
              });
 
@@ -600,6 +676,7 @@ fn advanced_class_methods() {
              Some(TemplateArgumentsDeclaration {
                     nested_level: 0,
                     names: vec!["K".to_string(), "V".to_string()],
+                    defaults: Vec::new(),
                   }));
   assert_eq!(data.methods[7].arguments.len(), 1);
   assert_eq!(data.methods[7].arguments[0].argument_type,
@@ -607,6 +684,7 @@ fn advanced_class_methods() {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::TemplateParameter {
                  nested_level: 0,
                  index: 1,
@@ -636,6 +714,7 @@ fn template_class_method() {
                &Some(TemplateArgumentsDeclaration {
                        nested_level: 0,
                        names: vec!["T".to_string()],
+                       defaults: Vec::new(),
                      }));
     assert!(bases.is_empty());
     assert!(fields.is_empty());
@@ -653,6 +732,7 @@ fn template_class_method() {
                                                    indirection: CppTypeIndirection::None,
                                                    is_const: false,
                                                    is_const2: false,
+                                                   is_volatile: false,
                                                    base: CppTypeBase::TemplateParameter {
                                                      nested_level: 0,
                                                      index: 0,
@@ -667,12 +747,15 @@ fn template_class_method() {
                                         visibility: CppVisibility::Public,
                                         is_signal: false,
                                         is_slot: false,
+                                        is_move_assignment: false,
+                                        ref_qualifier: None,
                                       }),
                operator: None,
                return_type: CppType {
                  indirection: CppTypeIndirection::None,
                  is_const: false,
                  is_const2: false,
+                 is_volatile: false,
                  base: CppTypeBase::TemplateParameter {
                    nested_level: 0,
                    index: 0,
@@ -684,9 +767,11 @@ fn template_class_method() {
                                    indirection: CppTypeIndirection::None,
                                    is_const: false,
                                    is_const2: false,
+                                   is_volatile: false,
                                    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                                  },
                                  has_default_value: false,
+                                 default_value: None,
                                }],
                doc: None,
                inheritance_chain: Vec::new(),
@@ -697,6 +782,8 @@ fn template_class_method() {
                template_arguments_values: None,
                declaration_code: Some("T get ( int index )".to_string()),
                is_ffi_whitelisted: false,
+               is_extern_c: false,
+               feature: None,
              });
 }
 
@@ -716,12 +803,14 @@ fn template_class_template_method() {
              Some(TemplateArgumentsDeclaration {
                     nested_level: 1,
                     names: vec!["F".to_string()],
+                    defaults: Vec::new(),
                   }));
   assert_eq!(data.methods[0].return_type,
              CppType {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::TemplateParameter {
                  nested_level: 1,
                  index: 0,
@@ -735,6 +824,7 @@ fn template_class_template_method() {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::TemplateParameter {
                  nested_level: 0,
                  index: 0,
@@ -764,6 +854,8 @@ fn simple_enum() {
                               value: 1,
                               doc: None,
                             }],
+               is_scoped: false,
+               is_anonymous: false,
              });
 }
 
@@ -796,7 +888,86 @@ fn simple_enum2() {
                               value: 3,
                               doc: None,
                             }],
+               is_scoped: false,
+               is_anonymous: false,
+             });
+}
+
+#[test]
+fn anonymous_enum_in_class() {
+  let data = run_parser("
+  class Class1 {
+  public:
+    enum { MaxSize = 256, MinSize = 1 };
+  };
+  enum { GlobalValue = 3 };
+  namespace ns1 {
+    enum { NamespaceValue = 4 };
+  }");
+  // anonymous enums outside of classes are skipped
+  assert_eq!(data.types.len(), 2);
+  assert_eq!(data.types[0].name, "Class1");
+  assert_eq!(data.types[1].name, "Class1::AnonymousEnum_MaxSize");
+  assert_eq!(data.types[1].kind,
+             CppTypeKind::Enum {
+               values: vec![CppEnumValue {
+                              name: "MaxSize".to_string(),
+                              value: 256,
+                              doc: None,
+                            },
+                            CppEnumValue {
+                              name: "MinSize".to_string(),
+                              value: 1,
+                              doc: None,
+                            }],
+               is_scoped: false,
+               is_anonymous: true,
+             });
+}
+
+#[test]
+fn scoped_enum() {
+  let data = run_parser("
+  enum class Enum1 {
+    Good,
+    Bad
+  };
+  enum struct Enum2 : unsigned char { Value1 = 3 };
+  enum Enum3 { Value2 };");
+  assert_eq!(data.types.len(), 3);
+  assert_eq!(data.types[0].name, "Enum1");
+  assert_eq!(data.types[0].kind,
+             CppTypeKind::Enum {
+               values: vec![CppEnumValue {
+                              name: "Good".to_string(),
+                              value: 0,
+                              doc: None,
+                            },
+                            CppEnumValue {
+                              name: "Bad".to_string(),
+                              value: 1,
+                              doc: None,
+                            }],
+               is_scoped: true,
+               is_anonymous: false,
+             });
+  assert_eq!(data.types[1].name, "Enum2");
+  assert_eq!(data.types[1].kind,
+             CppTypeKind::Enum {
+               values: vec![CppEnumValue {
+                              name: "Value1".to_string(),
+                              value: 3,
+                              doc: None,
+                            }],
+               is_scoped: true,
+               is_anonymous: false,
              });
+  assert_eq!(data.types[2].name, "Enum3");
+  if let CppTypeKind::Enum { is_scoped, .. } = data.types[2].kind {
+    assert!(!is_scoped);
+  } else {
+    panic!("invalid type kind");
+  }
 }
 
 #[test]
@@ -813,6 +984,7 @@ fn template_instantiation() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   assert_eq!(data.methods[0].return_type,
@@ -820,6 +992,7 @@ fn template_instantiation() {
                indirection: CppTypeIndirection::None,
                is_const: false,
                is_const2: false,
+               is_volatile: false,
                base: CppTypeBase::Class(CppTypeClassBase {
                                           name: "Vector".to_string(),
                                           template_arguments: Some(vec![int.clone()]),
@@ -957,6 +1130,7 @@ fn derived_class_simple() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Base".to_string(),
                                                     template_arguments: None,
@@ -988,6 +1162,7 @@ fn derived_class_simple_private() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Base".to_string(),
                                                     template_arguments: None,
@@ -1019,6 +1194,7 @@ fn derived_class_simple_virtual() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Base".to_string(),
                                                     template_arguments: None,
@@ -1048,6 +1224,7 @@ fn derived_class_multiple() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Base2".to_string(),
                                                     template_arguments: None,
@@ -1061,6 +1238,7 @@ fn derived_class_multiple() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Base1".to_string(),
                                                     template_arguments: None,
@@ -1116,6 +1294,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::None,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[1].return_type,
              &CppType {
@@ -1123,6 +1302,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::None,
                 is_const: true,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[2].return_type,
              &CppType {
@@ -1130,6 +1310,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[3].return_type,
              &CppType {
@@ -1137,6 +1318,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: true,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[4].return_type,
              &CppType {
@@ -1144,6 +1326,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[5].return_type,
              &CppType {
@@ -1151,6 +1334,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::PtrPtr,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[6].return_type,
              &CppType {
@@ -1158,6 +1342,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::PtrPtr,
                 is_const: false,
                 is_const2: true,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[7].return_type,
              &CppType {
@@ -1165,6 +1350,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::PtrPtr,
                 is_const: true,
                 is_const2: true,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[8].return_type,
              &CppType {
@@ -1172,6 +1358,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::PtrPtr,
                 is_const: true,
                 is_const2: true,
+                is_volatile: false,
               });
   assert_eq!(&data.methods[9].return_type,
              &CppType {
@@ -1179,6 +1366,7 @@ fn complex_const_types() {
                 indirection: CppTypeIndirection::PtrPtr,
                 is_const: true,
                 is_const2: true,
+                is_volatile: false,
               });
 }
 
@@ -1218,6 +1406,7 @@ fn fixed_size_integers() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::SpecificNumeric(CppSpecificNumericType {
                                          name: "GLuint64".to_string(),
                                          bits: 64,
@@ -1234,6 +1423,7 @@ fn fixed_size_integers() {
                 indirection: CppTypeIndirection::None,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
                 base: CppTypeBase::Class(CppTypeClassBase {
                                            name: "QVector".to_string(),
                                            template_arguments: Some(vec![type1.clone()]),
@@ -1243,6 +1433,49 @@ fn fixed_size_integers() {
 }
 
 
+#[test]
+fn pointer_sized_integers() {
+  let data = run_parser("
+  #include <cstddef>
+  size_t f1(std::ptrdiff_t x);
+  std::size_t f2(const size_t& x);
+  ");
+  assert_eq!(data.methods.len(), 2);
+  let size_type = |name: &str| {
+    CppType {
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+      base: CppTypeBase::PointerSizedInteger {
+        name: name.to_string(),
+        is_signed: false,
+      },
+    }
+  };
+  assert_eq!(&data.methods[0].name, "f1");
+  assert_eq!(data.methods[0].return_type, size_type("size_t"));
+  assert_eq!(data.methods[0].arguments[0].argument_type,
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::PointerSizedInteger {
+                 name: "std::ptrdiff_t".to_string(),
+                 is_signed: true,
+               },
+             });
+  assert_eq!(&data.methods[1].name, "f2");
+  assert_eq!(data.methods[1].return_type, size_type("std::size_t"));
+  assert_eq!(data.methods[1].arguments[0].argument_type,
+             CppType {
+               indirection: CppTypeIndirection::Ref,
+               is_const: true,
+               ..size_type("size_t")
+             });
+}
+
 #[test]
 fn template_class_with_base() {
   let data = run_parser("
@@ -1264,6 +1497,7 @@ fn template_class_with_base() {
                &Some(TemplateArgumentsDeclaration {
                        nested_level: 0,
                        names: vec!["T".to_string()],
+                       defaults: Vec::new(),
                      }));
     assert!(bases.is_empty());
     assert!(fields.is_empty());
@@ -1282,6 +1516,7 @@ fn template_class_with_base() {
                &Some(TemplateArgumentsDeclaration {
                        nested_level: 0,
                        names: vec!["T".to_string()],
+                       defaults: Vec::new(),
                      }));
     assert_eq!(bases.len(), 1);
     assert!(fields.is_empty());
@@ -1289,3 +1524,541 @@ fn template_class_with_base() {
     panic!("invalid type kind");
   }
 }
+
+#[test]
+fn class_allocation_operators() {
+  let data = run_parser("
+  #include <cstddef>
+  class C1 {
+  public:
+    static void* operator new(std::size_t size);
+    static void operator delete(void* ptr);
+    void* operator new[](std::size_t size);
+    void operator delete[](void* ptr, std::size_t size);
+  };");
+  assert_eq!(data.methods.len(), 4);
+  let operators: Vec<_> = data
+    .methods
+    .iter()
+    .map(|m| m.operator.clone())
+    .collect();
+  assert_eq!(operators,
+             vec![Some(CppOperator::New),
+                  Some(CppOperator::Delete),
+                  Some(CppOperator::NewArray),
+                  Some(CppOperator::DeleteArray)]);
+  for method in &data.methods {
+    let info = method.class_membership.as_ref().unwrap();
+    assert_eq!(&info.class_type.name, "C1");
+    assert!(info.is_static);
+  }
+}
+
+#[test]
+fn delegating_constructors() {
+  let data = run_parser("
+  class C1 {
+  public:
+    C1(int x, int y) : m_x(x), m_y(y) {}
+    C1(int x) : C1(x, 0) {}
+    C1() : C1(1) {}
+  private:
+    int m_x;
+    int m_y;
+  };");
+  let constructors: Vec<_> = data
+    .methods
+    .iter()
+    .filter(|m| m.is_constructor())
+    .map(|m| m.arguments.iter().map(|arg| arg.name.as_str()).collect::<Vec<_>>())
+    .collect();
+  assert_eq!(constructors, vec![vec!["x", "y"], vec!["x"], vec![]]);
+  for method in &data.methods {
+    assert_eq!(&method.class_membership.as_ref().unwrap().class_type.name, "C1");
+  }
+}
+
+#[test]
+fn trailing_return_type() {
+  let data = run_parser("
+  class C1 {
+  public:
+    auto func1(int x) -> int;
+    auto func2() -> const C1&;
+  };
+  auto func3(C1* c) -> C1*;");
+  assert_eq!(data.methods.len(), 3);
+  let c1_type = CppTypeBase::Class(CppTypeClassBase {
+                                     name: "C1".to_string(),
+                                     template_arguments: None,
+                                   });
+  assert_eq!(data.methods[0].name, "func1");
+  assert_eq!(data.methods[0].return_type,
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+             });
+  assert_eq!(data.methods[1].name, "func2");
+  assert_eq!(data.methods[1].return_type,
+             CppType {
+               indirection: CppTypeIndirection::Ref,
+               is_const: true,
+               is_const2: false,
+               is_volatile: false,
+               base: c1_type.clone(),
+             });
+  assert_eq!(data.methods[2].name, "func3");
+  assert_eq!(data.methods[2].return_type,
+             CppType {
+               indirection: CppTypeIndirection::Ptr,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: c1_type.clone(),
+             });
+}
+
+#[test]
+fn const_ptr_to_const() {
+  let data = run_parser("void func1(const int* const x, int* const y);");
+  assert_eq!(data.methods.len(), 1);
+  let arguments = &data.methods[0].arguments;
+  assert_eq!(arguments.len(), 2);
+  // constness of the pointer itself is not a part of the function signature,
+  // but constness of the pointee must be preserved
+  let x_type = CppType {
+    indirection: CppTypeIndirection::Ptr,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  assert_eq!(arguments[0].argument_type, x_type);
+  assert_eq!(arguments[0].argument_type.to_cpp_code(None).unwrap(),
+             "const int*");
+  let x_ffi_type = arguments[0]
+    .argument_type
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(x_ffi_type.ffi_type, x_type);
+  assert_eq!(arguments[1].argument_type,
+             CppType {
+               is_const: false,
+               ..x_type.clone()
+             });
+}
+
+#[test]
+fn array_reference() {
+  let data = run_parser("class Class1 {
+    public:
+      const int (&values() const)[4];
+      void setValues(const int (&values)[4]);
+    };");
+  assert_eq!(data.methods.len(), 2);
+  let array_type = CppType {
+    base: CppTypeBase::Array {
+      element: Box::new(CppType {
+                          base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                          indirection: CppTypeIndirection::None,
+                          is_const: true,
+                          is_const2: false,
+                          is_volatile: false,
+                        }),
+      size: 4,
+    },
+    indirection: CppTypeIndirection::Ref,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+  };
+  assert_eq!(&data.methods[0].name, "values");
+  assert_eq!(data.methods[0].return_type, array_type);
+  assert_eq!(&data.methods[1].name, "setValues");
+  assert_eq!(data.methods[1].arguments[0].argument_type, array_type);
+  assert_eq!(array_type.to_cpp_code(Some("values")).unwrap(),
+             "const int (&values)[4]");
+}
+
+#[test]
+fn volatile_pointer() {
+  let data = run_parser("void func1(volatile int* x);");
+  assert_eq!(data.methods.len(), 1);
+  let arg_type = &data.methods[0].arguments[0].argument_type;
+  assert_eq!(arg_type,
+             &CppType {
+               base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+               indirection: CppTypeIndirection::Ptr,
+               is_const: false,
+               is_const2: false,
+               is_volatile: true,
+             });
+  assert_eq!(arg_type.to_cpp_code(None).unwrap(), "volatile int*");
+}
+
+#[test]
+fn extern_c_func() {
+  let data = run_parser("extern \"C\" {\nint func1(int x);\n}\n\
+                         extern \"C\" int func2();\n\
+                         int func3();");
+  assert_eq!(data.methods.len(), 3);
+  assert_eq!(&data.methods[0].name, "func1");
+  assert!(data.methods[0].is_extern_c);
+  assert_eq!(&data.methods[1].name, "func2");
+  assert!(data.methods[1].is_extern_c);
+  assert_eq!(&data.methods[2].name, "func3");
+  assert!(!data.methods[2].is_extern_c);
+}
+
+#[test]
+fn template_class_with_default_argument() {
+  let data = run_parser("
+  template<class T>
+  class Alloc1 {};
+
+  template<class T, class A = Alloc1<T> >
+  class Vector1 {};
+  ");
+  assert_eq!(data.types.len(), 2);
+  assert_eq!(data.types[1].name, "Vector1");
+  let declaration = if let CppTypeKind::Class { template_arguments: Some(ref x), .. } =
+    data.types[1].kind {
+    x.clone()
+  } else {
+    panic!("invalid type kind");
+  };
+  let alloc_type = |arg: CppType| {
+    CppType {
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+      base: CppTypeBase::Class(CppTypeClassBase {
+                                 name: "Alloc1".to_string(),
+                                 template_arguments: Some(vec![arg]),
+                               }),
+    }
+  };
+  let template_parameter = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::TemplateParameter {
+      nested_level: 0,
+      index: 0,
+    },
+  };
+  assert_eq!(declaration.defaults,
+             vec![None, Some(alloc_type(template_parameter))]);
+
+  let int = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  assert_eq!(declaration.with_defaults(&[int.clone()]).unwrap(),
+             vec![int.clone(), alloc_type(int.clone())]);
+  assert!(declaration.with_defaults(&[]).is_err());
+}
+
+#[test]
+fn inline_namespace() {
+  let data = run_parser("namespace ns { inline namespace v1 { class Class1 {}; int func1(); } \
+                         namespace v2 { int func2(); } }");
+  assert_eq!(data.inline_namespaces, vec!["ns::v1".to_string()]);
+  assert!(data.types.iter().any(|t| &t.name == "ns::v1::Class1"));
+  assert!(data.methods.iter().any(|m| &m.name == "ns::v1::func1"));
+  assert!(data.methods.iter().any(|m| &m.name == "ns::v2::func2"));
+}
+
+#[test]
+fn move_assignment() {
+  let data = run_parser("class Class1 {\npublic:\n\
+                         Class1& operator=(const Class1& other);\n\
+                         Class1& operator=(Class1&& other);\n\
+                         Class1& operator=(int value);\n};");
+  assert_eq!(data.methods.len(), 3);
+  let is_move_assignment = |m: &CppMethod| {
+    m.class_membership
+      .as_ref()
+      .unwrap()
+      .is_move_assignment
+  };
+  assert!(!is_move_assignment(&data.methods[0]));
+  assert!(is_move_assignment(&data.methods[1]));
+  assert_eq!(data.methods[1].arguments[0].argument_type.indirection,
+             CppTypeIndirection::RValueRef);
+  assert!(!is_move_assignment(&data.methods[2]));
+}
+
+#[test]
+fn ref_qualifiers() {
+  let data = run_parser("class Class1 {\npublic:\n\
+                         int take() &&;\n\
+                         int get() const &;\n\
+                         int value();\n};");
+  assert_eq!(data.methods.len(), 3);
+  let ref_qualifier = |m: &CppMethod| m.class_membership.as_ref().unwrap().ref_qualifier;
+  assert_eq!(ref_qualifier(&data.methods[0]), Some(CppRefQualifier::RValue));
+  assert_eq!(ref_qualifier(&data.methods[1]), Some(CppRefQualifier::LValue));
+  assert_eq!(ref_qualifier(&data.methods[2]), None);
+  assert_eq!(data.methods[0].short_text(), "int Class1::take() &&");
+}
+
+#[test]
+fn string_view() {
+  let data = run_parser("namespace std {\n\
+                         template<class C> class char_traits {};\n\
+                         template<class C, class T = char_traits<C> >\n\
+                         class basic_string_view {};\n\
+                         typedef basic_string_view<char> string_view;\n}\n\
+                         void func1(std::string_view text);\n\
+                         void func2(const std::string_view& text);\n\
+                         void func3(std::basic_string_view<int> text);");
+  let find_method = |name: &str| data.methods.iter().find(|m| &m.name == name);
+  let arg_type = |name: &str| {
+    find_method(name).unwrap().arguments[0]
+      .argument_type
+      .clone()
+  };
+  assert_eq!(arg_type("func1"),
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::StringView,
+             });
+  assert_eq!(arg_type("func2"),
+             CppType {
+               indirection: CppTypeIndirection::Ref,
+               is_const: true,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::StringView,
+             });
+  assert!(find_method("func3").is_none());
+}
+
+#[test]
+fn span() {
+  let data = run_parser("namespace std {\n\
+                         typedef unsigned long size_t;\n\
+                         constexpr size_t dynamic_extent = size_t(-1);\n\
+                         template<class T, size_t Extent = dynamic_extent>\n\
+                         class span {};\n}\n\
+                         void func1(std::span<const int> values);\n\
+                         void func2(std::span<int> values);\n\
+                         void func3(std::span<int, 4> values);");
+  let find_method = |name: &str| data.methods.iter().find(|m| &m.name == name);
+  let arg_type = |name: &str| {
+    find_method(name).unwrap().arguments[0]
+      .argument_type
+      .clone()
+  };
+  let span_of = |is_const: bool| {
+    CppType {
+      indirection: CppTypeIndirection::None,
+      is_const: false,
+      is_const2: false,
+      is_volatile: false,
+      base: CppTypeBase::Span {
+        element: Box::new(CppType {
+                            indirection: CppTypeIndirection::None,
+                            is_const: is_const,
+                            is_const2: false,
+                            is_volatile: false,
+                            base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                          }),
+      },
+    }
+  };
+  assert_eq!(arg_type("func1"), span_of(true));
+  assert_eq!(arg_type("func2"), span_of(false));
+  assert!(find_method("func3").is_none());
+}
+
+#[test]
+fn unique_ptr() {
+  let data = run_parser("namespace std {\n\
+                         template<class T> struct default_delete {};\n\
+                         template<class T, class D = default_delete<T>>\n\
+                         class unique_ptr {};\n}\n\
+                         class Class1 {};\n\
+                         struct Deleter1 {};\n\
+                         std::unique_ptr<Class1> func1();\n\
+                         std::unique_ptr<Class1[]> func2();\n\
+                         std::unique_ptr<Class1, Deleter1> func3();");
+  let find_method = |name: &str| data.methods.iter().find(|m| &m.name == name);
+  assert_eq!(find_method("func1").unwrap().return_type,
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::UniquePtr {
+                 pointee: Box::new(CppType {
+                                     indirection: CppTypeIndirection::None,
+                                     is_const: false,
+                                     is_const2: false,
+                                     is_volatile: false,
+                                     base: CppTypeBase::Class(CppTypeClassBase {
+                                                                name: "Class1".to_string(),
+                                                                template_arguments: None,
+                                                              }),
+                                   }),
+               },
+             });
+  assert!(find_method("func2").is_none());
+  assert!(find_method("func3").is_none());
+}
+
+#[test]
+fn initializer_list() {
+  let data = run_parser("namespace std {\n\
+                         template<class E> class initializer_list {};\n}\n\
+                         std::initializer_list<int> func1();\n\
+                         template<class T> std::initializer_list<T> func2();");
+  let find_method = |name: &str| data.methods.iter().find(|m| &m.name == name);
+  assert_eq!(find_method("func1").unwrap().return_type,
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::InitializerList {
+                 element: Box::new(CppType {
+                                     indirection: CppTypeIndirection::None,
+                                     is_const: false,
+                                     is_const2: false,
+                                     is_volatile: false,
+                                     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                                   }),
+               },
+             });
+  assert!(find_method("func2").is_none());
+}
+
+#[test]
+fn member_function_pointer() {
+  let data = run_parser("class Class1 {\n\
+                         public:\n\
+                         int method1(int x);\n\
+                         int field1;\n\
+                         };\n\
+                         void func1(Class1* object, int (Class1::*method)(int));\n\
+                         void func2(Class1* object, int Class1::*field);");
+  let func1 = data.methods.iter().find(|m| &m.name == "func1").unwrap();
+  let int_type = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  assert_eq!(func1.arguments[1].argument_type,
+             CppType {
+               indirection: CppTypeIndirection::None,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::FunctionPointer(CppFunctionPointerType {
+                                                    return_type: Box::new(int_type.clone()),
+                                                    arguments: vec![int_type],
+                                                    allows_variadic_arguments: false,
+                                                    class_type: Some(CppTypeClassBase {
+                                                                       name: "Class1"
+                                                                         .to_string(),
+                                                                       template_arguments: None,
+                                                                     }),
+                                                  }),
+             });
+  assert_eq!(func1.arguments[1].argument_type.to_cpp_code(Some("method")).unwrap(),
+             "int (Class1::*method)(int)");
+  assert!(data.methods.iter().find(|m| &m.name == "func2").is_none());
+}
+
+#[test]
+fn feature_methods() {
+  let code = "class Class1 {
+  public:
+    int x() const;
+  #ifdef MYFAKELIB_EXTRA
+    int y() const;
+  #endif
+  };";
+  let mut data = run_parser(code);
+  assert_eq!(data.methods.len(), 1);
+  let extra_data = run_parser_with_args(code, vec!["-DMYFAKELIB_EXTRA".to_string()]);
+  assert_eq!(extra_data.methods.len(), 2);
+  data.add_feature_methods(extra_data, "extra");
+  assert_eq!(data.methods.len(), 2);
+  let x = data.methods.iter().find(|m| m.name == "x").unwrap();
+  assert_eq!(x.feature, None);
+  let y = data.methods.iter().find(|m| m.name == "y").unwrap();
+  assert_eq!(y.feature, Some("extra".to_string()));
+}
+
+#[test]
+fn crtp_base_methods() {
+  use cpp_post_processor::cpp_post_process;
+  use std::collections::HashMap;
+
+  let data = run_parser("template<class Derived>\n\
+                         class Base1 {\n\
+                         public:\n\
+                           Base1();\n\
+                           int value() const;\n\
+                           Derived& self();\n\
+                         };\n\
+                         class Class1 : public Base1<Class1> {\n\
+                         public:\n\
+                           int own() const;\n\
+                         };");
+  let class1 = data.types.iter().find(|t| t.name == "Class1").unwrap();
+  if let CppTypeKind::Class { ref bases, .. } = class1.kind {
+    assert_eq!(bases.len(), 1);
+    assert!(bases[0].is_crtp_base_of("Class1"));
+    assert!(!bases[0].is_crtp_base_of("Base1"));
+  } else {
+    panic!("invalid type kind");
+  }
+  let processed = cpp_post_process(data, Vec::new(), &HashMap::new()).unwrap();
+  let inherited = &processed.current.processed.inherited_methods;
+  assert_eq!(inherited.len(), 2);
+  let value = inherited.iter().find(|m| m.name == "value").unwrap();
+  assert_eq!(value.class_name(), Some(&"Class1".to_string()));
+  assert_eq!(value.inheritance_chain.len(), 1);
+  let self_method = inherited.iter().find(|m| m.name == "self").unwrap();
+  assert_eq!(self_method.return_type,
+             CppType {
+               indirection: CppTypeIndirection::Ref,
+               is_const: false,
+               is_const2: false,
+               is_volatile: false,
+               base: CppTypeBase::Class(CppTypeClassBase {
+                                          name: "Class1".to_string(),
+                                          template_arguments: None,
+                                        }),
+             });
+}
+
+#[test]
+fn final_class() {
+  let data = run_parser("class Class1 final {};\n\
+                         class Class2 {};\n\
+                         class Class3 final : public Class2 {};");
+  let is_final = |name: &str| match data.types.iter().find(|t| t.name == name).unwrap().kind {
+    CppTypeKind::Class { is_final, .. } => is_final,
+    _ => panic!("class expected"),
+  };
+  assert!(is_final("Class1"));
+  assert!(!is_final("Class2"));
+  assert!(is_final("Class3"));
+}
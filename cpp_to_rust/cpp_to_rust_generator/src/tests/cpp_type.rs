@@ -38,6 +38,7 @@ fn void_ptr() {
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Void,
   };
   assert_eq!(type1.is_void(), false);
@@ -63,6 +64,7 @@ fn int() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
   };
   assert_eq!(type1.is_void(), false);
@@ -87,6 +89,7 @@ fn bool_ptr() {
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
   };
   assert_eq!(type1.is_void(), false);
@@ -112,6 +115,7 @@ fn char_ptr_ptr() {
     indirection: CppTypeIndirection::PtrPtr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Char),
   };
   assert_eq!(type1.is_void(), false);
@@ -137,6 +141,7 @@ fn qint64() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::SpecificNumeric(CppSpecificNumericType {
                                          name: "qint64".to_string(),
                                          bits: 64,
@@ -167,6 +172,7 @@ fn quintptr() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::PointerSizedInteger {
       name: "quintptr".to_string(),
       is_signed: false,
@@ -196,6 +202,7 @@ fn enum1() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Enum { name: "Qt::CaseSensitivity".to_string() },
   };
   assert_eq!(type1.is_void(), false);
@@ -223,6 +230,7 @@ fn class_value() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QPoint".to_string(),
                                template_arguments: None,
@@ -248,6 +256,7 @@ fn class_value() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
                 base: CppTypeBase::Class(CppTypeClassBase {
                                            name: "QPoint".to_string(),
                                            template_arguments: None,
@@ -267,6 +276,7 @@ fn class_value() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: true,
                 is_const2: false,
+                is_volatile: false,
                 base: CppTypeBase::Class(CppTypeClassBase {
                                            name: "QPoint".to_string(),
                                            template_arguments: None,
@@ -278,12 +288,337 @@ fn class_value() {
   assert!(type1.needs_allocation_place_variants());
 }
 
+#[test]
+fn std_array_value() {
+  let type1 = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::Array {
+      element: Box::new(CppType {
+                          indirection: CppTypeIndirection::None,
+                          is_const: false,
+                          is_const2: false,
+                          is_volatile: false,
+                          base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                        }),
+      size: 4,
+      is_std_array: true,
+    },
+  };
+  assert_eq!(type1.base.is_class(), false);
+  assert_eq!(type1.base.is_std_array(), true);
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::array<int, 4>");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Short).unwrap(),
+             "std_array");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Full).unwrap(),
+             "int_std_array_4");
+
+  let ffi_return_type = type1.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap();
+  assert_eq!(&ffi_return_type.ffi_type.to_cpp_code(None).unwrap(),
+             "std::array<int, 4>*");
+  assert_eq!(ffi_return_type.conversion,
+             CppIndirectionChange::ValueToPointer);
+
+  let ffi_arg = type1
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(&ffi_arg.ffi_type.to_cpp_code(None).unwrap(),
+             "const std::array<int, 4>*");
+  assert_eq!(ffi_arg.conversion, CppIndirectionChange::ValueToPointer);
+  assert!(type1.needs_allocation_place_variants());
+}
+
+#[test]
+fn string_view_argument() {
+  let type1 = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::StringView,
+  };
+  assert_eq!(type1.base.is_class(), false);
+  assert_eq!(type1.base.is_string_view(), true);
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::string_view");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Short).unwrap(),
+             "string_view");
+  assert!(type1.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+  assert!(!type1.needs_allocation_place_variants());
+
+  let ffi_arg = type1
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(&ffi_arg.ffi_type.to_cpp_code(None).unwrap(),
+             "cpp_utils_str_ref");
+  assert_eq!(ffi_arg.conversion, CppIndirectionChange::StringViewToStrRef);
+
+  let const_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    ..type1.clone()
+  };
+  let ffi_arg2 = const_ref
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(ffi_arg2.ffi_type, ffi_arg.ffi_type);
+  assert_eq!(ffi_arg2.conversion, CppIndirectionChange::StringViewToStrRef);
+
+  let mut_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    ..type1.clone()
+  };
+  assert!(mut_ref.to_cpp_ffi_type(CppTypeRole::NotReturnType).is_err());
+}
+
+#[test]
+fn span_argument() {
+  let element = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: true,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  let type1 = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::Span { element: Box::new(element.clone()) },
+  };
+  assert_eq!(type1.base.is_class(), false);
+  assert_eq!(type1.base.is_span(), true);
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::span<const int>");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Short).unwrap(), "span");
+  assert!(type1.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+  assert!(!type1.needs_allocation_place_variants());
+
+  let ffi_arg = type1
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(&ffi_arg.ffi_type.to_cpp_code(None).unwrap(),
+             "cpp_utils_slice_ref");
+  assert_eq!(ffi_arg.conversion, CppIndirectionChange::SpanToSliceRef);
+
+  let mut_span = CppType {
+    base: CppTypeBase::Span { element: Box::new(CppType { is_const: false, ..element.clone() }) },
+    ..type1.clone()
+  };
+  assert_eq!(mut_span.to_cpp_code(None).unwrap(), "std::span<int>");
+  let ffi_arg2 = mut_span
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(ffi_arg2.ffi_type, ffi_arg.ffi_type);
+  assert_eq!(ffi_arg2.conversion, CppIndirectionChange::SpanToSliceRef);
+
+  let mut_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    ..type1.clone()
+  };
+  assert!(mut_ref.to_cpp_ffi_type(CppTypeRole::NotReturnType).is_err());
+
+  let pointers = CppType {
+    base: CppTypeBase::Span {
+      element: Box::new(CppType { indirection: CppTypeIndirection::Ptr, ..element.clone() }),
+    },
+    ..type1.clone()
+  };
+  assert!(pointers.to_cpp_ffi_type(CppTypeRole::NotReturnType).is_err());
+}
+
+#[test]
+fn std_function_argument() {
+  let int_type = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  let std_function = |function: CppFunctionPointerType| {
+    let function_type = CppType {
+      base: CppTypeBase::FunctionPointer(function),
+      ..int_type.clone()
+    };
+    CppType {
+      base: CppTypeBase::Class(CppTypeClassBase {
+                                 name: "std::function".to_string(),
+                                 template_arguments: Some(vec![function_type]),
+                               }),
+      ..int_type.clone()
+    }
+  };
+  let type1 = std_function(CppFunctionPointerType {
+                             return_type: Box::new(CppType::void()),
+                             arguments: vec![int_type.clone()],
+                             allows_variadic_arguments: false,
+                             class_type: None,
+                           });
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::function< void (int) >");
+  let ffi_arg = type1.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+  assert_eq!(&ffi_arg.ffi_type.to_cpp_code(None).unwrap(), "cpp_utils_closure");
+  assert_eq!(ffi_arg.conversion, CppIndirectionChange::StdFunctionToClosure);
+  assert_eq!(&ffi_arg.original_type, &type1);
+
+  let const_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    ..type1.clone()
+  };
+  assert_eq!(const_ref.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap().conversion,
+             CppIndirectionChange::StdFunctionToClosure);
+
+  // return values and mutable references are passed as objects
+  assert_eq!(type1.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap().conversion,
+             CppIndirectionChange::ValueToPointer);
+  let mut_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    ..type1.clone()
+  };
+  assert_eq!(mut_ref.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap().conversion,
+             CppIndirectionChange::ReferenceToPointer);
+
+  // closures can't receive class arguments
+  let class_type = CppType {
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "Class1".to_string(),
+                               template_arguments: None,
+                             }),
+    ..int_type.clone()
+  };
+  let type2 = std_function(CppFunctionPointerType {
+                             return_type: Box::new(int_type.clone()),
+                             arguments: vec![class_type],
+                             allows_variadic_arguments: false,
+                             class_type: None,
+                           });
+  assert_eq!(type2.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap().conversion,
+             CppIndirectionChange::ValueToPointer);
+}
+
+#[test]
+fn initializer_list_return() {
+  let element = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+  };
+  let type1 = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::InitializerList { element: Box::new(element.clone()) },
+  };
+  assert_eq!(type1.base.is_class(), false);
+  assert_eq!(type1.base.is_initializer_list(), true);
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::initializer_list<int>");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Short).unwrap(),
+             "initializer_list");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Full).unwrap(),
+             "int_initializer_list");
+  assert!(type1.to_cpp_ffi_type(CppTypeRole::NotReturnType).is_err());
+  assert!(!type1.needs_allocation_place_variants());
+
+  let ffi_return = type1.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap();
+  assert_eq!(&ffi_return.ffi_type.to_cpp_code(None).unwrap(),
+             "cpp_utils_vec_out");
+  assert_eq!(ffi_return.conversion,
+             CppIndirectionChange::InitializerListToVecOut);
+  assert_eq!(&ffi_return.original_type, &type1);
+
+  let const_ref = CppType {
+    indirection: CppTypeIndirection::Ref,
+    is_const: true,
+    ..type1.clone()
+  };
+  assert!(const_ref.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+
+  let classes = CppType {
+    base: CppTypeBase::InitializerList {
+      element: Box::new(CppType {
+                          base: CppTypeBase::Class(CppTypeClassBase {
+                                                     name: "Class1".to_string(),
+                                                     template_arguments: None,
+                                                   }),
+                          ..element.clone()
+                        }),
+    },
+    ..type1.clone()
+  };
+  assert!(classes.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+}
+
+#[test]
+fn unique_ptr_return() {
+  let pointee = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::Class(CppTypeClassBase {
+                               name: "Class1".to_string(),
+                               template_arguments: None,
+                             }),
+  };
+  let type1 = CppType {
+    indirection: CppTypeIndirection::None,
+    is_const: false,
+    is_const2: false,
+    is_volatile: false,
+    base: CppTypeBase::UniquePtr { pointee: Box::new(pointee.clone()) },
+  };
+  assert_eq!(type1.base.is_class(), false);
+  assert_eq!(type1.base.is_unique_ptr(), true);
+  assert_eq!(type1.to_cpp_code(None).unwrap(), "std::unique_ptr<Class1>");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Short).unwrap(), "unique_ptr");
+  assert_eq!(type1.caption(TypeCaptionStrategy::Full).unwrap(),
+             "Class1_unique_ptr");
+  assert!(type1.to_cpp_ffi_type(CppTypeRole::NotReturnType).is_err());
+  assert!(!type1.needs_allocation_place_variants());
+
+  let ffi_return = type1.to_cpp_ffi_type(CppTypeRole::ReturnType).unwrap();
+  assert_eq!(&ffi_return.ffi_type.to_cpp_code(None).unwrap(), "Class1*");
+  assert_eq!(ffi_return.conversion, CppIndirectionChange::UniquePtrToPtr);
+  assert_eq!(&ffi_return.original_type, &type1);
+
+  let const_pointee = CppType {
+    base: CppTypeBase::UniquePtr {
+      pointee: Box::new(CppType { is_const: true, ..pointee.clone() }),
+    },
+    ..type1.clone()
+  };
+  assert!(const_pointee.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+
+  let numbers = CppType {
+    base: CppTypeBase::UniquePtr {
+      pointee: Box::new(CppType {
+                          base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
+                          ..pointee.clone()
+                        }),
+    },
+    ..type1.clone()
+  };
+  assert!(numbers.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+
+  let reference = CppType {
+    indirection: CppTypeIndirection::Ref,
+    ..type1.clone()
+  };
+  assert!(reference.to_cpp_ffi_type(CppTypeRole::ReturnType).is_err());
+}
+
 #[test]
 fn class_const_ref() {
   let type1 = CppType {
     indirection: CppTypeIndirection::Ref,
     is_const: true,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QRectF".to_string(),
                                template_arguments: None,
@@ -311,6 +646,7 @@ fn class_const_ref() {
                   indirection: CppTypeIndirection::Ptr,
                   is_const: true,
                   is_const2: false,
+                  is_volatile: false,
                   base: CppTypeBase::Class(CppTypeClassBase {
                                              name: "QRectF".to_string(),
                                              template_arguments: None,
@@ -328,6 +664,7 @@ fn class_mut_ref() {
     indirection: CppTypeIndirection::Ref,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QRectF".to_string(),
                                template_arguments: None,
@@ -355,6 +692,7 @@ fn class_mut_ref() {
                   indirection: CppTypeIndirection::Ptr,
                   is_const: false,
                   is_const2: false,
+                  is_volatile: false,
                   base: CppTypeBase::Class(CppTypeClassBase {
                                              name: "QRectF".to_string(),
                                              template_arguments: None,
@@ -372,6 +710,7 @@ fn class_mut_ptr() {
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QObject".to_string(),
                                template_arguments: None,
@@ -401,6 +740,7 @@ fn class_with_template_args() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "QString".to_string(),
                                                     template_arguments: None,
@@ -410,6 +750,7 @@ fn class_with_template_args() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QVector".to_string(),
                                template_arguments: args.clone(),
@@ -437,6 +778,7 @@ fn class_with_template_args() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: false,
                 is_const2: false,
+                is_volatile: false,
                 base: CppTypeBase::Class(CppTypeClassBase {
                                            name: "QVector".to_string(),
                                            template_arguments: args.clone(),
@@ -456,6 +798,7 @@ fn class_with_template_args() {
                 indirection: CppTypeIndirection::Ptr,
                 is_const: true,
                 is_const2: false,
+                is_volatile: false,
                 base: CppTypeBase::Class(CppTypeClassBase {
                                            name: "QVector".to_string(),
                                            template_arguments: args.clone(),
@@ -473,12 +816,14 @@ fn nested_template_cpp_code() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QHash".to_string(),
                                template_arguments: Some(vec![CppType {
                                       indirection: CppTypeIndirection::None,
                                       is_const: false,
                                       is_const2: false,
+                                      is_volatile: false,
                                       base: CppTypeBase::Class(CppTypeClassBase {
                                         name: "QString".to_string(),
                                         template_arguments: None,
@@ -488,12 +833,14 @@ fn nested_template_cpp_code() {
                                       indirection: CppTypeIndirection::None,
                                       is_const: false,
                                       is_const2: false,
+                                      is_volatile: false,
                                       base: CppTypeBase::Class(CppTypeClassBase {
                                         name: "QList".to_string(),
                                         template_arguments: Some(vec![CppType {
                                                       indirection: CppTypeIndirection::None,
                                                       is_const: false,
     is_const2: false,
+    is_volatile: false,
                                                       base: CppTypeBase::Class(CppTypeClassBase {
                                                         name: "QString".to_string(),
                                                         template_arguments: None,
@@ -515,6 +862,7 @@ fn qflags() {
                          indirection: CppTypeIndirection::None,
                          is_const: false,
                          is_const2: false,
+                         is_volatile: false,
                          base: CppTypeBase::Class(CppTypeClassBase {
                                                     name: "Qt::AlignmentFlag".to_string(),
                                                     template_arguments: None,
@@ -524,6 +872,7 @@ fn qflags() {
     indirection: CppTypeIndirection::None,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::Class(CppTypeClassBase {
                                name: "QFlags".to_string(),
                                template_arguments: args.clone(),
@@ -554,6 +903,7 @@ fn qflags() {
                   indirection: CppTypeIndirection::None,
                   is_const: false,
                   is_const2: false,
+                  is_volatile: false,
                   base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::UInt),
                 });
     assert_eq!(&ffi_type.ffi_type.to_cpp_code(None).unwrap(),
@@ -563,11 +913,46 @@ fn qflags() {
   assert!(!type1.needs_allocation_place_variants());
 }
 
+#[test]
+fn qchar() {
+  let qchar = |indirection: CppTypeIndirection, is_const: bool| {
+    CppType {
+      indirection: indirection,
+      is_const: is_const,
+      is_const2: false,
+      is_volatile: false,
+      base: CppTypeBase::Class(CppTypeClassBase {
+                                 name: "QChar".to_string(),
+                                 template_arguments: None,
+                               }),
+    }
+  };
+  for type1 in &[qchar(CppTypeIndirection::None, false),
+                 qchar(CppTypeIndirection::Ref, true)] {
+    let ffi_type = type1.to_cpp_ffi_type(CppTypeRole::NotReturnType).unwrap();
+    assert_eq!(&ffi_type.original_type, type1);
+    assert_eq!(&ffi_type.ffi_type.to_cpp_code(None).unwrap(),
+               "unsigned short");
+    assert_eq!(ffi_type.conversion, CppIndirectionChange::QCharToUShort);
+  }
+
+  // return values and mutable references keep the class type
+  let ffi_type = qchar(CppTypeIndirection::None, false)
+    .to_cpp_ffi_type(CppTypeRole::ReturnType)
+    .unwrap();
+  assert_eq!(ffi_type.conversion, CppIndirectionChange::ValueToPointer);
+  let ffi_type = qchar(CppTypeIndirection::Ref, false)
+    .to_cpp_ffi_type(CppTypeRole::NotReturnType)
+    .unwrap();
+  assert_eq!(ffi_type.conversion, CppIndirectionChange::ReferenceToPointer);
+}
+
 fn create_template_parameter_type() -> CppType {
   CppType {
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::TemplateParameter {
       nested_level: 0,
       index: 0,
@@ -602,25 +987,30 @@ fn function1() {
   let type1 = CppType {
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     indirection: CppTypeIndirection::None,
     base: CppTypeBase::FunctionPointer(CppFunctionPointerType {
       allows_variadic_arguments: false,
+      class_type: None,
       return_type: Box::new(CppType {
         indirection: CppTypeIndirection::None,
         is_const: false,
         is_const2: false,
+        is_volatile: false,
         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
       }),
       arguments: vec![CppType {
                         indirection: CppTypeIndirection::None,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Int),
                       },
                       CppType {
                         indirection: CppTypeIndirection::Ptr,
                         is_const: false,
                         is_const2: false,
+                        is_volatile: false,
                         base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
                       }],
     }),
@@ -653,6 +1043,7 @@ fn instantiate1() {
     indirection: CppTypeIndirection::Ref,
     is_const: true,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::TemplateParameter {
       nested_level: 0,
       index: 0,
@@ -662,6 +1053,7 @@ fn instantiate1() {
     indirection: CppTypeIndirection::Ptr,
     is_const: false,
     is_const2: false,
+    is_volatile: false,
     base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::Bool),
   };
   let r = type1.instantiate(0, &[type2]).unwrap();
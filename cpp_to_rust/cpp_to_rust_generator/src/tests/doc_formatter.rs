@@ -0,0 +1,84 @@
+use doc_formatter::*;
+use cpp_method::CppMethodDoc;
+use rust_info::{RustMethod, RustMethodArguments, RustMethodDocItem, RustMethodScope};
+use rust_type::RustName;
+
+#[test]
+fn html_to_markdown_formatting() {
+  let html = "<p>Returns <code>QList&lt;int&gt;</code> &amp; more.</p>\
+              <pre>QList&lt;int&gt; x;\n    x.append(1);</pre>\
+              <a name=\"details\"></a>\
+              <p>See <a href=\"http://example.com/other.html\">other</a>.</p>";
+  assert_eq!(html_to_markdown(html, &[], &DocLinks::new(), "qt_core"),
+             "Returns `QList<int>` &amp; more.\n\n\
+              ```cpp\nQList<int> x;\n    x.append(1);\n```\n\n\
+              See [other](http://example.com/other.html).");
+}
+
+#[test]
+fn html_to_markdown_links() {
+  let mut links = DocLinks::new();
+  links.insert("http://doc.qt.io/qt-5/qobject.html".to_string(),
+               RustName::new(vec!["qt_core".to_string(),
+                                  "object".to_string(),
+                                  "Object".to_string()])
+                   .unwrap());
+  let html = "Uses <a href=\"http://doc.qt.io/qt-5/qobject.html#details\">QObject</a>.";
+  let cross_references = vec!["http://doc.qt.io/qt-5/qobject.html#details".to_string()];
+  assert_eq!(html_to_markdown(html, &cross_references, &links, "qt_core"),
+             "Uses [QObject](::object::Object).");
+  assert_eq!(html_to_markdown(html, &cross_references, &links, "qt_gui"),
+             "Uses [QObject](::qt_core::object::Object).");
+  assert_eq!(html_to_markdown(html, &[], &links, "qt_gui"),
+             "Uses [QObject](http://doc.qt.io/qt-5/qobject.html#details).");
+}
+
+#[test]
+fn method_doc_from_html() {
+  let doc_item = |mismatched_declaration: Option<String>| {
+    RustMethodDocItem {
+      doc: Some(CppMethodDoc {
+                  anchor: "size".to_string(),
+                  html: "<p>Returns the <b>number</b> of items.</p>".to_string(),
+                  mismatched_declaration: mismatched_declaration,
+                  url: "http://example.com/list.html#size".to_string(),
+                  cross_references: Vec::new(),
+                }),
+      rust_fns: Vec::new(),
+      cpp_fn: "int List::size() const".to_string(),
+      has_aliasing_references: false,
+      has_closure_arguments: false,
+      omitted_argument_defaults: Vec::new(),
+    }
+  };
+  let method = |doc_item: RustMethodDocItem| {
+    RustMethod {
+      scope: RustMethodScope::Free,
+      is_unsafe: false,
+      name: RustName::new(vec!["size".to_string()]).unwrap(),
+      arguments: RustMethodArguments::MultipleVariants {
+        params_trait_name: "SizeArgs".to_string(),
+        params_trait_lifetime: None,
+        common_return_type: None,
+        shared_arguments: Vec::new(),
+        variant_argument_name: "args".to_string(),
+        cpp_method_name: "List::size".to_string(),
+        variants: Vec::new(),
+      },
+      variant_docs: vec![doc_item],
+      common_doc: None,
+    }
+  };
+  let doc = method_doc(&method(doc_item(None)), &DocLinks::new(), "my_crate");
+  assert!(!doc.is_empty());
+  assert!(!doc.contains("<p>"));
+  assert!(!doc.contains("<b>"));
+  assert!(doc.contains("[C++ documentation](http://example.com/list.html#size):\n\n\
+                        Returns the number of items."));
+
+  let doc = method_doc(&method(doc_item(Some("int List::size()".to_string()))),
+                       &DocLinks::new(),
+                       "my_crate");
+  assert!(doc.contains("the closest match, `int List::size()`"));
+  assert!(doc.contains("Returns the number of items."));
+}
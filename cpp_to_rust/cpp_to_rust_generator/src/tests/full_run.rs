@@ -1,12 +1,17 @@
-use common::file_utils::{PathBufWithAdded, create_dir};
+use common::file_utils::{PathBufWithAdded, create_dir, create_file, file_to_string, load_json,
+                         load_toml};
 use common::utils::{run_command, add_env_path_item};
 use common::cpp_lib_builder::{CppLibBuilder, BuildType};
 use common::errors::fancy_unwrap;
-use config::{Config, CrateProperties, CacheUsage};
+use config::{Config, CrateProperties, CacheUsage, CppTypeAllocationPlace};
+use rust_api_lints::RustApiLint;
+use rust_info::{RustExportInfoJson, RUST_EXPORT_INFO_FORMAT_VERSION};
 use common::cpp_build_config::CppBuildConfigData;
 use common::target;
+use common::toml;
 use std::process::Command;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use tests::TempTestDir;
 
 fn build_cpp_lib() -> TempTestDir {
@@ -45,7 +50,16 @@ fn full_run() {
   let crate_dir = temp_dir.path().with_added("crate");
   let cpp_install_lib_dir = temp_dir.path().with_added("install").with_added("lib");
   assert!(cpp_install_lib_dir.exists());
-  let crate_properties = CrateProperties::new("rust_ctrt1", "0.0.0");
+  let mut crate_properties = CrateProperties::new("rust_ctrt1", "0.0.0");
+  crate_properties.set_custom_fields({
+    let mut dev_dependencies = toml::Table::new();
+    dev_dependencies.insert("serde_json".to_string(),
+                            toml::Value::String("0.9".to_string()));
+    let mut table = toml::Table::new();
+    table.insert("dev-dependencies".to_string(),
+                 toml::Value::Table(dev_dependencies));
+    table
+  });
 
   let mut config = Config::new(&crate_dir,
                                temp_dir.path().with_added("cache"),
@@ -80,21 +94,282 @@ fn full_run() {
   {
     let mut data = CppBuildConfigData::new();
     data.add_compiler_flag("-fPIC");
-    data.add_compiler_flag("-std=gnu++11");
+    data.add_compiler_flag("-std=gnu++20");
     config
       .cpp_build_config_mut()
       .add(target::Condition::Env(target::Env::Msvc).negate(), data);
   }
+  {
+    // `std::span` requires C++20
+    let mut data = CppBuildConfigData::new();
+    data.add_compiler_flag("/std:c++20");
+    config
+      .cpp_build_config_mut()
+      .add(target::Condition::Env(target::Env::Msvc), data);
+  }
   if target::current_env() == target::Env::Msvc {
-    config.add_cpp_parser_argument("-std=c++14");
+    config.add_cpp_parser_argument("-std=c++20");
   } else {
-    config.add_cpp_parser_argument("-std=gnu++11");
+    config.add_cpp_parser_argument("-std=gnu++20");
   }
   config.set_crate_template_path(&crate_template_path);
+  config.add_serde_enum("Ctrt1Color");
+  config.add_enum_value_migration("Ctrt1Color", 1, &[0, 1, 2], &[1, 2, 4]);
+  config.set_enums_non_exhaustive(true);
+  config.add_string_buffer_out("ctrt1_repeat_text", "buf", "buf_len");
+  config.add_string_buffer_out("ctrt1_user_name", "buf", "buf_len");
+  config.add_array_out_param("Ctrt1Transform::getMatrix", "out");
+  config.set_track_caller(true);
+  config.set_tracing_spans(true);
+  config.add_computed_type_constant("Ctrt1Token",
+                                    "registrationId",
+                                    "Ctrt1Token::registerType()");
+  config.add_template_method_instantiation("PolyHolder::findChild", vec!["PolyDerived*"]);
+  config.add_slicing_conversion("SlicingDerived", "SlicingBase");
+  config.add_functor_template_instantiation("IntList::sort", "bool", vec!["int", "int"]);
+  config.add_batch_operation("IntList::append");
+  config.add_virtual_clone("Ctrt1Shape", "clone");
+  config.add_virtual_clone("Ctrt1Square", "clone");
+  config.add_owned_ptr_return("Ctrt1Connection::open");
+  config.set_type_allocation_place(CppTypeAllocationPlace::Heap, "Ctrt1Handle");
+  config.add_fallible_constructor("Ctrt1Handle::Ctrt1Handle", vec!["int"], "object->isValid()");
+  config.add_exposed_protected_method("Ctrt1Widget::paintEvent");
+  config.add_exposed_protected_method("Ctrt1Widget::paintCount");
+  config.add_exposed_protected_method("Ctrt1Widget::paintedArea");
+  config.add_exposed_protected_method("Ctrt1Widget::repaint");
+  config.add_return_lifetime("Ctrt1Axis::coordinate", 0);
+  config.add_variadic_slice_function("ctrt1_sum_ints", "int", 4);
+  config.add_errno_method("ctrt1_checked_divide", "-1", "errno");
+  config.set_type_allocation_place(CppTypeAllocationPlace::Stack, "Ctrt1Builder");
+  config.add_allocation_benchmark("Ctrt1Builder");
+  config.add_ownership_transfer_arg("Ctrt1Layout::addWidget", 0);
+  config.set_type_allocation_place(CppTypeAllocationPlace::Stack, "Ctrt1Query");
+  config.add_consuming_method("Ctrt1Query::withLimit");
+  config.add_consuming_method("Ctrt1Query::withOffset");
+  config.set_type_allocation_place(CppTypeAllocationPlace::Stack, "Ctrt1Cursor");
+  config.set_type_allocation_place(CppTypeAllocationPlace::Stack, "std::map");
+  config.set_type_allocation_place(CppTypeAllocationPlace::Stack, "std::unordered_map");
+  config.add_cpp_feature("extra", vec!["CTRT1_EXTRA".to_string()]);
   config.set_cache_usage(CacheUsage::None);
   fancy_unwrap(config.exec());
   assert!(crate_dir.exists());
 
+  let ffi_symbols: Vec<String> = load_json(crate_dir.with_added("ffi_symbols.json")).unwrap();
+  let ffi_code = file_to_string(crate_dir.with_added("src").with_added("ffi.in.rs")).unwrap();
+  let mut ffi_functions: Vec<_> = ffi_code
+    .lines()
+    .filter_map(|line| if line.starts_with("  pub fn ") {
+                  line[9..].split('(').next().map(|name| name.to_string())
+                } else {
+                  None
+                })
+    .collect();
+  ffi_functions.sort();
+  assert!(!ffi_symbols.is_empty());
+  // extern "C" functions are bound directly and are not exported by the wrapper library
+  assert!(ffi_functions.iter().any(|name| name == "ctrt1_c_add"));
+  assert!(!ffi_symbols.iter().any(|name| name == "ctrt1_c_add"));
+  assert_eq!(ffi_symbols,
+             ffi_functions
+               .iter()
+               .filter(|name| *name != "ctrt1_c_add")
+               .cloned()
+               .collect::<Vec<_>>());
+  assert!(ffi_functions.iter().any(|name| name.contains("Ctrt1Widget_paintEvent")));
+  assert!(!ffi_functions.iter().any(|name| name.contains("Ctrt1Widget_resetPaintCount")));
+  // a pointer to a protected member can't omit arguments with default values
+  assert_eq!(ffi_functions
+               .iter()
+               .filter(|name| name.contains("Ctrt1Widget_repaint"))
+               .count(),
+             1);
+
+  let export_info: RustExportInfoJson =
+    load_json(crate_dir.with_added("rust_export_info.json")).unwrap();
+  assert_eq!(export_info.format_version, RUST_EXPORT_INFO_FORMAT_VERSION);
+  assert_eq!(export_info.crate_name, "rust_ctrt1");
+  assert_eq!(export_info.crate_version, "0.0.0");
+  assert!(export_info
+            .types
+            .iter()
+            .any(|t| {
+                   t.cpp_name == "Ctrt1Size" &&
+                   t.rust_name == "::rust_ctrt1::printable::Ctrt1Size" &&
+                   t.kind == "struct" && t.cpp_template_arguments.is_none()
+                 }));
+  assert!(export_info
+            .types
+            .iter()
+            .any(|t| t.cpp_name == "IntList" && t.rust_name == "::rust_ctrt1::int_list::IntList"));
+
+  let api_lints: BTreeMap<String, Vec<RustApiLint>> =
+    load_json(crate_dir.with_added("api_lints.json")).unwrap();
+  assert_eq!(api_lints.get("<::poly_base::PolyBase as \
+                            ::cpp_utils::UnsafeStaticCast<::poly_base::PolyDerived>>::static_cast"),
+             Some(&vec![RustApiLint::UnsafeStaticCast]));
+  assert_eq!(api_lints.get("::poly_base::PolyHolder::find_child_poly_derived"),
+             Some(&vec![RustApiLint::RawPointerReturn]));
+  assert!(api_lints
+            .keys()
+            .all(|key| !key.contains("StaticCast<") || key.contains("UnsafeStaticCast<")));
+
+  let bench_code = file_to_string(crate_dir
+                                    .with_added("benches")
+                                    .with_added("allocation_places.rs"))
+      .unwrap();
+  assert!(bench_code.contains("report(\"Ctrt1Builder\", as_struct, as_box);"));
+  assert!(bench_code.contains("mem::zeroed()"));
+  assert!(!bench_code.contains("mem::uninitialized()"));
+  let cargo_toml = load_toml(crate_dir.with_added("Cargo.toml")).unwrap();
+  let benches = cargo_toml
+    .get("bench")
+    .and_then(|value| value.as_slice())
+    .unwrap();
+  assert_eq!(benches.len(), 1);
+  let bench = benches[0].as_table().unwrap();
+  assert_eq!(bench.get("name").and_then(|value| value.as_str()),
+             Some("allocation_places"));
+  assert_eq!(bench.get("harness").and_then(|value| value.as_bool()), Some(false));
+
+  let enums_code = file_to_string(crate_dir.with_added("src").with_added("enums.rs")).unwrap();
+  assert!(enums_code.contains("#[non_exhaustive]\n#[derive(Debug, PartialEq, Eq, Clone)]\n\
+                               #[repr(C)]\npub enum Ctrt1Color"));
+  // values of Ctrt1Large don't fit in i32
+  assert!(enums_code.contains("#[repr(i64)]\npub enum Ctrt1Large"));
+  assert!(enums_code.contains("TryFrom<i64> for Ctrt1Large"));
+  assert!(enums_code.contains("From<Ctrt1Large> for i64"));
+  assert!(!enums_code.contains("TryFrom<i32> for Ctrt1Large"));
+
+  // the generated code may be wrapped by rustfmt at any point
+  let fallible_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("fallible.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(fallible_code.contains("->::std::result::Result<::cpp_utils::CppBox<\
+                                  ::fallible::Ctrt1Handle>,::cpp_utils::ConstructionError>"));
+
+  let checked_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("checked.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(checked_code.contains("->::std::result::Result<::libc::c_int,::cpp_utils::CppError>"));
+
+  // methods taking a member function pointer accept a closure wrapper
+  let callbacks_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("callbacks.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(callbacks_code.contains("pubstructMemberCallbackCounterVoid<'a>"));
+  assert!(callbacks_code.contains("FnMut(::libc::c_int)->::libc::c_int"));
+
+  // arrays filled by the callee are returned by value if configured
+  let arrays_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("arrays.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(arrays_code.contains("pubfnget_matrix(&self)->[::libc::c_float;16]"));
+  assert!(arrays_code.contains("pubfncopy_matrix(&self,out:&mut[::libc::c_float;16])"));
+
+  // `std::function` arguments accept closures owned by the C++ side
+  let functions_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("functions.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(functions_code.contains("callback:implFnMut(::libc::c_int)+Send+'static"));
+  assert!(functions_code.contains("func:implFnMut(::libc::c_int)->::libc::c_int+Send+'static"));
+  assert!(functions_code.contains("::cpp_utils::Closure::new(Box::new(func)asBox<FnMut(\
+                                   ::libc::c_int)->::libc::c_int+Send>"));
+  assert!(functions_code.contains("::cpp_utils::Closure::with_data(data,"));
+
+  // overloads returning different types are not named as a `_mut` pair
+  let mixed_slot_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("mixed_slot.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(mixed_slot_code.contains("fnvalue_int(&self)"));
+  assert!(mixed_slot_code.contains("fnvalue_double(&mutself)"));
+  assert!(!mixed_slot_code.contains("fnvalue_mut("));
+
+  // `Display` is implemented only for types with a stream operator
+  let printable_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("printable.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(printable_code.contains("impl::std::fmt::Displayfor::printable::Ctrt1Size"));
+  assert!(!mixed_slot_code.contains("::std::fmt::Display"));
+
+  // `operator bool` and `operator!` together produce validity checks
+  let validity_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("validity.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(validity_code.contains("pubfnis_valid(&self)->bool"));
+  assert!(validity_code.contains("pubfnis_null(&self)->bool"));
+  assert!(validity_code.contains("pubfninto_option(self)->Option<Self>"));
+
+  // elements of a returned initializer list are copied to an owned vector
+  let initializer_lists_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("initializer_lists.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(initializer_lists_code.contains("fnctrt1_small_primes()->Vec<::libc::c_int>"));
+  assert!(initializer_lists_code.contains("::cpp_utils::VecOut::new(&mutobject)"));
+
+  // `QChar` arguments accept a Rust `char`
+  let characters_code: String =
+    file_to_string(crate_dir.with_added("src").with_added("characters.rs"))
+      .unwrap()
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect();
+  assert!(characters_code.contains("pubfnset_value(&mutself,value:char)"));
+  assert!(characters_code.contains("pubfnset_value_by_ref(&mutself,value:char)"));
+
+  // a reference into a consumed object can't be given a safe lifetime
+  let chain_module_code =
+    file_to_string(crate_dir.with_added("src").with_added("chain.rs")).unwrap();
+  assert!(chain_module_code.contains("fn settings("));
+  assert!(!chain_module_code.contains("fn settings_of("));
+
+  // only functions that may panic are annotated with `#[track_caller]`
+  let utils_code: String = file_to_string(crate_dir.with_added("src").with_added("utils.rs"))
+    .unwrap()
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .collect();
+  assert!(utils_code.contains("#[track_caller]pubfnctrt1_user_name("));
+  assert!(utils_code.contains("pubfnctrt1_clamp<"));
+  assert!(!utils_code.contains("#[track_caller]pubfnctrt1_clamp<"));
+
+  let features_module_code =
+    file_to_string(crate_dir.with_added("src").with_added("features.rs")).unwrap();
+  let features_lines: Vec<_> = features_module_code.lines().map(|line| line.trim()).collect();
+  let gated_functions: Vec<_> = features_lines
+    .windows(2)
+    .filter(|lines| lines[0] == "#[cfg(feature = \"extra\")]")
+    .map(|lines| lines[1])
+    .collect();
+  assert_eq!(gated_functions.len(), 1);
+  assert!(gated_functions[0].starts_with("pub fn extra("));
+
   for cargo_cmd in &["update", "build", "test", "doc"] {
     let mut command = Command::new("cargo");
     command.arg(cargo_cmd);
@@ -106,13 +381,117 @@ fn full_run() {
     //      command.arg("--");
     //      command.arg("--nocapture");
     //    }
-    command.current_dir(&crate_dir);
-    command.env("CPP_TO_RUST_INCLUDE_PATHS", &include_path);
-    command.env("CPP_TO_RUST_LIB_PATHS", &cpp_install_lib_dir);
-    command.env("PATH",
-                add_env_path_item("PATH", vec![cpp_install_lib_dir.clone()]).unwrap());
-    command.env("LD_LIBRARY_PATH",
-                add_env_path_item("LD_LIBRARY_PATH", vec![cpp_install_lib_dir.clone()]).unwrap());
+    configure_crate_command(&mut command, &crate_dir, &include_path, &cpp_install_lib_dir);
+    run_command(&mut command).unwrap();
+  }
+  {
+    let mut command = Command::new("cargo");
+    command.arg("test").arg("-v").arg("-j1").arg("--features").arg("extra tracing");
+    configure_crate_command(&mut command, &crate_dir, &include_path, &cpp_install_lib_dir);
     run_command(&mut command).unwrap();
   }
+
+  // a match in another crate must have a wildcard arm
+  let client_crate_dir = temp_dir.path().with_added("client_crate");
+  create_dir(&client_crate_dir).unwrap();
+  create_dir(client_crate_dir.with_added("src")).unwrap();
+  create_file(client_crate_dir.with_added("Cargo.toml"))
+    .unwrap()
+    .write(format!("[package]\nname = \"ctrt1_client\"\nversion = \"0.0.0\"\n\n\
+                    [dependencies]\nrust_ctrt1 = {{ path = {:?} }}\n",
+                   crate_dir.display().to_string()))
+    .unwrap();
+  let match_code = |wildcard_arm: &str| {
+    format!("extern crate rust_ctrt1;\n\
+             use rust_ctrt1::enums::Ctrt1Color;\n\
+             fn main() {{\n\
+             let value = match Ctrt1Color::Green {{\n\
+             Ctrt1Color::Red => 1,\n\
+             Ctrt1Color::Green => 2,\n\
+             Ctrt1Color::Blue => 3,\n\
+             {}\n\
+             }};\n\
+             println!(\"{{}}\", value);\n\
+             }}\n",
+            wildcard_arm)
+  };
+  let build_client_crate = |code: String| {
+    create_file(client_crate_dir.with_added("src").with_added("main.rs"))
+      .unwrap()
+      .write(code)
+      .unwrap();
+    let mut command = Command::new("cargo");
+    command.arg("build");
+    configure_crate_command(&mut command, &client_crate_dir, &include_path, &cpp_install_lib_dir);
+    run_command(&mut command)
+  };
+  assert!(build_client_crate(match_code("_ => 0,")).is_ok());
+  assert!(build_client_crate(match_code("")).is_err());
+
+  // a reference returned by a chained getter can't outlive the temporary
+  let chain_code = |statements: &str| {
+    format!("extern crate rust_ctrt1;\n\
+             use rust_ctrt1::chain::Ctrt1Document;\n\
+             fn main() {{\n\
+             {}\n\
+             println!(\"{{}}\", settings.value());\n\
+             }}\n",
+            statements)
+  };
+  assert!(build_client_crate(chain_code("let document = Ctrt1Document::new(1);\n\
+                                        let settings = document.config().settings();"))
+            .is_ok());
+  assert!(build_client_crate(chain_code("let settings = \
+                                        Ctrt1Document::new(1).config().settings();"))
+            .is_err());
+
+  // a reference returned by `Ctrt1Axis::coordinate` borrows from its `point` argument
+  let axis_code = |inner_statements: &str, outer_statements: &str| {
+    format!("extern crate rust_ctrt1;\n\
+             use rust_ctrt1::axis::Ctrt1Axis;\n\
+             use rust_ctrt1::point::Ctrt1Point;\n\
+             fn main() {{\n\
+             let axis = Ctrt1Axis::new(false);\n\
+             let x;\n\
+             {{\n\
+             let point = Ctrt1Point::new();\n\
+             x = axis.coordinate(&point);\n\
+             {}\n\
+             }}\n\
+             {}\n\
+             }}\n",
+            inner_statements,
+            outer_statements)
+  };
+  assert!(build_client_crate(axis_code("println!(\"{}\", x);", "")).is_ok());
+  assert!(build_client_crate(axis_code("", "println!(\"{}\", x);")).is_err());
+
+  // closures passed to `std::function` arguments must be `Send`
+  let closure_code = |pointer_type: &str| {
+    format!("extern crate rust_ctrt1;\n\
+             use rust_ctrt1::functions::ctrt1_for_each_digit;\n\
+             fn main() {{\n\
+             let digits = {}::new(::std::sync::Mutex::new(Vec::new()));\n\
+             let digits2 = digits.clone();\n\
+             ctrt1_for_each_digit(42, move |digit| digits2.lock().unwrap().push(digit));\n\
+             println!(\"{{:?}}\", digits);\n\
+             }}\n",
+            pointer_type)
+  };
+  assert!(build_client_crate(closure_code("::std::sync::Arc")).is_ok());
+  assert!(build_client_crate(closure_code("::std::rc::Rc")).is_err());
+}
+
+fn configure_crate_command(command: &mut Command,
+                           crate_dir: &Path,
+                           include_path: &Path,
+                           cpp_install_lib_dir: &Path) {
+  command.current_dir(crate_dir);
+  command.env("CPP_TO_RUST_INCLUDE_PATHS", include_path);
+  command.env("CPP_TO_RUST_LIB_PATHS", cpp_install_lib_dir);
+  command.env("PATH",
+              add_env_path_item("PATH", vec![cpp_install_lib_dir.to_path_buf()]).unwrap());
+  command.env("LD_LIBRARY_PATH",
+              add_env_path_item("LD_LIBRARY_PATH", vec![cpp_install_lib_dir.to_path_buf()])
+                .unwrap());
 }
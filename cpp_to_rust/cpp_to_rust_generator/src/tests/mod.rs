@@ -1,8 +1,12 @@
 mod cpp_type;
-mod cpp_method;
+pub mod cpp_method;
+mod cpp_data;
 mod cpp_ffi_data;
+pub mod cpp_ffi_generator;
+mod cpp_code_generator;
 mod cpp_operator;
 mod cpp_parser;
+mod doc_formatter;
 mod full_run;
 
 use std::path::{Path, PathBuf};
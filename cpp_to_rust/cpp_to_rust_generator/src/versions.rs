@@ -15,3 +15,9 @@ pub const CPP_UTILS_VERSION: &'static str = "0.2.0";
 
 /// Version of `libc` crate.
 pub const LIBC_VERSION: &'static str = "0.2";
+
+/// Version of `serde` crate.
+pub const SERDE_VERSION: &'static str = "0.9";
+
+/// Version of `tracing` crate.
+pub const TRACING_VERSION: &'static str = "0.1.22";
@@ -10,11 +10,115 @@
 // placement new statements require this
 #include <new>
 
+// for std::move
+#include <utility>
+
+// for size_t
+#include <stddef.h>
+
+// for std::abort
+#include <cstdlib>
+
+// for std::memcpy
+#include <cstring>
+
+// for std::ostringstream used by stream operator wrappers
+#include <sstream>
+
+// for std::initializer_list return values
+#include <initializer_list>
+
+// for std::copy
+#include <algorithm>
+
+// for std::function arguments
+#include <functional>
+
+// for std::shared_ptr owning closures passed to std::function arguments
+#include <memory>
+
 // original C++ library includes generated by cpp_to_rust
 {include_directives_code}
 
 #include "{lib_name_lowercase}_exports.h"
 
+#ifndef CPP_UTILS_STR_REF_DEFINED
+#define CPP_UTILS_STR_REF_DEFINED
+// Rust string slice passed to a `std::string_view` argument.
+// The layout must match `cpp_utils::StrRef`.
+struct cpp_utils_str_ref {{
+    const char* data;
+    size_t size;
+}};
+#endif
+
+#ifndef CPP_UTILS_SLICE_REF_DEFINED
+#define CPP_UTILS_SLICE_REF_DEFINED
+// Rust slice passed to a `std::span` argument.
+// The layout must match `cpp_utils::SliceRef`.
+struct cpp_utils_slice_ref {{
+    void* data;
+    size_t size;
+}};
+#endif
+
+#ifndef CPP_UTILS_VEC_OUT_DEFINED
+#define CPP_UTILS_VEC_OUT_DEFINED
+// Rust vector receiving elements of a returned `std::initializer_list`.
+// `reserve` clears the vector and returns a pointer to space for `size` elements,
+// and `set_len` sets the vector's length after the elements are written.
+// The layout must match `cpp_utils::VecOut`.
+struct cpp_utils_vec_out {{
+    void* vec;
+    void* (*reserve)(void* vec, size_t size);
+    void (*set_len)(void* vec, size_t size);
+}};
+
+// Copies elements of `list` to the Rust vector while
+// the list's backing array is still alive.
+template<typename T>
+void cpp_utils_copy_to_vec_out(cpp_utils_vec_out output, std::initializer_list<T> list) {{
+    T* data = static_cast<T*>(output.reserve(output.vec, list.size()));
+    std::copy(list.begin(), list.end(), data);
+    output.set_len(output.vec, list.size());
+}}
+#endif
+
+#ifndef CPP_UTILS_CLOSURE_DEFINED
+#define CPP_UTILS_CLOSURE_DEFINED
+// Rust closure passed to a `std::function` argument.
+// `callback` is called with `data` as the first argument,
+// and `deleter` frees `data` when the closure is no longer used.
+// The layout must match `cpp_utils::Closure`.
+struct cpp_utils_closure {{
+    void* callback;
+    void* data;
+    void (*deleter)(void* data);
+}};
+
+template<typename Signature>
+class cpp_utils_closure_function;
+
+// Function object calling a Rust closure. Copies of the object
+// share ownership of the closure, so it's kept alive until
+// the last `std::function` holding it is destroyed.
+template<typename R, typename... Args>
+class cpp_utils_closure_function<R (Args...)> {{
+public:
+    explicit cpp_utils_closure_function(cpp_utils_closure closure)
+        : m_callback(reinterpret_cast<R (*)(void*, Args...)>(closure.callback)),
+          m_data(closure.data, closure.deleter) {{ }}
+
+    R operator()(Args... args) const {{
+        return m_callback(m_data.get(), args...);
+    }}
+
+private:
+    R (*m_callback)(void*, Args...);
+    std::shared_ptr<void> m_data;
+}};
+#endif
+
 // Calls destructor of `T` class. This template function
 // is necessary because it's not possible to use `x->~T()`
 // syntax directly if `T` contains `::`.
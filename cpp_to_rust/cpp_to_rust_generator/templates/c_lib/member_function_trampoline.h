@@ -0,0 +1,21 @@
+// Class generated by cpp_to_rust.
+// See the template at "cpp_to_rust/cpp_to_rust_generator/templates/c_lib/member_function_trampoline.h".
+class {class_name} : public {base_class} {{
+public:
+  {class_name}() : m_func(0), m_data(0) {{ }}
+  void set({func_arg}, void* data) {{
+    m_func = func;
+    m_data = data;
+  }}
+
+  {return_type} call({method_args}) {{
+    if (m_func) {{
+      return m_func({func_args});
+    }}
+    return {return_type}();
+  }}
+
+private:
+  {func_field};
+  void* m_data;
+}};
@@ -0,0 +1,9 @@
+// Class generated by cpp_to_rust.
+// See the template at "cpp_to_rust/cpp_to_rust_generator/templates/c_lib/protected_access.h".
+// Objects of this class are never created. It only makes protected methods
+// of the base class accessible from the wrapper functions.
+class {class_name} : public {base_class} {{
+public:
+{using_declarations}
+}};
+
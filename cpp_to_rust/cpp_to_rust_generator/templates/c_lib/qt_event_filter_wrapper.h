@@ -0,0 +1,21 @@
+// Class generated by cpp_to_rust.
+// See the template at "cpp_to_rust/cpp_to_rust_generator/templates/c_lib/qt_event_filter_wrapper.h".
+class {class_name} : public QObject {{
+public:
+  {class_name}() : m_func(0), m_data(0) {{ }}
+  void set({func_arg}, void* data) {{
+    m_func = func;
+    m_data = data;
+  }}
+
+  bool eventFilter(QObject* watched, QEvent* event) {{
+    if (m_func) {{
+      return m_func(m_data, watched, event);
+    }}
+    return false;
+  }}
+
+private:
+  {func_field};
+  void* m_data;
+}};
@@ -1,2 +1,40 @@
 #include "ctrt1/utils.h"
 #include "ctrt1/class1.h"
+#include "ctrt1/enums.h"
+#include "ctrt1/allocated1.h"
+#include "ctrt1/int_list.h"
+#include "ctrt1/functions.h"
+#include "ctrt1/poly_base.h"
+#include "ctrt1/arrays.h"
+#include "ctrt1/point.h"
+#include "ctrt1/slicing.h"
+#include "ctrt1/versioned.h"
+#include "ctrt1/movable.h"
+#include "ctrt1/prototype.h"
+#include "ctrt1/string_views.h"
+#include "ctrt1/lazy_value.h"
+#include "ctrt1/factory.h"
+#include "ctrt1/widget.h"
+#include "ctrt1/vector3.h"
+#include "ctrt1/axis.h"
+#include "ctrt1/variants.h"
+#include "ctrt1/variadic.h"
+#include "ctrt1/inheritance.h"
+#include "ctrt1/chain.h"
+#include "ctrt1/builder.h"
+#include "ctrt1/layout.h"
+#include "ctrt1/features.h"
+#include "ctrt1/spans.h"
+#include "ctrt1/query.h"
+#include "ctrt1/owned.h"
+#include "ctrt1/crtp.h"
+#include "ctrt1/fallible.h"
+#include "ctrt1/checked.h"
+#include "ctrt1/maps.h"
+#include "ctrt1/callbacks.h"
+#include "ctrt1/mixed_slot.h"
+#include "ctrt1/range.h"
+#include "ctrt1/printable.h"
+#include "ctrt1/initializer_lists.h"
+#include "ctrt1/validity.h"
+#include "ctrt1/characters.h"
@@ -0,0 +1,22 @@
+#ifndef CTRT1_ALLOCATED1_H
+#define CTRT1_ALLOCATED1_H
+
+#include "ctrt1/exports.h"
+#include <cstddef>
+
+class CTRT1_EXPORT Allocated1 {
+public:
+  Allocated1(int x);
+  int x() const;
+
+  static void* operator new(std::size_t size);
+  static void operator delete(void* ptr);
+
+  static int newCount();
+  static int deleteCount();
+
+private:
+  int m_x;
+};
+
+#endif // CTRT1_ALLOCATED1_H
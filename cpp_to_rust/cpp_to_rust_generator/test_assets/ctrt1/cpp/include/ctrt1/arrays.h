@@ -0,0 +1,29 @@
+#ifndef CTRT1_ARRAYS_H
+#define CTRT1_ARRAYS_H
+
+#include "ctrt1/exports.h"
+#include <array>
+
+class CTRT1_EXPORT Vector4 {
+public:
+  Vector4();
+  const int (&values() const)[4];
+  void setValues(const int (&values)[4]);
+
+  static std::array<int, 4> reversed(std::array<int, 4> values);
+
+private:
+  int m_values[4];
+};
+
+class CTRT1_EXPORT Ctrt1Transform {
+public:
+  explicit Ctrt1Transform(float scale);
+  void getMatrix(float (&out)[16]) const;
+  void copyMatrix(float (&out)[16]) const;
+
+private:
+  float m_scale;
+};
+
+#endif // CTRT1_ARRAYS_H
@@ -0,0 +1,16 @@
+#ifndef CTRT1_AXIS_H
+#define CTRT1_AXIS_H
+
+#include "ctrt1/exports.h"
+#include "ctrt1/point.h"
+
+class CTRT1_EXPORT Ctrt1Axis {
+public:
+  Ctrt1Axis(bool vertical);
+  const int& coordinate(const Ctrt1Point& point) const;
+
+private:
+  bool m_vertical;
+};
+
+#endif // CTRT1_AXIS_H
@@ -0,0 +1,19 @@
+#ifndef CTRT1_BUILDER_H
+#define CTRT1_BUILDER_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Builder {
+public:
+  Ctrt1Builder();
+  void append(int value);
+  int size() const &;
+  // consumes the collected values
+  int build() &&;
+
+private:
+  int m_count;
+  int m_sum;
+};
+
+#endif // CTRT1_BUILDER_H
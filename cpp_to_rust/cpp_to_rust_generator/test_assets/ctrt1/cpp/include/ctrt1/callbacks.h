@@ -0,0 +1,24 @@
+#ifndef CTRT1_CALLBACKS_H
+#define CTRT1_CALLBACKS_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Counter {
+public:
+  Counter();
+  void increment();
+  int add(int value);
+  int value() const;
+
+private:
+  int m_value;
+};
+
+// Calls `method` on `counter` `times` times.
+CTRT1_EXPORT void ctrt1_call_repeatedly(Counter* counter, void (Counter::*method)(), int times);
+
+// Returns the sum of results of calling `method` on `counter`
+// with each value from 0 to `count - 1`.
+CTRT1_EXPORT int ctrt1_sum_mapped(Counter* counter, int (Counter::*method)(int), int count);
+
+#endif // CTRT1_CALLBACKS_H
@@ -0,0 +1,36 @@
+#ifndef CTRT1_CHAIN_H
+#define CTRT1_CHAIN_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Settings {
+public:
+  Ctrt1Settings(int value);
+  int value() const;
+
+private:
+  int m_value;
+};
+
+class CTRT1_EXPORT Ctrt1Config {
+public:
+  Ctrt1Config(int value);
+  const Ctrt1Settings& settings() const;
+
+  // the returned reference points into the consumed object
+  static const Ctrt1Settings& settingsOf(Ctrt1Config&& config);
+
+private:
+  Ctrt1Settings m_settings;
+};
+
+class CTRT1_EXPORT Ctrt1Document {
+public:
+  Ctrt1Document(int value);
+  const Ctrt1Config& config() const;
+
+private:
+  Ctrt1Config m_config;
+};
+
+#endif // CTRT1_CHAIN_H
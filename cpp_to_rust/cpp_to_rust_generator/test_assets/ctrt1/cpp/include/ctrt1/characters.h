@@ -0,0 +1,26 @@
+#ifndef CTRT1_CHARACTERS_H
+#define CTRT1_CHARACTERS_H
+
+#include "ctrt1/exports.h"
+
+// Minimal replica of Qt's QChar. Arguments of this type
+// are converted to Rust `char` in the same way as in Qt crates.
+class CTRT1_EXPORT QChar {
+public:
+  QChar(unsigned short ucs);
+  unsigned short unicode() const;
+private:
+  unsigned short m_ucs;
+};
+
+class CTRT1_EXPORT Ctrt1CharBox {
+public:
+  Ctrt1CharBox();
+  void setValue(QChar value);
+  void setValueByRef(const QChar& value);
+  unsigned short code() const;
+private:
+  QChar m_value;
+};
+
+#endif // CTRT1_CHARACTERS_H
@@ -0,0 +1,10 @@
+#ifndef CTRT1_CHECKED_H
+#define CTRT1_CHECKED_H
+
+#include "ctrt1/exports.h"
+#include <cerrno>
+
+// Returns -1 and sets errno to EDOM if denominator is 0.
+CTRT1_EXPORT int ctrt1_checked_divide(int numerator, int denominator);
+
+#endif // CTRT1_CHECKED_H
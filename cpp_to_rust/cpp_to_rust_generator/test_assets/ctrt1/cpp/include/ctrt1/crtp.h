@@ -0,0 +1,23 @@
+#ifndef CTRT1_CRTP_H
+#define CTRT1_CRTP_H
+
+#include "ctrt1/exports.h"
+
+template<class Derived>
+class Ctrt1Counted {
+public:
+  int doubled() const {
+    return static_cast<const Derived*>(this)->count() * 2;
+  }
+};
+
+class CTRT1_EXPORT Ctrt1Apples : public Ctrt1Counted<Ctrt1Apples> {
+public:
+  Ctrt1Apples(int count);
+  int count() const;
+
+private:
+  int m_count;
+};
+
+#endif // CTRT1_CRTP_H
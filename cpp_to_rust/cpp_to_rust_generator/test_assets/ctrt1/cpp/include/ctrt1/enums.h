@@ -0,0 +1,42 @@
+#ifndef CTRT1_ENUMS_H
+#define CTRT1_ENUMS_H
+
+#include "ctrt1/exports.h"
+
+enum Ctrt1Color {
+  Red = 1,
+  Green = 2,
+  Blue = 4
+};
+
+enum Ctrt1Sparse {
+  SparseA = 1,
+  SparseB = 5,
+  SparseC = 100
+};
+
+enum Ctrt1Large : long long {
+  LargeSmall = 1,
+  LargeHuge = 0x100000000LL
+};
+
+namespace Ctrt1Shape {
+  enum Kind {
+    Circle = 1,
+    Square = 2
+  };
+}
+
+class CTRT1_EXPORT Ctrt1Brush {
+public:
+  enum Kind {
+    Solid = 1,
+    Hatched = 2
+  };
+  enum {
+    MaxSize = 256,
+    MinSize = 1
+  };
+};
+
+#endif // CTRT1_ENUMS_H
@@ -0,0 +1,19 @@
+#ifndef CTRT1_FACTORY_H
+#define CTRT1_FACTORY_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Connection {
+public:
+  static Ctrt1Connection* open(int port);
+  static Ctrt1Connection openLocal();
+  ~Ctrt1Connection();
+  int port() const;
+
+private:
+  Ctrt1Connection(int port);
+  Ctrt1Connection(const Ctrt1Connection& other);
+  int m_port;
+};
+
+#endif // CTRT1_FACTORY_H
@@ -0,0 +1,18 @@
+#ifndef CTRT1_FALLIBLE_H
+#define CTRT1_FALLIBLE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Handle {
+public:
+  Ctrt1Handle(int descriptor);
+  ~Ctrt1Handle();
+  bool isValid() const;
+  int descriptor() const;
+  static int liveCount();
+
+private:
+  int m_descriptor;
+};
+
+#endif // CTRT1_FALLIBLE_H
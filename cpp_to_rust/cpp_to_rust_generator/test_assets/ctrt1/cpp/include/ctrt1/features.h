@@ -0,0 +1,16 @@
+#ifndef CTRT1_FEATURES_H
+#define CTRT1_FEATURES_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Features {
+public:
+  Ctrt1Features();
+  int base() const;
+#ifdef CTRT1_EXTRA
+  // only available if the crate's "extra" feature is enabled
+  int extra() const;
+#endif
+};
+
+#endif // CTRT1_FEATURES_H
@@ -0,0 +1,24 @@
+#ifndef CTRT1_FUNCTIONS_H
+#define CTRT1_FUNCTIONS_H
+
+#include "ctrt1/exports.h"
+#include <functional>
+
+CTRT1_EXPORT std::function<int(int)> ctrt1_make_adder(int value);
+CTRT1_EXPORT void ctrt1_for_each_digit(int value, std::function<void(int)> callback);
+CTRT1_EXPORT int ctrt1_apply_twice(const std::function<int(int)>& func, int value);
+
+class CTRT1_EXPORT Ctrt1Notifier {
+public:
+  void setCallback(std::function<void(int)> callback);
+  void notify(int value) const;
+
+private:
+  std::function<void(int)> m_callback;
+};
+
+extern "C" {
+CTRT1_EXPORT int ctrt1_c_add(int a, int b);
+}
+
+#endif // CTRT1_FUNCTIONS_H
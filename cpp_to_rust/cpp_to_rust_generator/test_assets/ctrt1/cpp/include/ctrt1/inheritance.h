@@ -0,0 +1,35 @@
+#ifndef CTRT1_INHERITANCE_H
+#define CTRT1_INHERITANCE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Named {
+public:
+  Ctrt1Named(int id);
+  int id() const;
+  void setId(int id);
+
+private:
+  int m_id;
+};
+
+class CTRT1_EXPORT Ctrt1Sized {
+public:
+  Ctrt1Sized(int size);
+  int size() const;
+
+private:
+  int m_size;
+};
+
+class CTRT1_EXPORT Ctrt1Item : public Ctrt1Named {
+public:
+  Ctrt1Item(int id);
+};
+
+class CTRT1_EXPORT Ctrt1SizedItem : public Ctrt1Named, public Ctrt1Sized {
+public:
+  Ctrt1SizedItem(int id, int size);
+};
+
+#endif // CTRT1_INHERITANCE_H
@@ -0,0 +1,10 @@
+#ifndef CTRT1_INITIALIZER_LISTS_H
+#define CTRT1_INITIALIZER_LISTS_H
+
+#include "ctrt1/exports.h"
+#include <initializer_list>
+
+CTRT1_EXPORT std::initializer_list<int> ctrt1_small_primes();
+CTRT1_EXPORT std::initializer_list<int> ctrt1_no_values();
+
+#endif // CTRT1_INITIALIZER_LISTS_H
@@ -0,0 +1,36 @@
+#ifndef CTRT1_INT_LIST_H
+#define CTRT1_INT_LIST_H
+
+#include "ctrt1/exports.h"
+#include <algorithm>
+
+class CTRT1_EXPORT IntList {
+public:
+  IntList();
+  ~IntList();
+
+  void append(const int& value);
+  int size() const;
+  int at(int index) const;
+  int& first();
+  const int& first() const;
+  void reserve(int size);
+  int capacity() const;
+  int reserveCallCount() const;
+
+  template<class LessThan>
+  void sort(LessThan lessThan) {
+    std::sort(m_values, m_values + m_size, lessThan);
+  }
+
+private:
+  IntList(const IntList& other);
+  IntList& operator=(const IntList& other);
+
+  int* m_values;
+  int m_size;
+  int m_capacity;
+  int m_reserveCallCount;
+};
+
+#endif // CTRT1_INT_LIST_H
@@ -0,0 +1,23 @@
+#ifndef CTRT1_LAYOUT_H
+#define CTRT1_LAYOUT_H
+
+#include "ctrt1/exports.h"
+#include "ctrt1/widget.h"
+#include <vector>
+
+class CTRT1_EXPORT Ctrt1Layout {
+public:
+  Ctrt1Layout();
+  ~Ctrt1Layout();
+  // the layout takes ownership of the widget
+  void addWidget(Ctrt1Widget* widget);
+  int count() const;
+  void updateAll();
+  static int deletedWidgetsCount();
+
+private:
+  Ctrt1Layout(const Ctrt1Layout& other);
+  std::vector<Ctrt1Widget*> m_widgets;
+};
+
+#endif // CTRT1_LAYOUT_H
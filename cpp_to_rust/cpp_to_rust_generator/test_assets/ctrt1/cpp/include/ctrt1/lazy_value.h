@@ -0,0 +1,20 @@
+#ifndef CTRT1_LAZY_VALUE_H
+#define CTRT1_LAZY_VALUE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1LazyValue {
+public:
+  Ctrt1LazyValue(int base);
+  int value() const;
+  int computeCount() const;
+  void setBase(int base);
+
+private:
+  int m_base;
+  mutable int m_cachedValue;
+  mutable bool m_isCached;
+  mutable int m_computeCount;
+};
+
+#endif // CTRT1_LAZY_VALUE_H
@@ -0,0 +1,11 @@
+#ifndef CTRT1_MAPS_H
+#define CTRT1_MAPS_H
+
+#include "ctrt1/exports.h"
+#include <map>
+#include <unordered_map>
+
+CTRT1_EXPORT double ctrt1_sum_map_values(const std::map<int, double>& map);
+CTRT1_EXPORT std::unordered_map<int, int> ctrt1_squares(int count);
+
+#endif // CTRT1_MAPS_H
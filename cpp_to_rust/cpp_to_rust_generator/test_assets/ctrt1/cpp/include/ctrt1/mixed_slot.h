@@ -0,0 +1,19 @@
+#ifndef CTRT1_MIXED_SLOT_H
+#define CTRT1_MIXED_SLOT_H
+
+#include "ctrt1/exports.h"
+
+// The const and non-const overloads of `value` return different types,
+// so they are not a const/mutable pair.
+class CTRT1_EXPORT MixedSlot {
+public:
+  MixedSlot();
+  const int& value() const;
+  double& value();
+
+private:
+  int m_count;
+  double m_ratio;
+};
+
+#endif // CTRT1_MIXED_SLOT_H
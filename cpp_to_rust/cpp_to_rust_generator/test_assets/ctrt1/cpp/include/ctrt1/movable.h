@@ -0,0 +1,21 @@
+#ifndef CTRT1_MOVABLE_H
+#define CTRT1_MOVABLE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Movable {
+public:
+  Ctrt1Movable(int value);
+  ~Ctrt1Movable();
+  Ctrt1Movable& operator=(const Ctrt1Movable& other);
+  Ctrt1Movable& operator=(Ctrt1Movable&& other);
+  int value() const;
+  bool isMovedFrom() const;
+  static int movedFromDestroyedCount();
+
+private:
+  int m_value;
+  bool m_movedFrom;
+};
+
+#endif // CTRT1_MOVABLE_H
@@ -0,0 +1,20 @@
+#ifndef CTRT1_OWNED_H
+#define CTRT1_OWNED_H
+
+#include "ctrt1/exports.h"
+#include <memory>
+
+class CTRT1_EXPORT Ctrt1Resource {
+public:
+  static std::unique_ptr<Ctrt1Resource> create(int id);
+  ~Ctrt1Resource();
+  int id() const;
+  static int liveCount();
+
+private:
+  Ctrt1Resource(int id);
+  Ctrt1Resource(const Ctrt1Resource& other);
+  int m_id;
+};
+
+#endif // CTRT1_OWNED_H
@@ -0,0 +1,15 @@
+#ifndef CTRT1_POINT_H
+#define CTRT1_POINT_H
+
+#include "ctrt1/exports.h"
+#include "ctrt1/enums.h"
+
+class CTRT1_EXPORT Ctrt1Point {
+public:
+  Ctrt1Point();
+  int x;
+  int y;
+  Ctrt1Color color;
+};
+
+#endif // CTRT1_POINT_H
@@ -0,0 +1,37 @@
+#ifndef CTRT1_POLY_BASE_H
+#define CTRT1_POLY_BASE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT PolyBase {
+public:
+  virtual ~PolyBase();
+};
+
+class CTRT1_EXPORT PolyDerived : public PolyBase {
+public:
+  PolyDerived(int value);
+  ~PolyDerived();
+  int value() const;
+
+  static int destructorCallCount();
+
+private:
+  int m_value;
+  static int m_destructorCallCount;
+};
+
+class CTRT1_EXPORT PolyHolder {
+public:
+  PolyHolder(PolyBase* child);
+
+  template<typename T>
+  T findChild() const {
+    return dynamic_cast<T>(m_child);
+  }
+
+private:
+  PolyBase* m_child;
+};
+
+#endif // CTRT1_POLY_BASE_H
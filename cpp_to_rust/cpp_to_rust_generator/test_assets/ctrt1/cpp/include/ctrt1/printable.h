@@ -0,0 +1,21 @@
+#ifndef CTRT1_PRINTABLE_H
+#define CTRT1_PRINTABLE_H
+
+#include "ctrt1/exports.h"
+#include <ostream>
+
+class CTRT1_EXPORT Ctrt1Size {
+public:
+  Ctrt1Size(int width, int height);
+  int width() const;
+  int height() const;
+
+private:
+  int m_width;
+  int m_height;
+};
+
+// Writes the size as "<width>x<height>".
+CTRT1_EXPORT std::ostream& operator<<(std::ostream& stream, const Ctrt1Size& size);
+
+#endif // CTRT1_PRINTABLE_H
@@ -0,0 +1,29 @@
+#ifndef CTRT1_PROTOTYPE_H
+#define CTRT1_PROTOTYPE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Shape {
+public:
+  virtual ~Ctrt1Shape();
+  virtual Ctrt1Shape* clone() const = 0;
+  virtual int area() const = 0;
+
+protected:
+  Ctrt1Shape();
+  Ctrt1Shape(const Ctrt1Shape& other);
+};
+
+class CTRT1_EXPORT Ctrt1Square : public Ctrt1Shape {
+public:
+  Ctrt1Square(int side);
+  Ctrt1Shape* clone() const;
+  int area() const;
+  void setSide(int side);
+
+private:
+  Ctrt1Square(const Ctrt1Square& other);
+  int m_side;
+};
+
+#endif // CTRT1_PROTOTYPE_H
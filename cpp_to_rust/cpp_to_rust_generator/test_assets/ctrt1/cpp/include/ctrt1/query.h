@@ -0,0 +1,20 @@
+#ifndef CTRT1_QUERY_H
+#define CTRT1_QUERY_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Query {
+public:
+  Ctrt1Query();
+  // builder-style methods returning the object itself
+  Ctrt1Query& withLimit(int limit);
+  Ctrt1Query& withOffset(int offset);
+  int limit() const;
+  int offset() const;
+
+private:
+  int m_limit;
+  int m_offset;
+};
+
+#endif // CTRT1_QUERY_H
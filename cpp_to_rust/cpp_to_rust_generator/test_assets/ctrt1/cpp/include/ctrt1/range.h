@@ -0,0 +1,21 @@
+#ifndef CTRT1_RANGE_H
+#define CTRT1_RANGE_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Range {
+public:
+  Ctrt1Range(int start, int end);
+  // delegating constructors
+  Ctrt1Range(int end) : Ctrt1Range(0, end) {}
+  Ctrt1Range() : Ctrt1Range(0) {}
+
+  int start() const;
+  int end() const;
+
+private:
+  int m_start;
+  int m_end;
+};
+
+#endif // CTRT1_RANGE_H
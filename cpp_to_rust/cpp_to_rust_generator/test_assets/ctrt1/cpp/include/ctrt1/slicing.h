@@ -0,0 +1,24 @@
+#ifndef CTRT1_SLICING_H
+#define CTRT1_SLICING_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT SlicingBase {
+public:
+  SlicingBase(int x);
+  int x() const;
+
+private:
+  int m_x;
+};
+
+class CTRT1_EXPORT SlicingDerived : public SlicingBase {
+public:
+  SlicingDerived(int x, int y);
+  int y() const;
+
+private:
+  int m_y;
+};
+
+#endif // CTRT1_SLICING_H
@@ -0,0 +1,10 @@
+#ifndef CTRT1_SPANS_H
+#define CTRT1_SPANS_H
+
+#include "ctrt1/exports.h"
+#include <span>
+
+CTRT1_EXPORT int ctrt1_span_sum(std::span<const int> values);
+CTRT1_EXPORT void ctrt1_span_double(std::span<int> values);
+
+#endif // CTRT1_SPANS_H
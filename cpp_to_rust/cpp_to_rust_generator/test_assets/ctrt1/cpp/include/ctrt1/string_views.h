@@ -0,0 +1,10 @@
+#ifndef CTRT1_STRING_VIEWS_H
+#define CTRT1_STRING_VIEWS_H
+
+#include "ctrt1/exports.h"
+#include <string_view>
+
+CTRT1_EXPORT int ctrt1_text_size(std::string_view text);
+CTRT1_EXPORT int ctrt1_count_spaces(const std::string_view& text);
+
+#endif // CTRT1_STRING_VIEWS_H
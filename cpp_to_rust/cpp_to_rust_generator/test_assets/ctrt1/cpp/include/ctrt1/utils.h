@@ -2,9 +2,28 @@
 #define CTRT1_UTILS_H
 
 #include "ctrt1/exports.h"
+#include <cstddef>
 
 CTRT1_EXPORT const char* ctrt1_version();
 
 CTRT1_EXPORT int ctrt1_abs(int x);
 
+CTRT1_EXPORT size_t ctrt1_size_identity(size_t x);
+
+CTRT1_EXPORT std::ptrdiff_t ctrt1_ptrdiff_negate(std::ptrdiff_t x);
+
+CTRT1_EXPORT int ctrt1_repeat_text(int count, char* buf, int buf_len);
+// returns a negative value if `id` is invalid
+CTRT1_EXPORT int ctrt1_user_name(int id, char* buf, int buf_len);
+
+// overloaded function none of whose variants may panic
+CTRT1_EXPORT int ctrt1_clamp(int value, int max);
+CTRT1_EXPORT int ctrt1_clamp(int value, int min, int max);
+
+class CTRT1_EXPORT Ctrt1Token {
+public:
+  static int registerType();
+  static int registrationCount();
+};
+
 #endif // CTRT1_UTILS_H
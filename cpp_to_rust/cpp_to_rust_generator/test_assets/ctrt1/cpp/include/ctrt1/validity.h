@@ -0,0 +1,17 @@
+#ifndef CTRT1_VALIDITY_H
+#define CTRT1_VALIDITY_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Cursor {
+public:
+  Ctrt1Cursor(int position);
+  int position() const;
+  explicit operator bool() const;
+  bool operator!() const;
+
+private:
+  int m_position;
+};
+
+#endif // CTRT1_VALIDITY_H
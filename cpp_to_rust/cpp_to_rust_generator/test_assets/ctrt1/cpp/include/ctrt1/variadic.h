@@ -0,0 +1,8 @@
+#ifndef CTRT1_VARIADIC_H
+#define CTRT1_VARIADIC_H
+
+#include "ctrt1/exports.h"
+
+CTRT1_EXPORT int ctrt1_sum_ints(int count, ...);
+
+#endif // CTRT1_VARIADIC_H
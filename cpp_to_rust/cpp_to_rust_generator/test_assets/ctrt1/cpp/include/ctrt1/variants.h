@@ -0,0 +1,10 @@
+#ifndef CTRT1_VARIANTS_H
+#define CTRT1_VARIANTS_H
+
+#include "ctrt1/exports.h"
+#include <variant>
+
+CTRT1_EXPORT std::variant<int, double> ctrt1_scale_number(const std::variant<int, double>& value,
+                                                          int factor);
+
+#endif // CTRT1_VARIANTS_H
@@ -0,0 +1,20 @@
+#ifndef CTRT1_VECTOR3_H
+#define CTRT1_VECTOR3_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Vector3 {
+public:
+  Ctrt1Vector3(double x, double y, double z);
+  double x() const;
+  double y() const;
+  double z() const;
+
+private:
+  double m_x, m_y, m_z;
+};
+
+CTRT1_EXPORT Ctrt1Vector3 operator*(double factor, const Ctrt1Vector3& vector);
+CTRT1_EXPORT Ctrt1Vector3 operator*(const Ctrt1Vector3& vector, double factor);
+
+#endif // CTRT1_VECTOR3_H
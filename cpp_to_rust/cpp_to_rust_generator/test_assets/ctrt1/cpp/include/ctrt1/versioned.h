@@ -0,0 +1,20 @@
+#ifndef CTRT1_VERSIONED_H
+#define CTRT1_VERSIONED_H
+
+#include "ctrt1/exports.h"
+
+namespace ctrt1_versioned {
+  inline namespace v1 {
+    class CTRT1_EXPORT Counter {
+    public:
+      Counter(int value);
+      int value() const;
+    private:
+      int m_value;
+    };
+
+    CTRT1_EXPORT int doubled(int value);
+  }
+}
+
+#endif // CTRT1_VERSIONED_H
@@ -0,0 +1,24 @@
+#ifndef CTRT1_WIDGET_H
+#define CTRT1_WIDGET_H
+
+#include "ctrt1/exports.h"
+
+class CTRT1_EXPORT Ctrt1Widget {
+public:
+  Ctrt1Widget();
+  virtual ~Ctrt1Widget();
+  void update();
+
+protected:
+  virtual void paintEvent(int area);
+  int paintCount() const;
+  int paintedArea() const;
+  void resetPaintCount();
+  void repaint(int area = 5);
+
+private:
+  int m_paintCount;
+  int m_paintedArea;
+};
+
+#endif // CTRT1_WIDGET_H
@@ -0,0 +1,34 @@
+#include "ctrt1/allocated1.h"
+#include <cstdlib>
+#include <new>
+
+static int allocated1_new_count = 0;
+static int allocated1_delete_count = 0;
+
+Allocated1::Allocated1(int x) : m_x(x) {}
+
+int Allocated1::x() const {
+  return m_x;
+}
+
+void* Allocated1::operator new(std::size_t size) {
+  allocated1_new_count++;
+  void* ptr = std::malloc(size);
+  if (!ptr) {
+    throw std::bad_alloc();
+  }
+  return ptr;
+}
+
+void Allocated1::operator delete(void* ptr) {
+  allocated1_delete_count++;
+  std::free(ptr);
+}
+
+int Allocated1::newCount() {
+  return allocated1_new_count;
+}
+
+int Allocated1::deleteCount() {
+  return allocated1_delete_count;
+}
@@ -0,0 +1,41 @@
+#include "ctrt1/arrays.h"
+
+Vector4::Vector4() {
+  for (int i = 0; i < 4; i++) {
+    m_values[i] = 0;
+  }
+}
+
+const int (&Vector4::values() const)[4] {
+  return m_values;
+}
+
+void Vector4::setValues(const int (&values)[4]) {
+  for (int i = 0; i < 4; i++) {
+    m_values[i] = values[i];
+  }
+}
+
+std::array<int, 4> Vector4::reversed(std::array<int, 4> values) {
+  std::array<int, 4> result;
+  for (int i = 0; i < 4; i++) {
+    result[i] = values[3 - i];
+  }
+  return result;
+}
+
+Ctrt1Transform::Ctrt1Transform(float scale) : m_scale(scale) {}
+
+void Ctrt1Transform::getMatrix(float (&out)[16]) const {
+  for (int i = 0; i < 16; i++) {
+    out[i] = 0;
+  }
+  out[0] = m_scale;
+  out[5] = m_scale;
+  out[10] = m_scale;
+  out[15] = 1;
+}
+
+void Ctrt1Transform::copyMatrix(float (&out)[16]) const {
+  getMatrix(out);
+}
@@ -0,0 +1,7 @@
+#include "ctrt1/axis.h"
+
+Ctrt1Axis::Ctrt1Axis(bool vertical) : m_vertical(vertical) {}
+
+const int& Ctrt1Axis::coordinate(const Ctrt1Point& point) const {
+  return m_vertical ? point.y : point.x;
+}
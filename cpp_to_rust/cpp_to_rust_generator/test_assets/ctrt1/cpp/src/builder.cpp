@@ -0,0 +1,20 @@
+#include "ctrt1/builder.h"
+
+Ctrt1Builder::Ctrt1Builder() : m_count(0), m_sum(0) {
+}
+
+void Ctrt1Builder::append(int value) {
+  m_count++;
+  m_sum += value;
+}
+
+int Ctrt1Builder::size() const & {
+  return m_count;
+}
+
+int Ctrt1Builder::build() && {
+  int sum = m_sum;
+  m_count = 0;
+  m_sum = 0;
+  return sum;
+}
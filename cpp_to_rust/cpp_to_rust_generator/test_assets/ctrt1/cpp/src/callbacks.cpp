@@ -0,0 +1,30 @@
+#include "ctrt1/callbacks.h"
+
+Counter::Counter() : m_value(0) {}
+
+void Counter::increment() {
+  m_value++;
+}
+
+int Counter::add(int value) {
+  m_value += value;
+  return m_value;
+}
+
+int Counter::value() const {
+  return m_value;
+}
+
+void ctrt1_call_repeatedly(Counter* counter, void (Counter::*method)(), int times) {
+  for (int i = 0; i < times; i++) {
+    (counter->*method)();
+  }
+}
+
+int ctrt1_sum_mapped(Counter* counter, int (Counter::*method)(int), int count) {
+  int sum = 0;
+  for (int i = 0; i < count; i++) {
+    sum += (counter->*method)(i);
+  }
+  return sum;
+}
@@ -0,0 +1,26 @@
+#include "ctrt1/chain.h"
+
+Ctrt1Settings::Ctrt1Settings(int value) : m_value(value) {
+}
+
+int Ctrt1Settings::value() const {
+  return m_value;
+}
+
+Ctrt1Config::Ctrt1Config(int value) : m_settings(value) {
+}
+
+const Ctrt1Settings& Ctrt1Config::settings() const {
+  return m_settings;
+}
+
+const Ctrt1Settings& Ctrt1Config::settingsOf(Ctrt1Config&& config) {
+  return config.m_settings;
+}
+
+Ctrt1Document::Ctrt1Document(int value) : m_config(value) {
+}
+
+const Ctrt1Config& Ctrt1Document::config() const {
+  return m_config;
+}
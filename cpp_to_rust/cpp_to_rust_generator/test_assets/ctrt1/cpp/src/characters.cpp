@@ -0,0 +1,21 @@
+#include "ctrt1/characters.h"
+
+QChar::QChar(unsigned short ucs) : m_ucs(ucs) {}
+
+unsigned short QChar::unicode() const {
+  return m_ucs;
+}
+
+Ctrt1CharBox::Ctrt1CharBox() : m_value(0) {}
+
+void Ctrt1CharBox::setValue(QChar value) {
+  m_value = value;
+}
+
+void Ctrt1CharBox::setValueByRef(const QChar& value) {
+  m_value = value;
+}
+
+unsigned short Ctrt1CharBox::code() const {
+  return m_value.unicode();
+}
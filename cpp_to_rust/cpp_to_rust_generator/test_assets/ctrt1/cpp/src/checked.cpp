@@ -0,0 +1,9 @@
+#include "ctrt1/checked.h"
+
+int ctrt1_checked_divide(int numerator, int denominator) {
+  if (denominator == 0) {
+    errno = EDOM;
+    return -1;
+  }
+  return numerator / denominator;
+}
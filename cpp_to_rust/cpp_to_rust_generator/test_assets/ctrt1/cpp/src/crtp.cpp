@@ -0,0 +1,7 @@
+#include "ctrt1/crtp.h"
+
+Ctrt1Apples::Ctrt1Apples(int count) : m_count(count) {}
+
+int Ctrt1Apples::count() const {
+  return m_count;
+}
@@ -0,0 +1,17 @@
+#include "ctrt1/factory.h"
+
+Ctrt1Connection* Ctrt1Connection::open(int port) {
+  return new Ctrt1Connection(port);
+}
+
+Ctrt1Connection Ctrt1Connection::openLocal() {
+  return Ctrt1Connection(0);
+}
+
+Ctrt1Connection::~Ctrt1Connection() {}
+
+Ctrt1Connection::Ctrt1Connection(int port) : m_port(port) {}
+
+int Ctrt1Connection::port() const {
+  return m_port;
+}
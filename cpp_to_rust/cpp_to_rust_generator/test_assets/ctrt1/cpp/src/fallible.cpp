@@ -0,0 +1,23 @@
+#include "ctrt1/fallible.h"
+
+static int ctrt1_live_handles = 0;
+
+Ctrt1Handle::Ctrt1Handle(int descriptor) : m_descriptor(descriptor) {
+  ctrt1_live_handles++;
+}
+
+Ctrt1Handle::~Ctrt1Handle() {
+  ctrt1_live_handles--;
+}
+
+bool Ctrt1Handle::isValid() const {
+  return m_descriptor >= 0;
+}
+
+int Ctrt1Handle::descriptor() const {
+  return m_descriptor;
+}
+
+int Ctrt1Handle::liveCount() {
+  return ctrt1_live_handles;
+}
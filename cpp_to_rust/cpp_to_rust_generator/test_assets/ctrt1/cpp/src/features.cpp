@@ -0,0 +1,12 @@
+#define CTRT1_EXTRA
+#include "ctrt1/features.h"
+
+Ctrt1Features::Ctrt1Features() {}
+
+int Ctrt1Features::base() const {
+  return 1;
+}
+
+int Ctrt1Features::extra() const {
+  return 2;
+}
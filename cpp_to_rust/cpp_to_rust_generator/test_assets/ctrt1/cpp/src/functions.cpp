@@ -0,0 +1,30 @@
+#include "ctrt1/functions.h"
+
+std::function<int(int)> ctrt1_make_adder(int value) {
+  return [value](int x) { return x + value; };
+}
+
+void ctrt1_for_each_digit(int value, std::function<void(int)> callback) {
+  if (value >= 10) {
+    ctrt1_for_each_digit(value / 10, callback);
+  }
+  callback(value % 10);
+}
+
+int ctrt1_apply_twice(const std::function<int(int)>& func, int value) {
+  return func(func(value));
+}
+
+void Ctrt1Notifier::setCallback(std::function<void(int)> callback) {
+  m_callback = callback;
+}
+
+void Ctrt1Notifier::notify(int value) const {
+  if (m_callback) {
+    m_callback(value);
+  }
+}
+
+int ctrt1_c_add(int a, int b) {
+  return a + b;
+}
@@ -0,0 +1,25 @@
+#include "ctrt1/inheritance.h"
+
+Ctrt1Named::Ctrt1Named(int id) : m_id(id) {
+}
+
+int Ctrt1Named::id() const {
+  return m_id;
+}
+
+void Ctrt1Named::setId(int id) {
+  m_id = id;
+}
+
+Ctrt1Sized::Ctrt1Sized(int size) : m_size(size) {
+}
+
+int Ctrt1Sized::size() const {
+  return m_size;
+}
+
+Ctrt1Item::Ctrt1Item(int id) : Ctrt1Named(id) {
+}
+
+Ctrt1SizedItem::Ctrt1SizedItem(int id, int size) : Ctrt1Named(id), Ctrt1Sized(size) {
+}
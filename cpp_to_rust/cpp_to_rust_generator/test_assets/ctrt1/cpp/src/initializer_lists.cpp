@@ -0,0 +1,11 @@
+#include "ctrt1/initializer_lists.h"
+
+std::initializer_list<int> ctrt1_small_primes() {
+  // the backing array of a static list lives until the end of the program
+  static const std::initializer_list<int> primes = { 2, 3, 5, 7, 11 };
+  return primes;
+}
+
+std::initializer_list<int> ctrt1_no_values() {
+  return {};
+}
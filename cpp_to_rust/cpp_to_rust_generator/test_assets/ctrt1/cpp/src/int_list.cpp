@@ -0,0 +1,60 @@
+#include "ctrt1/int_list.h"
+
+IntList::IntList() : m_values(0), m_size(0), m_capacity(0), m_reserveCallCount(0) {}
+
+IntList::~IntList() {
+  delete[] m_values;
+}
+
+void IntList::append(const int& value) {
+  if (m_size == m_capacity) {
+    int new_capacity = m_capacity == 0 ? 1 : m_capacity * 2;
+    int* new_values = new int[new_capacity];
+    for (int i = 0; i < m_size; i++) {
+      new_values[i] = m_values[i];
+    }
+    delete[] m_values;
+    m_values = new_values;
+    m_capacity = new_capacity;
+  }
+  m_values[m_size] = value;
+  m_size++;
+}
+
+int IntList::size() const {
+  return m_size;
+}
+
+int IntList::at(int index) const {
+  return m_values[index];
+}
+
+int& IntList::first() {
+  return m_values[0];
+}
+
+const int& IntList::first() const {
+  return m_values[0];
+}
+
+void IntList::reserve(int size) {
+  m_reserveCallCount++;
+  if (size <= m_capacity) {
+    return;
+  }
+  int* new_values = new int[size];
+  for (int i = 0; i < m_size; i++) {
+    new_values[i] = m_values[i];
+  }
+  delete[] m_values;
+  m_values = new_values;
+  m_capacity = size;
+}
+
+int IntList::capacity() const {
+  return m_capacity;
+}
+
+int IntList::reserveCallCount() const {
+  return m_reserveCallCount;
+}
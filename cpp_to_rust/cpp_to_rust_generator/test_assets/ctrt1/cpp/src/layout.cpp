@@ -0,0 +1,30 @@
+#include "ctrt1/layout.h"
+
+static int deleted_widgets_count = 0;
+
+Ctrt1Layout::Ctrt1Layout() {}
+
+Ctrt1Layout::~Ctrt1Layout() {
+  for (size_t i = 0; i < m_widgets.size(); i++) {
+    delete m_widgets[i];
+    deleted_widgets_count++;
+  }
+}
+
+void Ctrt1Layout::addWidget(Ctrt1Widget* widget) {
+  m_widgets.push_back(widget);
+}
+
+int Ctrt1Layout::count() const {
+  return static_cast<int>(m_widgets.size());
+}
+
+void Ctrt1Layout::updateAll() {
+  for (size_t i = 0; i < m_widgets.size(); i++) {
+    m_widgets[i]->update();
+  }
+}
+
+int Ctrt1Layout::deletedWidgetsCount() {
+  return deleted_widgets_count;
+}
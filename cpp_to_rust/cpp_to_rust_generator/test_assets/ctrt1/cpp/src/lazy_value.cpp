@@ -0,0 +1,22 @@
+#include "ctrt1/lazy_value.h"
+
+Ctrt1LazyValue::Ctrt1LazyValue(int base) :
+  m_base(base), m_cachedValue(0), m_isCached(false), m_computeCount(0) {}
+
+int Ctrt1LazyValue::value() const {
+  if (!m_isCached) {
+    m_cachedValue = m_base * m_base;
+    m_isCached = true;
+    m_computeCount++;
+  }
+  return m_cachedValue;
+}
+
+int Ctrt1LazyValue::computeCount() const {
+  return m_computeCount;
+}
+
+void Ctrt1LazyValue::setBase(int base) {
+  m_base = base;
+  m_isCached = false;
+}
@@ -0,0 +1,17 @@
+#include "ctrt1/maps.h"
+
+double ctrt1_sum_map_values(const std::map<int, double>& map) {
+  double sum = 0;
+  for (const auto& entry : map) {
+    sum += entry.second;
+  }
+  return sum;
+}
+
+std::unordered_map<int, int> ctrt1_squares(int count) {
+  std::unordered_map<int, int> result;
+  for (int i = 0; i < count; i++) {
+    result[i] = i * i;
+  }
+  return result;
+}
@@ -0,0 +1,11 @@
+#include "ctrt1/mixed_slot.h"
+
+MixedSlot::MixedSlot() : m_count(1), m_ratio(0.5) {}
+
+const int& MixedSlot::value() const {
+  return m_count;
+}
+
+double& MixedSlot::value() {
+  return m_ratio;
+}
@@ -0,0 +1,38 @@
+#include "ctrt1/movable.h"
+
+static int moved_from_destroyed_count = 0;
+
+Ctrt1Movable::Ctrt1Movable(int value) : m_value(value), m_movedFrom(false) {
+}
+
+Ctrt1Movable::~Ctrt1Movable() {
+  if (m_movedFrom) {
+    moved_from_destroyed_count++;
+  }
+}
+
+Ctrt1Movable& Ctrt1Movable::operator=(const Ctrt1Movable& other) {
+  m_value = other.m_value;
+  m_movedFrom = other.m_movedFrom;
+  return *this;
+}
+
+Ctrt1Movable& Ctrt1Movable::operator=(Ctrt1Movable&& other) {
+  m_value = other.m_value;
+  m_movedFrom = false;
+  other.m_value = 0;
+  other.m_movedFrom = true;
+  return *this;
+}
+
+int Ctrt1Movable::value() const {
+  return m_value;
+}
+
+bool Ctrt1Movable::isMovedFrom() const {
+  return m_movedFrom;
+}
+
+int Ctrt1Movable::movedFromDestroyedCount() {
+  return moved_from_destroyed_count;
+}
@@ -0,0 +1,23 @@
+#include "ctrt1/owned.h"
+
+static int ctrt1_live_resources = 0;
+
+std::unique_ptr<Ctrt1Resource> Ctrt1Resource::create(int id) {
+  return std::unique_ptr<Ctrt1Resource>(new Ctrt1Resource(id));
+}
+
+Ctrt1Resource::~Ctrt1Resource() {
+  ctrt1_live_resources--;
+}
+
+Ctrt1Resource::Ctrt1Resource(int id) : m_id(id) {
+  ctrt1_live_resources++;
+}
+
+int Ctrt1Resource::id() const {
+  return m_id;
+}
+
+int Ctrt1Resource::liveCount() {
+  return ctrt1_live_resources;
+}
@@ -0,0 +1,4 @@
+#include "ctrt1/point.h"
+
+Ctrt1Point::Ctrt1Point() : x(0), y(0), color(Red) {
+}
@@ -0,0 +1,22 @@
+#include "ctrt1/poly_base.h"
+
+PolyBase::~PolyBase() {}
+
+int PolyDerived::m_destructorCallCount = 0;
+
+PolyDerived::PolyDerived(int value) : m_value(value) {}
+
+PolyDerived::~PolyDerived() {
+  m_destructorCallCount++;
+}
+
+int PolyDerived::value() const {
+  return m_value;
+}
+
+int PolyDerived::destructorCallCount() {
+  return m_destructorCallCount;
+}
+
+PolyHolder::PolyHolder(PolyBase* child) : m_child(child) {
+}
@@ -0,0 +1,15 @@
+#include "ctrt1/printable.h"
+
+Ctrt1Size::Ctrt1Size(int width, int height) : m_width(width), m_height(height) {}
+
+int Ctrt1Size::width() const {
+  return m_width;
+}
+
+int Ctrt1Size::height() const {
+  return m_height;
+}
+
+std::ostream& operator<<(std::ostream& stream, const Ctrt1Size& size) {
+  return stream << size.width() << "x" << size.height();
+}
@@ -0,0 +1,23 @@
+#include "ctrt1/prototype.h"
+
+Ctrt1Shape::Ctrt1Shape() {}
+
+Ctrt1Shape::Ctrt1Shape(const Ctrt1Shape&) {}
+
+Ctrt1Shape::~Ctrt1Shape() {}
+
+Ctrt1Square::Ctrt1Square(int side) : m_side(side) {}
+
+Ctrt1Square::Ctrt1Square(const Ctrt1Square& other) : Ctrt1Shape(other), m_side(other.m_side) {}
+
+Ctrt1Shape* Ctrt1Square::clone() const {
+  return new Ctrt1Square(*this);
+}
+
+int Ctrt1Square::area() const {
+  return m_side * m_side;
+}
+
+void Ctrt1Square::setSide(int side) {
+  m_side = side;
+}
@@ -0,0 +1,21 @@
+#include "ctrt1/query.h"
+
+Ctrt1Query::Ctrt1Query() : m_limit(-1), m_offset(0) {}
+
+Ctrt1Query& Ctrt1Query::withLimit(int limit) {
+  m_limit = limit;
+  return *this;
+}
+
+Ctrt1Query& Ctrt1Query::withOffset(int offset) {
+  m_offset = offset;
+  return *this;
+}
+
+int Ctrt1Query::limit() const {
+  return m_limit;
+}
+
+int Ctrt1Query::offset() const {
+  return m_offset;
+}
@@ -0,0 +1,11 @@
+#include "ctrt1/range.h"
+
+Ctrt1Range::Ctrt1Range(int start, int end) : m_start(start), m_end(end) {}
+
+int Ctrt1Range::start() const {
+  return m_start;
+}
+
+int Ctrt1Range::end() const {
+  return m_end;
+}
@@ -0,0 +1,15 @@
+#include "ctrt1/slicing.h"
+
+SlicingBase::SlicingBase(int x) : m_x(x) {
+}
+
+int SlicingBase::x() const {
+  return m_x;
+}
+
+SlicingDerived::SlicingDerived(int x, int y) : SlicingBase(x), m_y(y) {
+}
+
+int SlicingDerived::y() const {
+  return m_y;
+}
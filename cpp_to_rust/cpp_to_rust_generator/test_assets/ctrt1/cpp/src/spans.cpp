@@ -0,0 +1,15 @@
+#include "ctrt1/spans.h"
+
+int ctrt1_span_sum(std::span<const int> values) {
+  int sum = 0;
+  for (int value : values) {
+    sum += value;
+  }
+  return sum;
+}
+
+void ctrt1_span_double(std::span<int> values) {
+  for (int& value : values) {
+    value *= 2;
+  }
+}
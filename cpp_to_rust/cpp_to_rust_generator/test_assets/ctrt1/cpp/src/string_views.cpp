@@ -0,0 +1,15 @@
+#include "ctrt1/string_views.h"
+
+int ctrt1_text_size(std::string_view text) {
+  return static_cast<int>(text.size());
+}
+
+int ctrt1_count_spaces(const std::string_view& text) {
+  int count = 0;
+  for (char c : text) {
+    if (c == ' ') {
+      count++;
+    }
+  }
+  return count;
+}
@@ -1,4 +1,6 @@
 #include "ctrt1/utils.h"
+#include <string>
+#include <cstdio>
 
 int ctrt1_abs(int x) {
   if (x >= 0) {
@@ -11,3 +13,51 @@ int ctrt1_abs(int x) {
 const char* ctrt1_version() {
   return "0.0.1";
 }
+
+size_t ctrt1_size_identity(size_t x) {
+  return x;
+}
+
+std::ptrdiff_t ctrt1_ptrdiff_negate(std::ptrdiff_t x) {
+  return -x;
+}
+
+int ctrt1_repeat_text(int count, char* buf, int buf_len) {
+  std::string text;
+  for (int i = 0; i < count; i++) {
+    text += "ab";
+  }
+  return snprintf(buf, buf_len, "%s", text.c_str());
+}
+
+int ctrt1_user_name(int id, char* buf, int buf_len) {
+  if (id < 0) {
+    return -1;
+  }
+  return snprintf(buf, buf_len, "user%d", id);
+}
+
+int ctrt1_clamp(int value, int max) {
+  return ctrt1_clamp(value, 0, max);
+}
+
+int ctrt1_clamp(int value, int min, int max) {
+  if (value < min) {
+    return min;
+  }
+  if (value > max) {
+    return max;
+  }
+  return value;
+}
+
+static int ctrt1_registration_count = 0;
+
+int Ctrt1Token::registerType() {
+  ctrt1_registration_count++;
+  return 100 + ctrt1_registration_count;
+}
+
+int Ctrt1Token::registrationCount() {
+  return ctrt1_registration_count;
+}
@@ -0,0 +1,15 @@
+#include "ctrt1/validity.h"
+
+Ctrt1Cursor::Ctrt1Cursor(int position) : m_position(position) {}
+
+int Ctrt1Cursor::position() const {
+  return m_position;
+}
+
+Ctrt1Cursor::operator bool() const {
+  return m_position >= 0;
+}
+
+bool Ctrt1Cursor::operator!() const {
+  return m_position < 0;
+}
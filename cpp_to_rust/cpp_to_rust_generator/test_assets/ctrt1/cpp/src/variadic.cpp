@@ -0,0 +1,13 @@
+#include "ctrt1/variadic.h"
+#include <cstdarg>
+
+int ctrt1_sum_ints(int count, ...) {
+  va_list args;
+  va_start(args, count);
+  int sum = 0;
+  for (int i = 0; i < count; i++) {
+    sum += va_arg(args, int);
+  }
+  va_end(args);
+  return sum;
+}
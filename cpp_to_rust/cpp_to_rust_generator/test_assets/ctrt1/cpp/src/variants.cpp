@@ -0,0 +1,9 @@
+#include "ctrt1/variants.h"
+
+std::variant<int, double> ctrt1_scale_number(const std::variant<int, double>& value,
+                                             int factor) {
+  if (const int* int_value = std::get_if<int>(&value)) {
+    return *int_value * factor;
+  }
+  return std::get<double>(value) * factor;
+}
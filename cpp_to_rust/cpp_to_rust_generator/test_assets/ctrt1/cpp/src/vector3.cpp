@@ -0,0 +1,23 @@
+#include "ctrt1/vector3.h"
+
+Ctrt1Vector3::Ctrt1Vector3(double x, double y, double z) : m_x(x), m_y(y), m_z(z) {}
+
+double Ctrt1Vector3::x() const {
+  return m_x;
+}
+
+double Ctrt1Vector3::y() const {
+  return m_y;
+}
+
+double Ctrt1Vector3::z() const {
+  return m_z;
+}
+
+Ctrt1Vector3 operator*(double factor, const Ctrt1Vector3& vector) {
+  return Ctrt1Vector3(factor * vector.x(), factor * vector.y(), factor * vector.z());
+}
+
+Ctrt1Vector3 operator*(const Ctrt1Vector3& vector, double factor) {
+  return factor * vector;
+}
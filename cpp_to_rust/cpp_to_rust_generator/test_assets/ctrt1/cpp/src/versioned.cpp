@@ -0,0 +1,15 @@
+#include "ctrt1/versioned.h"
+
+namespace ctrt1_versioned {
+  inline namespace v1 {
+    Counter::Counter(int value) : m_value(value) {}
+
+    int Counter::value() const {
+      return m_value;
+    }
+
+    int doubled(int value) {
+      return value * 2;
+    }
+  }
+}
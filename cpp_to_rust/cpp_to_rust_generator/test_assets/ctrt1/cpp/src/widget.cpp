@@ -0,0 +1,30 @@
+#include "ctrt1/widget.h"
+
+Ctrt1Widget::Ctrt1Widget() : m_paintCount(0), m_paintedArea(0) {}
+
+Ctrt1Widget::~Ctrt1Widget() {}
+
+void Ctrt1Widget::update() {
+  paintEvent(1);
+}
+
+void Ctrt1Widget::paintEvent(int area) {
+  m_paintCount++;
+  m_paintedArea += area;
+}
+
+int Ctrt1Widget::paintCount() const {
+  return m_paintCount;
+}
+
+int Ctrt1Widget::paintedArea() const {
+  return m_paintedArea;
+}
+
+void Ctrt1Widget::resetPaintCount() {
+  m_paintCount = 0;
+}
+
+void Ctrt1Widget::repaint(int area) {
+  paintEvent(area);
+}
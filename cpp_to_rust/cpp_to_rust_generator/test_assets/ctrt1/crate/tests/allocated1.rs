@@ -0,0 +1,16 @@
+extern crate rust_ctrt1;
+use rust_ctrt1::allocated1::Allocated1;
+
+#[test]
+fn allocated1_custom_operators() {
+  let new_count = Allocated1::new_count();
+  let delete_count = Allocated1::delete_count();
+  {
+    let v = Allocated1::new(5);
+    assert_eq!(v.x(), 5);
+    assert_eq!(Allocated1::new_count(), new_count + 1);
+    assert_eq!(Allocated1::delete_count(), delete_count);
+  }
+  assert_eq!(Allocated1::new_count(), new_count + 1);
+  assert_eq!(Allocated1::delete_count(), delete_count + 1);
+}
@@ -0,0 +1,29 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::arrays::{Ctrt1Transform, Vector4};
+
+#[test]
+fn array_reference() {
+  let mut vector = Vector4::new();
+  assert_eq!(vector.values(), &[0, 0, 0, 0]);
+  vector.set_values(&[1, 2, 3, 4]);
+  let values: &[i32; 4] = vector.values();
+  assert_eq!(values, &[1, 2, 3, 4]);
+}
+
+#[test]
+fn std_array_value() {
+  let values: [i32; 4] = Vector4::reversed([1, 2, 3, 4]);
+  assert_eq!(values, [4, 3, 2, 1]);
+}
+
+#[test]
+fn array_out_param() {
+  let transform = Ctrt1Transform::new(2.0);
+  let matrix: [f32; 16] = transform.get_matrix();
+  assert_eq!(matrix,
+             [2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+  let mut copy = [5.0; 16];
+  transform.copy_matrix(&mut copy);
+  assert_eq!(copy, matrix);
+}
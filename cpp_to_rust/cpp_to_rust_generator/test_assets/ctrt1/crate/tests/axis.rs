@@ -0,0 +1,21 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::axis::Ctrt1Axis;
+use rust_ctrt1::point::Ctrt1Point;
+
+// compiles only if the returned reference borrows from `point`
+// and not from the temporary `Ctrt1Axis`
+fn coordinate(point: &Ctrt1Point, vertical: bool) -> &i32 {
+  let axis = Ctrt1Axis::new(vertical);
+  axis.coordinate(point)
+}
+
+#[test]
+fn return_lifetime_tied_to_argument() {
+  let mut point = Ctrt1Point::new();
+  point.set_x(3);
+  point.set_y(-4);
+  let x = coordinate(&point, false);
+  let y = coordinate(&point, true);
+  assert_eq!((*x, *y), (3, -4));
+}
@@ -0,0 +1,13 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::builder::Ctrt1Builder;
+
+#[test]
+fn rvalue_qualified_method_consumes_receiver() {
+  let mut builder = Ctrt1Builder::new();
+  builder.append(1);
+  builder.append(2);
+  assert_eq!(builder.size(), 2);
+  // `builder` is moved into `build` and can't be used afterwards
+  assert_eq!(builder.build(), 3);
+}
@@ -0,0 +1,27 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::callbacks::{ctrt1_call_repeatedly, ctrt1_sum_mapped, MemberCallbackCounterVoid,
+                            MemberCallbackCounterIntInt};
+
+#[test]
+fn member_function_pointer_closure() {
+  let mut calls = 0;
+  {
+    let callback = MemberCallbackCounterVoid::new(|| calls += 1);
+    ctrt1_call_repeatedly(&callback, 3);
+  }
+  assert_eq!(calls, 3);
+}
+
+#[test]
+fn member_function_pointer_closure_with_arguments() {
+  let mut arguments = Vec::new();
+  {
+    let callback = MemberCallbackCounterIntInt::new(|x| {
+                                                      arguments.push(x);
+                                                      x * 10
+                                                    });
+    assert_eq!(ctrt1_sum_mapped(&callback, 4), 60);
+  }
+  assert_eq!(arguments, vec![0, 1, 2, 3]);
+}
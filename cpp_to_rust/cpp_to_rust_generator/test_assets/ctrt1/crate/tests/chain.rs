@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::chain::{Ctrt1Document, Ctrt1Settings};
+
+fn settings(document: &Ctrt1Document) -> &Ctrt1Settings {
+  document.config().settings()
+}
+
+#[test]
+fn chained_getters() {
+  let document = Ctrt1Document::new(7);
+  assert_eq!(document.config().settings().value(), 7);
+  assert_eq!(Ctrt1Document::new(8).config().settings().value(), 8);
+  assert_eq!(settings(&document).value(), 7);
+}
@@ -0,0 +1,24 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::characters::Ctrt1CharBox;
+use std::panic;
+
+#[test]
+fn char_arguments() {
+  let mut value = Ctrt1CharBox::new();
+  value.set_value('a');
+  assert_eq!(value.code(), 'a' as u16);
+  value.set_value_by_ref('Ж');
+  assert_eq!(value.code(), 'Ж' as u32 as u16);
+  value.set_value('\u{ffff}');
+  assert_eq!(value.code(), 0xffff);
+}
+
+#[test]
+fn non_bmp_char_argument() {
+  let result = panic::catch_unwind(|| {
+                                     let mut value = Ctrt1CharBox::new();
+                                     value.set_value('\u{1f600}');
+                                   });
+  assert!(result.is_err());
+}
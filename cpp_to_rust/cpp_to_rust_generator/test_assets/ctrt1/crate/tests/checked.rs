@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::checked::ctrt1_checked_divide;
+use rust_ctrt1::cpp_utils::CppError;
+use rust_ctrt1::libc;
+
+#[test]
+fn errno_method() {
+  assert_eq!(ctrt1_checked_divide(7, 2), Ok(3));
+  assert_eq!(ctrt1_checked_divide(7, 0),
+             Err(CppError {
+                   function_name: "ctrt1_checked_divide",
+                   code: libc::EDOM,
+                 }));
+}
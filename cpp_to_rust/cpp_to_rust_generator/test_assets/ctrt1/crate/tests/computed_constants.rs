@@ -0,0 +1,11 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::utils::Ctrt1Token;
+
+#[test]
+fn computed_constant_initialized_once() {
+  assert_eq!(Ctrt1Token::registration_count(), 0);
+  assert_eq!(Ctrt1Token::registration_id(), 101);
+  assert_eq!(Ctrt1Token::registration_id(), 101);
+  assert_eq!(Ctrt1Token::registration_count(), 1);
+}
@@ -0,0 +1,10 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::crtp::Ctrt1Apples;
+
+#[test]
+fn crtp_base_method() {
+  let apples = Ctrt1Apples::new(3);
+  assert_eq!(apples.count(), 3);
+  assert_eq!(apples.doubled(), 6);
+}
@@ -0,0 +1,27 @@
+extern crate rust_ctrt1;
+use rust_ctrt1::enums::{Ctrt1Color, Ctrt1Large};
+use std::convert::TryFrom;
+
+#[test]
+fn enum_conversions_round_trip() {
+  for value in &[Ctrt1Color::Red, Ctrt1Color::Green, Ctrt1Color::Blue] {
+    let int_value: i32 = value.clone().into();
+    assert_eq!(Ctrt1Color::try_from(int_value).unwrap(), *value);
+  }
+  assert_eq!(i32::from(Ctrt1Color::Blue), 4);
+}
+
+#[test]
+fn enum_conversions_unknown_value() {
+  let error = Ctrt1Color::try_from(3).unwrap_err();
+  assert_eq!(error.enum_name, "Ctrt1Color");
+  assert_eq!(error.value, 3);
+}
+
+#[test]
+fn enum_conversions_large_values() {
+  assert_eq!(i64::from(Ctrt1Large::Huge), 0x100000000);
+  assert_eq!(Ctrt1Large::try_from(0x100000000).unwrap(), Ctrt1Large::Huge);
+  assert_eq!(Ctrt1Large::try_from(1i64).unwrap(), Ctrt1Large::Small);
+  assert_eq!(Ctrt1Large::try_from(0x100000001).unwrap_err().value, 0x100000001);
+}
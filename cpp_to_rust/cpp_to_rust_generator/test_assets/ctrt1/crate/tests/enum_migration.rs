@@ -0,0 +1,12 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::enums::Ctrt1Color;
+
+#[test]
+fn migrate_enum_value() {
+  // version 1 used `Red = 0`, `Green = 1`, `Blue = 2`
+  assert_eq!(Ctrt1Color::migrate_from_v1(0), Some(Ctrt1Color::Red));
+  assert_eq!(Ctrt1Color::migrate_from_v1(1), Some(Ctrt1Color::Green));
+  assert_eq!(Ctrt1Color::migrate_from_v1(2), Some(Ctrt1Color::Blue));
+  assert_eq!(Ctrt1Color::migrate_from_v1(3), None);
+}
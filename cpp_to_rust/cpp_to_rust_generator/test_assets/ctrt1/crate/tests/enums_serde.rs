@@ -0,0 +1,21 @@
+extern crate rust_ctrt1;
+extern crate serde_json;
+use rust_ctrt1::enums::Ctrt1Color;
+
+#[test]
+fn enums_serde_round_trip() {
+  for value in &[Ctrt1Color::Red, Ctrt1Color::Green, Ctrt1Color::Blue] {
+    let text = serde_json::to_string(value).unwrap();
+    let value2: Ctrt1Color = serde_json::from_str(&text).unwrap();
+    assert_eq!(&value2, value);
+  }
+  assert_eq!(serde_json::to_string(&Ctrt1Color::Green).unwrap(), "\"Green\"");
+  let value: Ctrt1Color = serde_json::from_str("\"Blue\"").unwrap();
+  assert_eq!(value, Ctrt1Color::Blue);
+}
+
+#[test]
+fn enums_serde_unknown_value() {
+  let result: Result<Ctrt1Color, _> = serde_json::from_str("\"Purple\"");
+  assert!(result.is_err());
+}
@@ -0,0 +1,23 @@
+extern crate rust_ctrt1;
+use rust_ctrt1::enums::{Ctrt1Color, Ctrt1Sparse};
+use std::convert::TryFrom;
+
+#[test]
+fn sparse_enum_try_from() {
+  assert_eq!(Ctrt1Sparse::try_from(1).unwrap(), Ctrt1Sparse::A);
+  assert_eq!(Ctrt1Sparse::try_from(5).unwrap(), Ctrt1Sparse::B);
+  assert_eq!(Ctrt1Sparse::try_from(100).unwrap(), Ctrt1Sparse::C);
+  for value in &[0, 2, 50, 99, 101] {
+    assert_eq!(Ctrt1Sparse::try_from(*value).unwrap_err().value, *value as i64);
+  }
+}
+
+#[test]
+fn sparse_enum_all_values() {
+  assert_eq!(Ctrt1Sparse::all_values(),
+             &[Ctrt1Sparse::A, Ctrt1Sparse::B, Ctrt1Sparse::C]);
+  for value in Ctrt1Sparse::all_values() {
+    assert_eq!(Ctrt1Sparse::try_from(i32::from(value.clone())).unwrap(), *value);
+  }
+  assert_eq!(Ctrt1Color::all_values().len(), 3);
+}
@@ -0,0 +1,16 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::factory::Ctrt1Connection;
+
+#[test]
+fn factory_returning_pointer() {
+  let connection = Ctrt1Connection::open(8080);
+  assert!(!connection.is_null());
+  assert_eq!(connection.port(), 8080);
+}
+
+#[test]
+fn factory_returning_value() {
+  let connection = Ctrt1Connection::open_local();
+  assert_eq!(connection.port(), 0);
+}
@@ -0,0 +1,17 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::fallible::Ctrt1Handle;
+use rust_ctrt1::cpp_utils::ConstructionError;
+
+#[test]
+fn fallible_constructor() {
+  {
+    let handle = Ctrt1Handle::new(3).unwrap();
+    assert_eq!(handle.descriptor(), 3);
+    assert_eq!(Ctrt1Handle::live_count(), 1);
+  }
+  assert_eq!(Ctrt1Handle::new(-1).unwrap_err(),
+             ConstructionError { class_name: "Ctrt1Handle" });
+  // the invalid object is deleted by the C++ wrapper
+  assert_eq!(Ctrt1Handle::live_count(), 0);
+}
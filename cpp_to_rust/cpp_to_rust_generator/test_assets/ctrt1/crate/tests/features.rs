@@ -0,0 +1,16 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::features::Ctrt1Features;
+
+#[test]
+fn features_base() {
+  let value = Ctrt1Features::new();
+  assert_eq!(value.base(), 1);
+}
+
+#[cfg(feature = "extra")]
+#[test]
+fn features_extra() {
+  let value = Ctrt1Features::new();
+  assert_eq!(value.extra(), 2);
+}
@@ -0,0 +1,49 @@
+extern crate rust_ctrt1;
+
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn std_function_return_value() {
+  use rust_ctrt1::functions::ctrt1_make_adder;
+  let adder = ctrt1_make_adder(5);
+  assert_eq!(adder.call(3), 8);
+  assert_eq!(adder.call(-5), 0);
+}
+
+#[test]
+fn extern_c_function() {
+  use rust_ctrt1::functions::ctrt1_c_add;
+  assert_eq!(ctrt1_c_add(2, 3), 5);
+}
+
+#[test]
+fn std_function_argument() {
+  use rust_ctrt1::functions::ctrt1_for_each_digit;
+  let digits = Arc::new(Mutex::new(Vec::new()));
+  let digits2 = digits.clone();
+  ctrt1_for_each_digit(4096, move |digit| digits2.lock().unwrap().push(digit));
+  assert_eq!(*digits.lock().unwrap(), vec![4, 0, 9, 6]);
+  // the closure is dropped after the C++ side releases it
+  assert_eq!(Arc::strong_count(&digits), 1);
+}
+
+#[test]
+fn std_function_const_ref_argument() {
+  use rust_ctrt1::functions::ctrt1_apply_twice;
+  assert_eq!(ctrt1_apply_twice(|x| x * 3, 2), 18);
+}
+
+#[test]
+fn std_function_stored_by_cpp() {
+  use rust_ctrt1::functions::Ctrt1Notifier;
+  let values = Arc::new(Mutex::new(Vec::new()));
+  let values2 = values.clone();
+  let mut notifier = Ctrt1Notifier::new();
+  notifier.set_callback(move |value| values2.lock().unwrap().push(value));
+  notifier.notify(1);
+  notifier.notify(2);
+  assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+  assert_eq!(Arc::strong_count(&values), 2);
+  drop(notifier);
+  assert_eq!(Arc::strong_count(&values), 1);
+}
@@ -0,0 +1,21 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::inheritance::{Ctrt1Item, Ctrt1SizedItem};
+
+#[test]
+fn deref_to_base() {
+  let mut item = Ctrt1Item::new(1);
+  assert_eq!(item.id(), 1);
+  item.set_id(2);
+  assert_eq!(item.id(), 2);
+}
+
+#[test]
+fn multiple_inheritance() {
+  let mut item = Ctrt1SizedItem::new(1, 10);
+  // the first base is reachable through `Deref`
+  item.set_id(3);
+  assert_eq!(item.id(), 3);
+  assert_eq!(item.as_base_ctrt1_sized().size(), 10);
+  assert_eq!(item.as_base_ctrt1_sized_mut().size(), 10);
+}
@@ -0,0 +1,18 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::initializer_lists::{ctrt1_no_values, ctrt1_small_primes};
+
+#[test]
+fn initializer_list_to_vec() {
+  let primes: Vec<i32> = ctrt1_small_primes();
+  assert_eq!(primes, vec![2, 3, 5, 7, 11]);
+  // each call returns an independent copy
+  let mut copy = ctrt1_small_primes();
+  copy.push(13);
+  assert_eq!(ctrt1_small_primes(), primes);
+}
+
+#[test]
+fn empty_initializer_list_to_vec() {
+  assert!(ctrt1_no_values().is_empty());
+}
@@ -0,0 +1,69 @@
+extern crate rust_ctrt1;
+use rust_ctrt1::int_list::{IntList, FunctorBoolIntInt};
+
+#[test]
+fn int_list_from_iterator() {
+  let list: IntList = [1, 2, 3, 4, 5].iter().collect();
+  assert_eq!(list.size(), 5);
+  assert_eq!(list.at(0), 1);
+  assert_eq!(list.at(4), 5);
+  assert_eq!(list.reserve_call_count(), 1);
+  assert_eq!(list.capacity(), 5);
+}
+
+#[test]
+fn int_list_extend_without_upper_bound() {
+  let mut list: IntList = [1, 2].iter().collect();
+  assert_eq!(list.reserve_call_count(), 1);
+  let mut count = 0;
+  list.extend(::std::iter::repeat(&7).take_while(|_| {
+                                                   count += 1;
+                                                   count <= 3
+                                                 }));
+  assert_eq!(list.size(), 5);
+  assert_eq!(list.at(4), 7);
+  assert_eq!(list.reserve_call_count(), 1);
+}
+
+#[test]
+fn int_list_sort_with_closure() {
+  let mut list: IntList = [3, 1, 2].iter().collect();
+  let mut calls = 0;
+  {
+    let greater = FunctorBoolIntInt::new(|a, b| {
+                                           calls += 1;
+                                           a > b
+                                         });
+    list.sort(&greater);
+  }
+  assert!(calls > 0);
+  assert_eq!(list.at(0), 3);
+  assert_eq!(list.at(1), 2);
+  assert_eq!(list.at(2), 1);
+}
+
+#[test]
+fn int_list_append_batch() {
+  let items = [3, 1, 4, 1, 5, 9, 2, 6];
+  let mut batched = IntList::new();
+  batched.append_batch(&items);
+  let mut per_element = IntList::new();
+  for item in &items {
+    per_element.append(item);
+  }
+  assert_eq!(batched.size(), per_element.size());
+  for i in 0..per_element.size() {
+    assert_eq!(batched.at(i), per_element.at(i));
+  }
+  batched.append_batch(&[]);
+  assert_eq!(batched.size(), items.len() as i32);
+}
+
+#[test]
+fn int_list_const_overloads() {
+  let mut list: IntList = [1, 2].iter().collect();
+  assert_eq!(*list.first(), 1);
+  *list.first_mut() = 5;
+  assert_eq!(*list.first(), 5);
+  assert_eq!(list.at(0), 5);
+}
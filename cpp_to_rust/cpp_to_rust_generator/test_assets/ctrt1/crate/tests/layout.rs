@@ -0,0 +1,21 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::layout::Ctrt1Layout;
+use rust_ctrt1::widget::Ctrt1Widget;
+
+#[test]
+fn ownership_transfer() {
+  let deleted_before = Ctrt1Layout::deleted_widgets_count();
+  {
+    let mut layout = Ctrt1Layout::new();
+    let mut widget = Ctrt1Widget::new();
+    widget.update();
+    layout.add_widget(widget);
+    layout.add_widget(Ctrt1Widget::new());
+    assert_eq!(layout.count(), 2);
+    layout.update_all();
+    assert_eq!(Ctrt1Layout::deleted_widgets_count(), deleted_before);
+  }
+  // widgets are deleted only once, by the layout
+  assert_eq!(Ctrt1Layout::deleted_widgets_count(), deleted_before + 2);
+}
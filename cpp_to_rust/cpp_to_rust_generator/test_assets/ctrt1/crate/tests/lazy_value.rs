@@ -0,0 +1,18 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::lazy_value::Ctrt1LazyValue;
+
+fn value_twice(lazy: &Ctrt1LazyValue) -> (i32, i32) {
+  (lazy.value(), lazy.value())
+}
+
+#[test]
+fn const_method_modifies_mutable_members() {
+  let mut lazy = Ctrt1LazyValue::new(5);
+  assert_eq!(lazy.compute_count(), 0);
+  assert_eq!(value_twice(&lazy), (25, 25));
+  assert_eq!(lazy.compute_count(), 1);
+  lazy.set_base(3);
+  assert_eq!(value_twice(&lazy), (9, 9));
+  assert_eq!(lazy.compute_count(), 2);
+}
@@ -0,0 +1,45 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::maps::{ctrt1_sum_map_values, ctrt1_squares, MapCIntCDouble, UnorderedMapCIntCInt};
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn std_map_insert_and_get() {
+  let mut map = MapCIntCDouble::new();
+  map.insert(2, 1.5);
+  map.insert(1, 0.5);
+  map.insert(2, 2.5);
+  assert_eq!(map.size(), 2);
+  assert_eq!(map.get(2), Some(&2.5));
+  assert_eq!(map.get(3), None);
+  let entries: Vec<_> = map.iter().collect();
+  assert_eq!(entries, vec![(&1, &0.5), (&2, &2.5)]);
+  assert_eq!(ctrt1_sum_map_values(&map), 3.0);
+
+  map.extend(vec![(3, 1.0)]);
+  let expected: BTreeMap<_, _> = vec![(1, 0.5), (2, 2.5), (3, 1.0)].into_iter().collect();
+  assert_eq!(BTreeMap::from(&map), expected);
+}
+
+#[test]
+fn std_map_from_collection() {
+  let collection: BTreeMap<_, _> = vec![(1, 0.5), (2, 2.5)].into_iter().collect();
+  let map = MapCIntCDouble::from(collection.clone());
+  assert_eq!(ctrt1_sum_map_values(&map), 3.0);
+  assert_eq!(BTreeMap::from(&map), collection);
+
+  let map: MapCIntCDouble = vec![(3, 1.0)].into_iter().collect();
+  assert_eq!(map.get(3), Some(&1.0));
+}
+
+#[test]
+fn std_unordered_map_conversion() {
+  let squares = ctrt1_squares(4);
+  assert_eq!(squares.get(3), Some(&9));
+  assert_eq!((&squares).into_iter().count(), 4);
+  let expected: HashMap<_, _> = (0..4).map(|i| (i, i * i)).collect();
+  assert_eq!(HashMap::from(&squares), expected);
+
+  let map = UnorderedMapCIntCInt::from(expected.clone());
+  assert_eq!(HashMap::from(&map), expected);
+}
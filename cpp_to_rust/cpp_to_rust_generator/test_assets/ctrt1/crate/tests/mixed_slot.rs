@@ -0,0 +1,13 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::mixed_slot::MixedSlot;
+
+#[test]
+fn const_overloads_with_different_return_types() {
+  let mut slot = MixedSlot::new();
+  assert_eq!(*slot.value_int(), 1);
+  assert_eq!(*slot.value_double(), 0.5);
+  *slot.value_double() = 2.5;
+  assert_eq!(*slot.value_double(), 2.5);
+  assert_eq!(*slot.value_int(), 1);
+}
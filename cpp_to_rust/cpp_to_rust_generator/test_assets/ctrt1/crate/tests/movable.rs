@@ -0,0 +1,26 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::movable::Ctrt1Movable;
+
+#[test]
+fn move_assignment() {
+  let mut target = Ctrt1Movable::new(1);
+  let source = Ctrt1Movable::new(2);
+  let destroyed_count = Ctrt1Movable::moved_from_destroyed_count();
+  target.assign_moved(source);
+  assert_eq!(target.value(), 2);
+  assert!(!target.is_moved_from());
+  // the source was left in moved-from state and destroyed after the call
+  assert_eq!(Ctrt1Movable::moved_from_destroyed_count(),
+             destroyed_count + 1);
+}
+
+#[test]
+fn copy_assignment() {
+  let mut target = Ctrt1Movable::new(1);
+  let source = Ctrt1Movable::new(3);
+  target.op_assign(&source);
+  assert_eq!(target.value(), 3);
+  assert_eq!(source.value(), 3);
+  assert!(!source.is_moved_from());
+}
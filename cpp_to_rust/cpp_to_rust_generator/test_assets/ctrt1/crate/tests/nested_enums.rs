@@ -0,0 +1,23 @@
+extern crate rust_ctrt1;
+use rust_ctrt1::enums::{ctrt1_brush, ctrt1_shape, Ctrt1Brush};
+
+#[test]
+fn namespace_enum_in_module() {
+  assert_eq!(ctrt1_shape::Kind::Circle as i32, 1);
+  assert_eq!(ctrt1_shape::Kind::Square as i32, 2);
+}
+
+#[test]
+fn class_enum_associated_consts() {
+  assert_eq!(Ctrt1Brush::KIND_SOLID, ctrt1_brush::Kind::Solid);
+  assert_eq!(Ctrt1Brush::KIND_HATCHED, ctrt1_brush::Kind::Hatched);
+  assert_eq!(Ctrt1Brush::KIND_HATCHED as i32, 2);
+}
+
+#[test]
+fn class_anonymous_enum_consts() {
+  assert_eq!(Ctrt1Brush::MaxSize, 256);
+  assert_eq!(Ctrt1Brush::MinSize, 1);
+  let size: i32 = Ctrt1Brush::MaxSize;
+  assert_eq!(size, 256);
+}
@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::owned::Ctrt1Resource;
+
+#[test]
+fn unique_ptr_return_is_owned() {
+  assert_eq!(Ctrt1Resource::live_count(), 0);
+  {
+    let resource = Ctrt1Resource::create(7);
+    assert!(!resource.is_null());
+    assert_eq!(resource.id(), 7);
+    assert_eq!(Ctrt1Resource::live_count(), 1);
+  }
+  assert_eq!(Ctrt1Resource::live_count(), 0);
+}
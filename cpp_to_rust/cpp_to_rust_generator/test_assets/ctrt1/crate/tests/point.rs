@@ -0,0 +1,14 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::point::Ctrt1Point;
+use rust_ctrt1::enums::Ctrt1Color;
+
+#[test]
+fn point_debug() {
+  let mut point = Ctrt1Point::new();
+  point.set_x(3);
+  point.set_y(-4);
+  point.set_color(Ctrt1Color::Blue);
+  assert_eq!(format!("{:?}", point),
+             "Ctrt1Point { x: 3, y: -4, color: Blue }");
+}
@@ -0,0 +1,32 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::poly_base::{PolyBase, PolyDerived, PolyHolder};
+use rust_ctrt1::cpp_utils::{self, CppBox, StaticCast};
+
+#[test]
+fn delete_through_empty_base() {
+  let count = PolyDerived::destructor_call_count();
+  let derived = PolyDerived::new(3);
+  assert_eq!(derived.value(), 3);
+  {
+    let base: &PolyBase = derived.static_cast();
+    assert!(!(base as *const PolyBase).is_null());
+  }
+  let base_ptr = cpp_utils::static_cast_mut::<PolyBase, _>(derived.into_raw());
+  let base = unsafe { CppBox::new(base_ptr) };
+  assert_eq!(PolyDerived::destructor_call_count(), count);
+  drop(base);
+  assert_eq!(PolyDerived::destructor_call_count(), count + 1);
+}
+
+#[test]
+fn template_method_instantiation() {
+  let mut derived = PolyDerived::new(5);
+  let holder = {
+    let base: &mut PolyBase = derived.static_cast_mut();
+    unsafe { PolyHolder::new(base as *mut PolyBase) }
+  };
+  let found = holder.find_child_poly_derived();
+  assert!(!found.is_null());
+  assert_eq!(unsafe { (*found).value() }, 5);
+}
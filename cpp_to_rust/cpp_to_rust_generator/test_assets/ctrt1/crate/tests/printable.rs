@@ -0,0 +1,10 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::printable::Ctrt1Size;
+
+#[test]
+fn display_from_stream_operator() {
+  let size = Ctrt1Size::new(640, 480);
+  assert_eq!(size.to_string(), "640x480");
+  assert_eq!(format!("size: {}", size), "size: 640x480");
+}
@@ -0,0 +1,24 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::prototype::{Ctrt1Shape, Ctrt1Square};
+use rust_ctrt1::cpp_utils::{self, CppBox};
+
+#[test]
+fn virtual_clone() {
+  let mut square = Ctrt1Square::new(3);
+  let copy = square.clone();
+  assert!(copy.as_ptr() != square.as_ptr());
+  square.set_side(4);
+  assert_eq!(square.area(), 16);
+  assert_eq!(copy.area(), 9);
+}
+
+#[test]
+fn virtual_clone_through_base() {
+  let square = Ctrt1Square::new(5);
+  let shape_ptr = cpp_utils::static_cast_mut::<Ctrt1Shape, _>(square.into_raw());
+  let shape = unsafe { CppBox::new(shape_ptr) };
+  let copy = shape.clone();
+  assert!(copy.as_ptr() != shape.as_ptr());
+  assert_eq!(copy.area(), 25);
+}
@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::query::Ctrt1Query;
+
+#[test]
+fn consuming_methods_chain() {
+  let query = Ctrt1Query::new().with_limit(10).with_offset(20);
+  assert_eq!(query.limit(), 10);
+  assert_eq!(query.offset(), 20);
+
+  // an owned value can be rebound to itself
+  let query = query.with_limit(5);
+  assert_eq!(query.limit(), 5);
+  assert_eq!(query.offset(), 20);
+}
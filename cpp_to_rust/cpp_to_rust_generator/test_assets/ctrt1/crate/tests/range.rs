@@ -0,0 +1,13 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::range::Ctrt1Range;
+
+#[test]
+fn delegating_constructors() {
+  let range = Ctrt1Range::new((2, 5));
+  assert_eq!((range.start(), range.end()), (2, 5));
+  let range = Ctrt1Range::new(5);
+  assert_eq!((range.start(), range.end()), (0, 5));
+  let range = Ctrt1Range::new(());
+  assert_eq!((range.start(), range.end()), (0, 0));
+}
@@ -0,0 +1,18 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::slicing::{SlicingBase, SlicingDerived};
+
+#[test]
+fn reference_upcast() {
+  let derived = SlicingDerived::new(1, 2);
+  let base: &SlicingBase = derived.as_ref();
+  assert_eq!(base.x(), 1);
+  assert_eq!(derived.y(), 2);
+}
+
+#[test]
+fn slicing_conversion() {
+  let derived = SlicingDerived::new(3, 4);
+  let base = SlicingBase::from(&derived);
+  assert_eq!(base.x(), 3);
+}
@@ -0,0 +1,19 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::spans::{ctrt1_span_double, ctrt1_span_sum};
+
+#[test]
+fn slice_to_const_span() {
+  assert_eq!(ctrt1_span_sum(&[1, 2, 3]), 6);
+  assert_eq!(ctrt1_span_sum(&[]), 0);
+  // only the elements of the slice are visible on the C++ side
+  let values = vec![1, 2, 3, 4, 5];
+  assert_eq!(ctrt1_span_sum(&values[1..3]), 5);
+}
+
+#[test]
+fn mut_slice_to_span() {
+  let mut values = vec![1, 2, 3, 4];
+  ctrt1_span_double(&mut values[..3]);
+  assert_eq!(values, vec![2, 4, 6, 4]);
+}
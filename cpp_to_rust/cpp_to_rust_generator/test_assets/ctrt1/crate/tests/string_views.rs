@@ -0,0 +1,22 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::string_views::{ctrt1_count_spaces, ctrt1_text_size};
+
+#[test]
+fn str_to_string_view() {
+  assert_eq!(ctrt1_text_size("abc"), 3);
+  assert_eq!(ctrt1_text_size(""), 0);
+  // size is in bytes, not characters
+  assert_eq!(ctrt1_text_size("héllo"), 6);
+  assert_eq!(ctrt1_count_spaces("a b c"), 2);
+}
+
+#[test]
+fn str_slice_to_string_view() {
+  // the slice is not null-terminated, so only its length
+  // must be used on the C++ side
+  let text = String::from("one two three four");
+  assert_eq!(ctrt1_text_size(&text[..7]), 7);
+  assert_eq!(ctrt1_count_spaces(&text[..7]), 1);
+  assert_eq!(ctrt1_count_spaces(&text), 3);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "tracing")]
+
+extern crate rust_ctrt1;
+
+use rust_ctrt1::features::Ctrt1Features;
+use rust_ctrt1::tracing::{span, Event, Metadata, Subscriber};
+use std::sync::{Arc, Mutex};
+
+struct SpanRecorder {
+  names: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for SpanRecorder {
+  fn enabled(&self, _metadata: &Metadata) -> bool {
+    true
+  }
+  fn new_span(&self, span: &span::Attributes) -> span::Id {
+    let mut names = self.names.lock().unwrap();
+    names.push(span.metadata().name().to_string());
+    span::Id::from_u64(names.len() as u64)
+  }
+  fn record(&self, _span: &span::Id, _values: &span::Record) {}
+  fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+  fn event(&self, _event: &Event) {}
+  fn enter(&self, _span: &span::Id) {}
+  fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn tracing_span_per_call() {
+  let names = Arc::new(Mutex::new(Vec::new()));
+  let recorder = SpanRecorder { names: names.clone() };
+  rust_ctrt1::tracing::subscriber::with_default(recorder, || {
+    let value = Ctrt1Features::new();
+    assert_eq!(value.base(), 1);
+  });
+  let names = names.lock().unwrap();
+  assert!(names.iter().any(|name| name == "Ctrt1Features::base"));
+}
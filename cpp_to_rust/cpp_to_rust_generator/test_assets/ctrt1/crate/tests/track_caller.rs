@@ -0,0 +1,23 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::utils::ctrt1_user_name;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn panic_points_to_caller() {
+  assert_eq!(ctrt1_user_name(5), "user5");
+
+  let panic_file = Arc::new(Mutex::new(None));
+  let panic_file_in_hook = panic_file.clone();
+  panic::set_hook(Box::new(move |info| {
+    *panic_file_in_hook.lock().unwrap() = info.location().map(|x| x.file().to_string());
+  }));
+  let result = panic::catch_unwind(|| ctrt1_user_name(-1));
+  let _ = panic::take_hook();
+  assert!(result.is_err());
+  let panic_file = panic_file.lock().unwrap().clone().expect("no panic location");
+  assert!(panic_file.ends_with("track_caller.rs"),
+          "unexpected panic location: {}",
+          panic_file);
+}
@@ -7,3 +7,32 @@ fn utils1() {
   assert_eq!(ctrt1_abs(0), 0);
   assert_eq!(ctrt1_abs(-2), 2);
 }
+
+#[test]
+fn pointer_sized_integers() {
+  use rust_ctrt1::utils::{ctrt1_size_identity, ctrt1_ptrdiff_negate};
+  let large: usize = usize::max_value() - 1;
+  assert_eq!(ctrt1_size_identity(large), large);
+  let large_signed: isize = isize::max_value() - 1;
+  assert_eq!(ctrt1_ptrdiff_negate(large_signed), -large_signed);
+}
+
+#[test]
+fn string_buffer_out() {
+  use rust_ctrt1::utils::ctrt1_repeat_text;
+  assert_eq!(ctrt1_repeat_text(0), "");
+  assert_eq!(ctrt1_repeat_text(3), "ababab");
+  // doesn't fit in the initial buffer
+  let long_text = ctrt1_repeat_text(1000);
+  assert_eq!(long_text.len(), 2000);
+  assert!(long_text.chars().all(|c| c == 'a' || c == 'b'));
+}
+
+#[test]
+fn overloaded_function() {
+  use rust_ctrt1::utils::ctrt1_clamp;
+  assert_eq!(ctrt1_clamp((5, 3)), 3);
+  assert_eq!(ctrt1_clamp((-1, 3)), 0);
+  assert_eq!(ctrt1_clamp((5, 1, 10)), 5);
+  assert_eq!(ctrt1_clamp((0, 1, 10)), 1);
+}
@@ -0,0 +1,18 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::validity::Ctrt1Cursor;
+
+#[test]
+fn validity_methods_are_consistent() {
+  for &position in &[-1, 0, 5] {
+    let cursor = Ctrt1Cursor::new(position);
+    assert_eq!(cursor.is_valid(), position >= 0);
+    assert_eq!(cursor.is_null(), !cursor.is_valid());
+  }
+}
+
+#[test]
+fn into_option() {
+  assert_eq!(Ctrt1Cursor::new(3).into_option().map(|c| c.position()), Some(3));
+  assert!(Ctrt1Cursor::new(-1).into_option().is_none());
+}
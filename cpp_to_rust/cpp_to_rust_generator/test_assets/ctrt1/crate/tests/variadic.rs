@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::variadic::ctrt1_sum_ints;
+
+#[test]
+fn variadic_function_as_slice() {
+  assert_eq!(ctrt1_sum_ints(0, &[]), 0);
+  assert_eq!(ctrt1_sum_ints(3, &[1, 2, 3]), 6);
+}
+
+#[test]
+#[should_panic(expected = "at most 4 items are supported")]
+fn variadic_function_too_many_items() {
+  ctrt1_sum_ints(5, &[1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,18 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::variants::{ctrt1_scale_number, VariantCIntCDouble, VariantCIntCDoubleRef};
+
+#[test]
+fn std_variant_round_trip() {
+  let int_value = VariantCIntCDouble::new(3i32);
+  match ctrt1_scale_number(&int_value, 2).get() {
+    VariantCIntCDoubleRef::CInt(value) => assert_eq!(*value, 6),
+    VariantCIntCDoubleRef::CDouble(_) => panic!("int alternative expected"),
+  }
+
+  let double_value = VariantCIntCDouble::new(1.25f64);
+  match ctrt1_scale_number(&double_value, 4).get() {
+    VariantCIntCDoubleRef::CInt(_) => panic!("double alternative expected"),
+    VariantCIntCDoubleRef::CDouble(value) => assert_eq!(*value, 5.0),
+  }
+}
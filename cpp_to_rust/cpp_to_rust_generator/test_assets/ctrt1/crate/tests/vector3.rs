@@ -0,0 +1,15 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::vector3::Ctrt1Vector3;
+
+fn components(vector: &Ctrt1Vector3) -> (f64, f64, f64) {
+  (vector.x(), vector.y(), vector.z())
+}
+
+#[test]
+fn scalar_times_vector() {
+  let vec3 = Ctrt1Vector3::new(1.0, -2.0, 0.5);
+  let scaled = 2.0 * &vec3;
+  assert_eq!(components(&scaled), (2.0, -4.0, 1.0));
+}
+
@@ -0,0 +1,10 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::versioned::ctrt1_versioned::{Counter, doubled};
+
+#[test]
+fn inline_namespace() {
+  let counter = Counter::new(5);
+  assert_eq!(counter.value(), 5);
+  assert_eq!(doubled(21), 42);
+}
@@ -0,0 +1,14 @@
+extern crate rust_ctrt1;
+
+use rust_ctrt1::widget::Ctrt1Widget;
+
+#[test]
+fn exposed_protected_methods() {
+  let mut widget = Ctrt1Widget::new();
+  widget.update();
+  widget.paint_event(10);
+  assert_eq!(widget.paint_count(), 2);
+  assert_eq!(widget.painted_area(), 11);
+  widget.repaint(3);
+  assert_eq!(widget.painted_area(), 14);
+}
@@ -30,6 +30,109 @@ mod tests {
     }
     assert!(value1.borrow().clone() == 42);
   }
+
+  #[test]
+  fn test_clone_uses_virtual_clone() {
+    use CppVirtualClone;
+    struct Struct2 {
+      value: i32,
+    }
+
+    unsafe extern "C" fn struct2_delete(this_ptr: *mut Struct2) {
+      drop(Box::from_raw(this_ptr));
+    }
+
+    impl CppDeletable for Struct2 {
+      fn deleter() -> Deleter<Self> {
+        struct2_delete
+      }
+    }
+
+    impl CppVirtualClone for Struct2 {
+      fn virtual_clone(&self) -> CppBox<Self> {
+        unsafe { CppBox::new(Box::into_raw(Box::new(Struct2 { value: self.value }))) }
+      }
+    }
+
+    let object1 = unsafe { CppBox::new(Box::into_raw(Box::new(Struct2 { value: 7 }))) };
+    let object2 = object1.clone();
+    assert!(object1.as_ptr() != object2.as_ptr());
+    assert_eq!(object2.value, 7);
+    let null_object: CppBox<Struct2> = CppBox::default();
+    assert!(null_object.clone().is_null());
+  }
+
+  /// Counts calls by incrementing the `i32` pointed to by `ptr`.
+  fn increment_free_count(ptr: *mut ::std::os::raw::c_void) {
+    unsafe {
+      *(ptr as *mut i32) += 1;
+    }
+  }
+
+  #[test]
+  fn test_owned_void_ptr_drop_calls_free_once() {
+    use OwnedVoidPtr;
+    let mut free_count = 0;
+    let ptr = &mut free_count as *mut i32 as *mut ::std::os::raw::c_void;
+    {
+      let guard = unsafe { OwnedVoidPtr::new(ptr, increment_free_count) };
+      assert_eq!(guard.as_mut_ptr(), ptr);
+    }
+    assert_eq!(free_count, 1);
+  }
+
+  #[test]
+  fn test_owned_void_ptr_into_raw() {
+    use OwnedVoidPtr;
+    let mut free_count = 0;
+    let ptr = &mut free_count as *mut i32 as *mut ::std::os::raw::c_void;
+    let guard = unsafe { OwnedVoidPtr::new(ptr, increment_free_count) };
+    assert_eq!(guard.into_raw(), ptr);
+    assert_eq!(free_count, 0);
+  }
+
+  #[test]
+  fn test_vec_out_resizes_vector() {
+    use VecOut;
+    let mut vec = vec![9, 9, 9, 9];
+    let output = VecOut::new(&mut vec);
+    let data = (output.reserve)(output.vec, 3) as *mut i32;
+    for i in 0..3 {
+      unsafe { *data.offset(i as isize) = i + 1 };
+    }
+    (output.set_len)(output.vec, 3);
+    assert_eq!(vec, vec![1, 2, 3]);
+
+    // the vector stays empty if the elements are not written
+    let output = VecOut::new(&mut vec);
+    (output.reserve)(output.vec, 5);
+    assert!(vec.is_empty());
+  }
+
+  #[test]
+  fn test_closure_calls_and_drops_function() {
+    use Closure;
+    use std::os::raw::c_void;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicIsize, Ordering};
+    extern "C" fn callback(data: *mut c_void, arg0: i32) -> i32 {
+      unsafe { Closure::with_data(data, |func: &mut Box<FnMut(i32) -> i32 + Send>| func(arg0)) }
+    }
+    let counter = Arc::new(AtomicIsize::new(0));
+    let counter2 = counter.clone();
+    let func: Box<FnMut(i32) -> i32 + Send> = Box::new(move |x| {
+      counter2.fetch_add(x as isize, Ordering::SeqCst) as i32 + x
+    });
+    let closure = Closure::new(func, callback as *const c_void);
+    assert_eq!(Arc::strong_count(&counter), 2);
+    let callback: extern "C" fn(*mut c_void, i32) -> i32 =
+      unsafe { std::mem::transmute(closure.callback) };
+    assert_eq!(callback(closure.data, 2), 2);
+    assert_eq!(callback(closure.data, 3), 5);
+    (closure.deleter)(closure.data);
+    assert_eq!(Arc::strong_count(&counter), 1);
+    assert_eq!(counter.load(Ordering::SeqCst), 5);
+  }
 }
 
 /// Deleter function type.
@@ -156,6 +259,31 @@ impl<T: CppDeletable> Drop for CppBox<T> {
   }
 }
 
+/// Indicates that a copy of the object can be created on the heap.
+///
+/// This trait is automatically implemented by `cpp_to_rust` for classes
+/// that have a virtual method returning a copy of the object
+/// (e.g. `virtual Base* clone() const`), if it was requested using
+/// `Config::add_virtual_clone`. Such classes usually don't have
+/// a public copy constructor. `CppBox` containing an object of such class
+/// implements `Clone`.
+pub trait CppVirtualClone: CppDeletable {
+  /// Creates a copy of the object by calling its C++ clone method.
+  /// The caller becomes the owner of the new object.
+  fn virtual_clone(&self) -> CppBox<Self>;
+}
+
+impl<T: CppVirtualClone> Clone for CppBox<T> {
+  /// Clones the object using `CppVirtualClone`.
+  /// Cloning a `CppBox` containing a null pointer produces another null `CppBox`.
+  fn clone(&self) -> CppBox<T> {
+    match unsafe { self.ptr.as_ref() } {
+      Some(value) => value.virtual_clone(),
+      None => CppBox::default(),
+    }
+  }
+}
+
 impl<T: CppDeletable> Default for CppBox<T> {
   fn default() -> CppBox<T> {
     CppBox {
@@ -165,6 +293,272 @@ impl<T: CppDeletable> Default for CppBox<T> {
   }
 }
 
+/// An owned untyped pointer returned by a C or C++ function.
+///
+/// Some functions return `void*` pointing to an allocation that
+/// must be released with a specific function (e.g. `free`).
+/// `OwnedVoidPtr` calls that function when dropped.
+/// `cpp_to_rust` uses this type for return values configured with
+/// `Config::add_owned_void_ptr_return`.
+pub struct OwnedVoidPtr {
+  ptr: *mut std::os::raw::c_void,
+  free_function: fn(*mut std::os::raw::c_void),
+}
+
+impl OwnedVoidPtr {
+  /// Encapsulates `ptr` that should be released by calling `free_function`.
+  ///
+  /// `free_function` will not be called for a null pointer.
+  pub unsafe fn new(ptr: *mut std::os::raw::c_void,
+                    free_function: fn(*mut std::os::raw::c_void))
+                    -> OwnedVoidPtr {
+    OwnedVoidPtr {
+      ptr: ptr,
+      free_function: free_function,
+    }
+  }
+  /// Returns constant raw pointer.
+  pub fn as_ptr(&self) -> *const std::os::raw::c_void {
+    self.ptr
+  }
+  /// Returns mutable raw pointer.
+  pub fn as_mut_ptr(&self) -> *mut std::os::raw::c_void {
+    self.ptr
+  }
+  /// Returns the pointer and destroys the guard without calling the free function.
+  /// The caller becomes responsible for releasing the allocation.
+  pub fn into_raw(mut self) -> *mut std::os::raw::c_void {
+    let ptr = self.ptr;
+    self.ptr = std::ptr::null_mut();
+    ptr
+  }
+  /// Returns true if the pointer is null.
+  pub fn is_null(&self) -> bool {
+    self.ptr.is_null()
+  }
+}
+
+impl Drop for OwnedVoidPtr {
+  fn drop(&mut self) {
+    if !self.ptr.is_null() {
+      (self.free_function)(self.ptr);
+    }
+  }
+}
+
+/// A string slice passed to a C++ function accepting `std::string_view`.
+///
+/// `cpp_to_rust` converts `&str` arguments to this type. The C++ side
+/// receives a view of the slice's bytes without copying them, so the view
+/// is only valid during the call. This type has the same layout as
+/// the `cpp_utils_str_ref` struct declared in the C++ wrapper library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct StrRef {
+  data: *const std::os::raw::c_char,
+  size: usize,
+}
+
+impl StrRef {
+  /// Creates a `StrRef` pointing to the bytes of `value`.
+  pub fn new(value: &str) -> StrRef {
+    StrRef {
+      data: value.as_ptr() as *const std::os::raw::c_char,
+      size: value.len(),
+    }
+  }
+}
+
+/// A slice passed to a C++ function accepting `std::span`.
+///
+/// `cpp_to_rust` converts `&[T]` and `&mut [T]` arguments to this type.
+/// The C++ side receives a span over the slice's elements without copying them,
+/// so the span is only valid during the call. This type has the same layout as
+/// the `cpp_utils_slice_ref` struct declared in the C++ wrapper library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SliceRef {
+  data: *mut std::os::raw::c_void,
+  size: usize,
+}
+
+impl SliceRef {
+  /// Creates a `SliceRef` pointing to the elements of `value`
+  /// for a span of const elements.
+  pub fn new<T>(value: &[T]) -> SliceRef {
+    SliceRef {
+      data: value.as_ptr() as *mut std::os::raw::c_void,
+      size: value.len(),
+    }
+  }
+
+  /// Creates a `SliceRef` pointing to the elements of `value`
+  /// for a span of mutable elements.
+  pub fn new_mut<T>(value: &mut [T]) -> SliceRef {
+    SliceRef {
+      data: value.as_mut_ptr() as *mut std::os::raw::c_void,
+      size: value.len(),
+    }
+  }
+}
+
+/// Rust vector passed to an FFI function returning a `std::initializer_list`.
+/// The C++ side reserves space in the vector, copies the list's elements to it
+/// and then sets the vector's length. The layout must match
+/// the `cpp_utils_vec_out` struct declared in the C++ wrapper library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VecOut {
+  vec: *mut std::os::raw::c_void,
+  reserve: extern "C" fn(*mut std::os::raw::c_void, usize) -> *mut std::os::raw::c_void,
+  set_len: extern "C" fn(*mut std::os::raw::c_void, usize),
+}
+
+/// Clears the `Vec<T>` pointed to by `vec`, reserves space for `size`
+/// elements and returns a pointer to this space. The vector stays empty
+/// until `set_vec_len` is called.
+extern "C" fn reserve_vec<T>(vec: *mut std::os::raw::c_void,
+                             size: usize)
+                             -> *mut std::os::raw::c_void {
+  let vec = unsafe { &mut *(vec as *mut Vec<T>) };
+  vec.clear();
+  vec.reserve_exact(size);
+  vec.as_mut_ptr() as *mut std::os::raw::c_void
+}
+
+/// Sets length of the `Vec<T>` pointed to by `vec` to `size` after
+/// the C++ side has written `size` elements to the space returned by
+/// `reserve_vec`. Aborts the process if `size` exceeds the reserved space.
+extern "C" fn set_vec_len<T>(vec: *mut std::os::raw::c_void, size: usize) {
+  let vec = unsafe { &mut *(vec as *mut Vec<T>) };
+  if size > vec.capacity() {
+    // unwinding out of an `extern "C"` function is not allowed
+    std::process::abort();
+  }
+  unsafe { vec.set_len(size) };
+}
+
+impl VecOut {
+  /// Creates a `VecOut` that will fill `value`. Only plain types
+  /// (numbers and enums) are supported as `T`.
+  pub fn new<T>(value: &mut Vec<T>) -> VecOut {
+    VecOut {
+      vec: value as *mut Vec<T> as *mut std::os::raw::c_void,
+      reserve: reserve_vec::<T>,
+      set_len: set_vec_len::<T>,
+    }
+  }
+}
+
+/// Rust closure passed to an FFI function taking a `std::function` argument.
+/// The C++ side wraps it into a function object that calls `callback`
+/// with `data` and frees `data` using `deleter` when the last copy
+/// of the `std::function` is destroyed. The layout must match
+/// the `cpp_utils_closure` struct declared in the C++ wrapper library.
+///
+/// The C++ side may call the function object from any thread, so the
+/// closure must be `Send`. The closure is not reentrant: if the C++ side
+/// calls the function object again while it's running, the process
+/// is aborted (see `Closure::with_data`).
+#[repr(C)]
+#[derive(Debug)]
+pub struct Closure {
+  callback: *const std::os::raw::c_void,
+  data: *mut std::os::raw::c_void,
+  deleter: extern "C" fn(*mut std::os::raw::c_void),
+}
+
+/// Drops the `Box<T>` converted to `data` by `Closure::new`.
+extern "C" fn drop_box<T>(data: *mut std::os::raw::c_void) {
+  drop(unsafe { Box::from_raw(data as *mut T) });
+}
+
+impl Closure {
+  /// Creates a `Closure` taking ownership of `data`. `callback` must be
+  /// an `extern "C"` function receiving a pointer to `data` as the first argument
+  /// and the arguments of the `std::function` as the rest of the arguments.
+  /// `callback` should access `data` using `Closure::with_data`.
+  pub fn new<T: Send + 'static>(data: T, callback: *const std::os::raw::c_void) -> Closure {
+    Closure {
+      callback: callback,
+      data: Box::into_raw(Box::new(std::cell::RefCell::new(data))) as *mut std::os::raw::c_void,
+      deleter: drop_box::<std::cell::RefCell<T>>,
+    }
+  }
+
+  /// Calls `func` with a mutable reference to the value passed
+  /// to `Closure::new`. `data` is the pointer the callback received.
+  /// Aborts the process if the value is already in use, i.e. if the closure
+  /// is called again by the C++ side while it's running.
+  ///
+  /// # Safety
+  ///
+  /// `data` must point to the data of an existing `Closure` created with value of type `T`.
+  pub unsafe fn with_data<T, R, F: FnOnce(&mut T) -> R>(data: *mut std::os::raw::c_void,
+                                                         func: F)
+                                                         -> R {
+    let cell = &*(data as *const std::cell::RefCell<T>);
+    match cell.try_borrow_mut() {
+      Ok(mut value) => func(&mut *value),
+      // unwinding out of an `extern "C"` callback is not allowed
+      Err(_) => std::process::abort(),
+    }
+  }
+}
+
+/// Error returned by `TryFrom<i32>` implementations of generated enums
+/// when the value doesn't correspond to any variant of the enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownEnumValue {
+  /// Name of the enum type
+  pub enum_name: &'static str,
+  /// The value that failed to convert
+  pub value: i64,
+}
+
+impl std::fmt::Display for UnknownEnumValue {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "unknown value of {}: {}", self.enum_name, self.value)
+  }
+}
+
+impl std::error::Error for UnknownEnumValue {}
+
+/// Error returned by generated constructors declared as fallible
+/// when the constructed C++ object is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstructionError {
+  /// Name of the C++ class
+  pub class_name: &'static str,
+}
+
+impl std::fmt::Display for ConstructionError {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "failed to construct {}", self.class_name)
+  }
+}
+
+impl std::error::Error for ConstructionError {}
+
+/// Error returned by generated wrappers of C++ functions that report
+/// errors errno-style, i.e. by returning a special value and storing
+/// the error code in a separate location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CppError {
+  /// Name of the C++ function
+  pub function_name: &'static str,
+  /// Error code obtained after the call
+  pub code: i32,
+}
+
+impl std::fmt::Display for CppError {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{} failed with error code {}", self.function_name, self.code)
+  }
+}
+
+impl std::error::Error for CppError {}
+
 /// This module contains `NewUninitialized` trait.
 /// It's an implementation detail of `cpp_to_rust` and should not be used directly.
 pub mod new_uninitialized {
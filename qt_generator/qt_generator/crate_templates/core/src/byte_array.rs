@@ -0,0 +1,11 @@
+include_generated!();
+
+use std;
+
+/// Allows to access contents of a `ByteArray` as a byte slice without copying.
+/// The slice borrows the byte array, so it can't be modified while the slice is alive.
+impl AsRef<[u8]> for ::byte_array::ByteArray {
+  fn as_ref(&self) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(self.const_data() as *const u8, self.size() as usize) }
+  }
+}
@@ -0,0 +1,40 @@
+include_generated!();
+
+/// Allows to convert UTF-16 code units to Qt characters
+impl From<u16> for ::char::Char {
+  fn from(value: u16) -> ::char::Char {
+    ::char::Char::new(value)
+  }
+}
+
+/// Allows to convert Qt characters to UTF-16 code units
+impl<'a> From<&'a ::char::Char> for u16 {
+  fn from(c: &'a ::char::Char) -> u16 {
+    c.unicode()
+  }
+}
+
+impl ::char::Char {
+  /// Creates Qt character from a Rust `char`.
+  ///
+  /// `QChar` holds a single UTF-16 code unit, so only characters from
+  /// the Basic Multilingual Plane can be represented. Returns `None`
+  /// if `c` is outside of the BMP and would require a surrogate pair.
+  pub fn from_rust_char(c: char) -> Option<::char::Char> {
+    let value = c as u32;
+    if value > 0xffff {
+      None
+    } else {
+      Some(::char::Char::new(value as u16))
+    }
+  }
+
+  /// Converts Qt character to a Rust `char`.
+  ///
+  /// Returns `None` if this character is a lone UTF-16 surrogate
+  /// (a half of a non-BMP character), because such values
+  /// are not valid Unicode scalar values.
+  pub fn to_rust_char(&self) -> Option<char> {
+    ::std::char::from_u32(self.unicode() as u32)
+  }
+}
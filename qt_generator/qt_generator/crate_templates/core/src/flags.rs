@@ -35,6 +35,17 @@ impl<E: FlaggableEnum> Flags<E> {
   pub fn is_empty(self) -> bool {
     self.value == 0
   }
+  /// Returns an iterator over variants of `E` that are enabled in `self`.
+  /// A variant is yielded if all its non-zero bits are enabled.
+  pub fn iter_set(self) -> impl Iterator<Item = E> {
+    E::flag_values()
+      .iter()
+      .cloned()
+      .filter(move |flag| {
+                let flag_value = flag.clone().to_flag_value();
+                flag_value != 0 && self.value & flag_value == flag_value
+              })
+  }
 }
 
 
@@ -67,11 +78,13 @@ impl<E: FlaggableEnum, T: EnumOrFlags<E>> std::ops::BitXor<T> for Flags<E> {
 }
 
 /// Enum type with values suitable for constructing OR-combinations for `Flags`.
-pub trait FlaggableEnum: Sized + Clone {
+pub trait FlaggableEnum: Sized + Clone + 'static {
   /// Returns integer value of this enum variant.
   fn to_flag_value(self) -> c_int;
   /// Returns name of the type for debug output.
   fn enum_name() -> &'static str;
+  /// Returns all variants of the enum.
+  fn flag_values() -> &'static [Self];
 }
 
 /// Trait representing types that can be converted to `Flags`.
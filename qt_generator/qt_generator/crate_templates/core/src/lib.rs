@@ -94,6 +94,14 @@
 //! ensure proper de-initialization of the value. It's not possible to transfer ownership
 //! of such object to C++ side.
 //!
+//! Many value-like types (`QString`, `QList`, `QByteArray`, etc.) are
+//! [implicitly shared](http://doc.qt.io/qt-5/implicit-sharing.html) in C++:
+//! copies share the same data until one of them is modified. This is transparent in Rust.
+//! A copy created with the copy constructor (e.g. `String::new(&other)`) may share data
+//! with the original, but all methods that receive `&mut self` call non-const C++ methods,
+//! and these methods detach the object from the shared data before modifying it.
+//! Mutating a copy never affects the original object.
+//!
 //! All other types are stored in C++ heap and handled using raw and smart pointers.
 //! Raw pointer types (e.g. `*mut qt_core::object::Object`) are the same pointers as in C++.
 //! There is no guarantee that the pointer is valid at any time, and the null pointer
@@ -0,0 +1,56 @@
+include_generated!();
+
+/// Provides access to Qt's `qobject_cast` conversion.
+///
+/// This trait is automatically implemented by `cpp_to_rust`.
+/// If `T` is a class derived from `QObject` that has its own meta-object
+/// (i.e. declared with `Q_OBJECT` macro), `Cast<T>` is implemented for `Object`.
+///
+/// Unlike `dynamic_cast`, `qobject_cast` uses Qt's meta-object system
+/// to check the type of the object at runtime, so it works without RTTI.
+/// Conversion returns `None` if the object is actually not an instance of
+/// the target type. `Object::downcast` and `Object::downcast_mut`
+/// are more convenient to use than methods of this trait.
+///
+/// For each implementation of this trait, `TryFrom<&Object>` is also implemented
+/// for `&T` (and `TryFrom<&mut Object>` for `&mut T`), so `try_into`
+/// can be used to perform the conversion.
+pub trait Cast<T> {
+  /// Convert type of a const reference.
+  /// Returns `None` if `self` is not an instance of `T`.
+  fn qobject_cast(&self) -> Option<&T>;
+  /// Convert type of a mutable reference.
+  /// Returns `None` if `self` is not an instance of `T`.
+  fn qobject_cast_mut(&mut self) -> Option<&mut T>;
+}
+
+impl Object {
+  /// Converts this object to a reference to a derived class `T`
+  /// using `qobject_cast`. Returns `None` if the object is not an instance of `T`.
+  pub fn downcast<T>(&self) -> Option<&T>
+    where Self: Cast<T>
+  {
+    self.qobject_cast()
+  }
+
+  /// Converts this object to a mutable reference to a derived class `T`
+  /// using `qobject_cast`. Returns `None` if the object is not an instance of `T`.
+  pub fn downcast_mut<T>(&mut self) -> Option<&mut T>
+    where Self: Cast<T>
+  {
+    self.qobject_cast_mut()
+  }
+}
+
+/// Error returned by `TryFrom` conversions based on `qobject_cast`
+/// if the object is not an instance of the target type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastError;
+
+impl ::std::fmt::Display for CastError {
+  fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    write!(f, "object is not an instance of the target type")
+  }
+}
+
+impl ::std::error::Error for CastError {}
@@ -34,4 +34,14 @@ impl ::string::String {
       std::str::from_utf8_unchecked(bytes).to_string()
     }
   }
+
+  /// Splits the string into substrings wherever `separator` occurs
+  /// and returns them as `std` strings. Empty substrings are kept,
+  /// and the separator is case sensitive (as in `QString::split` by default).
+  pub fn split_to_std<S: AsRef<str>>(&self, separator: S) -> Vec<std::string::String> {
+    let separator = ::string::String::from_std_str(separator);
+    let parts = self.split(&separator);
+    let parts: &::string_list::StringList = &parts;
+    parts.into()
+  }
 }
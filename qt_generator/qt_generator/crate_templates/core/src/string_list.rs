@@ -0,0 +1,10 @@
+include_generated!();
+
+use std;
+
+/// Allows to convert Qt string lists to vectors of `std` strings
+impl<'a> From<&'a ::string_list::StringList> for Vec<std::string::String> {
+  fn from(list: &'a ::string_list::StringList) -> Vec<std::string::String> {
+    (0..list.size()).map(|i| list.at(i).to_std_string()).collect()
+  }
+}
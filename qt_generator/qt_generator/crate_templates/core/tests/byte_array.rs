@@ -0,0 +1,23 @@
+extern crate qt_core;
+
+use qt_core::byte_array::ByteArray;
+use qt_core::string::String;
+
+fn byte_count<T: AsRef<[u8]>>(bytes: T) -> usize {
+  bytes.as_ref().len()
+}
+
+#[test]
+fn byte_array_as_ref() {
+  let byte_array = String::from("text1 ж").to_utf8();
+  let bytes: &[u8] = byte_array.as_ref();
+  assert_eq!(bytes, "text1 ж".as_bytes());
+  assert_eq!(byte_count(&byte_array), 8);
+}
+
+#[test]
+fn empty_byte_array_as_ref() {
+  let byte_array = ByteArray::new(());
+  let bytes: &[u8] = byte_array.as_ref();
+  assert!(bytes.is_empty());
+}
@@ -0,0 +1,27 @@
+extern crate qt_core;
+
+use qt_core::char::Char;
+
+#[test]
+fn char_from_rust_char() {
+  for c in &['a', 'Z', '0', 'é', 'Ж', '€', '\u{ffff}'] {
+    let qchar = Char::from_rust_char(*c).expect("BMP char should fit QChar");
+    assert_eq!(qchar.unicode(), *c as u32 as u16);
+    assert_eq!(qchar.to_rust_char(), Some(*c));
+  }
+}
+
+#[test]
+fn char_from_rust_char_non_bmp() {
+  assert!(Char::from_rust_char('\u{1f600}').is_none());
+  assert!(Char::from_rust_char('\u{10000}').is_none());
+}
+
+#[test]
+fn char_surrogate_to_rust_char() {
+  let high = Char::from(0xd83du16);
+  assert_eq!(u16::from(&high), 0xd83d);
+  assert_eq!(high.to_rust_char(), None);
+  let low = Char::from(0xde00u16);
+  assert_eq!(low.to_rust_char(), None);
+}
@@ -0,0 +1,34 @@
+extern crate qt_core;
+use qt_core::core_application::CoreApplication;
+use qt_core::timer::Timer;
+use qt_core::string::String;
+use qt_core::connection::Signal;
+
+use qt_core::libc::c_void;
+use qt_core::slots::raw::RawSlotNoArgs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NAME_CHANGES: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn name_changed(_data: *mut c_void) {
+  NAME_CHANGES.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn direct_signals() {
+  CoreApplication::create_and_exit(|app| {
+    let mut slot1 = RawSlotNoArgs::new();
+    unsafe {
+      slot1.set(name_changed, std::ptr::null_mut());
+    }
+    let mut timer = Timer::new();
+    // `objectNameChanged` is inherited from `QObject`
+    timer.object_name_changed().connect(slot1.as_ref());
+    timer.set_object_name(&String::from("timer1"));
+    assert_eq!(NAME_CHANGES.load(Ordering::SeqCst), 1);
+
+    timer.timeout().connect(&app.slots().quit());
+    timer.start(0);
+    CoreApplication::exec()
+  })
+}
@@ -0,0 +1,36 @@
+extern crate qt_core;
+use qt_core::core_application::CoreApplication;
+use qt_core::core_event::Event;
+use qt_core::core_event::event::Type;
+use qt_core::event_filter::EventFilter;
+use qt_core::object::Object;
+use std::cell::Cell;
+
+#[test]
+fn event_filter() {
+  CoreApplication::create_and_exit(|_app| {
+    let filtered_count = Cell::new(0);
+    let mut object = Object::new(());
+    let mut event = Event::new(Type::User);
+    {
+      let _filter = EventFilter::install(&mut object, |_watched, event| {
+        if event.type_() == Type::User {
+          filtered_count.set(filtered_count.get() + 1);
+          true
+        } else {
+          false
+        }
+      });
+      unsafe {
+        CoreApplication::send_event(object.as_mut_ptr(), event.as_mut_ptr());
+      }
+      assert_eq!(filtered_count.get(), 1);
+    }
+    // the filter is removed when dropped
+    unsafe {
+      CoreApplication::send_event(object.as_mut_ptr(), event.as_mut_ptr());
+    }
+    assert_eq!(filtered_count.get(), 1);
+    0
+  })
+}
@@ -0,0 +1,12 @@
+extern crate qt_core;
+
+use qt_core::flags::FlaggableEnum;
+use qt_core::qt::AlignmentFlag;
+
+#[test]
+fn iter_set() {
+  let flags = AlignmentFlag::Left | AlignmentFlag::Top;
+  let mut set_flags: Vec<_> = flags.iter_set().collect();
+  set_flags.sort_by_key(|flag| flag.clone().to_flag_value());
+  assert_eq!(set_flags, vec![AlignmentFlag::Left, AlignmentFlag::Top]);
+}
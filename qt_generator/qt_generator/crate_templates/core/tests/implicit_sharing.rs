@@ -0,0 +1,13 @@
+extern crate qt_core;
+
+use qt_core::string::String;
+
+#[test]
+fn mutate_copy_of_string() {
+  let original = String::from("text");
+  let mut copy = String::new(&original);
+  assert_eq!(copy.to_std_string(), "text");
+  copy.append(&String::from("1"));
+  assert_eq!(copy.to_std_string(), "text1");
+  assert_eq!(original.to_std_string(), "text");
+}
@@ -6,6 +6,9 @@ use qt_core::string_list_model::StringListModel;
 use qt_core::abstract_item_model::AbstractItemModel;
 use qt_core::abstract_table_model::AbstractTableModel;
 use qt_core::qt::ItemDataRole;
+use qt_core::object::{Object, CastError};
+use qt_core::timer::Timer;
+use std::convert::TryInto;
 
 #[test]
 fn models_and_casts() {
@@ -44,3 +47,44 @@ fn models_and_casts() {
   let table_model_attempt: Option<&AbstractTableModel> = abstract_model.dynamic_cast();
   assert!(table_model_attempt.is_none());
 }
+
+#[test]
+fn qobject_downcast() {
+  let string_list = StringList::new(());
+  let mut string_list_model = StringListModel::new(&string_list);
+  let object: &mut Object = string_list_model.static_cast_mut();
+  assert!(object.downcast::<StringListModel>().is_some());
+  assert!(object.downcast::<AbstractItemModel>().is_some());
+  assert!(object.downcast::<Timer>().is_none());
+  {
+    let model_back = object
+      .downcast_mut::<StringListModel>()
+      .expect("qobject_cast should be successful");
+    assert_eq!(model_back.row_count(()), 0);
+  }
+  assert!(object.downcast_mut::<Timer>().is_none());
+}
+
+#[test]
+fn qobject_try_into() {
+  let string_list = StringList::new(());
+  let mut string_list_model = StringListModel::new(&string_list);
+  let object: &mut Object = string_list_model.static_cast_mut();
+  {
+    let object_ref: &Object = object;
+    let model: &StringListModel = object_ref
+      .try_into()
+      .expect("qobject_cast should be successful");
+    assert_eq!(model.row_count(()), 0);
+    let timer: Result<&Timer, CastError> = object_ref.try_into();
+    assert_eq!(timer.err(), Some(CastError));
+  }
+  {
+    let model: &mut StringListModel = (&mut *object)
+      .try_into()
+      .expect("qobject_cast should be successful");
+    assert_eq!(model.row_count(()), 0);
+  }
+  let timer: Result<&mut Timer, CastError> = object.try_into();
+  assert!(timer.is_err());
+}
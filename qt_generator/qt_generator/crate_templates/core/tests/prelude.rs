@@ -0,0 +1,14 @@
+extern crate qt_core;
+
+use qt_core::prelude::*;
+use qt_core::qt::AlignmentFlag;
+
+#[test]
+fn flags_with_prelude() {
+  let flags = AlignmentFlag::Left | AlignmentFlag::Top;
+  assert!(flags.test_flag(AlignmentFlag::Left));
+  assert!(flags.test_flag(AlignmentFlag::Top));
+  assert!(!flags.test_flag(AlignmentFlag::Right));
+  assert_eq!(flags.to_int(),
+             AlignmentFlag::Left.to_flag_value() | AlignmentFlag::Top.to_flag_value());
+}
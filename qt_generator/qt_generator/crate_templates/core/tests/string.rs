@@ -0,0 +1,26 @@
+extern crate qt_core;
+
+use qt_core::string::String;
+use qt_core::string_list::StringList;
+
+#[test]
+fn split() {
+  let string = String::from("a,bc,,d");
+  let parts = string.split(&String::from(","));
+  let parts: &StringList = &parts;
+  let parts: Vec<std::string::String> = parts.into();
+  assert_eq!(parts, vec!["a", "bc", "", "d"]);
+
+  assert_eq!(string.split_to_std(","), vec!["a", "bc", "", "d"]);
+  assert_eq!(string.split_to_std(";"), vec!["a,bc,,d"]);
+}
+
+#[test]
+fn string_list_to_vec() {
+  let mut list = StringList::new(());
+  assert!(Vec::<std::string::String>::from(&list).is_empty());
+  list.append(&String::from("text1"));
+  list.append(&String::from("text2 ж"));
+  let items: Vec<std::string::String> = (&list).into();
+  assert_eq!(items, vec!["text1", "text2 ж"]);
+}
@@ -0,0 +1,19 @@
+extern crate qt_core;
+
+use qt_core::point::Point;
+use qt_core::variant::Variant;
+
+#[test]
+fn store_metatype_in_variant() {
+  let point = Point::new((1, 2));
+  let variant = Variant::from(&point);
+  let extracted = Point::try_from_variant(&variant).unwrap();
+  assert_eq!(extracted.x(), 1);
+  assert_eq!(extracted.y(), 2);
+}
+
+#[test]
+fn extract_metatype_from_invalid_variant() {
+  let variant = Variant::new(());
+  assert!(Point::try_from_variant(&variant).is_none());
+}
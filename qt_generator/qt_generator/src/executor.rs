@@ -203,7 +203,7 @@ fn make_config(sublib_name: &str,
             Ok(doc) => {
               // log::debug(format!("Found doc for type: {}", type1.name));
               type1.doc = Some(doc.0);
-              if let CppTypeKind::Enum { ref mut values } = type1.kind {
+              if let CppTypeKind::Enum { ref mut values, .. } = type1.kind {
                 let enum_namespace = if let Some(index) = type1.name.rfind("::") {
                   type1.name[0..index + 2].to_string()
                 } else {
@@ -173,6 +173,8 @@ pub fn core(config: &mut Config) -> Result<()> {
                                          "QSequentialIterable",
                                          "QString"]);
 
+  config.add_metatype("QPoint");
+
   config.add_cpp_ffi_generator_filter(|method| {
     if let Some(ref info) = method.class_membership {
       if info.class_type.to_cpp_pseudo_code() == "QFuture<void>" {
@@ -230,6 +232,7 @@ pub fn core(config: &mut Config) -> Result<()> {
       indirection: CppTypeIndirection::None,
       is_const: false,
       is_const2: false,
+      is_volatile: false,
       base: CppTypeBase::BuiltInNumeric(CppBuiltInNumericType::LongDouble),
     };
     if &method.name == "qHash" && method.class_membership.is_none() &&